@@ -0,0 +1,64 @@
+use chrono::Utc;
+use sea_orm::prelude::*;
+use sea_orm::{IntoActiveModel, NotSet, Set};
+
+use bamboo_common_backend_response::{ListQuery, Page};
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate::list::load_page;
+
+/// Failing to write the audit log must not undo the action, so errors are only logged
+pub async fn create_audit_log(entry: AuditLog, db: &DatabaseConnection) {
+    let mut model = entry.into_active_model();
+    model.id = NotSet;
+    model.created_at = Set(Utc::now().naive_utc());
+
+    if let Err(err) = model.insert(db).await {
+        log::error!("Failed to write audit log {err}");
+    }
+}
+
+/// Loads a page of the audit log, the newest entries come first unless another sort is requested
+pub async fn get_audit_log(
+    filter: AuditLogFilter,
+    query: &ListQuery,
+    db: &DatabaseConnection,
+) -> BambooResult<Page<AuditLog>> {
+    let mut select = audit_log::Entity::find();
+    if let Some(grove_id) = filter.grove_id {
+        select = select.filter(audit_log::Column::GroveId.eq(grove_id));
+    }
+    if let Some(action) = filter.action {
+        select = select.filter(audit_log::Column::Action.eq(action));
+    }
+    if let Some(actor_id) = filter.actor_id {
+        select = select.filter(audit_log::Column::ActorId.eq(actor_id));
+    }
+    if let Some(target_id) = filter.target_id {
+        select = select.filter(audit_log::Column::TargetId.eq(target_id));
+    }
+
+    let query = ListQuery {
+        sort: query
+            .sort
+            .clone()
+            .or_else(|| Some("-createdAt".to_string())),
+        ..query.clone()
+    };
+
+    load_page(
+        select,
+        &query,
+        &[
+            ("createdAt", audit_log::Column::CreatedAt),
+            ("actorName", audit_log::Column::ActorName),
+            ("action", audit_log::Column::Action),
+            ("targetName", audit_log::Column::TargetName),
+        ],
+        &[audit_log::Column::ActorName, audit_log::Column::TargetName],
+        "audit_log",
+        db,
+    )
+    .await
+}
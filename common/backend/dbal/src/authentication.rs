@@ -1,56 +1,53 @@
 use base64::Engine;
+use chrono::Duration;
 use rand::distributions::Uniform;
 use rand::Rng;
 use sea_orm::prelude::Expr;
-use sea_orm::ActiveValue::Set;
-use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, NotSet, QueryFilter,
-};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
 
+use bamboo_common_backend_response::ListQuery;
 use bamboo_common_core::entities::*;
 use bamboo_common_core::error::*;
 
 use crate as dbal;
+use crate::recovery_code::use_recovery_code;
+use crate::token::{create_token, hash_token};
 use crate::user::get_users;
 use crate::{decrypt_string, encrypt_string};
 
-pub async fn create_google_auth_token(
+/// Demo accounts are used for app store reviews, the reviewers cannot receive the two factor mail
+pub async fn create_demo_auth_token(
+    email: String,
     password: String,
+    user_agent: Option<String>,
+    idle_timeout: Duration,
     db: &DatabaseConnection,
 ) -> BambooResult<LoginResult> {
-    let user =
-        crate::user::get_user_by_email_or_username("playstore@google.bambushain".to_string(), db)
-            .await
-            .map_err(|err| {
-                log::error!("Failed to load user playstore@google.bambushain: {err}");
-                BambooError::not_found("user", "User not found")
-            })?;
+    let user = crate::user::get_user_by_email_or_username(email.clone(), db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load demo user {email}: {err}");
+            BambooError::not_found("user", "User not found")
+        })?;
 
     if !user.validate_password(password) {
         return Err(BambooError::unauthorized("user", "Invalid login data"));
     }
 
-    token::ActiveModel {
-        id: NotSet,
-        token: Set(uuid::Uuid::new_v4().to_string()),
-        user_id: Set(user.id),
-    }
-    .insert(db)
-    .await
-    .map(|token| LoginResult {
-        token: token.token,
-        user: user.clone().into(),
-    })
-    .map_err(|err| {
-        log::error!("{err}");
-        BambooError::database("token", "Failed to create token")
-    })
+    create_token(user.id, user_agent, idle_timeout, db)
+        .await
+        .map(|token| LoginResult {
+            token: token.token,
+            user: user.clone().into(),
+        })
 }
 
 pub async fn validate_auth_and_create_token(
     username: String,
     password: String,
     two_factor_code: String,
+    user_agent: Option<String>,
+    idle_timeout: Duration,
     db: &DatabaseConnection,
 ) -> BambooResult<LoginResult> {
     let user = crate::user::get_user_by_email_or_username(username.clone(), db)
@@ -62,21 +59,12 @@ pub async fn validate_auth_and_create_token(
 
     validate_login(user.id, two_factor_code, password, false, db).await?;
 
-    let result = token::ActiveModel {
-        id: NotSet,
-        token: Set(uuid::Uuid::new_v4().to_string()),
-        user_id: Set(user.id),
-    }
-    .insert(db)
-    .await
-    .map(|token| LoginResult {
-        token: token.token,
-        user: user.clone().into(),
-    })
-    .map_err(|err| {
-        log::error!("{err}");
-        BambooError::database("token", "Failed to create token")
-    });
+    let result = create_token(user.id, user_agent, idle_timeout, db)
+        .await
+        .map(|token| LoginResult {
+            token: token.token,
+            user: user.clone().into(),
+        });
 
     let _ = bamboo_common_core::entities::user::Entity::update_many()
         .col_expr(
@@ -140,7 +128,7 @@ pub async fn validate_auth_and_set_two_factor_code(
 
 pub async fn delete_token(token: String, db: &DatabaseConnection) -> BambooErrorResult {
     bamboo_common_core::entities::token::Entity::delete_many()
-        .filter(bamboo_common_core::entities::token::Column::Token.eq(token))
+        .filter(bamboo_common_core::entities::token::Column::Token.eq(hash_token(token.as_str())))
         .exec(db)
         .await
         .map(|_| ())
@@ -154,8 +142,8 @@ pub async fn get_tokens_by_grove(
     grove_id: i32,
     db: &DatabaseConnection,
 ) -> BambooResult<Vec<Token>> {
-    let users = get_users(grove_id, db).await?;
-    let users = users.iter().map(|user| user.id);
+    let users = get_users(grove_id, &ListQuery::default(), db).await?;
+    let users = users.entries.iter().map(|user| user.id);
     token::Entity::find()
         .filter(user::Column::Id.is_in(users))
         .inner_join(user::Entity)
@@ -181,8 +169,14 @@ pub async fn validate_login(
         return Err(BambooError::unauthorized("user", "Invalid login data"));
     }
 
-    if initial_validation || user.totp_validated.unwrap_or(false) {
+    if initial_validation {
         validate_totp_token(code, password, user, db).await
+    } else if user.totp_validated.unwrap_or(false) {
+        let user_id = user.id;
+        match validate_totp_token(code.clone(), password, user, db).await {
+            Ok(()) => Ok(()),
+            Err(err) => use_recovery_code(user_id, code, db).await.map_err(|_| err),
+        }
     } else {
         validate_email_token(code, password, user)
     }
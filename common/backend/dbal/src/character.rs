@@ -1,10 +1,12 @@
 use std::collections::{BTreeMap, BTreeSet};
 
+use chrono::Utc;
 use sea_orm::prelude::*;
 use sea_orm::sea_query::Expr;
 use sea_orm::ActiveValue::Set;
 use sea_orm::{Condition, IntoActiveModel, NotSet, QueryOrder, QuerySelect};
 
+use bamboo_common_backend_response::{ListQuery, Page};
 use bamboo_common_core::entities::*;
 use bamboo_common_core::entities::{
     character, custom_character_field, custom_character_field_option, custom_character_field_value,
@@ -12,6 +14,7 @@ use bamboo_common_core::entities::{
 use bamboo_common_core::error::*;
 
 use crate::free_company::get_free_company;
+use crate::list::load_page;
 
 async fn map_character(
     character: Character,
@@ -27,26 +30,37 @@ async fn map_character(
         custom_fields: fill_custom_fields(user_id, character.id, db).await?,
         free_company_id: character.free_company_id,
         free_company: get_free_company(character.free_company_id, user_id, db).await?,
+        deleted_at: character.deleted_at,
     })
 }
 
-pub async fn get_characters(user_id: i32, db: &DatabaseConnection) -> BambooResult<Vec<Character>> {
-    let characters = character::Entity::find()
-        .filter(character::Column::UserId.eq(user_id))
-        .order_by_asc(character::Column::Name)
-        .all(db)
-        .await
-        .map_err(|err| {
-            log::error!("{err}");
-            BambooError::database("character", "Failed to load characters")
-        })?;
+pub async fn get_characters(
+    user_id: i32,
+    query: &ListQuery,
+    db: &DatabaseConnection,
+) -> BambooResult<Page<Character>> {
+    let characters = load_page(
+        character::Entity::find()
+            .filter(character::Column::UserId.eq(user_id))
+            .filter(character::Column::DeletedAt.is_null()),
+        query,
+        &[
+            ("name", character::Column::Name),
+            ("world", character::Column::World),
+            ("race", character::Column::Race),
+        ],
+        &[character::Column::Name, character::Column::World],
+        "character",
+        db,
+    )
+    .await?;
 
     let mut result = vec![];
-    for character in characters {
+    for character in characters.entries {
         result.push(map_character(character.clone(), user_id, db).await?);
     }
 
-    Ok(result)
+    Ok(Page::new(result, characters.total))
 }
 
 pub async fn get_character(
@@ -56,6 +70,7 @@ pub async fn get_character(
 ) -> BambooResult<Character> {
     let character = character::Entity::find_by_id(id)
         .filter(character::Column::UserId.eq(user_id))
+        .filter(character::Column::DeletedAt.is_null())
         .one(db)
         .await
         .map_err(|err| {
@@ -146,44 +161,65 @@ async fn fill_custom_fields(
         .collect::<Vec<CustomField>>())
 }
 
-async fn character_exists_by_id(
+// Characters in the trash keep their name until they are purged or restored, the unique constraint still holds for them.
+// A conflict with one of them says so, otherwise the error would point to a character that cannot be seen
+fn check_character_conflict(conflicting_characters: Vec<Character>) -> BambooErrorResult {
+    if conflicting_characters
+        .iter()
+        .any(|character| character.deleted_at.is_none())
+    {
+        Err(BambooError::exists_already(
+            "character",
+            "A character with that name already exists",
+        ))
+    } else if !conflicting_characters.is_empty() {
+        Err(BambooError::exists_already(
+            "character",
+            "A character with that name is in the trash, restore it instead",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+async fn check_character_unique_by_id(
     id: i32,
     name: String,
     world: String,
     user_id: i32,
     db: &DatabaseConnection,
-) -> BambooResult<bool> {
+) -> BambooErrorResult {
     character::Entity::find()
         .filter(character::Column::Id.ne(id))
         .filter(character::Column::Name.eq(name))
         .filter(character::Column::World.eq(world))
         .filter(character::Column::UserId.eq(user_id))
-        .count(db)
+        .all(db)
         .await
-        .map(|count| count > 0)
         .map_err(|err| {
             log::error!("Failed to load characters {err}");
             BambooError::database("character", "Failed to load the characters")
         })
+        .and_then(check_character_conflict)
 }
 
-async fn character_exists_by_name(
+async fn check_character_unique(
     name: String,
     world: String,
     user_id: i32,
     db: &DatabaseConnection,
-) -> BambooResult<bool> {
+) -> BambooErrorResult {
     character::Entity::find()
         .filter(character::Column::Name.eq(name))
         .filter(character::Column::World.eq(world))
         .filter(character::Column::UserId.eq(user_id))
-        .count(db)
+        .all(db)
         .await
-        .map(|count| count > 0)
         .map_err(|err| {
             log::error!("Failed to load characters {err}");
             BambooError::database("character", "Failed to load the characters")
         })
+        .and_then(check_character_conflict)
 }
 
 pub async fn create_character(
@@ -191,14 +227,7 @@ pub async fn create_character(
     character: Character,
     db: &DatabaseConnection,
 ) -> BambooResult<Character> {
-    if character_exists_by_name(character.name.clone(), character.world.clone(), user_id, db)
-        .await?
-    {
-        return Err(BambooError::exists_already(
-            "character",
-            "A character with that name already exists",
-        ));
-    }
+    check_character_unique(character.name.clone(), character.world.clone(), user_id, db).await?;
 
     let mut model = character.clone().into_active_model();
     model.free_company_id = Set(character.free_company.map(|company| company.id));
@@ -221,23 +250,18 @@ pub async fn update_character(
     character: Character,
     db: &DatabaseConnection,
 ) -> BambooErrorResult {
-    if character_exists_by_id(
+    check_character_unique_by_id(
         id,
         character.name.clone(),
         character.world.clone(),
         user_id,
         db,
     )
-    .await?
-    {
-        return Err(BambooError::exists_already(
-            "character",
-            "A character with that name already exists",
-        ));
-    }
+    .await?;
     character::Entity::update_many()
         .filter(character::Column::Id.eq(id))
         .filter(character::Column::UserId.eq(user_id))
+        .filter(character::Column::DeletedAt.is_null())
         .col_expr(character::Column::Name, Expr::value(character.name.clone()))
         .col_expr(
             character::Column::FreeCompanyId,
@@ -325,10 +349,16 @@ async fn create_custom_field_values(
         .map(|_| ())
 }
 
+/// Moves the character into the trash, it is purged after the retention period
 pub async fn delete_character(id: i32, user_id: i32, db: &DatabaseConnection) -> BambooErrorResult {
-    character::Entity::delete_many()
+    character::Entity::update_many()
         .filter(character::Column::Id.eq(id))
         .filter(character::Column::UserId.eq(user_id))
+        .filter(character::Column::DeletedAt.is_null())
+        .col_expr(
+            character::Column::DeletedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
         .exec(db)
         .await
         .map_err(|err| {
@@ -337,3 +367,15 @@ pub async fn delete_character(id: i32, user_id: i32, db: &DatabaseConnection) ->
         })
         .map(|_| ())
 }
+
+pub async fn delete_characters(user_id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    character::Entity::delete_many()
+        .filter(character::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("character", "Failed to delete characters")
+        })
+        .map(|_| ())
+}
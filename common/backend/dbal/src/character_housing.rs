@@ -110,6 +110,8 @@ pub async fn create_character_housing(
     housing: CharacterHousing,
     db: &DatabaseConnection,
 ) -> BambooResult<CharacterHousing> {
+    housing.validate()?;
+
     if character_housing_exists_by_fields(
         user_id,
         character_id,
@@ -143,6 +145,8 @@ pub async fn update_character_housing(
     housing: CharacterHousing,
     db: &DatabaseConnection,
 ) -> BambooErrorResult {
+    housing.validate()?;
+
     if character_housing_exists_by_id(
         id,
         user_id,
@@ -1,32 +1,116 @@
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, Utc};
 use date_range::DateRange;
 use sea_orm::prelude::*;
-use sea_orm::{Condition, IntoActiveModel, NotSet, QueryOrder, Set};
+use sea_orm::{
+    Condition, ConnectionTrait, IntoActiveModel, NotSet, QueryOrder, Set, TransactionTrait,
+};
 
+use bamboo_common_backend_response::{ListQuery, Page};
 use bamboo_common_core::entities::event;
 use bamboo_common_core::entities::*;
 use bamboo_common_core::error::*;
 
+use crate::event_party_slot::delete_event_party_slots;
+use crate::grove::check_demo_grove;
+use crate::list::filter_condition;
+
 pub async fn get_events(
     grove_id: i32,
     range: DateRange,
     user_id: i32,
+    query: &ListQuery,
     db: &DatabaseConnection,
-) -> BambooResult<Vec<Event>> {
-    event::Entity::find()
+) -> BambooResult<Page<Event>> {
+    // Events with a time can move to the previous or next day once they are converted into the time zone of the viewer
+    let since = range.since().pred_opt().unwrap_or(range.since());
+    let until = range.until().succ_opt().unwrap_or(range.until());
+    // Recurring events are expanded after loading, so they are sorted and paged in memory
+    let descending = match query.sort_field() {
+        None | Some(("startDate", false)) => false,
+        Some(("startDate", true)) => true,
+        Some((field, _)) => {
+            return Err(BambooError::invalid_data(
+                "event",
+                format!("Cannot sort by {field}"),
+            ))
+        }
+    };
+    let filter = query
+        .filter_text()
+        .map(|filter| {
+            filter_condition(
+                &[event::Column::Title, event::Column::Description],
+                filter.as_str(),
+            )
+        })
+        .unwrap_or_else(Condition::all);
+
+    let mut events = event::Entity::find()
         .filter(event::Column::GroveId.eq(grove_id))
+        .filter(event::Column::DeletedAt.is_null())
         .filter(
             Condition::any()
                 .add(
                     Condition::all()
-                        .add(event::Column::StartDate.gte(range.since()))
-                        .add(event::Column::StartDate.lte(range.until())),
+                        .add(event::Column::Recurrence.is_null())
+                        .add(
+                            Condition::any()
+                                .add(
+                                    Condition::all()
+                                        .add(event::Column::StartDate.gte(since))
+                                        .add(event::Column::StartDate.lte(until)),
+                                )
+                                .add(
+                                    Condition::all()
+                                        .add(event::Column::EndDate.gte(since))
+                                        .add(event::Column::EndDate.lte(until)),
+                                ),
+                        ),
                 )
                 .add(
                     Condition::all()
-                        .add(event::Column::EndDate.gte(range.since()))
-                        .add(event::Column::EndDate.lte(range.until())),
+                        .add(event::Column::Recurrence.is_not_null())
+                        .add(event::Column::StartDate.lte(until)),
+                ),
+        )
+        .filter(
+            Condition::any()
+                .add(event::Column::IsPrivate.eq(false))
+                .add(
+                    Condition::all()
+                        .add(event::Column::IsPrivate.eq(true))
+                        .add(event::Column::UserId.eq(user_id)),
                 ),
         )
+        .filter(filter)
+        .order_by_asc(event::Column::Id)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load events {err}");
+            BambooError::database("event", "Failed to load events")
+        })?
+        .iter()
+        .flat_map(|event| event.occurrences(since, until))
+        .collect::<Vec<Event>>();
+    events.sort_by_key(|event| event.start_date);
+    if descending {
+        events.reverse();
+    }
+
+    Ok(Page::from_entries(events, query))
+}
+
+pub async fn get_calendar_feed_events(
+    grove_id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<Event>> {
+    event::Entity::find()
+        .filter(event::Column::GroveId.eq(grove_id))
+        .filter(event::Column::DeletedAt.is_null())
         .filter(
             Condition::any()
                 .add(event::Column::IsPrivate.eq(false))
@@ -50,9 +134,19 @@ pub async fn get_event(
     grove_id: i32,
     user_id: i32,
     db: &DatabaseConnection,
+) -> BambooResult<Event> {
+    find_event(id, grove_id, user_id, db).await
+}
+
+async fn find_event<C: ConnectionTrait>(
+    id: i32,
+    grove_id: i32,
+    user_id: i32,
+    db: &C,
 ) -> BambooResult<Event> {
     event::Entity::find_by_id(id)
         .filter(event::Column::GroveId.eq(grove_id))
+        .filter(event::Column::DeletedAt.is_null())
         .one(db)
         .await
         .map_err(|err| {
@@ -78,9 +172,23 @@ pub async fn create_event(
     user_id: i32,
     db: &DatabaseConnection,
 ) -> BambooResult<Event> {
+    insert_event(event, grove_id, user_id, db).await
+}
+
+async fn insert_event<C: ConnectionTrait>(
+    event: Event,
+    grove_id: i32,
+    user_id: i32,
+    db: &C,
+) -> BambooResult<Event> {
+    validate_recurrence(&event)?;
+    let event = validate_event(event)?;
+
     let mut model = event.clone().into_active_model();
     model.id = NotSet;
     model.grove_id = Set(grove_id);
+    model.series_id = Set(None);
+    model.series_date = Set(None);
     if event.is_private {
         model.user_id = Set(Some(user_id));
     }
@@ -97,32 +205,291 @@ pub async fn update_event(
     event: Event,
     db: &DatabaseConnection,
 ) -> BambooErrorResult {
+    validate_recurrence(&event)?;
+    let event = validate_event(event)?;
+
+    let previous_template = event::Entity::find_by_id(id)
+        .filter(event::Column::GroveId.eq(grove_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load event {err}");
+            BambooError::database("event", "Failed to load event")
+        })?
+        .and_then(|event| event.party_template);
+    let party_template = event.party_template;
+
     event::Entity::update_many()
         .filter(event::Column::Id.eq(id))
         .filter(event::Column::GroveId.eq(grove_id))
+        .filter(event::Column::DeletedAt.is_null())
         .col_expr(event::Column::StartDate, Expr::value(event.start_date))
         .col_expr(event::Column::EndDate, Expr::value(event.end_date))
+        .col_expr(event::Column::AllDay, Expr::value(event.all_day))
+        .col_expr(event::Column::StartTime, Expr::value(event.start_time))
+        .col_expr(event::Column::EndTime, Expr::value(event.end_time))
+        .col_expr(event::Column::TimeZone, Expr::value(event.time_zone))
         .col_expr(event::Column::Description, Expr::value(event.description))
         .col_expr(event::Column::Title, Expr::value(event.title))
         .col_expr(event::Column::Color, Expr::value(event.color))
+        .col_expr(event::Column::Recurrence, Expr::value(event.recurrence))
+        .col_expr(
+            event::Column::PartyTemplate,
+            event::Column::PartyTemplate.save_as(Expr::val(event.party_template)),
+        )
         .exec(db)
         .await
         .map_err(|err| {
             log::error!("Failed to update event {err}");
             BambooError::database("event", "Failed to update event")
+        })?;
+
+    if previous_template != party_template {
+        delete_event_party_slots(id, db).await?;
+    }
+
+    Ok(())
+}
+
+/// Moves the event into the trash, changed occurrences of a series go with it
+pub async fn delete_event(grove_id: i32, id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    event::Entity::update_many()
+        .filter(
+            Condition::any()
+                .add(event::Column::Id.eq(id))
+                .add(event::Column::SeriesId.eq(id)),
+        )
+        .filter(event::Column::GroveId.eq(grove_id))
+        .filter(event::Column::DeletedAt.is_null())
+        .col_expr(
+            event::Column::DeletedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete event {err}");
+            BambooError::database("event", "Failed to delete event")
         })
         .map(|_| ())
 }
 
-pub async fn delete_event(grove_id: i32, id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+/// Deletes every event of the demo grove, used to reset it
+pub async fn delete_demo_events(grove_id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    check_demo_grove(grove_id, db).await?;
+
     event::Entity::delete_many()
+        .filter(event::Column::GroveId.eq(grove_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete events {err}");
+            BambooError::database("event", "Failed to delete events")
+        })
+        .map(|_| ())
+}
+
+pub async fn create_event_occurrence(
+    grove_id: i32,
+    series_id: i32,
+    occurrence_date: NaiveDate,
+    event: Event,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Event> {
+    insert_event_occurrence(grove_id, series_id, occurrence_date, event, user_id, db).await
+}
+
+async fn insert_event_occurrence<C: ConnectionTrait>(
+    grove_id: i32,
+    series_id: i32,
+    occurrence_date: NaiveDate,
+    event: Event,
+    user_id: i32,
+    db: &C,
+) -> BambooResult<Event> {
+    let event = validate_event(event)?;
+    let series = find_event(series_id, grove_id, user_id, db).await?;
+    let mut recurrence = get_occurrence_recurrence(&series, occurrence_date)?;
+    recurrence.exceptions.insert(occurrence_date);
+
+    let mut model = event.into_active_model();
+    model.id = NotSet;
+    model.grove_id = Set(grove_id);
+    model.is_private = Set(series.is_private);
+    model.user_id = Set(series.user_id);
+    model.recurrence = Set(None);
+    model.series_id = Set(Some(series.id));
+    model.series_date = Set(Some(occurrence_date));
+
+    let occurrence = model.insert(db).await.map_err(|err| {
+        log::error!("Failed to create event occurrence {err}");
+        BambooError::database("event", "Failed to create event occurrence")
+    })?;
+
+    set_recurrence(grove_id, series.id, recurrence, db)
+        .await
+        .map(|_| occurrence)
+}
+
+pub async fn delete_event_occurrence(
+    grove_id: i32,
+    series_id: i32,
+    occurrence_date: NaiveDate,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Event> {
+    let series = get_event(series_id, grove_id, user_id, db).await?;
+    let mut recurrence = get_occurrence_recurrence(&series, occurrence_date)?;
+    recurrence.exceptions.insert(occurrence_date);
+
+    set_recurrence(grove_id, series.id, recurrence.clone(), db)
+        .await
+        .map(|_| Event {
+            recurrence: Some(recurrence),
+            ..series
+        })
+}
+
+/// Imports the entries of a calendar file in one transaction, the results are returned in the order of the entries.
+/// An entry consists of its uid, the date of the occurrence it overrides and the parsed event.
+/// Invalid entries are reported in the result, a failing database statement cancels the whole import.
+pub async fn import_events(
+    grove_id: i32,
+    user_id: i32,
+    entries: Vec<(Option<String>, Option<NaiveDate>, BambooResult<Event>)>,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<BambooResult<Event>>> {
+    let txn = db.begin().await.map_err(|err| {
+        log::error!("Failed to start the event import {err}");
+        BambooError::database("event", "Failed to import events")
+    })?;
+
+    let mut results = entries
+        .iter()
+        .map(|_| None)
+        .collect::<Vec<Option<BambooResult<Event>>>>();
+    let mut series_ids = BTreeMap::new();
+
+    // Series have to exist before their overridden occurrences can be imported
+    let (overrides, series): (Vec<_>, Vec<_>) = entries
+        .into_iter()
+        .enumerate()
+        .partition(|(_, (_, recurrence_id, _))| recurrence_id.is_some());
+    for (idx, (uid, recurrence_id, event)) in series.into_iter().chain(overrides) {
+        let result = match (event, recurrence_id) {
+            (Ok(event), Some(occurrence_date)) => {
+                if let Some(series_id) = uid.as_ref().and_then(|uid| series_ids.get(uid).copied()) {
+                    insert_event_occurrence(
+                        grove_id,
+                        series_id,
+                        occurrence_date,
+                        event,
+                        user_id,
+                        &txn,
+                    )
+                    .await
+                } else {
+                    Err(BambooError::validation(
+                        "event",
+                        "The series of this occurrence is not part of the file",
+                    ))
+                }
+            }
+            (Ok(event), None) => insert_event(event, grove_id, user_id, &txn).await,
+            (Err(err), _) => Err(err),
+        };
+
+        match &result {
+            Ok(event) => {
+                if let Some(uid) = uid.filter(|_| event.is_recurring()) {
+                    series_ids.insert(uid, event.id);
+                }
+            }
+            Err(err) if err.error_type == BambooErrorCode::Database => return Err(err.clone()),
+            Err(_) => {}
+        }
+        results[idx] = Some(result);
+    }
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit the event import {err}");
+        BambooError::database("event", "Failed to import events")
+    })?;
+
+    // Importing overridden occurrences adds exceptions to their series
+    let mut imported = vec![];
+    for result in results.into_iter().flatten() {
+        imported.push(match result {
+            Ok(event) if event.is_recurring() => {
+                Ok(get_event(event.id, grove_id, user_id, db).await?)
+            }
+            result => result,
+        });
+    }
+
+    Ok(imported)
+}
+
+fn get_occurrence_recurrence(
+    series: &Event,
+    occurrence_date: NaiveDate,
+) -> BambooResult<RecurrenceRule> {
+    let recurrence = series.recurrence.clone().ok_or(BambooError::invalid_data(
+        "event",
+        "The event is not a recurring event",
+    ))?;
+    if recurrence.is_occurrence(series.start_date, occurrence_date) {
+        Ok(recurrence)
+    } else {
+        Err(BambooError::not_found(
+            "event",
+            "The event has no occurrence on this date",
+        ))
+    }
+}
+
+async fn set_recurrence<C: ConnectionTrait>(
+    grove_id: i32,
+    id: i32,
+    recurrence: RecurrenceRule,
+    db: &C,
+) -> BambooErrorResult {
+    event::Entity::update_many()
         .filter(event::Column::Id.eq(id))
         .filter(event::Column::GroveId.eq(grove_id))
+        .col_expr(event::Column::Recurrence, Expr::value(recurrence))
         .exec(db)
         .await
         .map_err(|err| {
-            log::error!("Failed to delete event {err}");
-            BambooError::database("event", "Failed to delete event")
+            log::error!("Failed to update event recurrence {err}");
+            BambooError::database("event", "Failed to update event recurrence")
         })
         .map(|_| ())
 }
+
+fn validate_event(event: Event) -> BambooResult<Event> {
+    event.validate()?;
+
+    if event.all_day {
+        Ok(Event {
+            start_time: None,
+            end_time: None,
+            time_zone: None,
+            ..event
+        })
+    } else {
+        Ok(event)
+    }
+}
+
+fn validate_recurrence(event: &Event) -> BambooErrorResult {
+    if event.recurrence.is_some() && event.series_id.is_some() {
+        Err(BambooError::validation(
+            "event",
+            "A single occurrence of a series cannot recur",
+        ))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use sea_orm::prelude::*;
+use sea_orm::{IntoActiveModel, NotSet, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::entities::{character, event_attendance, user};
+use bamboo_common_core::error::*;
+
+use crate::character::get_character;
+
+pub async fn get_event_attendances(
+    event_id: i32,
+    event_date: NaiveDate,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<EventAttendance>> {
+    let attendances = event_attendance::Entity::find()
+        .filter(event_attendance::Column::EventId.eq(event_id))
+        .filter(event_attendance::Column::EventDate.eq(event_date))
+        .filter(user::Column::DeletedAt.is_null())
+        .find_also_related(user::Entity)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load event attendances {err}");
+            BambooError::database("event", "Failed to load event attendances")
+        })?;
+
+    let characters = character::Entity::find()
+        .filter(
+            character::Column::Id.is_in(
+                attendances
+                    .iter()
+                    .filter_map(|(attendance, _)| attendance.character_id)
+                    .collect::<Vec<i32>>(),
+            ),
+        )
+        .filter(character::Column::DeletedAt.is_null())
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load event attendances {err}");
+            BambooError::database("event", "Failed to load event attendances")
+        })?
+        .into_iter()
+        .map(|character| (character.id, character.name))
+        .collect::<BTreeMap<i32, String>>();
+
+    let mut attendances = attendances
+        .into_iter()
+        .map(|(attendance, user)| EventAttendance {
+            display_name: user.map(|user| user.display_name).unwrap_or_default(),
+            character_name: attendance
+                .character_id
+                .and_then(|character_id| characters.get(&character_id).cloned()),
+            ..attendance
+        })
+        .collect::<Vec<EventAttendance>>();
+    attendances.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+
+    Ok(attendances)
+}
+
+pub async fn set_event_attendance(
+    event: &Event,
+    event_date: NaiveDate,
+    attendance: EventAttendance,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<EventAttendance> {
+    if !event.starts_on(event_date) {
+        return Err(BambooError::validation(
+            "event",
+            "The event does not take place on this date",
+        ));
+    }
+    if attendance.fighter_job.is_some() && attendance.crafter_job.is_some() {
+        return Err(BambooError::validation(
+            "event",
+            "You can only attend with either a fighter or a crafter job",
+        ));
+    }
+    if let Some(character_id) = attendance.character_id {
+        get_character(character_id, user_id, db).await?;
+    }
+
+    let existing = event_attendance::Entity::find()
+        .filter(event_attendance::Column::EventId.eq(event.id))
+        .filter(event_attendance::Column::EventDate.eq(event_date))
+        .filter(event_attendance::Column::UserId.eq(user_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load event attendance {err}");
+            BambooError::database("event", "Failed to load event attendance")
+        })?;
+
+    let mut model = attendance.into_active_model();
+    model.id = if let Some(existing) = existing {
+        Set(existing.id)
+    } else {
+        NotSet
+    };
+    model.event_id = Set(event.id);
+    model.event_date = Set(event_date);
+    model.user_id = Set(user_id);
+
+    model.save(db).await.map_err(|err| {
+        log::error!("Failed to save event attendance {err}");
+        BambooError::database("event", "Failed to save event attendance")
+    })?;
+
+    event_attendance::Entity::find()
+        .filter(event_attendance::Column::EventId.eq(event.id))
+        .filter(event_attendance::Column::EventDate.eq(event_date))
+        .filter(event_attendance::Column::UserId.eq(user_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load event attendance {err}");
+            BambooError::database("event", "Failed to load event attendance")
+        })?
+        .ok_or(BambooError::not_found(
+            "event",
+            "The event attendance was not found",
+        ))
+}
+
+pub async fn delete_event_attendance(
+    event_id: i32,
+    event_date: NaiveDate,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    event_attendance::Entity::delete_many()
+        .filter(event_attendance::Column::EventId.eq(event_id))
+        .filter(event_attendance::Column::EventDate.eq(event_date))
+        .filter(event_attendance::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete event attendance {err}");
+            BambooError::database("event", "Failed to delete event attendance")
+        })
+        .map(|_| ())
+}
@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use sea_orm::prelude::*;
+use sea_orm::{IntoActiveModel, NotSet, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::entities::{character, event_party_slot, fighter, user};
+use bamboo_common_core::error::*;
+
+async fn get_fighter_of_user(
+    fighter_id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Fighter> {
+    fighter::Entity::find_by_id(fighter_id)
+        .filter(character::Column::UserId.eq(user_id))
+        .inner_join(character::Entity)
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load fighter {err}");
+            BambooError::database("event", "Failed to load fighter")
+        })?
+        .ok_or(BambooError::not_found("event", "The fighter was not found"))
+}
+
+pub async fn get_event_party_slots(
+    event_id: i32,
+    event_date: NaiveDate,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<EventPartySlot>> {
+    let slots = event_party_slot::Entity::find()
+        .filter(event_party_slot::Column::EventId.eq(event_id))
+        .filter(event_party_slot::Column::EventDate.eq(event_date))
+        .filter(user::Column::DeletedAt.is_null())
+        .find_also_related(user::Entity)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load event party slots {err}");
+            BambooError::database("event", "Failed to load event party slots")
+        })?;
+
+    let fighters = fighter::Entity::find()
+        .filter(
+            fighter::Column::Id.is_in(
+                slots
+                    .iter()
+                    .map(|(slot, _)| slot.fighter_id)
+                    .collect::<Vec<i32>>(),
+            ),
+        )
+        .find_also_related(character::Entity)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load event party slots {err}");
+            BambooError::database("event", "Failed to load event party slots")
+        })?
+        .into_iter()
+        .map(|(fighter, character)| {
+            (
+                fighter.id,
+                (
+                    fighter.job,
+                    character
+                        .map(|character| character.name)
+                        .unwrap_or_default(),
+                ),
+            )
+        })
+        .collect::<BTreeMap<i32, (FighterJob, String)>>();
+
+    let mut slots = slots
+        .into_iter()
+        .map(|(slot, user)| {
+            let (fighter_job, character_name) = fighters
+                .get(&slot.fighter_id)
+                .cloned()
+                .map(|(job, name)| (Some(job), name))
+                .unwrap_or_default();
+
+            EventPartySlot {
+                display_name: user.map(|user| user.display_name).unwrap_or_default(),
+                character_name,
+                fighter_job,
+                ..slot
+            }
+        })
+        .collect::<Vec<EventPartySlot>>();
+    slots.sort_by_key(|slot| slot.slot);
+
+    Ok(slots)
+}
+
+pub async fn set_event_party_slot(
+    event: &Event,
+    event_date: NaiveDate,
+    slot: i32,
+    fighter_id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<EventPartySlot> {
+    let template = event.party_template.ok_or(BambooError::validation(
+        "event",
+        "The event has no party template",
+    ))?;
+    if !event.starts_on(event_date) {
+        return Err(BambooError::validation(
+            "event",
+            "The event does not take place on this date",
+        ));
+    }
+
+    let role = usize::try_from(slot)
+        .ok()
+        .and_then(|slot| template.slots().get(slot).copied())
+        .ok_or(BambooError::validation(
+            "event",
+            "The slot does not exist in the party template",
+        ))?;
+    let fighter = get_fighter_of_user(fighter_id, user_id, db).await?;
+    if fighter.job.role() != role {
+        return Err(BambooError::validation(
+            "event",
+            format!("The slot requires a {role}"),
+        ));
+    }
+
+    let existing = event_party_slot::Entity::find()
+        .filter(event_party_slot::Column::EventId.eq(event.id))
+        .filter(event_party_slot::Column::EventDate.eq(event_date))
+        .filter(
+            event_party_slot::Column::Slot
+                .eq(slot)
+                .or(event_party_slot::Column::UserId.eq(user_id)),
+        )
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load event party slot {err}");
+            BambooError::database("event", "Failed to load event party slot")
+        })?;
+    if existing
+        .iter()
+        .any(|existing| existing.slot == slot && existing.user_id != user_id)
+    {
+        return Err(BambooError::exists_already(
+            "event",
+            "The slot is already taken",
+        ));
+    }
+
+    let mut model = EventPartySlot {
+        event_id: event.id,
+        event_date,
+        slot,
+        user_id,
+        fighter_id,
+        ..EventPartySlot::default()
+    }
+    .into_active_model();
+    model.id = if let Some(existing) = existing.first() {
+        Set(existing.id)
+    } else {
+        NotSet
+    };
+
+    model.save(db).await.map_err(|err| {
+        log::error!("Failed to save event party slot {err}");
+        BambooError::database("event", "Failed to save event party slot")
+    })?;
+
+    event_party_slot::Entity::find()
+        .filter(event_party_slot::Column::EventId.eq(event.id))
+        .filter(event_party_slot::Column::EventDate.eq(event_date))
+        .filter(event_party_slot::Column::UserId.eq(user_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load event party slot {err}");
+            BambooError::database("event", "Failed to load event party slot")
+        })?
+        .ok_or(BambooError::not_found(
+            "event",
+            "The event party slot was not found",
+        ))
+}
+
+pub async fn delete_event_party_slot(
+    event_id: i32,
+    event_date: NaiveDate,
+    slot: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    event_party_slot::Entity::delete_many()
+        .filter(event_party_slot::Column::EventId.eq(event_id))
+        .filter(event_party_slot::Column::EventDate.eq(event_date))
+        .filter(event_party_slot::Column::Slot.eq(slot))
+        .filter(event_party_slot::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete event party slot {err}");
+            BambooError::database("event", "Failed to delete event party slot")
+        })
+        .map(|_| ())
+}
+
+pub(crate) async fn delete_event_party_slots(
+    event_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    event_party_slot::Entity::delete_many()
+        .filter(event_party_slot::Column::EventId.eq(event_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete event party slots {err}");
+            BambooError::database("event", "Failed to delete event party slots")
+        })
+        .map(|_| ())
+}
@@ -0,0 +1,148 @@
+use chrono::NaiveDate;
+use sea_orm::prelude::*;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{Condition, NotSet, QueryOrder, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::entities::{event, event_attendance, event_reminder, user};
+use bamboo_common_core::error::*;
+
+pub async fn get_event_occurrences_between(
+    since: NaiveDate,
+    until: NaiveDate,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<Event>> {
+    event::Entity::find()
+        .filter(event::Column::DeletedAt.is_null())
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(event::Column::Recurrence.is_null())
+                        .add(event::Column::StartDate.gte(since))
+                        .add(event::Column::StartDate.lte(until)),
+                )
+                .add(
+                    Condition::all()
+                        .add(event::Column::Recurrence.is_not_null())
+                        .add(event::Column::StartDate.lte(until)),
+                ),
+        )
+        .order_by_asc(event::Column::Id)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load events {err}");
+            BambooError::database("event", "Failed to load events")
+        })
+        .map(|events| {
+            events
+                .iter()
+                .flat_map(|event| event.occurrences(since, until))
+                .filter(|event| event.start_date >= since)
+                .collect()
+        })
+}
+
+pub async fn get_event_reminder_recipients(
+    event: &Event,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<User>> {
+    let mut condition = Condition::all()
+        .add(user::Column::GroveId.eq(event.grove_id))
+        .add(user::Column::EventReminders.eq(true))
+        .add(user::Column::DeletedAt.is_null());
+    if event.is_private {
+        condition = condition.add(user::Column::Id.eq(event.user_id));
+    }
+
+    let attendances = event_attendance::Entity::find()
+        .filter(event_attendance::Column::EventId.eq(event.id))
+        .filter(event_attendance::Column::EventDate.eq(event.start_date))
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load event attendances {err}");
+            BambooError::database("event", "Failed to load event attendances")
+        })?;
+    if !attendances.is_empty() {
+        condition = condition.add(
+            user::Column::Id.is_in(
+                attendances
+                    .into_iter()
+                    .filter(|attendance| attendance.status != AttendanceStatus::Declined)
+                    .map(|attendance| attendance.user_id)
+                    .collect::<Vec<i32>>(),
+            ),
+        );
+    }
+
+    user::Entity::find()
+        .filter(condition)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load users {err}");
+            BambooError::database("user", "Failed to load users")
+        })
+}
+
+/// Stores that the reminder for the event was sent to the user, returns false if it was sent before.
+pub async fn mark_event_reminder_sent(
+    event_id: i32,
+    event_date: NaiveDate,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<bool> {
+    let reminder = event_reminder::ActiveModel {
+        id: NotSet,
+        event_id: Set(event_id),
+        event_date: Set(event_date),
+        user_id: Set(user_id),
+        sent_at: Set(chrono::Utc::now().naive_utc()),
+    };
+
+    match event_reminder::Entity::insert(reminder)
+        .on_conflict(
+            OnConflict::columns([
+                event_reminder::Column::EventId,
+                event_reminder::Column::EventDate,
+                event_reminder::Column::UserId,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .exec(db)
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(DbErr::RecordNotInserted) => Ok(false),
+        Err(err) => {
+            log::error!("Failed to save event reminder {err}");
+            Err(BambooError::database(
+                "event",
+                "Failed to save event reminder",
+            ))
+        }
+    }
+}
+
+/// Removes the reminder for the event again, so it is sent on the next try after sending it failed.
+pub async fn unmark_event_reminder_sent(
+    event_id: i32,
+    event_date: NaiveDate,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    event_reminder::Entity::delete_many()
+        .filter(event_reminder::Column::EventId.eq(event_id))
+        .filter(event_reminder::Column::EventDate.eq(event_date))
+        .filter(event_reminder::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete event reminder {err}");
+            BambooError::database("event", "Failed to delete event reminder")
+        })
+        .map(|_| ())
+}
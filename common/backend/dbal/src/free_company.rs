@@ -1,22 +1,26 @@
 use sea_orm::prelude::*;
-use sea_orm::{NotSet, QueryOrder, Set};
+use sea_orm::{NotSet, Set};
 
+use bamboo_common_backend_response::{ListQuery, Page};
 use bamboo_common_core::entities::*;
 use bamboo_common_core::error::*;
 
+use crate::list::load_page;
+
 pub async fn get_free_companies(
     user_id: i32,
+    query: &ListQuery,
     db: &DatabaseConnection,
-) -> BambooResult<Vec<FreeCompany>> {
-    free_company::Entity::find()
-        .filter(free_company::Column::UserId.eq(user_id))
-        .order_by_asc(free_company::Column::Name)
-        .all(db)
-        .await
-        .map_err(|err| {
-            log::error!("Failed to load free companies: {err}");
-            BambooError::not_found("free_company", "Free companies not found")
-        })
+) -> BambooResult<Page<FreeCompany>> {
+    load_page(
+        free_company::Entity::find().filter(free_company::Column::UserId.eq(user_id)),
+        query,
+        &[("name", free_company::Column::Name)],
+        &[free_company::Column::Name],
+        "free_company",
+        db,
+    )
+    .await
 }
 
 pub async fn get_free_company(
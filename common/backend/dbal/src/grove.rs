@@ -1,10 +1,12 @@
 use sea_orm::prelude::*;
-use sea_orm::{IntoActiveModel, NotSet, QueryOrder};
+use sea_orm::{IntoActiveModel, NotSet};
 
+use bamboo_common_backend_response::{ListQuery, Page};
 use bamboo_common_core::entities::*;
 use bamboo_common_core::error::*;
 
 use crate::authentication::get_tokens_by_grove;
+use crate::list::load_page;
 
 pub async fn get_grove_by_user_id(user_id: i32, db: &DatabaseConnection) -> BambooResult<Grove> {
     grove::Entity::find()
@@ -60,15 +62,21 @@ pub async fn get_grove_by_name(name: String, db: &DatabaseConnection) -> BambooR
         })?
 }
 
-pub async fn get_groves(db: &DatabaseConnection) -> BambooResult<Vec<Grove>> {
-    grove::Entity::find()
-        .order_by_asc(grove::Column::Id)
-        .all(db)
-        .await
-        .map_err(|err| {
-            log::error!("{err}");
-            BambooError::database("user", "Failed to execute database query")
-        })
+pub async fn get_groves(query: &ListQuery, db: &DatabaseConnection) -> BambooResult<Page<Grove>> {
+    load_page(
+        grove::Entity::find(),
+        query,
+        &[
+            ("id", grove::Column::Id),
+            ("name", grove::Column::Name),
+            ("isSuspended", grove::Column::IsSuspended),
+            ("isEnabled", grove::Column::IsEnabled),
+        ],
+        &[grove::Column::Name],
+        "grove",
+        db,
+    )
+    .await
 }
 
 pub async fn create_grove(name: String, db: &DatabaseConnection) -> BambooResult<Grove> {
@@ -81,6 +89,32 @@ pub async fn create_grove(name: String, db: &DatabaseConnection) -> BambooResult
     })
 }
 
+/// The demo grove is marked, so resetting it can never wipe a grove of real pandas
+pub async fn create_demo_grove(name: String, db: &DatabaseConnection) -> BambooResult<Grove> {
+    let mut active_model = Grove {
+        is_demo: true,
+        ..Grove::new(name, false, true)
+    }
+    .into_active_model();
+    active_model.id = NotSet;
+
+    active_model.insert(db).await.map_err(|err| {
+        log::error!("Failed to create demo grove {err}");
+        BambooError::database("grove", "Failed to create demo grove")
+    })
+}
+
+pub(crate) async fn check_demo_grove(id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    if get_grove_by_id(id, db).await?.is_demo {
+        Ok(())
+    } else {
+        Err(BambooError::insufficient_rights(
+            "grove",
+            "Only the demo grove can be reset",
+        ))
+    }
+}
+
 pub async fn migrate_between_groves(
     old_grove_id: Option<i32>,
     new_grove_id: i32,
@@ -191,3 +225,23 @@ pub async fn resume_grove(id: i32, db: &DatabaseConnection) -> BambooErrorResult
         })
         .map(|_| ())
 }
+
+pub async fn set_grove_notify_mods_on_forgot_password(
+    id: i32,
+    notify_mods: bool,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    grove::Entity::update_many()
+        .col_expr(
+            grove::Column::NotifyModsOnForgotPassword,
+            Expr::value(notify_mods),
+        )
+        .filter(grove::Column::Id.eq(id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove", "Failed to update grove")
+        })
+        .map(|_| ())
+}
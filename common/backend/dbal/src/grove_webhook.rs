@@ -0,0 +1,113 @@
+use sea_orm::prelude::*;
+use sea_orm::{IntoActiveModel, NotSet, QueryOrder, QuerySelect, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::entities::{grove_webhook, grove_webhook_delivery};
+use bamboo_common_core::error::*;
+
+pub async fn get_grove_webhooks(
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<GroveWebhook>> {
+    grove_webhook::Entity::find()
+        .filter(grove_webhook::Column::GroveId.eq(grove_id))
+        .order_by_asc(grove_webhook::Column::Id)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load webhooks {err}");
+            BambooError::database("webhook", "Failed to load webhooks")
+        })
+}
+
+pub async fn get_grove_webhook(
+    id: i32,
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveWebhook> {
+    grove_webhook::Entity::find_by_id(id)
+        .filter(grove_webhook::Column::GroveId.eq(grove_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load webhook {err}");
+            BambooError::database("webhook", "Failed to load webhook")
+        })?
+        .ok_or(BambooError::not_found(
+            "webhook",
+            "The webhook was not found",
+        ))
+}
+
+pub async fn create_grove_webhook(
+    grove_id: i32,
+    webhook: GroveWebhook,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveWebhook> {
+    if !webhook.url.starts_with("http://") && !webhook.url.starts_with("https://") {
+        return Err(BambooError::validation(
+            "webhook",
+            "The url must start with http:// or https://",
+        ));
+    }
+
+    let mut model = webhook.into_active_model();
+    model.id = NotSet;
+    model.grove_id = Set(grove_id);
+    model.secret = Set(uuid::Uuid::new_v4().simple().to_string());
+
+    model.insert(db).await.map_err(|err| {
+        log::error!("Failed to create webhook {err}");
+        BambooError::database("webhook", "Failed to create webhook")
+    })
+}
+
+pub async fn delete_grove_webhook(
+    id: i32,
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    grove_webhook::Entity::delete_many()
+        .filter(grove_webhook::Column::Id.eq(id))
+        .filter(grove_webhook::Column::GroveId.eq(grove_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete webhook {err}");
+            BambooError::database("webhook", "Failed to delete webhook")
+        })
+        .map(|_| ())
+}
+
+pub async fn get_grove_webhook_deliveries(
+    webhook_id: i32,
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<GroveWebhookDelivery>> {
+    let webhook = get_grove_webhook(webhook_id, grove_id, db).await?;
+
+    grove_webhook_delivery::Entity::find()
+        .filter(grove_webhook_delivery::Column::WebhookId.eq(webhook.id))
+        .order_by_desc(grove_webhook_delivery::Column::DeliveredAt)
+        .order_by_desc(grove_webhook_delivery::Column::Id)
+        .limit(100)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load webhook deliveries {err}");
+            BambooError::database("webhook", "Failed to load webhook deliveries")
+        })
+}
+
+pub async fn create_grove_webhook_delivery(
+    delivery: GroveWebhookDelivery,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveWebhookDelivery> {
+    let mut model = delivery.into_active_model();
+    model.id = NotSet;
+
+    model.insert(db).await.map_err(|err| {
+        log::error!("Failed to save webhook delivery {err}");
+        BambooError::database("webhook", "Failed to save webhook delivery")
+    })
+}
@@ -0,0 +1,224 @@
+use chrono::{Duration, Utc};
+use sea_orm::prelude::*;
+use sea_orm::{IntoActiveModel, NotSet, QueryOrder, Set, TransactionTrait};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate::token::hash_token;
+
+fn generate_invitation_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+pub async fn get_invitations(
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<Invitation>> {
+    invitation::Entity::find()
+        .filter(invitation::Column::GroveId.eq(grove_id))
+        .order_by_desc(invitation::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load invitations {err}");
+            BambooError::database("invitation", "Failed to load invitations")
+        })
+}
+
+pub async fn get_invitation(
+    grove_id: i32,
+    id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Invitation> {
+    invitation::Entity::find_by_id(id)
+        .filter(invitation::Column::GroveId.eq(grove_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load invitation {err}");
+            BambooError::database("invitation", "Failed to load invitation")
+        })?
+        .ok_or(BambooError::not_found(
+            "invitation",
+            "The invitation was not found",
+        ))
+}
+
+/// Returns the invitation together with the raw token, only the hash is stored
+pub async fn create_invitation(
+    grove_id: i32,
+    invitation: Invitation,
+    invited_by: String,
+    lifetime: Duration,
+    db: &DatabaseConnection,
+) -> BambooResult<(Invitation, String)> {
+    user::validate_profile(&invitation.email, &invitation.display_name)?;
+
+    let email_taken = user::Entity::find()
+        .filter(user::Column::Email.eq(invitation.email.clone()))
+        .count(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load users {err}");
+            BambooError::database("invitation", "Failed to load users")
+        })?
+        > 0
+        || invitation::Entity::find()
+            .filter(invitation::Column::Email.eq(invitation.email.clone()))
+            .count(db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to load invitations {err}");
+                BambooError::database("invitation", "Failed to load invitations")
+            })?
+            > 0;
+    if email_taken {
+        return Err(BambooError::exists_already(
+            "invitation",
+            "A user or invitation with that email exists already",
+        ));
+    }
+
+    let now = Utc::now().naive_utc();
+    let raw_token = generate_invitation_token();
+
+    invitation::ActiveModel {
+        id: NotSet,
+        grove_id: Set(grove_id),
+        email: Set(invitation.email),
+        display_name: Set(invitation.display_name),
+        is_mod: Set(invitation.is_mod),
+        invited_by: Set(invited_by),
+        token: Set(hash_token(raw_token.as_str())),
+        created_at: Set(now),
+        expires_at: Set(now + lifetime),
+    }
+    .insert(db)
+    .await
+    .map(|invitation| (invitation, raw_token))
+    .map_err(|err| {
+        log::error!("Failed to create invitation {err}");
+        BambooError::database("invitation", "Failed to create invitation")
+    })
+}
+
+/// Replaces the token of the invitation, so links from earlier mails stop working
+pub async fn renew_invitation(
+    grove_id: i32,
+    id: i32,
+    lifetime: Duration,
+    db: &DatabaseConnection,
+) -> BambooResult<(Invitation, String)> {
+    let raw_token = generate_invitation_token();
+    let mut model = get_invitation(grove_id, id, db).await?.into_active_model();
+    model.token = Set(hash_token(raw_token.as_str()));
+    model.expires_at = Set(Utc::now().naive_utc() + lifetime);
+
+    model
+        .update(db)
+        .await
+        .map(|invitation| (invitation, raw_token))
+        .map_err(|err| {
+            log::error!("Failed to renew invitation {err}");
+            BambooError::database("invitation", "Failed to renew invitation")
+        })
+}
+
+pub async fn delete_invitation(
+    grove_id: i32,
+    id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    invitation::Entity::delete_many()
+        .filter(invitation::Column::Id.eq(id))
+        .filter(invitation::Column::GroveId.eq(grove_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete invitation {err}");
+            BambooError::database("invitation", "Failed to delete invitation")
+        })
+        .and_then(|res| {
+            if res.rows_affected == 0 {
+                Err(BambooError::not_found(
+                    "invitation",
+                    "The invitation was not found",
+                ))
+            } else {
+                Ok(())
+            }
+        })
+}
+
+pub async fn accept_invitation(
+    token: String,
+    display_name: String,
+    password: String,
+    db: &DatabaseConnection,
+) -> BambooResult<User> {
+    if display_name.trim().is_empty() || password.is_empty() {
+        return Err(BambooError::validation(
+            "invitation",
+            "The name and password must not be empty",
+        ));
+    }
+
+    let invitation = invitation::Entity::find()
+        .filter(invitation::Column::Token.eq(hash_token(token.as_str())))
+        .filter(invitation::Column::ExpiresAt.gt(Utc::now().naive_utc()))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load invitation {err}");
+            BambooError::database("invitation", "Failed to load invitation")
+        })?
+        .ok_or(BambooError::unauthorized(
+            "invitation",
+            "The invitation is invalid or expired",
+        ))?;
+
+    // The user only exists when the invitation is consumed, a second accept with the same link finds nothing to delete
+    let txn = db.begin().await.map_err(|err| {
+        log::error!("Failed to start transaction {err}");
+        BambooError::database("invitation", "Failed to accept invitation")
+    })?;
+
+    let deleted = invitation::Entity::delete_by_id(invitation.id)
+        .exec(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete invitation {err}");
+            BambooError::database("invitation", "Failed to delete invitation")
+        })?;
+    if deleted.rows_affected == 0 {
+        return Err(BambooError::unauthorized(
+            "invitation",
+            "The invitation is invalid or expired",
+        ));
+    }
+
+    let user = crate::user::insert_user(
+        invitation.grove_id,
+        User::new(
+            invitation.email.clone(),
+            display_name.trim().to_string(),
+            "".into(),
+            invitation.is_mod,
+        ),
+        password,
+        &txn,
+    )
+    .await?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit transaction {err}");
+        BambooError::database("invitation", "Failed to accept invitation")
+    })?;
+
+    Ok(user)
+}
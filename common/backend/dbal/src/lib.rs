@@ -5,28 +5,59 @@ use sha2::Sha512;
 
 use bamboo_common_core::error::*;
 
+pub use crate::audit_log::*;
 pub use crate::authentication::*;
 pub use crate::character::*;
 pub use crate::character_housing::*;
 pub use crate::crafter::*;
 pub use crate::custom_field::*;
 pub use crate::event::*;
+pub use crate::event_attendance::*;
+pub use crate::event_party_slot::*;
+pub use crate::event_reminder::*;
 pub use crate::fighter::*;
 pub use crate::free_company::*;
 pub use crate::grove::*;
+pub use crate::grove_webhook::*;
+pub use crate::invitation::*;
+pub use crate::login_attempt::*;
 pub use crate::my::*;
+pub use crate::passkey::*;
+pub use crate::password_reset::*;
+pub use crate::personal_access_token::*;
+pub use crate::recovery_code::*;
+pub use crate::role::*;
+pub use crate::token::*;
+pub use crate::trash::*;
 pub use crate::user::*;
 
+mod audit_log;
 mod authentication;
 mod character;
 mod character_housing;
 mod crafter;
 mod custom_field;
 mod event;
+mod event_attendance;
+mod event_party_slot;
+mod event_reminder;
 mod fighter;
 mod free_company;
 mod grove;
+mod grove_webhook;
+mod invitation;
+mod list;
+mod login_attempt;
 mod my;
+mod passkey;
+mod password_reset;
+mod personal_access_token;
+mod recovery_code;
+mod role;
+#[cfg(test)]
+mod test_database;
+mod token;
+mod trash;
 mod user;
 
 fn get_passphrase(passphrase: &[u8]) -> BambooResult<Key> {
@@ -0,0 +1,74 @@
+use sea_orm::prelude::*;
+use sea_orm::sea_query::{Expr, Func};
+use sea_orm::{Condition, DatabaseConnection, Iterable, Order, QueryOrder, QuerySelect, Select};
+
+use bamboo_common_backend_response::{ListQuery, Page};
+use bamboo_common_core::error::*;
+
+/// Matches when any of the columns contains the filter text, the case is ignored
+pub(crate) fn filter_condition<C: ColumnTrait>(columns: &[C], filter: &str) -> Condition {
+    let pattern = format!(
+        "%{}%",
+        filter
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+
+    columns.iter().fold(Condition::any(), |condition, column| {
+        condition.add(Expr::expr(Func::lower(Expr::col(column.as_column_ref()))).like(&pattern))
+    })
+}
+
+/// Loads the page of the list query, the first sort column is used if no sort field is requested
+pub(crate) async fn load_page<E: EntityTrait>(
+    select: Select<E>,
+    query: &ListQuery,
+    sort_columns: &[(&str, E::Column)],
+    filter_columns: &[E::Column],
+    entity: &str,
+    db: &DatabaseConnection,
+) -> BambooResult<Page<E::Model>>
+where
+    E::Model: Sync,
+{
+    let (column, order) = if let Some((field, descending)) = query.sort_field() {
+        let column = sort_columns
+            .iter()
+            .find(|(name, _)| *name == field)
+            .map(|(_, column)| *column)
+            .ok_or_else(|| BambooError::invalid_data(entity, format!("Cannot sort by {field}")))?;
+
+        (column, if descending { Order::Desc } else { Order::Asc })
+    } else {
+        (sort_columns[0].1, Order::Asc)
+    };
+
+    let mut select = select;
+    if let Some(filter) = query.filter_text() {
+        select = select.filter(filter_condition(filter_columns, filter.as_str()));
+    }
+
+    let total = select.clone().count(db).await.map_err(|err| {
+        log::error!("Failed to count {entity} {err}");
+        BambooError::database(entity, "Failed to load the list")
+    })?;
+
+    // The primary key keeps the order stable, otherwise entries with the same value could show up on two pages
+    let mut select = select.order_by(column, order);
+    for key in E::PrimaryKey::iter() {
+        select = select.order_by_asc(key.into_column());
+    }
+    if let Some(offset) = query.offset() {
+        select = select.offset(offset).limit(query.limit());
+    }
+
+    select
+        .all(db)
+        .await
+        .map(|entries| Page::new(entries, total))
+        .map_err(|err| {
+            log::error!("Failed to load {entity} {err}");
+            BambooError::database(entity, "Failed to load the list")
+        })
+}
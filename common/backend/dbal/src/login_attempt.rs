@@ -0,0 +1,132 @@
+use chrono::{Duration, Utc};
+use sea_orm::prelude::*;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{NotSet, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+async fn get_login_attempt(
+    key: &str,
+    db: &DatabaseConnection,
+) -> BambooResult<Option<LoginAttempt>> {
+    login_attempt::Entity::find()
+        .filter(login_attempt::Column::Key.eq(key))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load login attempts {err}");
+            BambooError::database("login", "Failed to load login attempts")
+        })
+}
+
+pub async fn check_login_attempts(key: &str, db: &DatabaseConnection) -> BambooErrorResult {
+    let now = Utc::now().naive_utc();
+    if get_login_attempt(key, db)
+        .await?
+        .and_then(|attempt| attempt.locked_until)
+        .is_some_and(|locked_until| locked_until > now)
+    {
+        Err(BambooError::too_many_requests(
+            "login",
+            "Too many failed attempts, try again later",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns true when this attempt locked the key for the first time since the last successful login
+pub async fn record_failed_login_attempt(
+    key: String,
+    max_attempts: i32,
+    lockout: Duration,
+    max_lockout: Duration,
+    db: &DatabaseConnection,
+) -> BambooResult<bool> {
+    let now = Utc::now().naive_utc();
+
+    // The counter is increased by the database, concurrent failed logins cannot overwrite each other.
+    // Failures that happened longer ago than the longest lockout are forgotten
+    let attempt = login_attempt::Entity::insert(login_attempt::ActiveModel {
+        id: NotSet,
+        key: Set(key),
+        failed_attempts: Set(1),
+        last_failed_at: Set(now),
+        locked_until: Set(None),
+    })
+    .on_conflict(
+        OnConflict::column(login_attempt::Column::Key)
+            .value(
+                login_attempt::Column::FailedAttempts,
+                Expr::case(
+                    Expr::col((login_attempt::Entity, login_attempt::Column::LastFailedAt))
+                        .lt(now - max_lockout),
+                    1,
+                )
+                .finally(
+                    Expr::col((login_attempt::Entity, login_attempt::Column::FailedAttempts))
+                        .add(1),
+                ),
+            )
+            .value(login_attempt::Column::LastFailedAt, now)
+            .to_owned(),
+    )
+    .exec_with_returning(db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to save login attempt {err}");
+        BambooError::database("login", "Failed to save login attempt")
+    })?;
+
+    let failed_attempts = attempt.failed_attempts;
+    let locked_until = if failed_attempts >= max_attempts {
+        let backoff = 2_i32.saturating_pow((failed_attempts - max_attempts).min(16) as u32);
+        Some(now + (lockout * backoff).min(max_lockout))
+    } else {
+        None
+    };
+
+    // A concurrent attempt that counted further sets its own, longer lockout
+    login_attempt::Entity::update_many()
+        .col_expr(
+            login_attempt::Column::LockedUntil,
+            Expr::value(locked_until),
+        )
+        .filter(login_attempt::Column::Id.eq(attempt.id))
+        .filter(login_attempt::Column::FailedAttempts.eq(failed_attempts))
+        .exec(db)
+        .await
+        .map(|_| failed_attempts == max_attempts)
+        .map_err(|err| {
+            log::error!("Failed to save login attempt {err}");
+            BambooError::database("login", "Failed to save login attempt")
+        })
+}
+
+pub async fn reset_login_attempts(key: &str, db: &DatabaseConnection) -> BambooErrorResult {
+    login_attempt::Entity::delete_many()
+        .filter(login_attempt::Column::Key.eq(key))
+        .exec(db)
+        .await
+        .map(|_| ())
+        .map_err(|err| {
+            log::error!("Failed to reset login attempts {err}");
+            BambooError::database("login", "Failed to reset login attempts")
+        })
+}
+
+pub async fn delete_stale_login_attempts(
+    max_lockout: Duration,
+    db: &DatabaseConnection,
+) -> BambooResult<u64> {
+    login_attempt::Entity::delete_many()
+        .filter(login_attempt::Column::LastFailedAt.lt(Utc::now().naive_utc() - max_lockout))
+        .exec(db)
+        .await
+        .map(|res| res.rows_affected)
+        .map_err(|err| {
+            log::error!("Failed to delete stale login attempts {err}");
+            BambooError::database("login", "Failed to delete stale login attempts")
+        })
+}
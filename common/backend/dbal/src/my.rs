@@ -5,6 +5,7 @@ use bamboo_common_core::entities::*;
 use bamboo_common_core::error::*;
 
 use crate as dbal;
+use crate::recovery_code::{create_recovery_codes, delete_recovery_codes};
 use crate::{decrypt_string, encrypt_string};
 
 pub async fn change_my_password(
@@ -77,6 +78,8 @@ pub async fn enable_my_totp(
 }
 
 pub async fn disable_my_totp(id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    delete_recovery_codes(id, db).await?;
+
     user::Entity::update_many()
         .col_expr(
             user::Column::TotpSecret,
@@ -96,7 +99,7 @@ pub async fn validate_my_totp(
     password: String,
     code: String,
     db: &DatabaseConnection,
-) -> BambooResult<bool> {
+) -> BambooResult<Option<Vec<String>>> {
     let user = dbal::get_user_by_id_only(id, db).await?;
     let valid = dbal::validate_login(id, code, password.clone(), true, db)
         .await
@@ -110,8 +113,13 @@ pub async fn validate_my_totp(
         .filter(user::Column::Id.eq(id))
         .exec(db)
         .await
-        .map_err(|_| BambooError::database("user", "Totp could not be validated"))
-        .map(|_| valid)
+        .map_err(|_| BambooError::database("user", "Totp could not be validated"))?;
+
+    if valid {
+        create_recovery_codes(id, db).await.map(Some)
+    } else {
+        Ok(None)
+    }
 }
 
 pub async fn update_my_profile(
@@ -121,12 +129,9 @@ pub async fn update_my_profile(
     discord_name: String,
     db: &DatabaseConnection,
 ) -> BambooErrorResult {
-    if dbal::user_exists_by_id(id, email.clone(), display_name.clone(), db).await? {
-        return Err(BambooError::exists_already(
-            "user",
-            "A user with that email or name exists already",
-        ));
-    }
+    user::validate_profile(&email, &display_name)?;
+
+    dbal::check_user_unique_by_id(id, email.clone(), display_name.clone(), db).await?;
 
     user::Entity::update_many()
         .col_expr(user::Column::Email, Expr::value(email))
@@ -141,3 +146,51 @@ pub async fn update_my_profile(
         })
         .map(|_| ())
 }
+
+pub async fn get_my_calendar_feed_secret(id: i32, db: &DatabaseConnection) -> BambooResult<String> {
+    dbal::get_user_by_id_only(id, db)
+        .await?
+        .calendar_feed_secret
+        .ok_or(BambooError::not_found(
+            "user",
+            "The calendar feed was not created yet",
+        ))
+}
+
+pub async fn rotate_my_calendar_feed_secret(
+    id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<String> {
+    let secret = uuid::Uuid::new_v4().simple().to_string();
+
+    user::Entity::update_many()
+        .col_expr(
+            user::Column::CalendarFeedSecret,
+            Expr::value(secret.clone()),
+        )
+        .filter(user::Column::Id.eq(id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to rotate the calendar feed")
+        })
+        .map(|_| secret)
+}
+
+pub async fn set_my_event_reminders(
+    id: i32,
+    event_reminders: bool,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    user::Entity::update_many()
+        .col_expr(user::Column::EventReminders, Expr::value(event_reminders))
+        .filter(user::Column::Id.eq(id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to change the event reminders")
+        })
+        .map(|_| ())
+}
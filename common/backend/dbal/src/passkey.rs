@@ -0,0 +1,864 @@
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{Duration, NaiveDateTime, Utc};
+use rand::RngCore;
+use ring::signature;
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{DatabaseBackend, NotSet, QueryOrder, Set, Statement};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::entities::{passkey, passkey_challenge};
+use bamboo_common_core::error::*;
+
+use crate::token::create_token;
+
+const ALGORITHM_ES256: i32 = -7;
+const ALGORITHM_EDDSA: i32 = -8;
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+#[derive(Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    ceremony: String,
+    challenge: String,
+    origin: String,
+}
+
+struct AuthenticatorData {
+    flags: u8,
+    sign_count: u32,
+    credential_id: Option<Vec<u8>>,
+    credential_public_key: Option<Vec<u8>>,
+}
+
+// DER prefixes turning the raw key into the SubjectPublicKeyInfo browsers hand out
+const SPKI_PREFIX_ES256: [u8; 26] = [
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+];
+const SPKI_PREFIX_EDDSA: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+fn challenge_lifetime() -> Duration {
+    Duration::minutes(5)
+}
+
+fn decode(value: &str) -> BambooResult<Vec<u8>> {
+    BASE64_URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| BambooError::invalid_data("passkey", "Invalid base64url data"))
+}
+
+fn parse_client_data(
+    client_data_json: &[u8],
+    ceremony: &str,
+    origin: &str,
+) -> BambooResult<ClientData> {
+    let client_data = serde_json::from_slice::<ClientData>(client_data_json).map_err(|err| {
+        log::error!("Failed to parse client data {err}");
+        BambooError::invalid_data("passkey", "Invalid client data")
+    })?;
+
+    if client_data.ceremony != ceremony {
+        return Err(BambooError::unauthorized(
+            "passkey",
+            "Client data has the wrong type",
+        ));
+    }
+    if client_data.origin != origin {
+        log::warn!(
+            "Passkey ceremony from unexpected origin {}",
+            client_data.origin
+        );
+        return Err(BambooError::unauthorized(
+            "passkey",
+            "Client data has the wrong origin",
+        ));
+    }
+
+    Ok(client_data)
+}
+
+fn parse_authenticator_data(data: &[u8], rp_id: &str) -> BambooResult<AuthenticatorData> {
+    let invalid = BambooError::invalid_data("passkey", "Invalid authenticator data");
+    if data.len() < 37 {
+        return Err(invalid);
+    }
+
+    if data[..32] != Sha256::digest(rp_id.as_bytes())[..] {
+        return Err(BambooError::unauthorized(
+            "passkey",
+            "Authenticator data belongs to another relying party",
+        ));
+    }
+
+    let flags = data[32];
+    let sign_count = u32::from_be_bytes([data[33], data[34], data[35], data[36]]);
+    let (credential_id, credential_public_key) = if flags & FLAG_ATTESTED_CREDENTIAL_DATA != 0 {
+        // 16 bytes aaguid followed by the big endian length of the credential id
+        if data.len() < 55 {
+            return Err(invalid);
+        }
+        let length = u16::from_be_bytes([data[53], data[54]]) as usize;
+        let credential_id = data.get(55..55 + length).ok_or(invalid.clone())?;
+        // The COSE key follows the credential id
+        let credential_public_key = data.get(55 + length..).ok_or(invalid)?;
+
+        (
+            Some(credential_id.to_vec()),
+            Some(credential_public_key.to_vec()),
+        )
+    } else {
+        (None, None)
+    };
+
+    Ok(AuthenticatorData {
+        flags,
+        sign_count,
+        credential_id,
+        credential_public_key,
+    })
+}
+
+// Reads the head of a CBOR item and returns its major type and argument
+fn read_cbor_head(data: &[u8], position: &mut usize) -> BambooResult<(u8, u64)> {
+    let invalid = BambooError::invalid_data("passkey", "Invalid public key");
+    let initial = *data.get(*position).ok_or(invalid.clone())?;
+    *position += 1;
+
+    let length = match initial & 0x1f {
+        argument @ 0..=23 => return Ok((initial >> 5, argument as u64)),
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        _ => return Err(invalid),
+    };
+    let argument = data
+        .get(*position..*position + length)
+        .ok_or(invalid)?
+        .iter()
+        .fold(0, |argument, byte| argument << 8 | *byte as u64);
+    *position += length;
+
+    Ok((initial >> 5, argument))
+}
+
+// COSE keys are a CBOR map, we only need the algorithm (3) and the coordinates x (-2) and y (-3)
+fn parse_cose_key(data: &[u8]) -> BambooResult<(i32, Vec<u8>)> {
+    let invalid = BambooError::invalid_data("passkey", "Invalid public key");
+    let mut position = 0;
+    let (major, entries) = read_cbor_head(data, &mut position)?;
+    if major != 5 {
+        return Err(invalid);
+    }
+
+    let mut algorithm = None;
+    let mut x = None;
+    let mut y = None;
+    for _ in 0..entries {
+        let key = match read_cbor_head(data, &mut position)? {
+            (0, argument) => argument as i64,
+            (1, argument) => -1 - argument as i64,
+            _ => return Err(invalid),
+        };
+        match read_cbor_head(data, &mut position)? {
+            (0, argument) if key == 3 => algorithm = Some(argument as i64),
+            (1, argument) if key == 3 => algorithm = Some(-1 - argument as i64),
+            (0 | 1, _) => {}
+            (major @ (2 | 3), length) => {
+                let end = position
+                    .checked_add(length as usize)
+                    .ok_or(invalid.clone())?;
+                let value = data.get(position..end).ok_or(invalid.clone())?;
+                position = end;
+                match (major, key) {
+                    (2, -2) => x = Some(value),
+                    (2, -3) => y = Some(value),
+                    _ => {}
+                }
+            }
+            _ => return Err(invalid),
+        }
+    }
+
+    let algorithm = algorithm.ok_or(invalid.clone())? as i32;
+    let raw_key = match (algorithm, x, y) {
+        (ALGORITHM_ES256, Some(x), Some(y)) if x.len() == 32 && y.len() == 32 => {
+            [&[0x04], x, y].concat()
+        }
+        (ALGORITHM_EDDSA, Some(x), _) if x.len() == 32 => x.to_vec(),
+        _ => {
+            return Err(BambooError::invalid_data(
+                "passkey",
+                "The public key algorithm is not supported",
+            ))
+        }
+    };
+
+    Ok((algorithm, subject_public_key_info(algorithm, &raw_key)))
+}
+
+fn subject_public_key_info(algorithm: i32, raw_key: &[u8]) -> Vec<u8> {
+    let prefix: &[u8] = if algorithm == ALGORITHM_ES256 {
+        &SPKI_PREFIX_ES256
+    } else {
+        &SPKI_PREFIX_EDDSA
+    };
+
+    [prefix, raw_key].concat()
+}
+
+// The browser hands out the key as SubjectPublicKeyInfo, ring only needs the raw key at its end
+fn raw_public_key(public_key: &[u8], algorithm: i32) -> BambooResult<&[u8]> {
+    let length = match algorithm {
+        ALGORITHM_ES256 if public_key.len() == 91 => 65,
+        ALGORITHM_EDDSA if public_key.len() == 44 => 32,
+        _ => {
+            return Err(BambooError::invalid_data(
+                "passkey",
+                "The public key algorithm is not supported",
+            ))
+        }
+    };
+
+    Ok(&public_key[public_key.len() - length..])
+}
+
+// The authenticator signs its data followed by the hash of the client data
+fn verify_signature(
+    public_key: &[u8],
+    algorithm: i32,
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+    signature: &[u8],
+) -> BambooErrorResult {
+    let verification_algorithm: &dyn signature::VerificationAlgorithm = match algorithm {
+        ALGORITHM_ES256 => &signature::ECDSA_P256_SHA256_ASN1,
+        _ => &signature::ED25519,
+    };
+    let mut signed_data = authenticator_data.to_vec();
+    signed_data.extend_from_slice(&Sha256::digest(client_data_json));
+
+    signature::UnparsedPublicKey::new(
+        verification_algorithm,
+        raw_public_key(public_key, algorithm)?,
+    )
+    .verify(&signed_data, signature)
+    .map_err(|_| BambooError::unauthorized("passkey", "Invalid login data"))
+}
+
+// A challenge that was already taken is gone, so it cannot be used a second time
+fn check_passkey_challenge(
+    challenge: Option<PasskeyChallenge>,
+    now: NaiveDateTime,
+) -> BambooResult<PasskeyChallenge> {
+    challenge
+        .filter(|challenge| now - challenge.created_at <= challenge_lifetime())
+        .ok_or(BambooError::unauthorized(
+            "passkey",
+            "The challenge is invalid or expired",
+        ))
+}
+
+async fn take_passkey_challenge(
+    challenge: String,
+    db: &DatabaseConnection,
+) -> BambooResult<PasskeyChallenge> {
+    // Loading and deleting in one statement, only one of two concurrent requests gets the challenge
+    let stored_challenge = passkey_challenge::Entity::find()
+        .from_raw_sql(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "DELETE FROM authentication.passkey_challenge WHERE challenge = $1 RETURNING *",
+            [challenge.into()],
+        ))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to take passkey challenge {err}");
+            BambooError::database("passkey", "Failed to load passkey challenge")
+        })?;
+
+    check_passkey_challenge(stored_challenge, Utc::now().naive_utc())
+}
+
+pub async fn create_passkey_challenge(
+    user_id: Option<i32>,
+    db: &DatabaseConnection,
+) -> BambooResult<String> {
+    let now = Utc::now().naive_utc();
+    let _ = passkey_challenge::Entity::delete_many()
+        .filter(passkey_challenge::Column::CreatedAt.lt(now - challenge_lifetime()))
+        .exec(db)
+        .await;
+
+    let mut challenge = [0_u8; 32];
+    rand::thread_rng().fill_bytes(&mut challenge);
+    let challenge = BASE64_URL_SAFE_NO_PAD.encode(challenge);
+
+    passkey_challenge::ActiveModel {
+        id: NotSet,
+        challenge: Set(challenge.clone()),
+        user_id: Set(user_id),
+        created_at: Set(now),
+    }
+    .insert(db)
+    .await
+    .map(|_| challenge)
+    .map_err(|err| {
+        log::error!("Failed to create passkey challenge {err}");
+        BambooError::database("passkey", "Failed to create passkey challenge")
+    })
+}
+
+pub async fn get_passkeys(user_id: i32, db: &DatabaseConnection) -> BambooResult<Vec<Passkey>> {
+    passkey::Entity::find()
+        .filter(passkey::Column::UserId.eq(user_id))
+        .order_by_asc(passkey::Column::Name)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load passkeys {err}");
+            BambooError::database("passkey", "Failed to load passkeys")
+        })
+}
+
+pub async fn create_passkey(
+    user_id: i32,
+    registration: PasskeyRegistration,
+    rp_id: &str,
+    origin: &str,
+    db: &DatabaseConnection,
+) -> BambooResult<Passkey> {
+    if registration.name.trim().is_empty() {
+        return Err(BambooError::validation(
+            "passkey",
+            "The passkey needs a name",
+        ));
+    }
+
+    let client_data = parse_client_data(
+        &decode(registration.client_data_json.as_str())?,
+        "webauthn.create",
+        origin,
+    )?;
+    let challenge = take_passkey_challenge(client_data.challenge, db).await?;
+    if challenge.user_id != Some(user_id) {
+        return Err(BambooError::unauthorized(
+            "passkey",
+            "The challenge belongs to another user",
+        ));
+    }
+
+    let authenticator_data =
+        parse_authenticator_data(&decode(registration.authenticator_data.as_str())?, rp_id)?;
+    if authenticator_data.flags & FLAG_USER_PRESENT == 0 {
+        return Err(BambooError::unauthorized(
+            "passkey",
+            "The user was not present",
+        ));
+    }
+
+    let credential_id = decode(registration.credential_id.as_str())?;
+    if authenticator_data.credential_id != Some(credential_id.clone()) {
+        return Err(BambooError::invalid_data(
+            "passkey",
+            "The credential id does not match the authenticator data",
+        ));
+    }
+
+    // The key is taken from the signed authenticator data, so nobody can register a key the authenticator does not own
+    let (algorithm, public_key) =
+        parse_cose_key(&authenticator_data.credential_public_key.ok_or(
+            BambooError::invalid_data("passkey", "The authenticator data contain no public key"),
+        )?)?;
+
+    let credential_id = BASE64_URL_SAFE_NO_PAD.encode(credential_id);
+    let exists = passkey::Entity::find()
+        .filter(passkey::Column::CredentialId.eq(credential_id.clone()))
+        .count(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to check passkey {err}");
+            BambooError::database("passkey", "Failed to check passkey")
+        })?
+        > 0;
+    if exists {
+        return Err(BambooError::exists_already(
+            "passkey",
+            "The passkey is already registered",
+        ));
+    }
+
+    passkey::ActiveModel {
+        id: NotSet,
+        user_id: Set(user_id),
+        name: Set(registration.name.trim().to_string()),
+        credential_id: Set(credential_id),
+        public_key: Set(BASE64_URL_SAFE_NO_PAD.encode(public_key)),
+        algorithm: Set(algorithm),
+        sign_count: Set(authenticator_data.sign_count as i64),
+        created_at: Set(Utc::now().naive_utc()),
+        last_used_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!("Failed to create passkey {err}");
+        BambooError::database("passkey", "Failed to create passkey")
+    })
+}
+
+pub async fn delete_passkey(id: i32, user_id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    passkey::Entity::delete_many()
+        .filter(passkey::Column::Id.eq(id))
+        .filter(passkey::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete passkey {err}");
+            BambooError::database("passkey", "Failed to delete passkey")
+        })
+        .and_then(|res| {
+            if res.rows_affected == 0 {
+                Err(BambooError::not_found("passkey", "Passkey not found"))
+            } else {
+                Ok(())
+            }
+        })
+}
+
+pub async fn validate_passkey_and_create_token(
+    login: PasskeyLogin,
+    rp_id: &str,
+    origin: &str,
+    user_agent: Option<String>,
+    idle_timeout: Duration,
+    db: &DatabaseConnection,
+) -> BambooResult<LoginResult> {
+    let invalid_login = BambooError::unauthorized("passkey", "Invalid login data");
+
+    let client_data_json = decode(login.client_data_json.as_str())?;
+    let client_data = parse_client_data(&client_data_json, "webauthn.get", origin)?;
+    let challenge = take_passkey_challenge(client_data.challenge, db).await?;
+
+    let credential_id = BASE64_URL_SAFE_NO_PAD.encode(decode(login.credential_id.as_str())?);
+    let stored_passkey = passkey::Entity::find()
+        .filter(passkey::Column::CredentialId.eq(credential_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load passkey {err}");
+            BambooError::database("passkey", "Failed to load passkey")
+        })?
+        .ok_or(invalid_login.clone())?;
+    if challenge
+        .user_id
+        .is_some_and(|user_id| user_id != stored_passkey.user_id)
+    {
+        return Err(invalid_login);
+    }
+
+    let raw_authenticator_data = decode(login.authenticator_data.as_str())?;
+    let authenticator_data = parse_authenticator_data(&raw_authenticator_data, rp_id)?;
+    // Passkeys replace password and second factor, so the authenticator has to verify the user itself
+    if authenticator_data.flags & FLAG_USER_PRESENT == 0
+        || authenticator_data.flags & FLAG_USER_VERIFIED == 0
+    {
+        return Err(BambooError::unauthorized(
+            "passkey",
+            "The user was not verified",
+        ));
+    }
+
+    verify_signature(
+        &decode(stored_passkey.public_key.as_str())?,
+        stored_passkey.algorithm,
+        &raw_authenticator_data,
+        &client_data_json,
+        &decode(login.signature.as_str())?,
+    )?;
+
+    // Authenticators without a counter always report zero, everything else has to move forward.
+    // Checking the count in the update itself keeps two concurrent logins from passing with the same count
+    let sign_count = authenticator_data.sign_count as i64;
+    let sign_count_condition = if sign_count == 0 {
+        passkey::Column::SignCount.eq(0)
+    } else {
+        passkey::Column::SignCount.lt(sign_count)
+    };
+    let updated = passkey::Entity::update_many()
+        .col_expr(passkey::Column::SignCount, Expr::value(sign_count))
+        .col_expr(
+            passkey::Column::LastUsedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .filter(passkey::Column::Id.eq(stored_passkey.id))
+        .filter(sign_count_condition)
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to update passkey {err}");
+            BambooError::database("passkey", "Failed to update passkey")
+        })?;
+    if updated.rows_affected == 0 {
+        log::warn!(
+            "Passkey {} reported a stale sign count, it might be cloned",
+            stored_passkey.id
+        );
+        return Err(invalid_login);
+    }
+
+    let user = crate::user::get_user_by_id_only(stored_passkey.user_id, db).await?;
+
+    create_token(user.id, user_agent, idle_timeout, db)
+        .await
+        .map(|token| LoginResult {
+            token: token.token,
+            user: user.clone().into(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, Ed25519KeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+
+    use crate::test_database::TestDatabase;
+
+    use super::*;
+
+    const RP_ID: &str = "bambushain.app";
+    const ORIGIN: &str = "https://bambushain.app";
+
+    fn key_pair() -> Ed25519KeyPair {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+    }
+
+    // Map with kty OKP, alg EdDSA, crv Ed25519 and the key as x
+    fn cose_key(raw_key: &[u8]) -> Vec<u8> {
+        [
+            &[0xa4, 0x01, 0x01, 0x03, 0x27, 0x20, 0x06, 0x21, 0x58, 0x20],
+            raw_key,
+        ]
+        .concat()
+    }
+
+    fn authenticator_data(
+        rp_id: &str,
+        sign_count: u32,
+        credential: Option<(&[u8], &[u8])>,
+    ) -> Vec<u8> {
+        let mut data = Sha256::digest(rp_id.as_bytes()).to_vec();
+        if let Some((credential_id, public_key)) = credential {
+            data.push(FLAG_USER_PRESENT | FLAG_USER_VERIFIED | FLAG_ATTESTED_CREDENTIAL_DATA);
+            data.extend_from_slice(&sign_count.to_be_bytes());
+            data.extend_from_slice(&[0; 16]);
+            data.extend_from_slice(&(credential_id.len() as u16).to_be_bytes());
+            data.extend_from_slice(credential_id);
+            data.extend_from_slice(public_key);
+        } else {
+            data.push(FLAG_USER_PRESENT | FLAG_USER_VERIFIED);
+            data.extend_from_slice(&sign_count.to_be_bytes());
+        }
+
+        data
+    }
+
+    fn client_data(ceremony: &str, origin: &str) -> Vec<u8> {
+        client_data_with_challenge(ceremony, origin, "challenge")
+    }
+
+    fn client_data_with_challenge(ceremony: &str, origin: &str, challenge: &str) -> Vec<u8> {
+        serde_json::json!({
+            "type": ceremony,
+            "challenge": challenge,
+            "origin": origin,
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    enum SoftwareKey {
+        Es256(EcdsaKeyPair),
+        Ed25519(Ed25519KeyPair),
+    }
+
+    // Plays the browser and the authenticator in the ceremonies
+    struct SoftwareAuthenticator {
+        key: SoftwareKey,
+        credential_id: Vec<u8>,
+        sign_count: u32,
+    }
+
+    impl SoftwareAuthenticator {
+        fn es256() -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 =
+                EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+            let key_pair =
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng)
+                    .unwrap();
+
+            Self::new(SoftwareKey::Es256(key_pair))
+        }
+
+        fn ed25519() -> Self {
+            Self::new(SoftwareKey::Ed25519(key_pair()))
+        }
+
+        fn new(key: SoftwareKey) -> Self {
+            let mut credential_id = vec![0; 16];
+            rand::thread_rng().fill_bytes(&mut credential_id);
+
+            Self {
+                key,
+                credential_id,
+                sign_count: 0,
+            }
+        }
+
+        fn cose_key(&self) -> Vec<u8> {
+            match &self.key {
+                // Map with kty EC2, alg ES256, crv P-256 and the coordinates x and y
+                SoftwareKey::Es256(key_pair) => {
+                    let public_key = key_pair.public_key().as_ref();
+                    [
+                        &[0xa5, 0x01, 0x02, 0x03, 0x26, 0x20, 0x01, 0x21, 0x58, 0x20],
+                        &public_key[1..33],
+                        &[0x22, 0x58, 0x20],
+                        &public_key[33..],
+                    ]
+                    .concat()
+                }
+                SoftwareKey::Ed25519(key_pair) => cose_key(key_pair.public_key().as_ref()),
+            }
+        }
+
+        fn sign(&self, data: &[u8]) -> Vec<u8> {
+            match &self.key {
+                SoftwareKey::Es256(key_pair) => key_pair
+                    .sign(&SystemRandom::new(), data)
+                    .unwrap()
+                    .as_ref()
+                    .to_vec(),
+                SoftwareKey::Ed25519(key_pair) => key_pair.sign(data).as_ref().to_vec(),
+            }
+        }
+
+        fn register(&mut self, challenge: &str) -> PasskeyRegistration {
+            self.sign_count += 1;
+            let authenticator_data = authenticator_data(
+                RP_ID,
+                self.sign_count,
+                Some((&self.credential_id, &self.cose_key())),
+            );
+
+            PasskeyRegistration {
+                name: "Software".to_string(),
+                credential_id: BASE64_URL_SAFE_NO_PAD.encode(&self.credential_id),
+                client_data_json: BASE64_URL_SAFE_NO_PAD.encode(client_data_with_challenge(
+                    "webauthn.create",
+                    ORIGIN,
+                    challenge,
+                )),
+                authenticator_data: BASE64_URL_SAFE_NO_PAD.encode(authenticator_data),
+            }
+        }
+
+        fn assert(&mut self, challenge: &str) -> PasskeyLogin {
+            self.sign_count += 1;
+            let authenticator_data = authenticator_data(RP_ID, self.sign_count, None);
+            let client_data = client_data_with_challenge("webauthn.get", ORIGIN, challenge);
+            let signature = self.sign(
+                &[
+                    authenticator_data.as_slice(),
+                    &Sha256::digest(&client_data)[..],
+                ]
+                .concat(),
+            );
+
+            PasskeyLogin {
+                credential_id: BASE64_URL_SAFE_NO_PAD.encode(&self.credential_id),
+                client_data_json: BASE64_URL_SAFE_NO_PAD.encode(client_data),
+                authenticator_data: BASE64_URL_SAFE_NO_PAD.encode(authenticator_data),
+                signature: BASE64_URL_SAFE_NO_PAD.encode(signature),
+            }
+        }
+    }
+
+    async fn login(login: PasskeyLogin, db: &DatabaseConnection) -> BambooResult<LoginResult> {
+        validate_passkey_and_create_token(login, RP_ID, ORIGIN, None, Duration::days(30), db).await
+    }
+
+    async fn register_and_login(mut authenticator: SoftwareAuthenticator, algorithm: i32) {
+        let database = TestDatabase::migrated().await;
+        database
+            .execute("INSERT INTO grove.grove (name) VALUES ('Grove')")
+            .await;
+        database
+            .execute("INSERT INTO authentication.user (email, password, display_name, discord_name, grove_id) VALUES ('panda@example.com', '', 'Panda', '', 1)")
+            .await;
+        let db = &database.db;
+
+        let challenge = create_passkey_challenge(Some(1), db).await.unwrap();
+        let passkey = create_passkey(
+            1,
+            authenticator.register(challenge.as_str()),
+            RP_ID,
+            ORIGIN,
+            db,
+        )
+        .await
+        .unwrap();
+        assert_eq!(passkey.algorithm, algorithm);
+
+        let challenge = create_passkey_challenge(None, db).await.unwrap();
+        let assertion = authenticator.assert(challenge.as_str());
+        let result = login(assertion.clone(), db).await.unwrap();
+        assert_eq!(result.user.display_name, "Panda");
+
+        // The challenge was taken by the first login, replaying the assertion finds nothing
+        assert!(login(assertion, db).await.is_err());
+
+        // A cloned authenticator signs fresh challenges with the same count, only one of them may pass
+        let first_challenge = create_passkey_challenge(None, db).await.unwrap();
+        let second_challenge = create_passkey_challenge(None, db).await.unwrap();
+        let first_assertion = authenticator.assert(first_challenge.as_str());
+        authenticator.sign_count -= 1;
+        let second_assertion = authenticator.assert(second_challenge.as_str());
+        let (first, second) = tokio::join!(login(first_assertion, db), login(second_assertion, db));
+        assert!(first.is_ok() != second.is_ok());
+
+        database.drop().await;
+    }
+
+    fn challenge() -> PasskeyChallenge {
+        PasskeyChallenge {
+            id: 1,
+            challenge: "challenge".to_string(),
+            user_id: Some(1),
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    #[test]
+    fn test_wrong_origin() {
+        let client_data = client_data("webauthn.get", "https://evil.example");
+
+        assert!(parse_client_data(&client_data, "webauthn.get", ORIGIN).is_err());
+        assert!(parse_client_data(&client_data, "webauthn.get", "https://evil.example").is_ok());
+    }
+
+    #[test]
+    fn test_wrong_ceremony() {
+        let client_data = client_data("webauthn.create", ORIGIN);
+
+        assert!(parse_client_data(&client_data, "webauthn.get", ORIGIN).is_err());
+    }
+
+    #[test]
+    fn test_wrong_rp_id() {
+        let data = authenticator_data("evil.example", 2, None);
+
+        assert!(parse_authenticator_data(&data, RP_ID).is_err());
+        assert!(parse_authenticator_data(&data, "evil.example").is_ok());
+    }
+
+    #[test]
+    fn test_public_key_from_authenticator_data() {
+        let key_pair = key_pair();
+        let data = authenticator_data(
+            RP_ID,
+            1,
+            Some((b"credential", &cose_key(key_pair.public_key().as_ref()))),
+        );
+
+        let authenticator_data = parse_authenticator_data(&data, RP_ID).unwrap();
+        let (algorithm, public_key) =
+            parse_cose_key(&authenticator_data.credential_public_key.unwrap()).unwrap();
+
+        assert_eq!(
+            authenticator_data.credential_id,
+            Some(b"credential".to_vec())
+        );
+        assert_eq!(algorithm, ALGORITHM_EDDSA);
+        assert_eq!(
+            raw_public_key(&public_key, algorithm).unwrap(),
+            key_pair.public_key().as_ref()
+        );
+    }
+
+    #[test]
+    fn test_mismatched_key() {
+        let registered_key = key_pair();
+        let signing_key = key_pair();
+        let (algorithm, public_key) =
+            parse_cose_key(&cose_key(registered_key.public_key().as_ref())).unwrap();
+        let authenticator_data = authenticator_data(RP_ID, 2, None);
+        let client_data = client_data("webauthn.get", ORIGIN);
+        let signed_data = [
+            authenticator_data.as_slice(),
+            &Sha256::digest(&client_data)[..],
+        ]
+        .concat();
+
+        let foreign_signature = signing_key.sign(&signed_data);
+        assert!(verify_signature(
+            &public_key,
+            algorithm,
+            &authenticator_data,
+            &client_data,
+            foreign_signature.as_ref()
+        )
+        .is_err());
+
+        let signature = registered_key.sign(&signed_data);
+        assert!(verify_signature(
+            &public_key,
+            algorithm,
+            &authenticator_data,
+            &client_data,
+            signature.as_ref()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_unsupported_key() {
+        // Same map but with alg RS256
+        let mut key = cose_key(&[0; 32]);
+        key[4] = 0x39;
+        key.insert(5, 0x00);
+        key.insert(5, 0x01);
+
+        assert!(parse_cose_key(&key).is_err());
+    }
+
+    #[test]
+    fn test_expired_challenge() {
+        let now = Utc::now().naive_utc() + Duration::minutes(10);
+
+        assert!(check_passkey_challenge(Some(challenge()), now).is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server in DATABASE_URL"]
+    async fn test_register_and_login_es256() {
+        register_and_login(SoftwareAuthenticator::es256(), ALGORITHM_ES256).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server in DATABASE_URL"]
+    async fn test_register_and_login_eddsa() {
+        register_and_login(SoftwareAuthenticator::ed25519(), ALGORITHM_EDDSA).await;
+    }
+}
@@ -0,0 +1,142 @@
+use chrono::{Duration, Utc};
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{NotSet, Set, TransactionTrait};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate::recovery_code::{delete_recovery_codes, use_recovery_code};
+use crate::token::hash_token;
+
+pub async fn create_password_reset_token(
+    user_id: i32,
+    lifetime: Duration,
+    db: &DatabaseConnection,
+) -> BambooResult<String> {
+    let now = Utc::now().naive_utc();
+    let _ = password_reset::Entity::delete_many()
+        .filter(password_reset::Column::ExpiresAt.lte(now))
+        .exec(db)
+        .await;
+
+    let raw_token = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+
+    password_reset::ActiveModel {
+        id: NotSet,
+        user_id: Set(user_id),
+        token: Set(hash_token(raw_token.as_str())),
+        expires_at: Set(now + lifetime),
+    }
+    .insert(db)
+    .await
+    .map(|_| raw_token)
+    .map_err(|err| {
+        log::error!("Failed to create password reset token {err}");
+        BambooError::database("user", "Failed to create password reset token")
+    })
+}
+
+pub async fn reset_password(
+    token: String,
+    password: String,
+    recovery_code: Option<String>,
+    db: &DatabaseConnection,
+) -> BambooResult<User> {
+    if password.is_empty() {
+        return Err(BambooError::validation("user", "The password is empty"));
+    }
+
+    let invalid_token = BambooError::unauthorized("user", "The reset link is invalid or expired");
+    let password_reset = password_reset::Entity::find()
+        .filter(password_reset::Column::Token.eq(hash_token(token.as_str())))
+        .filter(password_reset::Column::ExpiresAt.gt(Utc::now().naive_utc()))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load password reset token {err}");
+            BambooError::database("user", "Failed to load password reset token")
+        })?
+        .ok_or(invalid_token.clone())?;
+
+    let user = crate::user::get_user_by_id_only(password_reset.user_id, db).await?;
+    let hashed_password = bcrypt::hash(password, 12).map_err(|err| {
+        log::error!("{err}");
+        BambooError::unknown("user", "Failed to hash the password")
+    })?;
+
+    // Either the whole reset happens or nothing, a failed step must not burn the recovery code or keep old sessions
+    let txn = db.begin().await.map_err(|err| {
+        log::error!("Failed to start transaction {err}");
+        BambooError::database("user", "Failed to reset the password")
+    })?;
+
+    // Deleting first makes sure a link can only be used once, a concurrent request waits for this one and finds nothing
+    let deleted = password_reset::Entity::delete_many()
+        .filter(password_reset::Column::UserId.eq(password_reset.user_id))
+        .exec(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete password reset tokens {err}");
+            BambooError::database("user", "Failed to delete password reset tokens")
+        })?;
+    if deleted.rows_affected == 0 {
+        return Err(invalid_token);
+    }
+
+    // The mail alone must not get around app two factor, so the second factor is proven with a recovery code
+    if user.totp_validated.unwrap_or(false) {
+        let recovery_code = recovery_code.ok_or(BambooError::unauthorized(
+            "user",
+            "A recovery code is needed to reset the password",
+        ))?;
+        use_recovery_code(user.id, recovery_code, &txn).await?;
+    }
+
+    // The totp secret is encrypted with the old password, so app two factor has to be set up again
+    user::Entity::update_many()
+        .col_expr(user::Column::Password, Expr::value(hashed_password))
+        .col_expr(
+            user::Column::TotpSecret,
+            Expr::value::<Option<Vec<u8>>>(None),
+        )
+        .col_expr(user::Column::TotpSecretEncrypted, Expr::value(false))
+        .col_expr(user::Column::TotpValidated, Expr::value(false))
+        .filter(user::Column::Id.eq(password_reset.user_id))
+        .exec(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to update user")
+        })?;
+    delete_recovery_codes(password_reset.user_id, &txn).await?;
+
+    // Whoever had access before the reset loses it, sessions and personal access tokens alike
+    token::Entity::delete_many()
+        .filter(token::Column::UserId.eq(password_reset.user_id))
+        .exec(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to delete tokens")
+        })?;
+    personal_access_token::Entity::delete_many()
+        .filter(personal_access_token::Column::UserId.eq(password_reset.user_id))
+        .exec(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to delete personal access tokens")
+        })?;
+
+    txn.commit().await.map_err(|err| {
+        log::error!("Failed to commit transaction {err}");
+        BambooError::database("user", "Failed to reset the password")
+    })?;
+
+    crate::user::get_user_by_id_only(password_reset.user_id, db).await
+}
@@ -0,0 +1,178 @@
+use chrono::{Duration, Utc};
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{Condition, NotSet, QueryOrder, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate::token::hash_token;
+
+const PERSONAL_ACCESS_TOKEN_PREFIX: &str = "bpat_";
+
+/// Personal access tokens carry a prefix, so they can be told apart from session tokens and found by secret scanners
+pub fn is_personal_access_token(token: &str) -> bool {
+    token.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX)
+}
+
+fn generate_personal_access_token() -> String {
+    format!(
+        "{PERSONAL_ACCESS_TOKEN_PREFIX}{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+pub async fn get_personal_access_tokens(
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<PersonalAccessToken>> {
+    personal_access_token::Entity::find()
+        .filter(personal_access_token::Column::UserId.eq(user_id))
+        .order_by_asc(personal_access_token::Column::Name)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load personal access tokens {err}");
+            BambooError::database(
+                "personal_access_token",
+                "Failed to load personal access tokens",
+            )
+        })
+}
+
+/// Returns the personal access token together with the raw token, only the hash is stored
+pub async fn create_personal_access_token(
+    user_id: i32,
+    personal_access_token: PersonalAccessToken,
+    db: &DatabaseConnection,
+) -> BambooResult<(PersonalAccessToken, String)> {
+    let now = Utc::now().naive_utc();
+    if personal_access_token.name.trim().is_empty() {
+        return Err(BambooError::validation(
+            "personal_access_token",
+            "The token needs a name",
+        ));
+    }
+    if personal_access_token.scopes().is_empty() {
+        return Err(BambooError::validation(
+            "personal_access_token",
+            "The token needs at least one scope",
+        ));
+    }
+    if personal_access_token
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= now)
+    {
+        return Err(BambooError::validation(
+            "personal_access_token",
+            "The expiry must be in the future",
+        ));
+    }
+
+    let raw_token = generate_personal_access_token();
+
+    personal_access_token::ActiveModel {
+        id: NotSet,
+        user_id: Set(user_id),
+        name: Set(personal_access_token.name.trim().to_string()),
+        token: Set(hash_token(raw_token.as_str())),
+        read_events: Set(personal_access_token.read_events),
+        write_events: Set(personal_access_token.write_events),
+        read_characters: Set(personal_access_token.read_characters),
+        write_characters: Set(personal_access_token.write_characters),
+        read_users: Set(personal_access_token.read_users),
+        created_at: Set(now),
+        expires_at: Set(personal_access_token.expires_at),
+        last_used_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .map(|personal_access_token| (personal_access_token, raw_token))
+    .map_err(|err| {
+        log::error!("Failed to create personal access token {err}");
+        BambooError::database(
+            "personal_access_token",
+            "Failed to create personal access token",
+        )
+    })
+}
+
+pub async fn delete_personal_access_token(
+    id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    personal_access_token::Entity::delete_many()
+        .filter(personal_access_token::Column::Id.eq(id))
+        .filter(personal_access_token::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete personal access token {err}");
+            BambooError::database(
+                "personal_access_token",
+                "Failed to delete personal access token",
+            )
+        })
+        .and_then(|res| {
+            if res.rows_affected == 0 {
+                Err(BambooError::not_found(
+                    "personal_access_token",
+                    "Personal access token not found",
+                ))
+            } else {
+                Ok(())
+            }
+        })
+}
+
+pub async fn get_user_by_valid_personal_access_token(
+    token: String,
+    db: &DatabaseConnection,
+) -> BambooResult<(PersonalAccessToken, User)> {
+    let now = Utc::now().naive_utc();
+    let not_found = BambooError::unauthorized("authentication", "Token or user not found");
+
+    let (personal_access_token, user) = personal_access_token::Entity::find()
+        .filter(personal_access_token::Column::Token.eq(hash_token(token.as_str())))
+        .filter(
+            Condition::any()
+                .add(personal_access_token::Column::ExpiresAt.is_null())
+                .add(personal_access_token::Column::ExpiresAt.gt(now)),
+        )
+        .filter(user::Column::DeletedAt.is_null())
+        .find_also_related(user::Entity)
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            not_found.clone()
+        })?
+        .ok_or(not_found.clone())?;
+    let user = user.ok_or(not_found)?;
+
+    // Scripts tend to send many requests in a row, writing the timestamp once a minute is precise enough
+    if personal_access_token
+        .last_used_at
+        .is_none_or(|last_used_at| now - last_used_at > Duration::minutes(1))
+    {
+        personal_access_token::Entity::update_many()
+            .col_expr(
+                personal_access_token::Column::LastUsedAt,
+                Expr::value(Some(now)),
+            )
+            .filter(personal_access_token::Column::Id.eq(personal_access_token.id))
+            .exec(db)
+            .await
+            .map_err(|err| {
+                log::error!("{err}");
+                BambooError::database(
+                    "personal_access_token",
+                    "Failed to update personal access token",
+                )
+            })?;
+    }
+
+    Ok((personal_access_token, user))
+}
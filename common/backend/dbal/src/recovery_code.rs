@@ -0,0 +1,223 @@
+use chrono::Utc;
+use rand::Rng;
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{NotSet, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate as dbal;
+
+const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_LENGTH: usize = 16;
+const RECOVERY_CODE_ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+// A code carries about 79 bits of randomness, so a lower cost than for passwords is enough and checking all codes of a user stays fast
+const RECOVERY_CODE_HASH_COST: u32 = 10;
+
+// Codes are handed out as xxxx-xxxx-xxxx-xxxx, but users tend to type them without the dashes or in uppercase
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|char| char.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn hash_recovery_code(code: &str) -> BambooResult<String> {
+    bcrypt::hash(normalize_recovery_code(code), RECOVERY_CODE_HASH_COST).map_err(|err| {
+        log::error!("Failed to hash recovery code {err}");
+        BambooError::unknown("user", "Failed to hash the recovery code")
+    })
+}
+
+fn generate_recovery_code() -> String {
+    let mut rng = rand::thread_rng();
+    let code = (0..RECOVERY_CODE_LENGTH)
+        .map(|_| RECOVERY_CODE_ALPHABET[rng.gen_range(0..RECOVERY_CODE_ALPHABET.len())] as char)
+        .collect::<Vec<char>>();
+
+    code.chunks(4)
+        .map(|group| group.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
+pub(crate) async fn create_recovery_codes(
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<String>> {
+    delete_recovery_codes(user_id, db).await?;
+
+    let codes = (0..RECOVERY_CODE_COUNT)
+        .map(|_| generate_recovery_code())
+        .collect::<Vec<String>>();
+    let models = codes
+        .iter()
+        .map(|code| {
+            Ok(recovery_code::ActiveModel {
+                id: NotSet,
+                user_id: Set(user_id),
+                code: Set(hash_recovery_code(code.as_str())?),
+                used_at: Set(None),
+            })
+        })
+        .collect::<BambooResult<Vec<recovery_code::ActiveModel>>>()?;
+
+    recovery_code::Entity::insert_many(models)
+        .exec(db)
+        .await
+        .map(|_| codes)
+        .map_err(|err| {
+            log::error!("Failed to create recovery codes {err}");
+            BambooError::database("user", "Failed to create recovery codes")
+        })
+}
+
+pub(crate) async fn delete_recovery_codes<C: ConnectionTrait>(
+    user_id: i32,
+    db: &C,
+) -> BambooErrorResult {
+    recovery_code::Entity::delete_many()
+        .filter(recovery_code::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map(|_| ())
+        .map_err(|err| {
+            log::error!("Failed to delete recovery codes {err}");
+            BambooError::database("user", "Failed to delete recovery codes")
+        })
+}
+
+pub(crate) async fn use_recovery_code<C: ConnectionTrait>(
+    user_id: i32,
+    code: String,
+    db: &C,
+) -> BambooErrorResult {
+    let invalid = BambooError::unauthorized("user", "Failed to validate");
+    let code = normalize_recovery_code(code.as_str());
+    // Two factor codes end up here too when they are wrong, those never need the expensive check
+    if code.len() != RECOVERY_CODE_LENGTH {
+        return Err(invalid);
+    }
+
+    let recovery_codes = recovery_code::Entity::find()
+        .filter(recovery_code::Column::UserId.eq(user_id))
+        .filter(recovery_code::Column::UsedAt.is_null())
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load recovery codes {err}");
+            BambooError::database("user", "Failed to validate")
+        })?;
+    // Every code is hashed with its own salt, so it cannot be looked up and each one is checked
+    let recovery_code = recovery_codes
+        .into_iter()
+        .find(|recovery_code| {
+            bcrypt::verify(code.as_str(), recovery_code.code.as_str()).unwrap_or(false)
+        })
+        .ok_or(invalid.clone())?;
+
+    // Only one of two concurrent logins with the same code can flip used_at
+    recovery_code::Entity::update_many()
+        .col_expr(
+            recovery_code::Column::UsedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .filter(recovery_code::Column::Id.eq(recovery_code.id))
+        .filter(recovery_code::Column::UsedAt.is_null())
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to use recovery code {err}");
+            BambooError::database("user", "Failed to validate")
+        })
+        .and_then(|res| {
+            if res.rows_affected == 0 {
+                Err(invalid)
+            } else {
+                Ok(())
+            }
+        })
+}
+
+pub async fn regenerate_my_recovery_codes(
+    id: i32,
+    password: String,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<String>> {
+    let user = dbal::get_user_by_id_only(id, db).await?;
+    if !user.validate_password(password) {
+        return Err(BambooError::insufficient_rights(
+            "user",
+            "The password is invalid",
+        ));
+    }
+
+    if !user.totp_validated.unwrap_or(false) {
+        return Err(BambooError::invalid_data(
+            "user",
+            "Recovery codes are only available with app two factor",
+        ));
+    }
+
+    create_recovery_codes(id, db).await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_database::TestDatabase;
+
+    use super::*;
+
+    #[test]
+    fn test_generated_code() {
+        let code = generate_recovery_code();
+
+        assert_eq!(code.len(), 19);
+        assert_eq!(
+            normalize_recovery_code(code.as_str()).len(),
+            RECOVERY_CODE_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_hash_is_salted() {
+        let code = generate_recovery_code();
+        let first_hash = hash_recovery_code(code.as_str()).unwrap();
+        let second_hash = hash_recovery_code(code.as_str()).unwrap();
+
+        assert_ne!(first_hash, second_hash);
+        assert!(bcrypt::verify(
+            normalize_recovery_code(code.to_uppercase().replace('-', "").as_str()),
+            first_hash.as_str()
+        )
+        .unwrap());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server in DATABASE_URL"]
+    async fn test_code_can_be_used_once() {
+        let database = TestDatabase::migrated().await;
+        database
+            .execute("INSERT INTO grove.grove (name) VALUES ('Grove')")
+            .await;
+        database
+            .execute("INSERT INTO authentication.user (email, password, display_name, discord_name, grove_id) VALUES ('panda@example.com', '', 'Panda', '', 1)")
+            .await;
+        let db = &database.db;
+
+        let codes = create_recovery_codes(1, db).await.unwrap();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+
+        assert!(use_recovery_code(1, "123456".to_string(), db)
+            .await
+            .is_err());
+        assert!(use_recovery_code(1, codes[3].to_uppercase(), db)
+            .await
+            .is_ok());
+        assert!(use_recovery_code(1, codes[3].clone(), db).await.is_err());
+        assert!(use_recovery_code(1, codes[4].clone(), db).await.is_ok());
+
+        database.drop().await;
+    }
+}
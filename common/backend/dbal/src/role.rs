@@ -0,0 +1,261 @@
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{IntoActiveModel, NotSet, QueryOrder, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+pub async fn get_roles(grove_id: i32, db: &DatabaseConnection) -> BambooResult<Vec<Role>> {
+    role::Entity::find()
+        .filter(role::Column::GroveId.eq(grove_id))
+        .order_by_asc(role::Column::Name)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load roles {err}");
+            BambooError::database("role", "Failed to load roles")
+        })
+}
+
+pub async fn get_role(grove_id: i32, id: i32, db: &DatabaseConnection) -> BambooResult<Role> {
+    role::Entity::find_by_id(id)
+        .filter(role::Column::GroveId.eq(grove_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load role {err}");
+            BambooError::database("role", "Failed to load role")
+        })?
+        .ok_or(BambooError::not_found("role", "The role was not found"))
+}
+
+pub(crate) async fn get_or_create_mod_role<C: ConnectionTrait>(
+    grove_id: i32,
+    db: &C,
+) -> BambooResult<Role> {
+    let role = role::Entity::find()
+        .filter(role::Column::GroveId.eq(grove_id))
+        .filter(role::Column::Name.eq(MOD_ROLE_NAME))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load mod role {err}");
+            BambooError::database("role", "Failed to load role")
+        })?;
+    if let Some(role) = role {
+        return Ok(role);
+    }
+
+    let mut model = Role::new(
+        MOD_ROLE_NAME.to_string(),
+        &[
+            Permission::ManageUsers,
+            Permission::ManageEvents,
+            Permission::ManageGrove,
+            Permission::ViewAuditLog,
+        ],
+    )
+    .into_active_model();
+    model.id = NotSet;
+    model.grove_id = Set(grove_id);
+
+    model.insert(db).await.map_err(|err| {
+        log::error!("Failed to create mod role {err}");
+        BambooError::database("role", "Failed to create role")
+    })
+}
+
+async fn role_exists_by_name(
+    grove_id: i32,
+    id: Option<i32>,
+    name: String,
+    db: &DatabaseConnection,
+) -> BambooResult<bool> {
+    let mut query = role::Entity::find()
+        .filter(role::Column::GroveId.eq(grove_id))
+        .filter(role::Column::Name.eq(name));
+    if let Some(id) = id {
+        query = query.filter(role::Column::Id.ne(id));
+    }
+
+    query.count(db).await.map(|count| count > 0).map_err(|err| {
+        log::error!("Failed to check for role {err}");
+        BambooError::database("role", "Failed to load roles")
+    })
+}
+
+pub async fn create_role(grove_id: i32, role: Role, db: &DatabaseConnection) -> BambooResult<Role> {
+    if role.name.trim().is_empty() {
+        return Err(BambooError::validation("role", "The name is empty"));
+    }
+    if role_exists_by_name(grove_id, None, role.name.clone(), db).await? {
+        return Err(BambooError::exists_already(
+            "role",
+            "A role with that name exists already",
+        ));
+    }
+
+    let mut model = role.into_active_model();
+    model.id = NotSet;
+    model.grove_id = Set(grove_id);
+
+    model.insert(db).await.map_err(|err| {
+        log::error!("Failed to create role {err}");
+        BambooError::database("role", "Failed to create role")
+    })
+}
+
+pub async fn update_role(
+    grove_id: i32,
+    id: i32,
+    role: Role,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    if role.name.trim().is_empty() {
+        return Err(BambooError::validation("role", "The name is empty"));
+    }
+    if role_exists_by_name(grove_id, Some(id), role.name.clone(), db).await? {
+        return Err(BambooError::exists_already(
+            "role",
+            "A role with that name exists already",
+        ));
+    }
+
+    let existing = get_role(grove_id, id, db).await?;
+    if existing.name == MOD_ROLE_NAME {
+        return Err(BambooError::validation(
+            "role",
+            "The mod role cannot be changed",
+        ));
+    }
+
+    let mut model = existing.into_active_model();
+    model.name = Set(role.name);
+    model.manage_users = Set(role.manage_users);
+    model.manage_events = Set(role.manage_events);
+    model.manage_grove = Set(role.manage_grove);
+    model.view_audit_log = Set(role.view_audit_log);
+
+    model
+        .update(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to update role {err}");
+            BambooError::database("role", "Failed to update role")
+        })
+        .map(|_| ())
+}
+
+pub async fn delete_role(grove_id: i32, id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    if get_role(grove_id, id, db).await?.name == MOD_ROLE_NAME {
+        return Err(BambooError::validation(
+            "role",
+            "The mod role cannot be deleted",
+        ));
+    }
+
+    role::Entity::delete_many()
+        .filter(role::Column::Id.eq(id))
+        .filter(role::Column::GroveId.eq(grove_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete role {err}");
+            BambooError::database("role", "Failed to delete role")
+        })
+        .map(|_| ())
+}
+
+pub async fn change_role(
+    grove_id: i32,
+    user_id: i32,
+    role_id: Option<i32>,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    if let Some(role_id) = role_id {
+        get_role(grove_id, role_id, db).await?;
+    }
+    // The mod flag follows the mod role, so the checks for mods and the permissions of the role agree
+    let is_mod = role_id == Some(get_or_create_mod_role(grove_id, db).await?.id);
+
+    user::Entity::update_many()
+        .filter(user::Column::GroveId.eq(grove_id))
+        .filter(user::Column::Id.eq(user_id))
+        .col_expr(user::Column::RoleId, Expr::value(role_id))
+        .col_expr(user::Column::IsMod, Expr::value(is_mod))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to change role {err}");
+            BambooError::database("user", "Failed to update user")
+        })
+        .map(|_| ())
+}
+
+pub async fn get_user_role(user: &User, db: &DatabaseConnection) -> BambooResult<Option<Role>> {
+    if let Some(role_id) = user.role_id {
+        get_role(user.grove_id, role_id, db).await.map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+pub async fn user_has_permission(
+    user: &User,
+    permission: Permission,
+    db: &DatabaseConnection,
+) -> BambooResult<bool> {
+    get_user_role(user, db)
+        .await
+        .map(|role| role.is_some_and(|role| role.has_permission(permission)))
+}
+
+#[cfg(test)]
+mod tests {
+    use bamboo_common_backend_migration::{Migrator, MigratorTrait};
+
+    use crate::test_database::TestDatabase;
+
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server in DATABASE_URL"]
+    async fn test_seeding_keeps_existing_mod_role() {
+        let database = TestDatabase::new().await;
+        database
+            .migrate_until("m20240326_184930_seed_mod_role")
+            .await;
+
+        database
+            .execute("INSERT INTO grove.grove (name) VALUES ('Grove')")
+            .await;
+        database
+            .execute("INSERT INTO grove.role (grove_id, name, manage_users, manage_events, manage_grove, view_audit_log) VALUES (1, 'Mod', false, true, false, false)")
+            .await;
+        database
+            .execute("INSERT INTO authentication.user (email, password, display_name, discord_name, grove_id, role_id) VALUES ('panda@example.com', '', 'Panda', '', 1, 1)")
+            .await;
+        database
+            .execute("INSERT INTO authentication.user (email, password, display_name, discord_name, grove_id, is_mod) VALUES ('mod@example.com', '', 'Mod', '', 1, true)")
+            .await;
+
+        Migrator::up(&database.db, None).await.unwrap();
+        let db = &database.db;
+
+        let old_role = get_role(1, 1, db).await.unwrap();
+        assert_eq!(old_role.name, "Mod (alt 1)");
+        assert_eq!(old_role.permissions(), vec![Permission::ManageEvents]);
+
+        let mod_role = get_or_create_mod_role(1, db).await.unwrap();
+        assert_ne!(mod_role.id, old_role.id);
+        assert_eq!(mod_role.permissions().len(), 4);
+
+        let panda = crate::user::get_user_by_id_only(1, db).await.unwrap();
+        assert_eq!(panda.role_id, Some(old_role.id));
+        assert!(!panda.is_mod);
+        let bamboo_mod = crate::user::get_user_by_id_only(2, db).await.unwrap();
+        assert_eq!(bamboo_mod.role_id, Some(mod_role.id));
+
+        database.drop().await;
+    }
+}
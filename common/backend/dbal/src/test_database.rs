@@ -0,0 +1,63 @@
+use bamboo_common_backend_migration::{Migrator, MigratorTrait};
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection, Statement};
+
+// Every test gets its own database next to the one in DATABASE_URL, so tests can run in parallel
+pub(crate) struct TestDatabase {
+    pub db: DatabaseConnection,
+    admin: DatabaseConnection,
+    name: String,
+}
+
+impl TestDatabase {
+    pub async fn new() -> Self {
+        let url = std::env::var("DATABASE_URL").expect("Needs DATABASE_URL");
+        let admin = Database::connect(url.as_str()).await.unwrap();
+        let name = format!("bamboo_test_{}", uuid::Uuid::new_v4().simple());
+        admin
+            .execute(Statement::from_string(
+                admin.get_database_backend(),
+                format!("CREATE DATABASE {name}"),
+            ))
+            .await
+            .unwrap();
+
+        let (server, _) = url.rsplit_once('/').expect("DATABASE_URL has no database");
+        let db = Database::connect(format!("{server}/{name}")).await.unwrap();
+
+        Self { db, admin, name }
+    }
+
+    pub async fn migrated() -> Self {
+        let database = Self::new().await;
+        Migrator::up(&database.db, None).await.unwrap();
+
+        database
+    }
+
+    // Runs all migrations before the given one
+    pub async fn migrate_until(&self, migration: &str) {
+        let steps = Migrator::migrations()
+            .iter()
+            .position(|m| m.name() == migration)
+            .expect("Unknown migration");
+        Migrator::up(&self.db, Some(steps as u32)).await.unwrap();
+    }
+
+    pub async fn execute(&self, sql: &str) {
+        self.db
+            .execute(Statement::from_string(self.db.get_database_backend(), sql))
+            .await
+            .unwrap();
+    }
+
+    pub async fn drop(self) {
+        self.db.close().await.unwrap();
+        self.admin
+            .execute(Statement::from_string(
+                self.admin.get_database_backend(),
+                format!("DROP DATABASE {} WITH (FORCE)", self.name),
+            ))
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,200 @@
+use chrono::{Duration, Utc};
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{NotSet, QueryOrder, Set};
+use sha2::{Digest, Sha256};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+pub(crate) async fn create_token(
+    user_id: i32,
+    user_agent: Option<String>,
+    idle_timeout: Duration,
+    db: &DatabaseConnection,
+) -> BambooResult<Token> {
+    let now = Utc::now().naive_utc();
+    let raw_token = uuid::Uuid::new_v4().to_string();
+
+    token::ActiveModel {
+        id: NotSet,
+        token: Set(hash_token(raw_token.as_str())),
+        user_id: Set(user_id),
+        created_at: Set(now),
+        last_used_at: Set(now),
+        expires_at: Set(now + idle_timeout),
+        user_agent: Set(user_agent),
+    }
+    .insert(db)
+    .await
+    .map(|token| Token {
+        token: raw_token,
+        ..token
+    })
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("token", "Failed to create token")
+    })
+}
+
+pub async fn get_user_by_valid_token(
+    token: String,
+    idle_timeout: Duration,
+    max_lifetime: Duration,
+    db: &DatabaseConnection,
+) -> BambooResult<User> {
+    let now = Utc::now().naive_utc();
+    let not_found = BambooError::unauthorized("authentication", "Token or user not found");
+
+    let (stored_token, user) = token::Entity::find()
+        .filter(token::Column::Token.eq(hash_token(token.as_str())))
+        .filter(token::Column::ExpiresAt.gt(now))
+        .filter(user::Column::DeletedAt.is_null())
+        .find_also_related(user::Entity)
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            not_found.clone()
+        })?
+        .ok_or(not_found.clone())?;
+    let user = user.ok_or(not_found)?;
+
+    if now - stored_token.last_used_at > Duration::minutes(1) {
+        let expires_at = (now + idle_timeout).min(stored_token.created_at + max_lifetime);
+        token::Entity::update_many()
+            .col_expr(token::Column::LastUsedAt, Expr::value(now))
+            .col_expr(token::Column::ExpiresAt, Expr::value(expires_at))
+            .filter(token::Column::Id.eq(stored_token.id))
+            .exec(db)
+            .await
+            .map_err(|err| {
+                log::error!("{err}");
+                BambooError::database("token", "Failed to update token")
+            })?;
+    }
+
+    Ok(user)
+}
+
+pub async fn get_sessions(user_id: i32, db: &DatabaseConnection) -> BambooResult<Vec<Token>> {
+    token::Entity::find()
+        .filter(token::Column::UserId.eq(user_id))
+        .filter(token::Column::ExpiresAt.gt(Utc::now().naive_utc()))
+        .order_by_desc(token::Column::LastUsedAt)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("token", "Failed to load sessions")
+        })
+}
+
+pub async fn delete_session(id: i32, user_id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    token::Entity::delete_many()
+        .filter(token::Column::Id.eq(id))
+        .filter(token::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("token", "Failed to delete session")
+        })
+        .and_then(|res| {
+            if res.rows_affected == 0 {
+                Err(BambooError::not_found("token", "Session not found"))
+            } else {
+                Ok(())
+            }
+        })
+}
+
+pub async fn delete_other_sessions(
+    user_id: i32,
+    current_token: String,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    token::Entity::delete_many()
+        .filter(token::Column::UserId.eq(user_id))
+        .filter(token::Column::Token.ne(hash_token(current_token.as_str())))
+        .exec(db)
+        .await
+        .map(|_| ())
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("token", "Failed to delete sessions")
+        })
+}
+
+pub async fn delete_expired_tokens(db: &DatabaseConnection) -> BambooResult<u64> {
+    token::Entity::delete_many()
+        .filter(token::Column::ExpiresAt.lte(Utc::now().naive_utc()))
+        .exec(db)
+        .await
+        .map(|res| res.rows_affected)
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("token", "Failed to delete expired tokens")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use bamboo_common_backend_migration::{Migrator, MigratorTrait};
+
+    use crate::authentication::delete_token;
+    use crate::test_database::TestDatabase;
+
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "needs a postgres server in DATABASE_URL"]
+    async fn test_legacy_token_survives_rehash() {
+        let database = TestDatabase::new().await;
+        database
+            .migrate_until("m20240322_181204_rehash_legacy_tokens")
+            .await;
+
+        // Before hashing tokens were stored as the plain uuid handed to the client
+        let legacy_token = uuid::Uuid::new_v4().to_string();
+        database
+            .execute("INSERT INTO grove.grove (name) VALUES ('Grove')")
+            .await;
+        database
+            .execute("INSERT INTO authentication.user (email, password, display_name, discord_name, grove_id) VALUES ('panda@example.com', '', 'Panda', '', 1)")
+            .await;
+        database
+            .execute(format!("INSERT INTO authentication.token (user_id, token) VALUES (1, '{legacy_token}')").as_str())
+            .await;
+
+        Migrator::up(&database.db, None).await.unwrap();
+
+        let user = get_user_by_valid_token(
+            legacy_token.clone(),
+            Duration::days(30),
+            Duration::days(90),
+            &database.db,
+        )
+        .await
+        .unwrap();
+        assert_eq!(user.display_name, "Panda");
+
+        delete_token(legacy_token.clone(), &database.db)
+            .await
+            .unwrap();
+        assert!(get_user_by_valid_token(
+            legacy_token,
+            Duration::days(30),
+            Duration::days(90),
+            &database.db,
+        )
+        .await
+        .is_err());
+
+        database.drop().await;
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{Condition, QueryOrder};
+
+use bamboo_common_backend_response::{ListQuery, Page};
+use bamboo_common_core::entities::*;
+use bamboo_common_core::entities::{character, event, user};
+use bamboo_common_core::error::*;
+
+use crate::character::get_character;
+use crate::event::get_event;
+use crate::user::get_user;
+
+/// Loads the trash of the user, users are only included for those allowed to manage them
+pub async fn get_trash(
+    grove_id: i32,
+    user_id: i32,
+    include_users: bool,
+    retention: Duration,
+    query: &ListQuery,
+    db: &DatabaseConnection,
+) -> BambooResult<Page<TrashEntry>> {
+    // The trash combines three tables, so it is sorted and paged in memory
+    let descending = match query.sort_field() {
+        None | Some(("deletedAt", true)) => true,
+        Some(("deletedAt", false)) => false,
+        Some((field, _)) => {
+            return Err(BambooError::invalid_data(
+                "trash",
+                format!("Cannot sort by {field}"),
+            ))
+        }
+    };
+    let entry = |entry_type: TrashEntryType, id: i32, name: String, deleted_at: NaiveDateTime| {
+        TrashEntry::new(entry_type, id, name, deleted_at, deleted_at + retention)
+    };
+
+    let mut entries = character::Entity::find()
+        .filter(character::Column::UserId.eq(user_id))
+        .filter(character::Column::DeletedAt.is_not_null())
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load deleted characters {err}");
+            BambooError::database("trash", "Failed to load the trash")
+        })?
+        .into_iter()
+        .filter_map(|character| {
+            character.deleted_at.map(|deleted_at| {
+                entry(
+                    TrashEntryType::Character,
+                    character.id,
+                    character.name,
+                    deleted_at,
+                )
+            })
+        })
+        .collect::<Vec<TrashEntry>>();
+
+    let events = event::Entity::find()
+        .filter(event::Column::GroveId.eq(grove_id))
+        .filter(event::Column::DeletedAt.is_not_null())
+        .filter(
+            Condition::any()
+                .add(event::Column::IsPrivate.eq(false))
+                .add(event::Column::UserId.eq(user_id)),
+        )
+        .order_by_asc(event::Column::Id)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load deleted events {err}");
+            BambooError::database("trash", "Failed to load the trash")
+        })?;
+    // Changed occurrences deleted together with their series are restored with it
+    let deleted_series = events
+        .iter()
+        .map(|event| (event.id, event.deleted_at))
+        .collect::<BTreeMap<i32, Option<NaiveDateTime>>>();
+    entries.extend(
+        events
+            .into_iter()
+            .filter(|event| {
+                event.series_id.is_none_or(|series_id| {
+                    deleted_series.get(&series_id) != Some(&event.deleted_at)
+                })
+            })
+            .filter_map(|event| {
+                event.deleted_at.map(|deleted_at| {
+                    entry(TrashEntryType::Event, event.id, event.title, deleted_at)
+                })
+            }),
+    );
+
+    if include_users {
+        entries.extend(
+            user::Entity::find()
+                .filter(user::Column::GroveId.eq(grove_id))
+                .filter(user::Column::DeletedAt.is_not_null())
+                .all(db)
+                .await
+                .map_err(|err| {
+                    log::error!("Failed to load deleted users {err}");
+                    BambooError::database("trash", "Failed to load the trash")
+                })?
+                .into_iter()
+                .filter_map(|user| {
+                    user.deleted_at.map(|deleted_at| {
+                        entry(TrashEntryType::User, user.id, user.display_name, deleted_at)
+                    })
+                }),
+        );
+    }
+
+    if let Some(filter) = query.filter_text() {
+        entries.retain(|entry| entry.name.to_lowercase().contains(filter.as_str()));
+    }
+    entries.sort_by_key(|entry| entry.deleted_at);
+    if descending {
+        entries.reverse();
+    }
+
+    Ok(Page::from_entries(entries, query))
+}
+
+pub async fn restore_character(
+    id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Character> {
+    let result = character::Entity::update_many()
+        .filter(character::Column::Id.eq(id))
+        .filter(character::Column::UserId.eq(user_id))
+        .filter(character::Column::DeletedAt.is_not_null())
+        .col_expr(
+            character::Column::DeletedAt,
+            Expr::value::<Option<NaiveDateTime>>(None),
+        )
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to restore character {err}");
+            BambooError::database("character", "Failed to restore the character")
+        })?;
+    if result.rows_affected == 0 {
+        return Err(BambooError::not_found(
+            "character",
+            "The character was not found in the trash",
+        ));
+    }
+
+    get_character(id, user_id, db).await
+}
+
+pub async fn restore_event(
+    grove_id: i32,
+    id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Event> {
+    let not_found = BambooError::not_found("event", "The event was not found in the trash");
+    let deleted_at = event::Entity::find_by_id(id)
+        .filter(event::Column::GroveId.eq(grove_id))
+        .filter(
+            Condition::any()
+                .add(event::Column::IsPrivate.eq(false))
+                .add(event::Column::UserId.eq(user_id)),
+        )
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load event {err}");
+            BambooError::database("event", "Failed to restore the event")
+        })?
+        .and_then(|event| event.deleted_at)
+        .ok_or(not_found)?;
+
+    event::Entity::update_many()
+        .filter(event::Column::GroveId.eq(grove_id))
+        .filter(
+            Condition::any().add(event::Column::Id.eq(id)).add(
+                Condition::all()
+                    .add(event::Column::SeriesId.eq(id))
+                    .add(event::Column::DeletedAt.eq(deleted_at)),
+            ),
+        )
+        .col_expr(
+            event::Column::DeletedAt,
+            Expr::value::<Option<NaiveDateTime>>(None),
+        )
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to restore event {err}");
+            BambooError::database("event", "Failed to restore the event")
+        })?;
+
+    get_event(id, grove_id, user_id, db).await
+}
+
+pub async fn restore_user(grove_id: i32, id: i32, db: &DatabaseConnection) -> BambooResult<User> {
+    let result = user::Entity::update_many()
+        .filter(user::Column::Id.eq(id))
+        .filter(user::Column::GroveId.eq(grove_id))
+        .filter(user::Column::DeletedAt.is_not_null())
+        .col_expr(
+            user::Column::DeletedAt,
+            Expr::value::<Option<NaiveDateTime>>(None),
+        )
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to restore user {err}");
+            BambooError::database("user", "Failed to restore the user")
+        })?;
+    if result.rows_affected == 0 {
+        return Err(BambooError::not_found(
+            "user",
+            "The user was not found in the trash",
+        ));
+    }
+
+    get_user(grove_id, id, db).await
+}
+
+/// Deletes everything that is in the trash longer than the retention, returns the number of purged entries
+pub async fn purge_trash(retention: Duration, db: &DatabaseConnection) -> BambooResult<u64> {
+    let older_than = Utc::now().naive_utc() - retention;
+
+    let characters = character::Entity::delete_many()
+        .filter(character::Column::DeletedAt.lt(older_than))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to purge characters {err}");
+            BambooError::database("trash", "Failed to purge the trash")
+        })?;
+    let events = event::Entity::delete_many()
+        .filter(event::Column::DeletedAt.lt(older_than))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to purge events {err}");
+            BambooError::database("trash", "Failed to purge the trash")
+        })?;
+    let users = user::Entity::delete_many()
+        .filter(user::Column::DeletedAt.lt(older_than))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to purge users {err}");
+            BambooError::database("trash", "Failed to purge the trash")
+        })?;
+
+    Ok(characters.rows_affected + events.rows_affected + users.rows_affected)
+}
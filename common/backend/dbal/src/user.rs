@@ -1,17 +1,23 @@
-use sea_orm::prelude::*;
+use chrono::Utc;
 use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, IntoActiveModel,
-    JoinType, NotSet, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    IntoActiveModel, NotSet, QueryFilter, QueryOrder, Set,
 };
 
+use bamboo_common_backend_response::{ListQuery, Page};
 use bamboo_common_core::entities::*;
 use bamboo_common_core::error::*;
 
 use crate as dbal;
+use crate::grove::check_demo_grove;
+use crate::list::load_page;
+use crate::recovery_code::delete_recovery_codes;
+use crate::role::get_or_create_mod_role;
 
 pub async fn get_user_by_id_only(id: i32, db: &DatabaseConnection) -> BambooResult<User> {
     user::Entity::find_by_id(id)
+        .filter(user::Column::DeletedAt.is_null())
         .one(db)
         .await
         .map_err(|err| {
@@ -30,6 +36,7 @@ pub async fn get_user_by_id_only(id: i32, db: &DatabaseConnection) -> BambooResu
 pub async fn get_user(grove_id: i32, id: i32, db: &DatabaseConnection) -> BambooResult<User> {
     user::Entity::find_by_id(id)
         .filter(user::Column::GroveId.eq(grove_id))
+        .filter(user::Column::DeletedAt.is_null())
         .one(db)
         .await
         .map_err(|err| {
@@ -45,23 +52,26 @@ pub async fn get_user(grove_id: i32, id: i32, db: &DatabaseConnection) -> Bamboo
         })?
 }
 
-pub async fn get_user_by_token(token: String, db: &DatabaseConnection) -> BambooResult<User> {
+pub async fn get_user_by_calendar_feed_secret(
+    secret: String,
+    db: &DatabaseConnection,
+) -> BambooResult<User> {
     user::Entity::find()
-        .filter(token::Column::Token.eq(token))
-        .join(JoinType::InnerJoin, user::Relation::Token.def())
+        .filter(user::Column::CalendarFeedSecret.eq(secret))
+        .filter(user::Column::DeletedAt.is_null())
         .one(db)
         .await
         .map_err(|err| {
             log::error!("{err}");
-            BambooError::unauthorized("authentication", "Token or user not found")
+            BambooError::database("user", "Failed to execute database query")
         })
         .map(|data| {
             if let Some(data) = data {
                 Ok(data)
             } else {
-                Err(BambooError::unauthorized(
-                    "authentication",
-                    "Token or user not found",
+                Err(BambooError::not_found(
+                    "user",
+                    "The calendar feed was not found",
                 ))
             }
         })?
@@ -79,6 +89,7 @@ pub async fn get_user_by_email_or_username(
                         .add(user::Column::Email.eq(username.clone()))
                         .add(user::Column::DisplayName.eq(username)),
                 )
+                .add(user::Column::DeletedAt.is_null())
                 .add(
                     Condition::any()
                         .add(
@@ -110,16 +121,30 @@ pub async fn get_user_by_email_or_username(
         })?
 }
 
-pub async fn get_users(grove_id: i32, db: &DatabaseConnection) -> BambooResult<Vec<User>> {
-    user::Entity::find()
-        .filter(user::Column::GroveId.eq(grove_id))
-        .order_by_asc(user::Column::DisplayName)
-        .all(db)
-        .await
-        .map_err(|err| {
-            log::error!("{err}");
-            BambooError::database("user", "Failed to load users")
-        })
+pub async fn get_users(
+    grove_id: i32,
+    query: &ListQuery,
+    db: &DatabaseConnection,
+) -> BambooResult<Page<User>> {
+    load_page(
+        user::Entity::find()
+            .filter(user::Column::GroveId.eq(grove_id))
+            .filter(user::Column::DeletedAt.is_null()),
+        query,
+        &[
+            ("displayName", user::Column::DisplayName),
+            ("email", user::Column::Email),
+            ("discordName", user::Column::DiscordName),
+        ],
+        &[
+            user::Column::DisplayName,
+            user::Column::Email,
+            user::Column::DiscordName,
+        ],
+        "user",
+        db,
+    )
+    .await
 }
 
 pub async fn get_users_with_mod_rights(
@@ -131,6 +156,7 @@ pub async fn get_users_with_mod_rights(
     user::Entity::find()
         .filter(user::Column::IsMod.eq(true))
         .filter(user::Column::GroveId.eq(grove.id))
+        .filter(user::Column::DeletedAt.is_null())
         .order_by_asc(user::Column::DisplayName)
         .all(db)
         .await
@@ -140,12 +166,33 @@ pub async fn get_users_with_mod_rights(
         })
 }
 
-pub(crate) async fn user_exists_by_id(
+// Users in the trash keep their email and name until they are purged or restored, the unique constraints still hold for them.
+// A conflict with one of them says so, otherwise the error would point to a user nobody can see
+fn check_user_conflict(conflicting_users: Vec<User>) -> BambooErrorResult {
+    if conflicting_users
+        .iter()
+        .any(|user| user.deleted_at.is_none())
+    {
+        Err(BambooError::exists_already(
+            "user",
+            "A user with that email or name exists already",
+        ))
+    } else if !conflicting_users.is_empty() {
+        Err(BambooError::exists_already(
+            "user",
+            "A user with that email or name is in the trash, restore them instead",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) async fn check_user_unique_by_id(
     id: i32,
     email: String,
     name: String,
     db: &DatabaseConnection,
-) -> BambooResult<bool> {
+) -> BambooErrorResult {
     user::Entity::find()
         .filter(user::Column::Id.ne(id))
         .filter(
@@ -153,33 +200,33 @@ pub(crate) async fn user_exists_by_id(
                 .add(user::Column::Email.eq(email))
                 .add(user::Column::DisplayName.eq(name)),
         )
-        .count(db)
+        .all(db)
         .await
-        .map(|count| count > 0)
         .map_err(|err| {
             log::error!("Failed to load users {err}");
             BambooError::database("user", "Failed to load users")
         })
+        .and_then(check_user_conflict)
 }
 
-async fn user_exists_by_email_and_name(
+async fn check_user_unique<C: ConnectionTrait>(
     email: String,
     name: String,
-    db: &DatabaseConnection,
-) -> BambooResult<bool> {
+    db: &C,
+) -> BambooErrorResult {
     user::Entity::find()
         .filter(
             Condition::any()
                 .add(user::Column::Email.eq(email))
                 .add(user::Column::DisplayName.eq(name)),
         )
-        .count(db)
+        .all(db)
         .await
-        .map(|count| count > 0)
         .map_err(|err| {
             log::error!("Failed to load users {err}");
             BambooError::database("user", "Failed to load users")
         })
+        .and_then(check_user_conflict)
 }
 
 pub async fn create_user(
@@ -188,16 +235,26 @@ pub async fn create_user(
     password: String,
     db: &DatabaseConnection,
 ) -> BambooResult<User> {
-    if user_exists_by_email_and_name(user.email.clone(), user.display_name.clone(), db).await? {
-        return Err(BambooError::exists_already(
-            "user",
-            "A user with that email or name exists already",
-        ));
-    }
+    insert_user(grove_id, user, password, db).await
+}
 
+pub(crate) async fn insert_user<C: ConnectionTrait>(
+    grove_id: i32,
+    user: User,
+    password: String,
+    db: &C,
+) -> BambooResult<User> {
+    user.validate()?;
+
+    check_user_unique(user.email.clone(), user.display_name.clone(), db).await?;
+
+    let is_mod = user.is_mod;
     let mut model = user.into_active_model();
     model.id = NotSet;
     model.grove_id = Set(grove_id);
+    if is_mod {
+        model.role_id = Set(Some(get_or_create_mod_role(grove_id, db).await?.id));
+    }
     model.set_password(&password).map_err(|err| {
         log::error!("{err}");
         BambooError::database("user", "Failed to hash password user")
@@ -209,9 +266,23 @@ pub async fn create_user(
     })
 }
 
+/// Moves the user into the trash and logs them out, personal access tokens stop working until the user is restored
 pub async fn delete_user(grove_id: i32, id: i32, db: &DatabaseConnection) -> BambooErrorResult {
-    user::Entity::delete_by_id(id)
+    token::Entity::delete_many()
+        .filter(token::Column::UserId.eq(id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to delete user")
+        })
+        .map(|_| ())?;
+
+    user::Entity::update_many()
+        .filter(user::Column::Id.eq(id))
         .filter(user::Column::GroveId.eq(grove_id))
+        .filter(user::Column::DeletedAt.is_null())
+        .col_expr(user::Column::DeletedAt, Expr::value(Utc::now().naive_utc()))
         .exec(db)
         .await
         .map_err(|err| {
@@ -221,16 +292,47 @@ pub async fn delete_user(grove_id: i32, id: i32, db: &DatabaseConnection) -> Bam
         .map(|_| ())
 }
 
+/// Deletes every user of the demo grove except the given one, used to reset it
+pub async fn delete_other_demo_users(
+    grove_id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    check_demo_grove(grove_id, db).await?;
+
+    user::Entity::delete_many()
+        .filter(user::Column::GroveId.eq(grove_id))
+        .filter(user::Column::Id.ne(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to delete users")
+        })
+        .map(|_| ())
+}
+
 pub async fn change_mod_status(
     grove_id: i32,
     id: i32,
     is_mod: bool,
     db: &DatabaseConnection,
 ) -> BambooErrorResult {
+    let mod_role = get_or_create_mod_role(grove_id, db).await?;
+    let role_id = if is_mod {
+        Some(mod_role.id)
+    } else {
+        get_user(grove_id, id, db)
+            .await?
+            .role_id
+            .filter(|role_id| *role_id != mod_role.id)
+    };
+
     user::Entity::update_many()
         .filter(user::Column::GroveId.eq(grove_id))
         .filter(user::Column::Id.eq(id))
         .col_expr(user::Column::IsMod, Expr::value(is_mod))
+        .col_expr(user::Column::RoleId, Expr::value(role_id))
         .exec(db)
         .await
         .map_err(|err| {
@@ -288,12 +390,9 @@ pub async fn update_profile(
     discord_name: String,
     db: &DatabaseConnection,
 ) -> BambooErrorResult {
-    if user_exists_by_id(id, email.clone(), display_name.clone(), db).await? {
-        return Err(BambooError::exists_already(
-            "user",
-            "A user with that email or name exists already",
-        ));
-    }
+    user::validate_profile(&email, &display_name)?;
+
+    check_user_unique_by_id(id, email.clone(), display_name.clone(), db).await?;
 
     user::Entity::update_many()
         .col_expr(user::Column::Email, Expr::value(email))
@@ -311,6 +410,9 @@ pub async fn update_profile(
 }
 
 pub async fn disable_totp(grove_id: i32, id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    let user = get_user(grove_id, id, db).await?;
+    delete_recovery_codes(user.id, db).await?;
+
     user::Entity::update_many()
         .col_expr(
             user::Column::TotpSecret,
@@ -328,15 +430,44 @@ pub async fn disable_totp(grove_id: i32, id: i32, db: &DatabaseConnection) -> Ba
 
 pub async fn get_users_filtered_for_management(
     grove_id: i32,
+    query: &ListQuery,
     db: &DatabaseConnection,
-) -> BambooResult<Vec<User>> {
-    user::Entity::find()
-        .filter(user::Column::GroveId.eq(grove_id))
-        .order_by_asc(user::Column::Id)
-        .all(db)
-        .await
-        .map_err(|err| {
-            log::error!("{err}");
-            BambooError::database("user", "Failed to load users")
-        })
+) -> BambooResult<Page<User>> {
+    load_page(
+        user::Entity::find()
+            .filter(user::Column::GroveId.eq(grove_id))
+            .filter(user::Column::DeletedAt.is_null()),
+        query,
+        &[
+            ("id", user::Column::Id),
+            ("displayName", user::Column::DisplayName),
+            ("email", user::Column::Email),
+            ("isMod", user::Column::IsMod),
+        ],
+        &[user::Column::DisplayName, user::Column::Email],
+        "user",
+        db,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_with_trashed_user() {
+        let trashed_user = User {
+            deleted_at: Some(chrono::Utc::now().naive_utc()),
+            ..User::default()
+        };
+
+        let err = check_user_conflict(vec![trashed_user.clone()]).unwrap_err();
+        assert!(err.message.contains("trash"));
+
+        let err = check_user_conflict(vec![trashed_user, User::default()]).unwrap_err();
+        assert!(!err.message.contains("trash"));
+
+        assert!(check_user_conflict(vec![]).is_ok());
+    }
 }
@@ -96,3 +96,107 @@ Alles Gute vom 🐼"#
         log::error!("{err:#?}");
     });
 }
+
+pub async fn send_account_locked_mail(
+    display_name: String,
+    to: String,
+    locked_minutes: i64,
+    env_service: EnvService,
+) {
+    let env_service = env_service.clone();
+    let html_body = format!(
+        r#"
+<html lang="de" style="font-family: system-ui,-apple-system,'Segoe UI','Roboto','Ubuntu','Cantarell','Noto Sans',sans-serif,'Apple Color Emoji','Segoe UI Emoji','Segoe UI Symbol','Noto Color Emoji';">
+<head>
+
+</head>
+<body>
+    <article style="margin: 4rem 0; padding: 4rem 2rem; border-radius: 0.25rem; background: #fff; box-shadow: 0.0145rem 0.029rem 0.174rem rgba(27, 40, 50, 0.01698),0.0335rem 0.067rem 0.402rem rgba(27, 40, 50, 0.024),0.0625rem 0.125rem 0.75rem rgba(27, 40, 50, 0.03),0.1125rem 0.225rem 1.35rem rgba(27, 40, 50, 0.036),0.2085rem 0.417rem 2.502rem rgba(27, 40, 50, 0.04302),0.5rem 1rem 6rem rgba(27, 40, 50, 0.06),0 0 0 0.0625rem rgba(27, 40, 50, 0.015);">
+        Hallo {display_name},<br><br>
+        jemand hat mehrfach vergeblich versucht sich mit deinem Konto im Bambushain anzumelden. Zu deinem Schutz ist die Anmeldung deshalb für {locked_minutes} Minuten gesperrt.<br><br>
+        Wenn du das nicht warst, ändere bitte dein Passwort und aktiviere Zwei Faktor per App, falls du das noch nicht getan hast.<br><br>
+        Alles Gute vom 🐼
+    </article>
+</body>
+</html>"#
+    );
+    let plain_body = format!(
+        r#"
+Hallo {display_name},
+
+jemand hat mehrfach vergeblich versucht sich mit deinem Konto im Bambushain anzumelden. Zu deinem Schutz ist die Anmeldung deshalb für {locked_minutes} Minuten gesperrt.
+
+Wenn du das nicht warst, ändere bitte dein Passwort und aktiviere Zwei Faktor per App, falls du das noch nicht getan hast.
+
+Alles Gute vom 🐼"#
+    );
+
+    let _ = send_mail(
+        env_service,
+        "Dein Konto im Bambushain wurde vorübergehend gesperrt",
+        to,
+        plain_body,
+        html_body,
+    )
+    .await
+    .map(|_| ())
+    .map_err(|err| {
+        log::error!("Failed to send email {err}");
+        log::error!("{err:#?}");
+    });
+}
+
+pub async fn send_password_reset_mail(
+    display_name: String,
+    to: String,
+    link: String,
+    valid_minutes: i64,
+    env_service: EnvService,
+) -> BambooErrorResult {
+    let env_service = env_service.clone();
+    let html_body = format!(
+        r#"
+<html lang="de" style="font-family: system-ui,-apple-system,'Segoe UI','Roboto','Ubuntu','Cantarell','Noto Sans',sans-serif,'Apple Color Emoji','Segoe UI Emoji','Segoe UI Symbol','Noto Color Emoji';">
+<head>
+
+</head>
+<body>
+    <article style="margin: 4rem 0; padding: 4rem 2rem; border-radius: 0.25rem; background: #fff; box-shadow: 0.0145rem 0.029rem 0.174rem rgba(27, 40, 50, 0.01698),0.0335rem 0.067rem 0.402rem rgba(27, 40, 50, 0.024),0.0625rem 0.125rem 0.75rem rgba(27, 40, 50, 0.03),0.1125rem 0.225rem 1.35rem rgba(27, 40, 50, 0.036),0.2085rem 0.417rem 2.502rem rgba(27, 40, 50, 0.04302),0.5rem 1rem 6rem rgba(27, 40, 50, 0.06),0 0 0 0.0625rem rgba(27, 40, 50, 0.015);">
+        Hallo {display_name},<br><br>
+        du hast ein neues Passwort für den Bambushain angefordert. Über den folgenden Link kannst du in den nächsten {valid_minutes} Minuten ein neues Passwort setzen:<br><br>
+        <a href="{link}">{link}</a><br><br>
+        Wenn du kein neues Passwort angefordert hast, kannst du diese Email einfach ignorieren.<br><br>
+        Alles Gute vom 🐼
+    </article>
+</body>
+</html>"#
+    );
+    let plain_body = format!(
+        r#"
+Hallo {display_name},
+
+du hast ein neues Passwort für den Bambushain angefordert. Über den folgenden Link kannst du in den nächsten {valid_minutes} Minuten ein neues Passwort setzen:
+
+{link}
+
+Wenn du kein neues Passwort angefordert hast, kannst du diese Email einfach ignorieren.
+
+Alles Gute vom 🐼"#
+    );
+
+    send_mail(
+        env_service,
+        "Dein neues Passwort für den Bambushain",
+        to,
+        plain_body,
+        html_body,
+    )
+    .await
+    .map_err(|err| {
+        log::error!("Failed to send email {err}");
+        log::error!("{err:#?}");
+
+        BambooError::mailing("Failed to send password reset mail")
+    })
+    .map(|_| ())
+}
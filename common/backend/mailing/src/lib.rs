@@ -1,4 +1,5 @@
 pub mod authentication;
+pub mod event;
 mod mailer;
 pub mod support;
 pub mod user;
@@ -1,13 +1,32 @@
-use bamboo_common_backend_services::EnvService;
+use bamboo_common_backend_services::{EnvService, InvitationPolicy};
+use bamboo_common_core::entities::Invitation;
 use bamboo_common_core::error::{BambooError, BambooErrorResult};
 
 use crate::mailer::send_mail;
 
-pub async fn send_user_created(
+pub async fn send_invitation(
+    invitation: &Invitation,
+    token: &str,
+    policy: &InvitationPolicy,
+    env_service: EnvService,
+) -> BambooErrorResult {
+    send_user_invited(
+        invitation.display_name.clone(),
+        invitation.invited_by.clone(),
+        invitation.email.clone(),
+        policy.link(token),
+        policy.lifetime.num_days(),
+        env_service,
+    )
+    .await
+}
+
+pub async fn send_user_invited(
     display_name: String,
-    created_by: String,
+    invited_by: String,
     to: String,
-    password: String,
+    link: String,
+    valid_days: i64,
     env_service: EnvService,
 ) -> BambooErrorResult {
     let env_service = env_service.clone();
@@ -20,9 +39,11 @@ pub async fn send_user_created(
 <body>
     <article style="margin: 4rem 0; padding: 4rem 2rem; border-radius: 0.25rem; background: #fff; box-shadow: 0.0145rem 0.029rem 0.174rem rgba(27, 40, 50, 0.01698),0.0335rem 0.067rem 0.402rem rgba(27, 40, 50, 0.024),0.0625rem 0.125rem 0.75rem rgba(27, 40, 50, 0.03),0.1125rem 0.225rem 1.35rem rgba(27, 40, 50, 0.036),0.2085rem 0.417rem 2.502rem rgba(27, 40, 50, 0.04302),0.5rem 1rem 6rem rgba(27, 40, 50, 0.06),0 0 0 0.0625rem rgba(27, 40, 50, 0.015);">
         Hallo {display_name},<br><br>
-        {created_by} hat dir einen Account im Bambushain angelegt, willkommen bei den Pandas, schön das du da bist 🙂
+        {invited_by} hat dich in den Bambushain eingeladen, willkommen bei den Pandas, schön das du da bist 🙂
         <br><br>
-        Du kannst dich unter <a style="color: #598c79;text-decoration: none" href="https://pandas.bambushain.app">https://pandas.bambushain.app</a> mit der Emailadresse <kbd style="background-color: #1b2832; color: #fff; vertical-align: baseline; display: inline-block; padding: .375rem .5rem; border-radius: 0.25rem; font-weight: bolder; line-height: initial; font-size: .875em; font-family: menlo, consolas, 'roboto mono', 'ubuntu monospace','noto mono','oxygen mono','liberation mono',monospace,'apple color emoji','segoe ui symbol','noto emoji'">{to}</kbd> und dem Passwort <kbd style="background-color: #1b2832; color: #fff; vertical-align: baseline; display: inline-block; padding: .375rem .5rem; border-radius: 0.25rem; font-weight: bolder; line-height: initial; font-size: .875em; font-family: menlo, consolas, 'roboto mono', 'ubuntu monospace','noto mono','oxygen mono','liberation mono',monospace,'apple color emoji','segoe ui symbol','noto emoji'">{password}</kbd> anmelden. Wenn beides korrekt ist wird dir an deine Emailadresse ein Zwei Faktor Code geschickt.<br><br>
+        Über den folgenden Link kannst du in den nächsten {valid_days} Tagen deinen Namen und dein Passwort festlegen:<br><br>
+        <a style="color: #598c79;text-decoration: none" href="{link}">{link}</a><br><br>
+        Danach kannst du dich mit der Emailadresse <kbd style="background-color: #1b2832; color: #fff; vertical-align: baseline; display: inline-block; padding: .375rem .5rem; border-radius: 0.25rem; font-weight: bolder; line-height: initial; font-size: .875em; font-family: menlo, consolas, 'roboto mono', 'ubuntu monospace','noto mono','oxygen mono','liberation mono',monospace,'apple color emoji','segoe ui symbol','noto emoji'">{to}</kbd> und deinem Passwort anmelden.<br><br>
         Alles Gute vom 🐼
     </article>
 </body>
@@ -32,16 +53,20 @@ pub async fn send_user_created(
         r#"
 Hallo {display_name},
 
-{created_by} hat dir einen Account im Bambushain angelegt, willkommen bei den Pandas, schön das du da bist 🙂
+{invited_by} hat dich in den Bambushain eingeladen, willkommen bei den Pandas, schön das du da bist 🙂
+
+Über den folgenden Link kannst du in den nächsten {valid_days} Tagen deinen Namen und dein Passwort festlegen:
+
+{link}
 
-Du kannst dich unter https://pandas.bambushain.app mit der Emailadresse {to} und dem Passwort {password} anmelden. Wenn beides korrekt ist wird dir an deine Emailadresse ein Zwei Faktor Code geschickt.
+Danach kannst du dich mit der Emailadresse {to} und deinem Passwort anmelden.
 
 Alles Gute vom 🐼"#
     );
 
     send_mail(
         env_service,
-        "Willkommen im Bambushain",
+        "Deine Einladung in den Bambushain",
         to,
         plain_body,
         html_body,
@@ -51,7 +76,7 @@ Alles Gute vom 🐼"#
         log::error!("Failed to send email {err}");
         log::error!("{err:#?}");
 
-        BambooError::mailing("Failed to send welcome email")
+        BambooError::mailing("Failed to send invitation email")
     })
 }
 
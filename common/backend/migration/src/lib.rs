@@ -24,6 +24,30 @@ mod m20231230_000521_update_table_event_add_column_grove_id;
 mod m20231230_001307_update_table_user_add_column_grove_id;
 mod m20231230_231220_update_table_character_change_unique;
 mod m20240117_125532_fix_foreign_key_custom_character_field;
+mod m20240203_184512_update_table_event_add_recurrence;
+mod m20240205_201133_update_table_user_add_column_calendar_feed_secret;
+mod m20240208_193047_update_table_event_add_times;
+mod m20240211_174520_create_table_event_attendance;
+mod m20240214_201508_create_table_event_party_slot;
+mod m20240217_090412_create_table_event_reminder;
+mod m20240220_184233_create_table_grove_webhook;
+mod m20240223_201744_update_table_token_add_session_columns;
+mod m20240226_193012_create_table_passkey;
+mod m20240229_181547_create_table_recovery_code;
+mod m20240302_102233_create_table_login_attempt;
+mod m20240305_190114_create_table_password_reset;
+mod m20240308_201522_create_table_invitation;
+mod m20240311_183044_create_table_audit_log;
+mod m20240314_192208_create_table_role;
+mod m20240317_164810_create_table_personal_access_token;
+mod m20240320_190817_add_soft_delete_columns;
+mod m20240322_181204_rehash_legacy_tokens;
+mod m20240324_102517_update_table_recovery_code_add_totp_secret;
+mod m20240326_184930_seed_mod_role;
+mod m20240328_191405_create_table_sse_notification;
+mod m20240329_184512_update_table_grove_add_column_is_demo;
+mod m20240330_172214_update_type_audit_action_add_role_values;
+mod m20240331_190233_update_table_recovery_code_drop_totp_secret;
 
 pub struct Migrator;
 
@@ -55,6 +79,30 @@ impl MigratorTrait for Migrator {
             Box::new(m20231230_001307_update_table_user_add_column_grove_id::Migration),
             Box::new(m20231230_231220_update_table_character_change_unique::Migration),
             Box::new(m20240117_125532_fix_foreign_key_custom_character_field::Migration),
+            Box::new(m20240203_184512_update_table_event_add_recurrence::Migration),
+            Box::new(m20240205_201133_update_table_user_add_column_calendar_feed_secret::Migration),
+            Box::new(m20240208_193047_update_table_event_add_times::Migration),
+            Box::new(m20240211_174520_create_table_event_attendance::Migration),
+            Box::new(m20240214_201508_create_table_event_party_slot::Migration),
+            Box::new(m20240217_090412_create_table_event_reminder::Migration),
+            Box::new(m20240220_184233_create_table_grove_webhook::Migration),
+            Box::new(m20240223_201744_update_table_token_add_session_columns::Migration),
+            Box::new(m20240226_193012_create_table_passkey::Migration),
+            Box::new(m20240229_181547_create_table_recovery_code::Migration),
+            Box::new(m20240302_102233_create_table_login_attempt::Migration),
+            Box::new(m20240305_190114_create_table_password_reset::Migration),
+            Box::new(m20240308_201522_create_table_invitation::Migration),
+            Box::new(m20240311_183044_create_table_audit_log::Migration),
+            Box::new(m20240314_192208_create_table_role::Migration),
+            Box::new(m20240317_164810_create_table_personal_access_token::Migration),
+            Box::new(m20240320_190817_add_soft_delete_columns::Migration),
+            Box::new(m20240322_181204_rehash_legacy_tokens::Migration),
+            Box::new(m20240324_102517_update_table_recovery_code_add_totp_secret::Migration),
+            Box::new(m20240326_184930_seed_mod_role::Migration),
+            Box::new(m20240328_191405_create_table_sse_notification::Migration),
+            Box::new(m20240329_184512_update_table_grove_add_column_is_demo::Migration),
+            Box::new(m20240330_172214_update_type_audit_action_add_role_values::Migration),
+            Box::new(m20240331_190233_update_table_recovery_code_drop_totp_secret::Migration),
         ]
     }
 }
@@ -1,4 +1,5 @@
 use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::Statement;
 
 use crate::m20220101_000001_create_schemas::Schemas;
 use crate::m20230724_121111_create_table_character::Character;
@@ -10,14 +11,13 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        manager
-            .drop_foreign_key(
-                ForeignKey::drop()
-                    .table((Schemas::FinalFantasy, CustomCharacterFieldValue::Table))
-                    .name("custom_character_field_value_custom_character_field_id_fkey1")
-                    .to_owned(),
-            )
-            .await?;
+        let db = manager.get_connection();
+        // Only databases created before this migration have the broken key, fresh ones never got it
+        let drop_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            "ALTER TABLE final_fantasy.custom_character_field_value DROP CONSTRAINT IF EXISTS custom_character_field_value_custom_character_field_id_fkey1",
+        );
+        db.execute(drop_stmt).await?;
         manager
             .create_foreign_key(
                 ForeignKey::create()
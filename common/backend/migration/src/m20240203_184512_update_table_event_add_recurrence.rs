@@ -0,0 +1,52 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Bamboo, Event::Table))
+                    .add_column(ColumnDef::new(Event::Recurrence).json_binary().null())
+                    .add_column(ColumnDef::new(Event::SeriesId).integer().null())
+                    .add_column(ColumnDef::new(Event::SeriesDate).date().null())
+                    .add_foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Bamboo, Event::Table), Event::SeriesId)
+                            .to((Schemas::Bamboo, Event::Table), Event::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .get_foreign_key(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Bamboo, Event::Table))
+                    .drop_foreign_key(Alias::new("event_series_id_fkey"))
+                    .drop_column(Event::SeriesDate)
+                    .drop_column(Event::SeriesId)
+                    .drop_column(Event::Recurrence)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Id,
+    Recurrence,
+    SeriesId,
+    SeriesDate,
+}
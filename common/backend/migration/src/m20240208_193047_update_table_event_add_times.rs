@@ -0,0 +1,51 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Bamboo, Event::Table))
+                    .add_column(
+                        ColumnDef::new(Event::AllDay)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .add_column(ColumnDef::new(Event::StartTime).time().null())
+                    .add_column(ColumnDef::new(Event::EndTime).time().null())
+                    .add_column(ColumnDef::new(Event::TimeZone).string().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Bamboo, Event::Table))
+                    .drop_column(Event::TimeZone)
+                    .drop_column(Event::EndTime)
+                    .drop_column(Event::StartTime)
+                    .drop_column(Event::AllDay)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    AllDay,
+    StartTime,
+    EndTime,
+    TimeZone,
+}
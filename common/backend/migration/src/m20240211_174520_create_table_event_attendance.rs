@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{EnumIter, Iterable};
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20230724_121011_create_table_user::User;
+use crate::m20230724_121111_create_table_character::Character;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum((Schemas::Bamboo, Alias::new("attendance_status")))
+                    .values(AttendanceStatus::iter().collect::<Vec<AttendanceStatus>>())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Bamboo, EventAttendance::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EventAttendance::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(EventAttendance::EventId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(EventAttendance::EventDate).date().not_null())
+                    .col(ColumnDef::new(EventAttendance::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(EventAttendance::CharacterId)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(EventAttendance::FighterJob)
+                            .custom(Alias::new("final_fantasy.fighter_job"))
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(EventAttendance::CrafterJob)
+                            .custom(Alias::new("final_fantasy.crafter_job"))
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(EventAttendance::Status)
+                            .custom(Alias::new("bamboo.attendance_status"))
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Bamboo, EventAttendance::Table),
+                                EventAttendance::EventId,
+                            )
+                            .to((Schemas::Bamboo, Event::Table), Event::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Bamboo, EventAttendance::Table),
+                                EventAttendance::UserId,
+                            )
+                            .to((Schemas::Authentication, User::Table), User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Bamboo, EventAttendance::Table),
+                                EventAttendance::CharacterId,
+                            )
+                            .to((Schemas::FinalFantasy, Character::Table), Character::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .index(
+                        Index::create()
+                            .col(EventAttendance::EventId)
+                            .col(EventAttendance::EventDate)
+                            .col(EventAttendance::UserId)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Bamboo, EventAttendance::Table))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_type(
+                Type::drop()
+                    .name((Schemas::Bamboo, Alias::new("attendance_status")))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum EventAttendance {
+    Table,
+    Id,
+    EventId,
+    EventDate,
+    UserId,
+    CharacterId,
+    FighterJob,
+    CrafterJob,
+    Status,
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Id,
+}
+
+#[derive(Iden, EnumIter)]
+enum AttendanceStatus {
+    Accepted,
+    Tentative,
+    Declined,
+}
@@ -0,0 +1,151 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{EnumIter, Iterable};
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20230724_121011_create_table_user::User;
+use crate::m20230724_165656_create_table_fighter::Fighter;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum((Schemas::Bamboo, Alias::new("party_template")))
+                    .values(PartyTemplate::iter().collect::<Vec<PartyTemplate>>())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Bamboo, Event::Table))
+                    .add_column(
+                        ColumnDef::new(Event::PartyTemplate)
+                            .custom(Alias::new("bamboo.party_template"))
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Bamboo, EventPartySlot::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EventPartySlot::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(EventPartySlot::EventId).integer().not_null())
+                    .col(ColumnDef::new(EventPartySlot::EventDate).date().not_null())
+                    .col(ColumnDef::new(EventPartySlot::Slot).integer().not_null())
+                    .col(ColumnDef::new(EventPartySlot::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(EventPartySlot::FighterId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Bamboo, EventPartySlot::Table),
+                                EventPartySlot::EventId,
+                            )
+                            .to((Schemas::Bamboo, Event::Table), Event::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Bamboo, EventPartySlot::Table),
+                                EventPartySlot::UserId,
+                            )
+                            .to((Schemas::Authentication, User::Table), User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Bamboo, EventPartySlot::Table),
+                                EventPartySlot::FighterId,
+                            )
+                            .to((Schemas::FinalFantasy, Fighter::Table), Fighter::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .col(EventPartySlot::EventId)
+                            .col(EventPartySlot::EventDate)
+                            .col(EventPartySlot::Slot)
+                            .unique(),
+                    )
+                    .index(
+                        Index::create()
+                            .col(EventPartySlot::EventId)
+                            .col(EventPartySlot::EventDate)
+                            .col(EventPartySlot::UserId)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Bamboo, EventPartySlot::Table))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Bamboo, Event::Table))
+                    .drop_column(Event::PartyTemplate)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_type(
+                Type::drop()
+                    .name((Schemas::Bamboo, Alias::new("party_template")))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum EventPartySlot {
+    Table,
+    Id,
+    EventId,
+    EventDate,
+    Slot,
+    UserId,
+    FighterId,
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Id,
+    PartyTemplate,
+}
+
+#[derive(Iden, EnumIter)]
+enum PartyTemplate {
+    LightParty,
+    FullParty,
+    Alliance,
+}
@@ -0,0 +1,139 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20231229_235511_create_table_grove::Grove;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Grove, GroveWebhook::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GroveWebhook::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GroveWebhook::GroveId).integer().not_null())
+                    .col(ColumnDef::new(GroveWebhook::Url).text().not_null())
+                    .col(ColumnDef::new(GroveWebhook::Secret).string().not_null())
+                    .col(
+                        ColumnDef::new(GroveWebhook::DiscordFormat)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Grove, GroveWebhook::Table), GroveWebhook::GroveId)
+                            .to((Schemas::Grove, Grove::Table), Grove::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Grove, GroveWebhookDelivery::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GroveWebhookDelivery::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveWebhookDelivery::WebhookId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveWebhookDelivery::EventType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveWebhookDelivery::Attempt)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveWebhookDelivery::StatusCode)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveWebhookDelivery::Success)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(GroveWebhookDelivery::Error).text().null())
+                    .col(
+                        ColumnDef::new(GroveWebhookDelivery::DeliveredAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Grove, GroveWebhookDelivery::Table),
+                                GroveWebhookDelivery::WebhookId,
+                            )
+                            .to((Schemas::Grove, GroveWebhook::Table), GroveWebhook::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Grove, GroveWebhookDelivery::Table))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Grove, GroveWebhook::Table))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GroveWebhook {
+    Table,
+    Id,
+    GroveId,
+    Url,
+    Secret,
+    DiscordFormat,
+}
+
+#[derive(DeriveIden)]
+enum GroveWebhookDelivery {
+    Table,
+    Id,
+    WebhookId,
+    EventType,
+    Attempt,
+    StatusCode,
+    Success,
+    Error,
+    DeliveredAt,
+}
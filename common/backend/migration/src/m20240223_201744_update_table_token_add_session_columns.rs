@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Authentication, Token::Table))
+                    .add_column(
+                        ColumnDef::new(Token::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .add_column(
+                        ColumnDef::new(Token::LastUsedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .add_column(
+                        ColumnDef::new(Token::ExpiresAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::cust("now() + interval '30 days'")),
+                    )
+                    .add_column(ColumnDef::new(Token::UserAgent).text().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table((Schemas::Authentication, Token::Table))
+                    .name("idx_token_expires_at")
+                    .col(Token::ExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Authentication, Token::Table))
+                    .drop_column(Token::CreatedAt)
+                    .drop_column(Token::LastUsedAt)
+                    .drop_column(Token::ExpiresAt)
+                    .drop_column(Token::UserAgent)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Token {
+    Table,
+    CreatedAt,
+    LastUsedAt,
+    ExpiresAt,
+    UserAgent,
+}
@@ -0,0 +1,134 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20230724_121011_create_table_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Authentication, Passkey::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Passkey::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Passkey::UserId).integer().not_null())
+                    .col(ColumnDef::new(Passkey::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(Passkey::CredentialId)
+                            .text()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Passkey::PublicKey).text().not_null())
+                    .col(ColumnDef::new(Passkey::Algorithm).integer().not_null())
+                    .col(
+                        ColumnDef::new(Passkey::SignCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(Passkey::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(Passkey::LastUsedAt).timestamp().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Authentication, Passkey::Table), Passkey::UserId)
+                            .to((Schemas::Authentication, User::Table), User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Authentication, PasskeyChallenge::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PasskeyChallenge::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PasskeyChallenge::Challenge)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(PasskeyChallenge::UserId).integer().null())
+                    .col(
+                        ColumnDef::new(PasskeyChallenge::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Authentication, PasskeyChallenge::Table),
+                                PasskeyChallenge::UserId,
+                            )
+                            .to((Schemas::Authentication, User::Table), User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Authentication, PasskeyChallenge::Table))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Authentication, Passkey::Table))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Passkey {
+    Table,
+    Id,
+    UserId,
+    Name,
+    CredentialId,
+    PublicKey,
+    Algorithm,
+    SignCount,
+    CreatedAt,
+    LastUsedAt,
+}
+
+#[derive(DeriveIden)]
+enum PasskeyChallenge {
+    Table,
+    Id,
+    Challenge,
+    UserId,
+    CreatedAt,
+}
@@ -0,0 +1,126 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{EnumIter, Iterable};
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20230724_121011_create_table_user::User;
+use crate::m20231229_235511_create_table_grove::Grove;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum((Schemas::Grove, Alias::new("audit_action")))
+                    .values(AuditAction::iter().collect::<Vec<AuditAction>>())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Grove, AuditLog::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditLog::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AuditLog::GroveId).integer().null())
+                    .col(ColumnDef::new(AuditLog::ActorId).integer().null())
+                    .col(ColumnDef::new(AuditLog::ActorName).string().not_null())
+                    .col(
+                        ColumnDef::new(AuditLog::Action)
+                            .custom(Alias::new("grove.audit_action"))
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AuditLog::TargetId).integer().null())
+                    .col(ColumnDef::new(AuditLog::TargetName).string().not_null())
+                    .col(ColumnDef::new(AuditLog::CreatedAt).timestamp().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Grove, AuditLog::Table), AuditLog::GroveId)
+                            .to((Schemas::Grove, Grove::Table), Grove::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Grove, AuditLog::Table), AuditLog::ActorId)
+                            .to((Schemas::Authentication, User::Table), User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Grove, AuditLog::Table), AuditLog::TargetId)
+                            .to((Schemas::Authentication, User::Table), User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table((Schemas::Grove, AuditLog::Table))
+                    .col(AuditLog::GroveId)
+                    .col(AuditLog::CreatedAt)
+                    .name("audit_log_grove_id_created_at_idx")
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Grove, AuditLog::Table))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_type(
+                Type::drop()
+                    .name((Schemas::Grove, Alias::new("audit_action")))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditLog {
+    Table,
+    Id,
+    GroveId,
+    ActorId,
+    ActorName,
+    Action,
+    TargetId,
+    TargetName,
+    CreatedAt,
+}
+
+#[derive(Iden, EnumIter)]
+enum AuditAction {
+    UserDeleted,
+    ModRightsGranted,
+    ModRightsRevoked,
+    PasswordReset,
+    TotpDisabled,
+    ProfileUpdated,
+    InvitationCreated,
+    InvitationRevoked,
+    GroveCreated,
+    GroveEnabled,
+    GroveDisabled,
+    GroveSuspended,
+    GroveResumed,
+    GroveDeleted,
+}
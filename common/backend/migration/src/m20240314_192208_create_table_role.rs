@@ -0,0 +1,122 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20231229_235511_create_table_grove::Grove;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Grove, Role::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Role::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Role::GroveId).integer().not_null())
+                    .col(ColumnDef::new(Role::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(Role::ManageUsers)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Role::ManageEvents)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Role::ManageGrove)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Role::ViewAuditLog)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Grove, Role::Table), Role::GroveId)
+                            .to((Schemas::Grove, Grove::Table), Grove::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(Index::create().col(Role::GroveId).col(Role::Name).unique())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Authentication, User::Table))
+                    .add_column(ColumnDef::new(User::RoleId).integer().null())
+                    .add_foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Authentication, User::Table), User::RoleId)
+                            .to((Schemas::Grove, Role::Table), Role::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .get_foreign_key(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_type(
+                Type::alter()
+                    .name((Schemas::Grove, Alias::new("audit_action")))
+                    .add_value(Alias::new("role_assigned"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres cannot drop enum values, role_assigned stays in grove.audit_action
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Authentication, User::Table))
+                    .drop_column(User::RoleId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Grove, Role::Table))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Role {
+    Table,
+    Id,
+    GroveId,
+    Name,
+    ManageUsers,
+    ManageEvents,
+    ManageGrove,
+    ViewAuditLog,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    RoleId,
+}
@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::FinalFantasy, Character::Table))
+                    .add_column(ColumnDef::new(Character::DeletedAt).timestamp().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Bamboo, Event::Table))
+                    .add_column(ColumnDef::new(Event::DeletedAt).timestamp().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Authentication, User::Table))
+                    .add_column(ColumnDef::new(User::DeletedAt).timestamp().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_type(
+                Type::alter()
+                    .name((Schemas::Grove, Alias::new("audit_action")))
+                    .add_value(Alias::new("user_restored"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres cannot drop enum values, user_restored stays in grove.audit_action
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Authentication, User::Table))
+                    .drop_column(User::DeletedAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Bamboo, Event::Table))
+                    .drop_column(Event::DeletedAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::FinalFantasy, Character::Table))
+                    .drop_column(Character::DeletedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Character {
+    Table,
+    DeletedAt,
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    DeletedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    DeletedAt,
+}
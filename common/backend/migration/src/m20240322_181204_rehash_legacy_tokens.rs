@@ -0,0 +1,25 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::Statement;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        // Tokens created before hashing was introduced are still stored in plaintext, hashed tokens are 64 hex chars
+        let rehash_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            "UPDATE authentication.token SET token = encode(sha256(convert_to(token, 'UTF8')), 'hex') WHERE token !~ '^[0-9a-f]{64}$'",
+        );
+        db.execute(rehash_stmt).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // The plaintext tokens cannot be restored from their hashes
+        Ok(())
+    }
+}
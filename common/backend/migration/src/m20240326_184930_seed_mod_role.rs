@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::Statement;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        // A role named Mod that exists already keeps its permissions and its pandas under a new name,
+        // otherwise its pandas would silently get every permission without being mods
+        let rename_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            "UPDATE grove.role SET name = 'Mod (alt ' || id || ')' WHERE name = 'Mod'",
+        );
+        db.execute(rename_stmt).await?;
+
+        // Mods no longer get every permission implicitly
+        let seed_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            "INSERT INTO grove.role (grove_id, name, manage_users, manage_events, manage_grove, view_audit_log) SELECT id, 'Mod', true, true, true, true FROM grove.grove",
+        );
+        db.execute(seed_stmt).await?;
+
+        let assign_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            "UPDATE authentication.\"user\" AS u SET role_id = r.id FROM grove.role AS r WHERE u.is_mod AND r.grove_id = u.grove_id AND r.name = 'Mod'",
+        );
+        db.execute(assign_stmt).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // The mod role stays, mods just keep it as their role
+        Ok(())
+    }
+}
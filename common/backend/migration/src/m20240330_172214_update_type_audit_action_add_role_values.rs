@@ -0,0 +1,30 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for value in ["role_created", "role_updated", "role_deleted"] {
+            manager
+                .alter_type(
+                    Type::alter()
+                        .name((Schemas::Grove, Alias::new("audit_action")))
+                        .add_value(Alias::new(value))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres cannot drop enum values, the role values stay in grove.audit_action
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::Statement;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Authentication, RecoveryCode::Table))
+                    .drop_column(RecoveryCode::TotpSecret)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        // The existing codes are unsalted sha256 hashes, users have to generate new ones
+        let delete_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            "DELETE FROM authentication.recovery_code",
+        );
+        db.execute(delete_stmt).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Authentication, RecoveryCode::Table))
+                    .add_column(ColumnDef::new(RecoveryCode::TotpSecret).binary().null())
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RecoveryCode {
+    Table,
+    TotpSecret,
+}
@@ -0,0 +1,133 @@
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+
+use actix_web::dev::Payload;
+use actix_web::{web, FromRequest, HttpRequest};
+use serde::de::DeserializeOwned;
+
+use bamboo_common_core_error::BambooFieldError;
+
+/// Reads the json body like web::Json, but keeps the field that could not be deserialized.
+/// The body is read as json value first, so the content type check and size limit of web::Json still apply
+pub struct JsonBody<T>(Result<web::Json<T>, Option<BambooFieldError>>);
+
+impl<T> JsonBody<T> {
+    /// Fails without a field when the body is missing or no valid json
+    pub fn into_result(self) -> Result<web::Json<T>, Option<BambooFieldError>> {
+        self.0
+    }
+}
+
+impl<T> Deref for JsonBody<T> {
+    type Target = Result<web::Json<T>, Option<BambooFieldError>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub(crate) fn field_error(
+    err: serde_path_to_error::Error<serde_json::Error>,
+) -> Option<BambooFieldError> {
+    let path = err.path().to_string();
+    let message = err.inner().to_string();
+    // Missing fields are reported on the surrounding object, the name is only part of the message
+    if let Some(name) = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next())
+    {
+        let field = if path == "." {
+            name.to_string()
+        } else {
+            format!("{path}.{name}")
+        };
+
+        Some(BambooFieldError::required(field, message))
+    } else if path == "." {
+        None
+    } else {
+        Some(BambooFieldError::invalid_format(path, message))
+    }
+}
+
+impl<T: DeserializeOwned + 'static> FromRequest for JsonBody<T> {
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let value = web::Json::<serde_json::Value>::from_request(req, payload);
+
+        Box::pin(async move {
+            let Ok(value) = value.await else {
+                return Ok(Self(Err(None)));
+            };
+
+            Ok(Self(
+                serde_path_to_error::deserialize(value.into_inner())
+                    .map(web::Json)
+                    .map_err(field_error),
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use bamboo_common_core_error::BambooFieldErrorCode;
+
+    use super::*;
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    #[serde(rename_all = "camelCase")]
+    struct Recurrence {
+        interval: u32,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    #[serde(rename_all = "camelCase")]
+    struct Event {
+        title: String,
+        recurrence: Option<Recurrence>,
+    }
+
+    fn deserialize(value: serde_json::Value) -> Option<BambooFieldError> {
+        serde_path_to_error::deserialize::<_, Event>(value)
+            .map(|_| ())
+            .map_err(field_error)
+            .unwrap_err()
+    }
+
+    #[test]
+    fn test_missing_field() {
+        let field = deserialize(serde_json::json!({})).unwrap();
+
+        assert_eq!(field.field, "title");
+        assert_eq!(field.code, BambooFieldErrorCode::Required);
+    }
+
+    #[test]
+    fn test_missing_nested_field() {
+        let field = deserialize(serde_json::json!({"title": "Raid", "recurrence": {}})).unwrap();
+
+        assert_eq!(field.field, "recurrence.interval");
+        assert_eq!(field.code, BambooFieldErrorCode::Required);
+    }
+
+    #[test]
+    fn test_invalid_field() {
+        let field = deserialize(serde_json::json!({"title": 1})).unwrap();
+
+        assert_eq!(field.field, "title");
+        assert_eq!(field.code, BambooFieldErrorCode::InvalidFormat);
+    }
+
+    #[test]
+    fn test_invalid_body() {
+        assert!(deserialize(serde_json::json!([])).is_none());
+    }
+}
@@ -1,14 +1,22 @@
+pub use crate::json::*;
+pub use crate::list::*;
+
+mod json;
+mod list;
+
+/// Fails with the field that could not be deserialized, the body has to be extracted as JsonBody
 #[macro_export]
 macro_rules! check_missing_fields {
     ($body:expr, $entity:expr) => {
-        if let Some(body) = $body {
-            Ok(body)
-        } else {
-            Err(bamboo_common::core::error::BambooError::invalid_data(
-                $entity,
-                "There are missing fields",
-            ))
-        }
+        $body
+            .into_result()
+            .map_err(|field| bamboo_common::core::error::BambooError {
+                fields: field.into_iter().collect(),
+                ..bamboo_common::core::error::BambooError::invalid_data(
+                    $entity,
+                    "There are missing fields",
+                )
+            })
     };
 }
 
@@ -54,6 +62,17 @@ macro_rules! list {
     };
 }
 
+/// Sends the entries of the page and the total in the X-Total-Count header
+#[macro_export]
+macro_rules! page {
+    ($page:expr) => {{
+        let page = $page;
+        actix_web::HttpResponse::Ok()
+            .insert_header(($crate::TOTAL_COUNT_HEADER, page.total.to_string()))
+            .json(page.entries)
+    }};
+}
+
 #[macro_export]
 macro_rules! created {
     ($data:expr) => {
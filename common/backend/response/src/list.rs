@@ -0,0 +1,89 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+pub const DEFAULT_PAGE_LIMIT: u64 = 50;
+pub const MAX_PAGE_LIMIT: u64 = 200;
+pub const TOTAL_COUNT_HEADER: &str = "X-Total-Count";
+
+/// Paging, sorting and filtering of list routes, without a page all entries are returned
+#[derive(Deserialize, IntoParams, Debug, Clone, Default, Eq, PartialEq)]
+#[into_params(parameter_in = Query)]
+pub struct ListQuery {
+    /// The page to load, the first page is 1
+    pub page: Option<u64>,
+    /// The entries per page, defaults to 50 and is capped at 200
+    pub limit: Option<u64>,
+    /// The field to sort by, a leading - sorts descending
+    pub sort: Option<String>,
+    /// Only entries containing this text are returned, the case is ignored
+    pub filter: Option<String>,
+}
+
+impl ListQuery {
+    pub fn limit(&self) -> u64 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .clamp(1, MAX_PAGE_LIMIT)
+    }
+
+    pub fn offset(&self) -> Option<u64> {
+        self.page
+            .map(|page| page.saturating_sub(1).saturating_mul(self.limit()))
+    }
+
+    /// Returns the field to sort by and whether to sort descending
+    pub fn sort_field(&self) -> Option<(&str, bool)> {
+        self.sort
+            .as_deref()
+            .map(str::trim)
+            .filter(|sort| !sort.is_empty())
+            .map(|sort| {
+                if let Some(field) = sort.strip_prefix('-') {
+                    (field, true)
+                } else {
+                    (sort, false)
+                }
+            })
+    }
+
+    pub fn filter_text(&self) -> Option<String> {
+        self.filter
+            .as_deref()
+            .map(str::trim)
+            .filter(|filter| !filter.is_empty())
+            .map(str::to_lowercase)
+    }
+}
+
+/// The entries of the requested page and the number of entries matching the filter
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Page<T> {
+    pub entries: Vec<T>,
+    pub total: u64,
+}
+
+impl<T> Page<T> {
+    pub fn new(entries: Vec<T>, total: u64) -> Self {
+        Self { entries, total }
+    }
+
+    /// Cuts the page out of entries that are already filtered and sorted
+    pub fn from_entries(entries: Vec<T>, query: &ListQuery) -> Self {
+        let total = entries.len() as u64;
+        let entries = if let Some(offset) = query.offset() {
+            entries
+                .into_iter()
+                .skip(offset as usize)
+                .take(query.limit() as usize)
+                .collect()
+        } else {
+            entries
+        };
+
+        Self::new(entries, total)
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page::new(self.entries.into_iter().map(f).collect(), self.total)
+    }
+}
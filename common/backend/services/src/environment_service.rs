@@ -10,6 +10,13 @@ impl EnvironmentService {
         std::env::var(key.into()).unwrap_or(default.into())
     }
 
+    /// The address the pandas open Bambushain at, links sent in mails point there
+    pub fn get_frontend_url(&self) -> String {
+        self.get_env("FRONTEND_URL", "http://localhost:8070")
+            .trim_end_matches('/')
+            .to_string()
+    }
+
     pub fn get_env_opt(&self, key: impl Into<String>) -> Option<String> {
         std::env::var(key.into()).ok()
     }
@@ -0,0 +1,27 @@
+use chrono::Duration;
+
+use crate::environment_service::EnvironmentService;
+
+/// Pandas and groves both invite pandas, so both read the lifetime and build the link the same way
+pub struct InvitationPolicy {
+    pub lifetime: Duration,
+    pub url: String,
+}
+
+impl InvitationPolicy {
+    pub fn new(env_service: &EnvironmentService) -> Self {
+        let lifetime = env_service
+            .get_env("INVITATION_LIFETIME_DAYS", "7")
+            .parse::<i64>()
+            .unwrap_or(7);
+
+        Self {
+            lifetime: Duration::days(lifetime),
+            url: format!("{}/invitation", env_service.get_frontend_url()),
+        }
+    }
+
+    pub fn link(&self, token: &str) -> String {
+        format!("{}?token={token}", self.url)
+    }
+}
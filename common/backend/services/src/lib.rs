@@ -2,8 +2,10 @@ use actix_web::web;
 use sea_orm::DatabaseConnection;
 
 pub use crate::environment_service::EnvironmentService;
+pub use crate::invitation_policy::InvitationPolicy;
 
 mod environment_service;
+mod invitation_policy;
 pub mod minio_service;
 
 pub type EnvService = web::Data<crate::environment_service::EnvironmentService>;
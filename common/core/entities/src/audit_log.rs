@@ -0,0 +1,169 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::NaiveDateTime;
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "frontend")]
+use strum_macros::EnumIter;
+#[cfg(feature = "backend")]
+use utoipa::{IntoParams, ToSchema};
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+
+#[derive(
+    Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Default,
+)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveActiveEnum, ToSchema),
+    sea_orm(rs_type = "String", db_type = "Enum", enum_name = "grove.audit_action")
+)]
+pub enum AuditAction {
+    #[default]
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "user_deleted"))]
+    UserDeleted,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "mod_rights_granted"))]
+    ModRightsGranted,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "mod_rights_revoked"))]
+    ModRightsRevoked,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "password_reset"))]
+    PasswordReset,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "totp_disabled"))]
+    TotpDisabled,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "profile_updated"))]
+    ProfileUpdated,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "invitation_created"))]
+    InvitationCreated,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "invitation_revoked"))]
+    InvitationRevoked,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "grove_created"))]
+    GroveCreated,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "grove_enabled"))]
+    GroveEnabled,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "grove_disabled"))]
+    GroveDisabled,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "grove_suspended"))]
+    GroveSuspended,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "grove_resumed"))]
+    GroveResumed,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "grove_deleted"))]
+    GroveDeleted,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "role_assigned"))]
+    RoleAssigned,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "user_restored"))]
+    UserRestored,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "role_created"))]
+    RoleCreated,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "role_updated"))]
+    RoleUpdated,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "role_deleted"))]
+    RoleDeleted,
+}
+
+impl Display for AuditAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            AuditAction::UserDeleted => "Panda gelöscht",
+            AuditAction::ModRightsGranted => "Modrechte gegeben",
+            AuditAction::ModRightsRevoked => "Modrechte entzogen",
+            AuditAction::PasswordReset => "Passwort zurückgesetzt",
+            AuditAction::TotpDisabled => "Zwei Faktor per App deaktiviert",
+            AuditAction::ProfileUpdated => "Profil bearbeitet",
+            AuditAction::InvitationCreated => "Panda eingeladen",
+            AuditAction::InvitationRevoked => "Einladung zurückgezogen",
+            AuditAction::GroveCreated => "Hain erstellt",
+            AuditAction::GroveEnabled => "Hain aktiviert",
+            AuditAction::GroveDisabled => "Hain deaktiviert",
+            AuditAction::GroveSuspended => "Hain gesperrt",
+            AuditAction::GroveResumed => "Hain entsperrt",
+            AuditAction::GroveDeleted => "Hain gelöscht",
+            AuditAction::RoleAssigned => "Rolle geändert",
+            AuditAction::UserRestored => "Panda wiederhergestellt",
+            AuditAction::RoleCreated => "Rolle erstellt",
+            AuditAction::RoleUpdated => "Rolle bearbeitet",
+            AuditAction::RoleDeleted => "Rolle gelöscht",
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "audit_log", schema_name = "grove"),
+    schema(as = AuditLog)
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    pub grove_id: Option<i32>,
+    pub actor_id: Option<i32>,
+    pub actor_name: String,
+    pub action: AuditAction,
+    pub target_id: Option<i32>,
+    pub target_name: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::grove::Entity",
+        from = "Column::GroveId",
+        to = "super::grove::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    Grove,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Grove.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn new(
+        grove_id: Option<i32>,
+        actor_id: Option<i32>,
+        actor_name: String,
+        action: AuditAction,
+        target_id: Option<i32>,
+        target_name: String,
+    ) -> Self {
+        Self {
+            id: i32::default(),
+            grove_id,
+            actor_id,
+            actor_name,
+            action,
+            target_id,
+            target_name,
+            created_at: NaiveDateTime::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(IntoParams),
+    into_params(parameter_in = Query)
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogFilter {
+    pub grove_id: Option<i32>,
+    pub action: Option<AuditAction>,
+    pub actor_id: Option<i32>,
+    pub target_id: Option<i32>,
+}
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::*;
@@ -6,6 +8,7 @@ use bamboo_common_backend_macros::*;
 use crate::user::WebUser;
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Login {
     pub email: String,
@@ -25,6 +28,7 @@ impl Login {
 }
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct RequestTwoFactor {
     pub email: String,
@@ -32,7 +36,7 @@ pub struct RequestTwoFactor {
 }
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
-#[cfg_attr(feature = "backend", derive(Responder))]
+#[cfg_attr(feature = "backend", derive(Responder, ToSchema))]
 pub struct LoginResult {
     pub user: WebUser,
     pub token: String,
@@ -40,13 +44,14 @@ pub struct LoginResult {
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
 #[serde(rename_all = "camelCase")]
-#[cfg_attr(feature = "backend", derive(Responder))]
+#[cfg_attr(feature = "backend", derive(Responder, ToSchema))]
 pub struct TwoFactorResult {
     pub user: WebUser,
     pub two_factor_code: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ChangeMyPassword {
     pub old_password: String,
@@ -54,7 +59,18 @@ pub struct ChangeMyPassword {
 }
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ForgotPassword {
     pub email: String,
 }
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ResetPassword {
+    pub token: String,
+    pub password: String,
+    #[serde(default)]
+    pub recovery_code: Option<String>,
+}
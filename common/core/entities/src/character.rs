@@ -3,6 +3,8 @@ use std::cmp::Ordering;
 #[cfg(feature = "backend")]
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::*;
@@ -14,7 +16,7 @@ use crate::{CustomField, FreeCompany};
 #[derive(Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Clone, Default, Copy)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveActiveEnum),
+    derive(DeriveActiveEnum, ToSchema),
     sea_orm(
         rs_type = "String",
         db_type = "Enum",
@@ -104,8 +106,9 @@ impl From<String> for CharacterRace {
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveEntityModel, Responder),
-    sea_orm(table_name = "character", schema_name = "final_fantasy")
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "character", schema_name = "final_fantasy"),
+    schema(as = Character)
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
@@ -121,6 +124,9 @@ pub struct Model {
     #[cfg(feature = "backend")]
     #[serde(skip)]
     pub free_company_id: Option<i32>,
+    #[cfg(feature = "backend")]
+    #[serde(skip)]
+    pub deleted_at: Option<chrono::NaiveDateTime>,
     #[cfg_attr(feature = "backend", sea_orm(ignore))]
     pub custom_fields: Vec<CustomField>,
     #[cfg_attr(feature = "backend", sea_orm(ignore))]
@@ -192,6 +198,8 @@ impl Model {
             user_id: i32::default(),
             #[cfg(feature = "backend")]
             free_company_id: None,
+            #[cfg(feature = "backend")]
+            deleted_at: None,
             custom_fields,
             free_company,
         }
@@ -3,16 +3,22 @@ use std::cmp::Ordering;
 #[cfg(feature = "backend")]
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::*;
+use bamboo_common_core_error::{check_fields, BambooErrorResult, BambooFieldError};
 #[cfg(feature = "frontend")]
 use strum_macros::EnumIter;
 
+pub const MAX_WARD: i16 = 30;
+pub const MAX_PLOT: i16 = 60;
+
 #[derive(Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Clone, Default, Copy)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveActiveEnum),
+    derive(DeriveActiveEnum, ToSchema),
     sea_orm(
         rs_type = "String",
         db_type = "Enum",
@@ -87,7 +93,7 @@ impl Ord for HousingDistrict {
 #[derive(Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Clone, Default, Copy)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveActiveEnum),
+    derive(DeriveActiveEnum, ToSchema),
     sea_orm(
         rs_type = "String",
         db_type = "Enum",
@@ -152,8 +158,9 @@ impl Ord for HousingType {
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveEntityModel, Responder),
-    sea_orm(table_name = "character_housing", schema_name = "final_fantasy")
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "character_housing", schema_name = "final_fantasy"),
+    schema(as = CharacterHousing)
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
@@ -222,4 +229,22 @@ impl Model {
             character_id,
         }
     }
+    /// Every housing district has 30 wards with 60 plots each
+    pub fn validate(&self) -> BambooErrorResult {
+        let mut fields = vec![];
+        if !(1..=MAX_WARD).contains(&self.ward) {
+            fields.push(BambooFieldError::out_of_range(
+                "ward",
+                format!("The ward must be between 1 and {MAX_WARD}"),
+            ));
+        }
+        if !(1..=MAX_PLOT).contains(&self.plot) {
+            fields.push(BambooFieldError::out_of_range(
+                "plot",
+                format!("The plot must be between 1 and {MAX_PLOT}"),
+            ));
+        }
+
+        check_fields("character_housing", fields)
+    }
 }
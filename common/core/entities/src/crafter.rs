@@ -3,6 +3,8 @@ use std::cmp::Ordering;
 #[cfg(feature = "backend")]
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::*;
@@ -12,7 +14,7 @@ use strum_macros::EnumIter;
 #[derive(Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Clone, Default, Copy)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveActiveEnum),
+    derive(DeriveActiveEnum, ToSchema),
     sea_orm(
         rs_type = "String",
         db_type = "Enum",
@@ -134,8 +136,9 @@ impl From<String> for CrafterJob {
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveEntityModel, Responder),
-    sea_orm(table_name = "crafter", schema_name = "final_fantasy")
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "crafter", schema_name = "final_fantasy"),
+    schema(as = Crafter)
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
@@ -4,6 +4,8 @@ use std::collections::BTreeSet;
 #[cfg(feature = "backend")]
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::*;
@@ -11,6 +13,7 @@ use bamboo_common_backend_macros::*;
 use crate::CustomCharacterFieldOption;
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
 pub struct CustomField {
     pub values: BTreeSet<String>,
     pub label: String,
@@ -20,8 +23,9 @@ pub struct CustomField {
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveEntityModel, Responder),
-    sea_orm(table_name = "custom_character_field", schema_name = "final_fantasy")
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "custom_character_field", schema_name = "final_fantasy"),
+    schema(as = CustomCharacterField)
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
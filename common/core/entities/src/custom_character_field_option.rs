@@ -3,6 +3,8 @@ use std::cmp::Ordering;
 #[cfg(feature = "backend")]
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::*;
@@ -10,11 +12,12 @@ use bamboo_common_backend_macros::*;
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveEntityModel, Responder),
+    derive(DeriveEntityModel, Responder, ToSchema),
     sea_orm(
         table_name = "custom_character_field_option",
         schema_name = "final_fantasy"
-    )
+    ),
+    schema(as = CustomCharacterFieldOption)
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
@@ -1,6 +1,8 @@
 #[cfg(feature = "backend")]
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::*;
@@ -10,11 +12,12 @@ use crate::{CustomCharacterField, CustomCharacterFieldOption};
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveEntityModel, Responder),
+    derive(DeriveEntityModel, Responder, ToSchema),
     sea_orm(
         table_name = "custom_character_field_value",
         schema_name = "final_fantasy"
-    )
+    ),
+    schema(as = CustomCharacterFieldValue)
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
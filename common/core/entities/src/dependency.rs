@@ -1,10 +1,12 @@
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::Responder;
 
 #[derive(Debug, Deserialize, Serialize, Clone, Hash, Eq, PartialEq)]
-#[cfg_attr(feature = "backend", derive(Responder))]
+#[cfg_attr(feature = "backend", derive(Responder, ToSchema))]
 pub struct DependencyDetails {
     pub name: String,
     pub authors: String,
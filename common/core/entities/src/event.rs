@@ -1,23 +1,292 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-use chrono::NaiveDate;
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday};
+use chrono_tz::Tz;
 use color_art::{color, Color};
 #[cfg(feature = "backend")]
 use sea_orm::entity::prelude::*;
+#[cfg(feature = "backend")]
+use sea_orm::FromJsonQueryResult;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "frontend")]
+use strum_macros::EnumIter;
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::*;
+use bamboo_common_core_error::{check_fields, BambooError, BambooErrorResult, BambooFieldError};
+
+use crate::FighterRole;
 
 fn set_false() -> bool {
     false
 }
 
+fn set_true() -> bool {
+    true
+}
+
+fn set_one() -> u32 {
+    1
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+#[cfg_attr(feature = "frontend", derive(EnumIter))]
+pub enum RecurrenceFrequency {
+    Daily,
+    #[default]
+    Weekly,
+    Monthly,
+}
+
+impl Display for RecurrenceFrequency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            RecurrenceFrequency::Daily => "Täglich",
+            RecurrenceFrequency::Weekly => "Wöchentlich",
+            RecurrenceFrequency::Monthly => "Monatlich",
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+#[cfg_attr(feature = "frontend", derive(EnumIter))]
+pub enum RecurrenceWeekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Display for RecurrenceWeekday {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            RecurrenceWeekday::Monday => "Montag",
+            RecurrenceWeekday::Tuesday => "Dienstag",
+            RecurrenceWeekday::Wednesday => "Mittwoch",
+            RecurrenceWeekday::Thursday => "Donnerstag",
+            RecurrenceWeekday::Friday => "Freitag",
+            RecurrenceWeekday::Saturday => "Samstag",
+            RecurrenceWeekday::Sunday => "Sonntag",
+        })
+    }
+}
+
+impl From<Weekday> for RecurrenceWeekday {
+    fn from(value: Weekday) -> Self {
+        match value {
+            Weekday::Mon => RecurrenceWeekday::Monday,
+            Weekday::Tue => RecurrenceWeekday::Tuesday,
+            Weekday::Wed => RecurrenceWeekday::Wednesday,
+            Weekday::Thu => RecurrenceWeekday::Thursday,
+            Weekday::Fri => RecurrenceWeekday::Friday,
+            Weekday::Sat => RecurrenceWeekday::Saturday,
+            Weekday::Sun => RecurrenceWeekday::Sunday,
+        }
+    }
+}
+
+impl From<RecurrenceWeekday> for Weekday {
+    fn from(value: RecurrenceWeekday) -> Self {
+        match value {
+            RecurrenceWeekday::Monday => Weekday::Mon,
+            RecurrenceWeekday::Tuesday => Weekday::Tue,
+            RecurrenceWeekday::Wednesday => Weekday::Wed,
+            RecurrenceWeekday::Thursday => Weekday::Thu,
+            RecurrenceWeekday::Friday => Weekday::Fri,
+            RecurrenceWeekday::Saturday => Weekday::Sat,
+            RecurrenceWeekday::Sunday => Weekday::Sun,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "backend", derive(FromJsonQueryResult, ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceRule {
+    pub frequency: RecurrenceFrequency,
+    #[serde(default = "set_one")]
+    pub interval: u32,
+    #[serde(default)]
+    pub by_weekday: BTreeSet<RecurrenceWeekday>,
+    #[serde(default)]
+    pub until: Option<NaiveDate>,
+    #[serde(default)]
+    pub count: Option<u32>,
+    #[serde(default)]
+    pub exceptions: BTreeSet<NaiveDate>,
+}
+
+impl RecurrenceRule {
+    pub fn new(frequency: RecurrenceFrequency) -> Self {
+        Self {
+            frequency,
+            interval: 1,
+            by_weekday: BTreeSet::new(),
+            until: None,
+            count: None,
+            exceptions: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the start dates of all occurrences of a series beginning at `start` up to and including `until`.
+    /// Excluded dates are part of the result, because they still count towards the `count` limit.
+    pub fn occurrence_dates(&self, start: NaiveDate, until: NaiveDate) -> Vec<NaiveDate> {
+        let interval = self.interval.max(1);
+        let last = self.until.map_or(until, |rule_until| rule_until.min(until));
+        let weekdays = if self.by_weekday.is_empty() {
+            vec![start.weekday()]
+        } else {
+            self.by_weekday
+                .iter()
+                .map(|weekday| Weekday::from(*weekday))
+                .collect::<Vec<Weekday>>()
+        };
+
+        let mut dates = vec![];
+        let mut period = 0u32;
+        'periods: while let Some(steps) = period.checked_mul(interval) {
+            let (period_start, candidates) = match self.frequency {
+                RecurrenceFrequency::Daily => {
+                    let Some(date) = start.checked_add_days(Days::new(steps as u64)) else {
+                        break;
+                    };
+                    (date, vec![date])
+                }
+                RecurrenceFrequency::Weekly => {
+                    let Some(week_start) = start
+                        .week(Weekday::Mon)
+                        .first_day()
+                        .checked_add_days(Days::new(steps as u64 * 7))
+                    else {
+                        break;
+                    };
+                    let candidates = weekdays
+                        .iter()
+                        .filter_map(|weekday| {
+                            week_start
+                                .checked_add_days(Days::new(weekday.num_days_from_monday() as u64))
+                        })
+                        .filter(|date| *date >= start)
+                        .collect::<Vec<NaiveDate>>();
+                    (week_start, candidates)
+                }
+                RecurrenceFrequency::Monthly => {
+                    let months = start.month0() as i64 + steps as i64;
+                    let year = start.year() as i64 + months / 12;
+                    let month = (months % 12) as u32 + 1;
+                    let Some(month_start) = i32::try_from(year)
+                        .ok()
+                        .and_then(|year| NaiveDate::from_ymd_opt(year, month, 1))
+                    else {
+                        break;
+                    };
+                    // Months which do not have the day of the series start are skipped, like in RFC 5545
+                    let candidates =
+                        NaiveDate::from_ymd_opt(month_start.year(), month, start.day())
+                            .into_iter()
+                            .collect::<Vec<NaiveDate>>();
+                    (month_start, candidates)
+                }
+            };
+            if period_start > last {
+                break;
+            }
+
+            for date in candidates {
+                if date > last {
+                    break 'periods;
+                }
+                if self
+                    .count
+                    .is_some_and(|count| dates.len() >= count as usize)
+                {
+                    break 'periods;
+                }
+
+                dates.push(date);
+            }
+
+            period += 1;
+        }
+
+        dates
+    }
+
+    pub fn is_occurrence(&self, start: NaiveDate, date: NaiveDate) -> bool {
+        !self.exceptions.contains(&date) && self.occurrence_dates(start, date).contains(&date)
+    }
+}
+
+#[derive(Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveActiveEnum, ToSchema),
+    sea_orm(
+        rs_type = "String",
+        db_type = "Enum",
+        enum_name = "bamboo.party_template"
+    )
+)]
+pub enum PartyTemplate {
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "light_party"))]
+    LightParty,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "full_party"))]
+    FullParty,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "alliance"))]
+    Alliance,
+}
+
+impl Display for PartyTemplate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PartyTemplate::LightParty => "Leichte Gruppe",
+            PartyTemplate::FullParty => "Volle Gruppe",
+            PartyTemplate::Alliance => "Allianz",
+        })
+    }
+}
+
+impl PartyTemplate {
+    /// Returns the role of every slot, the position in the list is the number of the slot.
+    pub fn slots(&self) -> Vec<FighterRole> {
+        let (tanks, healers, dps) = match self {
+            PartyTemplate::LightParty => (1, 1, 2),
+            PartyTemplate::FullParty => (2, 2, 4),
+            PartyTemplate::Alliance => (3, 6, 15),
+        };
+
+        [
+            vec![FighterRole::Tank; tanks],
+            vec![FighterRole::Healer; healers],
+            vec![FighterRole::Dps; dps],
+        ]
+        .concat()
+    }
+}
+
+/// Attaches the time zone to a local date time, times skipped by a daylight saving transition are moved past the gap.
+fn localize(tz: Tz, date_time: NaiveDateTime) -> Option<DateTime<Tz>> {
+    tz.from_local_datetime(&date_time).earliest().or_else(|| {
+        tz.from_local_datetime(&(date_time + chrono::Duration::hours(1)))
+            .earliest()
+    })
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveEntityModel, Responder),
-    sea_orm(table_name = "event", schema_name = "bamboo")
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "event", schema_name = "bamboo"),
+    schema(as = Event)
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
@@ -28,6 +297,14 @@ pub struct Model {
     pub description: String,
     pub start_date: NaiveDate,
     pub end_date: NaiveDate,
+    #[serde(default = "set_true")]
+    pub all_day: bool,
+    #[serde(default)]
+    pub start_time: Option<NaiveTime>,
+    #[serde(default)]
+    pub end_time: Option<NaiveTime>,
+    #[serde(default)]
+    pub time_zone: Option<String>,
     pub color: String,
     #[serde(default = "set_false")]
     pub is_private: bool,
@@ -36,6 +313,21 @@ pub struct Model {
     #[cfg(feature = "backend")]
     #[serde(skip)]
     pub grove_id: i32,
+    #[cfg_attr(feature = "backend", sea_orm(column_type = "JsonBinary", nullable))]
+    #[serde(default)]
+    pub recurrence: Option<RecurrenceRule>,
+    #[serde(default)]
+    pub party_template: Option<PartyTemplate>,
+    #[serde(default)]
+    pub series_id: Option<i32>,
+    #[serde(default)]
+    pub series_date: Option<NaiveDate>,
+    #[cfg_attr(feature = "backend", sea_orm(ignore))]
+    #[serde(default)]
+    pub series_start_date: Option<NaiveDate>,
+    #[cfg(feature = "backend")]
+    #[serde(skip)]
+    pub deleted_at: Option<chrono::NaiveDateTime>,
 }
 
 #[cfg(feature = "backend")]
@@ -57,6 +349,14 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Grove,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::SeriesId",
+        to = "Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Series,
 }
 
 #[cfg(feature = "backend")]
@@ -85,6 +385,7 @@ impl Model {
         end_date: NaiveDate,
         color: Color,
         is_private: bool,
+        recurrence: Option<RecurrenceRule>,
     ) -> Self {
         Self {
             id: i32::default(),
@@ -92,9 +393,37 @@ impl Model {
             description,
             start_date,
             end_date,
+            all_day: true,
+            start_time: None,
+            end_time: None,
+            time_zone: None,
             color: color.hex(),
             is_private,
             user_id: None,
+            recurrence,
+            party_template: None,
+            series_id: None,
+            series_date: None,
+            series_start_date: None,
+        }
+    }
+
+    #[cfg(feature = "frontend")]
+    pub fn with_party_template(self, party_template: Option<PartyTemplate>) -> Self {
+        Self {
+            party_template,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "frontend")]
+    pub fn with_times(self, start_time: NaiveTime, end_time: NaiveTime, time_zone: Tz) -> Self {
+        Self {
+            all_day: false,
+            start_time: Some(start_time),
+            end_time: Some(end_time),
+            time_zone: Some(time_zone.name().to_string()),
+            ..self
         }
     }
 
@@ -105,4 +434,484 @@ impl Model {
     pub fn color(&self) -> Color {
         Color::from_str(self.color.as_str()).unwrap_or(color!(#9f2637))
     }
+
+    /// Checks the fields the user enters, the times are only checked for events with a time
+    pub fn validate(&self) -> BambooErrorResult {
+        let mut fields = vec![];
+        if self.title.trim().is_empty() {
+            fields.push(BambooFieldError::required("title", "The title is empty"));
+        }
+        if Color::from_str(self.color.as_str()).is_err() {
+            fields.push(BambooFieldError::invalid_format(
+                "color",
+                "The color is not a valid color",
+            ));
+        }
+        if self.end_date < self.start_date {
+            fields.push(BambooFieldError::out_of_range(
+                "endDate",
+                "The end date cannot be before the start date",
+            ));
+        }
+
+        if !self.all_day {
+            if self.start_time.is_none() {
+                fields.push(BambooFieldError::required(
+                    "startTime",
+                    "An event with a time needs a start time",
+                ));
+            }
+            if self.end_time.is_none() {
+                fields.push(BambooFieldError::required(
+                    "endTime",
+                    "An event with a time needs an end time",
+                ));
+            }
+            if self.tz().is_none() {
+                fields.push(BambooFieldError::invalid_format(
+                    "timeZone",
+                    "The time zone of the event is invalid",
+                ));
+            } else if self.end_date >= self.start_date
+                && self.end_date_time() < self.start_date_time()
+            {
+                fields.push(BambooFieldError::out_of_range(
+                    "endTime",
+                    "The end of an event cannot be before its start",
+                ));
+            }
+        }
+
+        if let Some(recurrence) = &self.recurrence {
+            if recurrence.interval == 0 {
+                fields.push(BambooFieldError::out_of_range(
+                    "recurrence.interval",
+                    "The interval of a recurring event must be at least one",
+                ));
+            }
+            if recurrence.count == Some(0) {
+                fields.push(BambooFieldError::out_of_range(
+                    "recurrence.count",
+                    "The count of a recurring event must be at least one",
+                ));
+            }
+            if recurrence
+                .until
+                .is_some_and(|until| until < self.start_date)
+            {
+                fields.push(BambooFieldError::out_of_range(
+                    "recurrence.until",
+                    "A recurring event cannot end before it starts",
+                ));
+            }
+        }
+
+        check_fields("event", fields)
+    }
+
+    pub fn tz(&self) -> Option<Tz> {
+        Tz::from_str(self.time_zone.as_deref()?).ok()
+    }
+
+    pub fn start_date_time(&self) -> Option<DateTime<Tz>> {
+        if self.all_day {
+            None
+        } else {
+            localize(self.tz()?, self.start_date.and_time(self.start_time?))
+        }
+    }
+
+    pub fn end_date_time(&self) -> Option<DateTime<Tz>> {
+        if self.all_day {
+            None
+        } else {
+            localize(self.tz()?, self.end_date.and_time(self.end_time?))
+        }
+    }
+
+    /// Returns the start and end date of the event as seen from the time zone `tz`.
+    /// All-day events take place on the same dates everywhere.
+    pub fn dates_in<T: TimeZone>(&self, tz: &T) -> (NaiveDate, NaiveDate) {
+        if let (Some(start), Some(end)) = (self.start_date_time(), self.end_date_time()) {
+            (
+                start.with_timezone(tz).date_naive(),
+                end.with_timezone(tz).date_naive(),
+            )
+        } else {
+            (self.start_date, self.end_date)
+        }
+    }
+
+    pub fn is_recurring(&self) -> bool {
+        self.recurrence.is_some()
+    }
+
+    pub fn is_occurrence_override(&self) -> bool {
+        self.series_id.is_some()
+    }
+
+    /// Checks whether the event or one of its occurrences starts on `date`.
+    pub fn starts_on(&self, date: NaiveDate) -> bool {
+        if let Some(recurrence) = &self.recurrence {
+            recurrence.is_occurrence(self.start_date, date)
+        } else {
+            self.start_date == date
+        }
+    }
+
+    /// Expands the event into all of its occurrences overlapping the range between `since` and `until`.
+    /// Each occurrence keeps the id of the series, starts on the date of the occurrence and remembers the start of the series.
+    pub fn occurrences(&self, since: NaiveDate, until: NaiveDate) -> Vec<Self> {
+        if let Some(recurrence) = &self.recurrence {
+            let duration = self.end_date - self.start_date;
+
+            recurrence
+                .occurrence_dates(self.start_date, until)
+                .into_iter()
+                .filter(|date| !recurrence.exceptions.contains(date))
+                .map(|date| Self {
+                    start_date: date,
+                    end_date: date + duration,
+                    series_start_date: Some(self.start_date),
+                    ..self.clone()
+                })
+                .filter(|occurrence| occurrence.end_date >= since)
+                .collect()
+        } else if self.start_date <= until && self.end_date >= since {
+            vec![self.clone()]
+        } else {
+            vec![]
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder, ToSchema))]
+pub struct CalendarFeed {
+    pub secret: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EventImportError {
+    pub entry: usize,
+    pub title: Option<String>,
+    pub error: BambooError,
+}
+
+impl EventImportError {
+    pub fn new(entry: usize, title: Option<String>, error: BambooError) -> Self {
+        Self {
+            entry,
+            title,
+            error,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder, ToSchema))]
+pub struct EventImport {
+    #[cfg_attr(feature = "backend", schema(value_type = Vec<Event>))]
+    pub events: Vec<Model>,
+    pub errors: Vec<EventImportError>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> Model {
+        Model {
+            title: "Raid Abend".to_string(),
+            start_date: NaiveDate::from_ymd_opt(2024, 3, 10).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2024, 3, 10).unwrap(),
+            all_day: false,
+            start_time: NaiveTime::from_hms_opt(20, 0, 0),
+            end_time: NaiveTime::from_hms_opt(23, 0, 0),
+            time_zone: Some("Europe/Berlin".to_string()),
+            color: "#598c79".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn invalid_fields(event: Model) -> Vec<String> {
+        event
+            .validate()
+            .unwrap_err()
+            .fields
+            .into_iter()
+            .map(|field| field.field)
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_event() {
+        assert!(event().validate().is_ok());
+    }
+
+    #[test]
+    fn test_end_date_before_start_date() {
+        let event = Model {
+            end_date: NaiveDate::from_ymd_opt(2024, 3, 9).unwrap(),
+            ..event()
+        };
+
+        assert_eq!(invalid_fields(event), vec!["endDate"]);
+    }
+
+    #[test]
+    fn test_end_time_before_start_time() {
+        let event = Model {
+            end_time: NaiveTime::from_hms_opt(19, 0, 0),
+            ..event()
+        };
+
+        assert_eq!(invalid_fields(event), vec!["endTime"]);
+    }
+
+    #[test]
+    fn test_invalid_color() {
+        let event = Model {
+            color: "not a color".to_string(),
+            ..event()
+        };
+
+        assert_eq!(invalid_fields(event), vec!["color"]);
+    }
+
+    #[test]
+    fn test_invalid_time_zone() {
+        let event = Model {
+            time_zone: Some("Europe/Bamboo".to_string()),
+            ..event()
+        };
+
+        assert_eq!(invalid_fields(event), vec!["timeZone"]);
+    }
+
+    #[test]
+    fn test_all_day_event_needs_no_time_zone() {
+        let event = Model {
+            all_day: true,
+            start_time: None,
+            end_time: None,
+            time_zone: None,
+            ..event()
+        };
+
+        assert!(event.validate().is_ok());
+    }
+
+    #[test]
+    fn test_party_template_slots() {
+        for (template, tanks, healers, dps) in [
+            (PartyTemplate::LightParty, 1, 1, 2),
+            (PartyTemplate::FullParty, 2, 2, 4),
+            (PartyTemplate::Alliance, 3, 6, 15),
+        ] {
+            let slots = template.slots();
+            let count = |role: FighterRole| slots.iter().filter(|slot| **slot == role).count();
+
+            assert_eq!(count(FighterRole::Tank), tanks, "tanks of {template}");
+            assert_eq!(count(FighterRole::Healer), healers, "healers of {template}");
+            assert_eq!(count(FighterRole::Dps), dps, "dps of {template}");
+        }
+        assert_eq!(PartyTemplate::Alliance.slots().len(), 24);
+    }
+
+    #[test]
+    fn test_weekly_by_weekday() {
+        let recurrence = RecurrenceRule {
+            by_weekday: BTreeSet::from([RecurrenceWeekday::Monday, RecurrenceWeekday::Friday]),
+            ..RecurrenceRule::new(RecurrenceFrequency::Weekly)
+        };
+
+        assert_eq!(
+            recurrence.occurrence_dates(date(2024, 3, 6), date(2024, 3, 18)),
+            vec![
+                date(2024, 3, 8),
+                date(2024, 3, 11),
+                date(2024, 3, 15),
+                date(2024, 3, 18)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weekly_with_interval() {
+        let recurrence = RecurrenceRule {
+            interval: 2,
+            ..RecurrenceRule::new(RecurrenceFrequency::Weekly)
+        };
+
+        assert_eq!(
+            recurrence.occurrence_dates(date(2024, 3, 6), date(2024, 4, 3)),
+            vec![date(2024, 3, 6), date(2024, 3, 20), date(2024, 4, 3)]
+        );
+    }
+
+    #[test]
+    fn test_daily_with_interval() {
+        let recurrence = RecurrenceRule {
+            interval: 3,
+            ..RecurrenceRule::new(RecurrenceFrequency::Daily)
+        };
+
+        assert_eq!(
+            recurrence.occurrence_dates(date(2024, 2, 27), date(2024, 3, 5)),
+            vec![date(2024, 2, 27), date(2024, 3, 1), date(2024, 3, 4)]
+        );
+    }
+
+    #[test]
+    fn test_monthly_on_the_31st() {
+        let recurrence = RecurrenceRule::new(RecurrenceFrequency::Monthly);
+
+        assert_eq!(
+            recurrence.occurrence_dates(date(2024, 1, 31), date(2024, 8, 31)),
+            vec![
+                date(2024, 1, 31),
+                date(2024, 3, 31),
+                date(2024, 5, 31),
+                date(2024, 7, 31),
+                date(2024, 8, 31)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monthly_on_the_29th() {
+        let recurrence = RecurrenceRule {
+            count: Some(3),
+            ..RecurrenceRule::new(RecurrenceFrequency::Monthly)
+        };
+
+        assert_eq!(
+            recurrence.occurrence_dates(date(2023, 1, 29), date(2023, 12, 31)),
+            vec![date(2023, 1, 29), date(2023, 3, 29), date(2023, 4, 29)]
+        );
+        assert_eq!(
+            recurrence.occurrence_dates(date(2024, 1, 29), date(2024, 12, 31)),
+            vec![date(2024, 1, 29), date(2024, 2, 29), date(2024, 3, 29)]
+        );
+    }
+
+    #[test]
+    fn test_monthly_on_the_30th_with_interval() {
+        let recurrence = RecurrenceRule {
+            interval: 2,
+            ..RecurrenceRule::new(RecurrenceFrequency::Monthly)
+        };
+
+        assert_eq!(
+            recurrence.occurrence_dates(date(2023, 12, 30), date(2024, 6, 30)),
+            vec![date(2023, 12, 30), date(2024, 4, 30), date(2024, 6, 30)]
+        );
+    }
+
+    #[test]
+    fn test_count_and_until() {
+        let by_count = RecurrenceRule {
+            count: Some(3),
+            until: Some(date(2024, 3, 31)),
+            ..RecurrenceRule::new(RecurrenceFrequency::Daily)
+        };
+        let by_until = RecurrenceRule {
+            count: Some(10),
+            until: Some(date(2024, 3, 2)),
+            ..RecurrenceRule::new(RecurrenceFrequency::Daily)
+        };
+
+        assert_eq!(
+            by_count.occurrence_dates(date(2024, 3, 1), date(2024, 12, 31)),
+            vec![date(2024, 3, 1), date(2024, 3, 2), date(2024, 3, 3)]
+        );
+        assert_eq!(
+            by_until.occurrence_dates(date(2024, 3, 1), date(2024, 12, 31)),
+            vec![date(2024, 3, 1), date(2024, 3, 2)]
+        );
+        assert_eq!(
+            by_count.occurrence_dates(date(2024, 3, 1), date(2024, 3, 1)),
+            vec![date(2024, 3, 1)]
+        );
+    }
+
+    #[test]
+    fn test_exceptions_count_towards_the_limit() {
+        let recurrence = RecurrenceRule {
+            count: Some(3),
+            exceptions: BTreeSet::from([date(2024, 3, 2)]),
+            ..RecurrenceRule::new(RecurrenceFrequency::Daily)
+        };
+        let event = Model {
+            start_date: date(2024, 3, 1),
+            end_date: date(2024, 3, 1),
+            recurrence: Some(recurrence.clone()),
+            ..event()
+        };
+
+        assert_eq!(
+            recurrence.occurrence_dates(date(2024, 3, 1), date(2024, 3, 31)),
+            vec![date(2024, 3, 1), date(2024, 3, 2), date(2024, 3, 3)]
+        );
+        assert!(recurrence.is_occurrence(date(2024, 3, 1), date(2024, 3, 1)));
+        assert!(!recurrence.is_occurrence(date(2024, 3, 1), date(2024, 3, 2)));
+        assert!(recurrence.is_occurrence(date(2024, 3, 1), date(2024, 3, 3)));
+        assert!(!recurrence.is_occurrence(date(2024, 3, 1), date(2024, 3, 4)));
+        assert_eq!(
+            event
+                .occurrences(date(2024, 3, 1), date(2024, 3, 31))
+                .into_iter()
+                .map(|occurrence| occurrence.start_date)
+                .collect::<Vec<NaiveDate>>(),
+            vec![date(2024, 3, 1), date(2024, 3, 3)]
+        );
+    }
+
+    #[test]
+    fn test_occurrences_overlapping_the_range() {
+        let event = Model {
+            id: 42,
+            start_date: date(2024, 3, 10),
+            end_date: date(2024, 3, 11),
+            recurrence: Some(RecurrenceRule::new(RecurrenceFrequency::Weekly)),
+            ..event()
+        };
+
+        let occurrences = event.occurrences(date(2024, 3, 18), date(2024, 3, 24));
+
+        assert_eq!(
+            occurrences
+                .iter()
+                .map(|occurrence| (occurrence.start_date, occurrence.end_date))
+                .collect::<Vec<(NaiveDate, NaiveDate)>>(),
+            vec![
+                (date(2024, 3, 17), date(2024, 3, 18)),
+                (date(2024, 3, 24), date(2024, 3, 25))
+            ]
+        );
+        assert!(occurrences.iter().all(|occurrence| occurrence.id == 42
+            && occurrence.series_start_date == Some(date(2024, 3, 10))));
+    }
+
+    #[test]
+    fn test_occurrences_of_single_event() {
+        let event = event();
+
+        assert_eq!(
+            event.occurrences(date(2024, 3, 1), date(2024, 3, 31)),
+            vec![event.clone()]
+        );
+        assert!(event
+            .occurrences(date(2024, 3, 11), date(2024, 3, 31))
+            .is_empty());
+    }
 }
@@ -0,0 +1,157 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::NaiveDate;
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "frontend")]
+use strum_macros::EnumIter;
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+
+use crate::{CrafterJob, FighterJob, FighterRole};
+
+#[derive(
+    Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Default,
+)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveActiveEnum, ToSchema),
+    sea_orm(
+        rs_type = "String",
+        db_type = "Enum",
+        enum_name = "bamboo.attendance_status"
+    )
+)]
+pub enum AttendanceStatus {
+    #[default]
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "accepted"))]
+    Accepted,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "tentative"))]
+    Tentative,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "declined"))]
+    Declined,
+}
+
+impl Display for AttendanceStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            AttendanceStatus::Accepted => "Zugesagt",
+            AttendanceStatus::Tentative => "Vielleicht",
+            AttendanceStatus::Declined => "Abgesagt",
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "event_attendance", schema_name = "bamboo"),
+    schema(as = EventAttendance)
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    #[serde(default)]
+    pub event_id: i32,
+    #[serde(default)]
+    pub event_date: NaiveDate,
+    #[serde(default)]
+    pub user_id: i32,
+    #[serde(default)]
+    pub character_id: Option<i32>,
+    #[serde(default)]
+    pub fighter_job: Option<FighterJob>,
+    #[serde(default)]
+    pub crafter_job: Option<CrafterJob>,
+    pub status: AttendanceStatus,
+    #[cfg_attr(feature = "backend", sea_orm(ignore))]
+    #[serde(default)]
+    pub display_name: String,
+    #[cfg_attr(feature = "backend", sea_orm(ignore))]
+    #[serde(default)]
+    pub character_name: Option<String>,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::event::Entity",
+        from = "Column::EventId",
+        to = "super::event::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Event,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::character::Entity",
+        from = "Column::CharacterId",
+        to = "super::character::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    Character,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Event.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::character::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Character.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    #[cfg(feature = "frontend")]
+    pub fn new(
+        event_date: NaiveDate,
+        status: AttendanceStatus,
+        character_id: Option<i32>,
+        fighter_job: Option<FighterJob>,
+        crafter_job: Option<CrafterJob>,
+    ) -> Self {
+        Self {
+            event_date,
+            status,
+            character_id,
+            fighter_job,
+            crafter_job,
+            ..Self::default()
+        }
+    }
+
+    pub fn role(&self) -> Option<FighterRole> {
+        self.fighter_job.map(FighterJob::role)
+    }
+}
@@ -0,0 +1,110 @@
+use chrono::NaiveDate;
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+
+use crate::{FighterJob, FighterRole};
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "event_party_slot", schema_name = "bamboo"),
+    schema(as = EventPartySlot)
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    #[serde(default)]
+    pub event_id: i32,
+    pub event_date: NaiveDate,
+    pub slot: i32,
+    #[serde(default)]
+    pub user_id: i32,
+    pub fighter_id: i32,
+    #[cfg_attr(feature = "backend", sea_orm(ignore))]
+    #[serde(default)]
+    pub display_name: String,
+    #[cfg_attr(feature = "backend", sea_orm(ignore))]
+    #[serde(default)]
+    pub character_name: String,
+    #[cfg_attr(feature = "backend", sea_orm(ignore))]
+    #[serde(default)]
+    pub fighter_job: Option<FighterJob>,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::event::Entity",
+        from = "Column::EventId",
+        to = "super::event::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Event,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::fighter::Entity",
+        from = "Column::FighterId",
+        to = "super::fighter::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Fighter,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Event.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::fighter::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Fighter.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    #[cfg(feature = "frontend")]
+    pub fn new(event_date: NaiveDate, slot: i32, fighter_id: i32) -> Self {
+        Self {
+            event_date,
+            slot,
+            fighter_id,
+            ..Self::default()
+        }
+    }
+
+    pub fn role(&self) -> Option<FighterRole> {
+        self.fighter_job.map(FighterJob::role)
+    }
+}
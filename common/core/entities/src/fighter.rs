@@ -1,8 +1,11 @@
 use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 
 #[cfg(feature = "backend")]
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::*;
@@ -12,7 +15,7 @@ use strum_macros::EnumIter;
 #[derive(Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Clone, Default, Copy)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveActiveEnum),
+    derive(DeriveActiveEnum, ToSchema),
     sea_orm(
         rs_type = "String",
         db_type = "Enum",
@@ -63,7 +66,51 @@ pub enum FighterJob {
     BlueMage,
 }
 
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+#[cfg_attr(feature = "frontend", derive(EnumIter))]
+pub enum FighterRole {
+    Tank,
+    Healer,
+    Dps,
+}
+
+impl Display for FighterRole {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            FighterRole::Tank => "Tank",
+            FighterRole::Healer => "Heiler",
+            FighterRole::Dps => "DPS",
+        })
+    }
+}
+
 impl FighterJob {
+    pub fn role(self) -> FighterRole {
+        match self {
+            FighterJob::Paladin
+            | FighterJob::Warrior
+            | FighterJob::DarkKnight
+            | FighterJob::Gunbreaker => FighterRole::Tank,
+            FighterJob::WhiteMage
+            | FighterJob::Scholar
+            | FighterJob::Astrologian
+            | FighterJob::Sage => FighterRole::Healer,
+            FighterJob::Monk
+            | FighterJob::Dragoon
+            | FighterJob::Ninja
+            | FighterJob::Samurai
+            | FighterJob::Reaper
+            | FighterJob::Bard
+            | FighterJob::Machinist
+            | FighterJob::Dancer
+            | FighterJob::BlackMage
+            | FighterJob::Summoner
+            | FighterJob::RedMage
+            | FighterJob::BlueMage => FighterRole::Dps,
+        }
+    }
+
     pub fn get_file_name(self) -> String {
         match self {
             FighterJob::Paladin => "paladin.webp",
@@ -188,8 +235,9 @@ impl From<String> for FighterJob {
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveEntityModel, Responder),
-    sea_orm(table_name = "fighter", schema_name = "final_fantasy")
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "fighter", schema_name = "final_fantasy"),
+    schema(as = Fighter)
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
@@ -1,6 +1,8 @@
 #[cfg(feature = "backend")]
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::*;
@@ -16,8 +18,9 @@ fn set_true() -> bool {
 #[derive(Serialize, Deserialize, Debug, Eq, Ord, PartialOrd, PartialEq, Clone, Default)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveEntityModel, Responder),
-    sea_orm(table_name = "grove", schema_name = "grove")
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "grove", schema_name = "grove"),
+    schema(as = Grove)
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
@@ -29,6 +32,10 @@ pub struct Model {
     pub is_suspended: bool,
     #[serde(default = "set_true")]
     pub is_enabled: bool,
+    #[serde(default = "set_false")]
+    pub notify_mods_on_forgot_password: bool,
+    #[serde(default = "set_false")]
+    pub is_demo: bool,
 }
 
 #[cfg(feature = "backend")]
@@ -64,11 +71,14 @@ impl Model {
             name,
             is_suspended,
             is_enabled,
+            notify_mods_on_forgot_password: false,
+            is_demo: false,
         }
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, Ord, PartialOrd, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CreateGroveRequest {
     pub grove_name: String,
@@ -0,0 +1,91 @@
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "grove_webhook", schema_name = "grove"),
+    schema(as = GroveWebhook)
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    #[serde(default)]
+    pub grove_id: i32,
+    pub url: String,
+    #[serde(default, skip_serializing)]
+    pub secret: String,
+    #[serde(default)]
+    pub discord_format: bool,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::grove::Entity",
+        from = "Column::GroveId",
+        to = "super::grove::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Grove,
+    #[sea_orm(has_many = "super::grove_webhook_delivery::Entity")]
+    Delivery,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Grove.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove_webhook_delivery::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Delivery.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    #[cfg(feature = "frontend")]
+    pub fn new(url: String, discord_format: bool) -> Self {
+        Self {
+            url,
+            discord_format,
+            ..Self::default()
+        }
+    }
+}
+
+/// Returned once after creation, the secret is not part of any other response
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder, ToSchema))]
+pub struct CreatedGroveWebhook {
+    #[cfg_attr(feature = "backend", schema(value_type = GroveWebhook))]
+    pub webhook: Model,
+    pub secret: String,
+}
+
+impl CreatedGroveWebhook {
+    pub fn new(webhook: Model) -> Self {
+        Self {
+            secret: webhook.secret.clone(),
+            webhook,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use chrono::NaiveDateTime;
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "invitation", schema_name = "authentication"),
+    schema(as = Invitation)
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    #[serde(default)]
+    pub grove_id: i32,
+    #[cfg_attr(feature = "backend", sea_orm(unique))]
+    pub email: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub is_mod: bool,
+    #[serde(default)]
+    pub invited_by: String,
+    #[cfg_attr(feature = "backend", sea_orm(unique))]
+    #[serde(skip)]
+    pub token: String,
+    #[serde(default)]
+    pub created_at: NaiveDateTime,
+    #[serde(default)]
+    pub expires_at: NaiveDateTime,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::grove::Entity",
+        from = "Column::GroveId",
+        to = "super::grove::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Grove,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Grove.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn new(email: String, display_name: String, is_mod: bool) -> Self {
+        Self {
+            email,
+            display_name,
+            is_mod,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptInvitation {
+    pub token: String,
+    pub display_name: String,
+    pub password: String,
+}
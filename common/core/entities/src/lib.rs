@@ -1,3 +1,6 @@
+pub use crate::audit_log::AuditAction;
+pub use crate::audit_log::AuditLogFilter;
+pub use crate::audit_log::Model as AuditLog;
 pub use crate::authentication::*;
 pub use crate::character::CharacterRace;
 pub use crate::character::Model as Character;
@@ -11,13 +14,52 @@ pub use crate::custom_character_field::Model as CustomCharacterField;
 pub use crate::custom_character_field_option::Model as CustomCharacterFieldOption;
 pub use crate::custom_character_field_value::Model as CustomCharacterFieldValue;
 pub use crate::dependency::*;
+pub use crate::event::CalendarFeed;
+pub use crate::event::EventImport;
+pub use crate::event::EventImportError;
 pub use crate::event::Model as Event;
+pub use crate::event::PartyTemplate;
+pub use crate::event::RecurrenceFrequency;
+pub use crate::event::RecurrenceRule;
+pub use crate::event::RecurrenceWeekday;
+pub use crate::event_attendance::AttendanceStatus;
+pub use crate::event_attendance::Model as EventAttendance;
+pub use crate::event_party_slot::Model as EventPartySlot;
+pub use crate::event_reminder::Model as EventReminder;
 pub use crate::fighter::FighterJob;
+pub use crate::fighter::FighterRole;
 pub use crate::fighter::Model as Fighter;
 pub use crate::free_company::Model as FreeCompany;
 pub use crate::grove::Model as Grove;
+pub use crate::grove_webhook::CreatedGroveWebhook;
+pub use crate::grove_webhook::Model as GroveWebhook;
+pub use crate::grove_webhook_delivery::Model as GroveWebhookDelivery;
+pub use crate::invitation::AcceptInvitation;
+pub use crate::invitation::Model as Invitation;
+pub use crate::login_attempt::Model as LoginAttempt;
+pub use crate::passkey::Model as Passkey;
+pub use crate::passkey::PasskeyLogin;
+pub use crate::passkey::PasskeyLoginOptions;
+pub use crate::passkey::PasskeyRegistration;
+pub use crate::passkey::PasskeyRegistrationOptions;
+pub use crate::passkey_challenge::Model as PasskeyChallenge;
+pub use crate::password_reset::Model as PasswordReset;
+pub use crate::personal_access_token::CreatedPersonalAccessToken;
+pub use crate::personal_access_token::Model as PersonalAccessToken;
+pub use crate::personal_access_token::TokenScope;
+pub use crate::recovery_code::Model as RecoveryCode;
+pub use crate::recovery_code::RecoveryCodes;
+pub use crate::recovery_code::RegenerateRecoveryCodes;
+pub use crate::role::AssignRole;
+pub use crate::role::Model as Role;
+pub use crate::role::Permission;
+pub use crate::role::MOD_ROLE_NAME;
+pub use crate::sse::*;
 pub use crate::support::*;
 pub use crate::token::Model as Token;
+pub use crate::token::Session;
+pub use crate::trash::TrashEntry;
+pub use crate::trash::TrashEntryType;
 pub use crate::user::GroveUser;
 pub use crate::user::Model as User;
 pub use crate::user::TotpQrCode;
@@ -25,6 +67,7 @@ pub use crate::user::UpdateProfile;
 pub use crate::user::ValidateTotp;
 pub use crate::user::WebUser;
 
+pub mod audit_log;
 pub mod authentication;
 pub mod character;
 pub mod character_housing;
@@ -34,9 +77,24 @@ pub mod custom_character_field_option;
 pub mod custom_character_field_value;
 pub mod dependency;
 pub mod event;
+pub mod event_attendance;
+pub mod event_party_slot;
+pub mod event_reminder;
 pub mod fighter;
 pub mod free_company;
 pub mod grove;
+pub mod grove_webhook;
+pub mod grove_webhook_delivery;
+pub mod invitation;
+pub mod login_attempt;
+pub mod passkey;
+pub mod passkey_challenge;
+pub mod password_reset;
+pub mod personal_access_token;
+pub mod recovery_code;
+pub mod role;
+pub mod sse;
 pub mod support;
 pub mod token;
+pub mod trash;
 pub mod user;
@@ -0,0 +1,149 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::NaiveDateTime;
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "frontend")]
+use strum_macros::EnumIter;
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+
+#[derive(
+    Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash,
+)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+pub enum TokenScope {
+    ReadEvents,
+    WriteEvents,
+    ReadCharacters,
+    WriteCharacters,
+    ReadUsers,
+}
+
+impl Display for TokenScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenScope::ReadEvents => "Events lesen",
+            TokenScope::WriteEvents => "Events bearbeiten",
+            TokenScope::ReadCharacters => "Charaktere lesen",
+            TokenScope::WriteCharacters => "Charaktere bearbeiten",
+            TokenScope::ReadUsers => "Pandas lesen",
+        })
+    }
+}
+
+/// A long living token for scripts and bots, it only grants access to the routes covered by its scopes
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "personal_access_token", schema_name = "authentication"),
+    schema(as = PersonalAccessToken)
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    #[serde(skip)]
+    pub user_id: i32,
+    pub name: String,
+    #[cfg_attr(feature = "backend", sea_orm(unique))]
+    #[serde(skip)]
+    pub token: String,
+    pub read_events: bool,
+    pub write_events: bool,
+    pub read_characters: bool,
+    pub write_characters: bool,
+    pub read_users: bool,
+    #[serde(default)]
+    pub created_at: NaiveDateTime,
+    #[serde(default)]
+    pub expires_at: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub last_used_at: Option<NaiveDateTime>,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn new(name: String, scopes: &[TokenScope], expires_at: Option<NaiveDateTime>) -> Self {
+        Self {
+            name,
+            read_events: scopes.contains(&TokenScope::ReadEvents),
+            write_events: scopes.contains(&TokenScope::WriteEvents),
+            read_characters: scopes.contains(&TokenScope::ReadCharacters),
+            write_characters: scopes.contains(&TokenScope::WriteCharacters),
+            read_users: scopes.contains(&TokenScope::ReadUsers),
+            expires_at,
+            ..Self::default()
+        }
+    }
+
+    pub fn has_scope(&self, scope: TokenScope) -> bool {
+        match scope {
+            TokenScope::ReadEvents => self.read_events,
+            TokenScope::WriteEvents => self.write_events,
+            TokenScope::ReadCharacters => self.read_characters,
+            TokenScope::WriteCharacters => self.write_characters,
+            TokenScope::ReadUsers => self.read_users,
+        }
+    }
+
+    pub fn scopes(&self) -> Vec<TokenScope> {
+        [
+            TokenScope::ReadEvents,
+            TokenScope::WriteEvents,
+            TokenScope::ReadCharacters,
+            TokenScope::WriteCharacters,
+            TokenScope::ReadUsers,
+        ]
+        .into_iter()
+        .filter(|scope| self.has_scope(*scope))
+        .collect()
+    }
+}
+
+/// Returned once after creation, the raw token cannot be loaded again afterwards
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder, ToSchema))]
+pub struct CreatedPersonalAccessToken {
+    #[cfg_attr(feature = "backend", schema(value_type = PersonalAccessToken))]
+    pub personal_access_token: Model,
+    pub token: String,
+}
+
+impl CreatedPersonalAccessToken {
+    pub fn new(personal_access_token: Model, token: String) -> Self {
+        Self {
+            personal_access_token,
+            token,
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt::{Display, Formatter};
+
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "frontend")]
+use strum_macros::EnumIter;
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+
+/// Every grove has a role with this name that holds all permissions, mods get it assigned
+pub const MOD_ROLE_NAME: &str = "Mod";
+
+#[derive(
+    Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash,
+)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+pub enum Permission {
+    ManageUsers,
+    ManageEvents,
+    ManageGrove,
+    ViewAuditLog,
+}
+
+impl Display for Permission {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Permission::ManageUsers => "Pandas verwalten",
+            Permission::ManageEvents => "Events verwalten",
+            Permission::ManageGrove => "Hain verwalten",
+            Permission::ViewAuditLog => "Protokoll ansehen",
+        })
+    }
+}
+
+/// A set of permissions that can be handed to pandas in their grove.
+/// Mods get the role named Mod, which holds every permission.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "role", schema_name = "grove"),
+    schema(as = Role)
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    #[serde(default)]
+    pub grove_id: i32,
+    pub name: String,
+    pub manage_users: bool,
+    pub manage_events: bool,
+    pub manage_grove: bool,
+    pub view_audit_log: bool,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::grove::Entity",
+        from = "Column::GroveId",
+        to = "super::grove::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Grove,
+    #[sea_orm(has_many = "super::user::Entity")]
+    User,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Grove.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn new(name: String, permissions: &[Permission]) -> Self {
+        Self {
+            name,
+            manage_users: permissions.contains(&Permission::ManageUsers),
+            manage_events: permissions.contains(&Permission::ManageEvents),
+            manage_grove: permissions.contains(&Permission::ManageGrove),
+            view_audit_log: permissions.contains(&Permission::ViewAuditLog),
+            ..Self::default()
+        }
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        match permission {
+            Permission::ManageUsers => self.manage_users,
+            Permission::ManageEvents => self.manage_events,
+            Permission::ManageGrove => self.manage_grove,
+            Permission::ViewAuditLog => self.view_audit_log,
+        }
+    }
+
+    pub fn permissions(&self) -> Vec<Permission> {
+        [
+            Permission::ManageUsers,
+            Permission::ManageEvents,
+            Permission::ManageGrove,
+            Permission::ViewAuditLog,
+        ]
+        .into_iter()
+        .filter(|permission| self.has_permission(*permission))
+        .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AssignRole {
+    pub role_id: Option<i32>,
+}
+
+impl AssignRole {
+    pub fn new(role_id: Option<i32>) -> Self {
+        Self { role_id }
+    }
+}
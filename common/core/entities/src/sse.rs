@@ -0,0 +1,55 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum SseTopic {
+    Event,
+    User,
+    Character,
+    FreeCompany,
+    Housing,
+    Grove,
+}
+
+impl SseTopic {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Event,
+            Self::User,
+            Self::Character,
+            Self::FreeCompany,
+            Self::Housing,
+            Self::Grove,
+        ]
+    }
+}
+
+impl Display for SseTopic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Event => "event",
+            Self::User => "user",
+            Self::Character => "character",
+            Self::FreeCompany => "free-company",
+            Self::Housing => "housing",
+            Self::Grove => "grove",
+        })
+    }
+}
+
+impl FromStr for SseTopic {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|topic| topic.to_string() == s)
+            .ok_or(())
+    }
+}
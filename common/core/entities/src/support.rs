@@ -1,14 +1,18 @@
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 use bamboo_common_core_error::BambooError;
 
 #[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
 pub struct SupportRequest {
     pub subject: String,
     pub message: String,
 }
 
 #[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
 pub struct GlitchTipErrorRequest {
     pub page: String,
     pub form: String,
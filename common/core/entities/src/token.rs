@@ -1,6 +1,9 @@
+use chrono::NaiveDateTime;
 #[cfg(feature = "backend")]
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::*;
@@ -8,8 +11,9 @@ use bamboo_common_backend_macros::*;
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveEntityModel, Responder),
-    sea_orm(table_name = "token", schema_name = "authentication")
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "token", schema_name = "authentication"),
+    schema(as = Token)
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
@@ -19,6 +23,10 @@ pub struct Model {
     pub user_id: i32,
     #[cfg_attr(feature = "backend", sea_orm(unique))]
     pub token: String,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub user_agent: Option<String>,
 }
 
 #[cfg(feature = "backend")]
@@ -43,3 +51,28 @@ impl Related<super::user::Entity> for Entity {
 
 #[cfg(feature = "backend")]
 impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub id: i32,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub user_agent: Option<String>,
+    pub current: bool,
+}
+
+impl Session {
+    pub fn from_token(token: Model, current_token: &str) -> Self {
+        Self {
+            id: token.id,
+            created_at: token.created_at,
+            last_used_at: token.last_used_at,
+            expires_at: token.expires_at,
+            user_agent: token.user_agent,
+            current: token.token == current_token,
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum TrashEntryType {
+    Character,
+    Event,
+    User,
+}
+
+impl Display for TrashEntryType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TrashEntryType::Character => "Charakter",
+            TrashEntryType::Event => "Event",
+            TrashEntryType::User => "Panda",
+        })
+    }
+}
+
+/// A deleted character, event or user, it can be restored until it gets purged
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    pub entry_type: TrashEntryType,
+    pub id: i32,
+    pub name: String,
+    pub deleted_at: NaiveDateTime,
+    pub purge_at: NaiveDateTime,
+}
+
+impl TrashEntry {
+    pub fn new(
+        entry_type: TrashEntryType,
+        id: i32,
+        name: String,
+        deleted_at: NaiveDateTime,
+        purge_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            entry_type,
+            id,
+            name,
+            deleted_at,
+            purge_at,
+        }
+    }
+}
@@ -5,6 +5,11 @@ use sea_orm::entity::prelude::*;
 #[cfg(feature = "backend")]
 use sea_orm::ActiveValue::Set;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "backend")]
+use utoipa::ToSchema;
+
+use crate::role::{Model as Role, Permission};
+use bamboo_common_core_error::{check_fields, BambooErrorResult, BambooFieldError};
 
 #[cfg(feature = "backend")]
 use bamboo_common_backend_macros::*;
@@ -12,8 +17,9 @@ use bamboo_common_backend_macros::*;
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
 #[cfg_attr(
     feature = "backend",
-    derive(DeriveEntityModel, Responder),
-    sea_orm(table_name = "user", schema_name = "authentication")
+    derive(DeriveEntityModel, Responder, ToSchema),
+    sea_orm(table_name = "user", schema_name = "authentication"),
+    schema(as = User)
 )]
 #[serde(rename_all = "camelCase")]
 pub struct Model {
@@ -27,6 +33,8 @@ pub struct Model {
     pub password: String,
     pub display_name: String,
     pub is_mod: bool,
+    #[serde(default)]
+    pub role_id: Option<i32>,
     pub discord_name: String,
     #[cfg(feature = "backend")]
     pub two_factor_code: Option<String>,
@@ -39,6 +47,15 @@ pub struct Model {
     #[cfg(feature = "backend")]
     #[serde(skip)]
     pub grove_id: i32,
+    #[cfg(feature = "backend")]
+    #[cfg_attr(feature = "backend", sea_orm(unique))]
+    #[serde(skip)]
+    pub calendar_feed_secret: Option<String>,
+    #[serde(default)]
+    pub event_reminders: bool,
+    #[cfg(feature = "backend")]
+    #[serde(skip)]
+    pub deleted_at: Option<chrono::NaiveDateTime>,
 }
 
 #[cfg(feature = "backend")]
@@ -58,6 +75,14 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Grove,
+    #[sea_orm(
+        belongs_to = "super::role::Entity",
+        from = "Column::RoleId",
+        to = "super::role::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    Role,
 }
 
 #[cfg(feature = "backend")]
@@ -88,6 +113,13 @@ impl Related<super::grove::Entity> for Entity {
     }
 }
 
+#[cfg(feature = "backend")]
+impl Related<super::role::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Role.def()
+    }
+}
+
 #[cfg(feature = "backend")]
 impl ActiveModelBehavior for ActiveModel {}
 
@@ -105,7 +137,45 @@ impl ActiveModel {
     }
 }
 
+/// Only checks the shape of the address, whether it exists shows when the first mail is sent
+pub fn is_valid_email(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+
+    !local.is_empty()
+        && !domain.contains('@')
+        && !email.chars().any(char::is_whitespace)
+        && domain
+            .split_once('.')
+            .is_some_and(|(name, tld)| !name.is_empty() && !tld.is_empty() && !tld.ends_with('.'))
+}
+
+pub fn validate_profile(email: &str, display_name: &str) -> BambooErrorResult {
+    let mut fields = vec![];
+    if email.trim().is_empty() {
+        fields.push(BambooFieldError::required("email", "The email is empty"));
+    } else if !is_valid_email(email) {
+        fields.push(BambooFieldError::invalid_format(
+            "email",
+            "The email is not a valid email address",
+        ));
+    }
+    if display_name.trim().is_empty() {
+        fields.push(BambooFieldError::required(
+            "displayName",
+            "The name is empty",
+        ));
+    }
+
+    check_fields("user", fields)
+}
+
 impl Model {
+    pub fn validate(&self) -> BambooErrorResult {
+        validate_profile(&self.email, &self.display_name)
+    }
+
     pub fn new(email: String, display_name: String, discord_name: String, is_mod: bool) -> Self {
         Self {
             id: i32::default(),
@@ -113,6 +183,7 @@ impl Model {
             #[cfg(feature = "backend")]
             password: String::default(),
             is_mod,
+            role_id: None,
             display_name,
             discord_name,
             #[cfg(feature = "backend")]
@@ -124,6 +195,11 @@ impl Model {
             totp_validated: None,
             #[cfg(feature = "backend")]
             grove_id: -1,
+            #[cfg(feature = "backend")]
+            calendar_feed_secret: None,
+            event_reminders: false,
+            #[cfg(feature = "backend")]
+            deleted_at: None,
         }
     }
 
@@ -142,16 +218,34 @@ impl Model {
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
 #[serde(rename_all = "camelCase")]
-#[cfg_attr(feature = "backend", derive(Responder))]
+#[cfg_attr(feature = "backend", derive(Responder, ToSchema))]
 pub struct WebUser {
     #[serde(default)]
     pub id: i32,
     pub display_name: String,
     pub email: String,
     pub is_mod: bool,
+    #[serde(default)]
+    pub role_id: Option<i32>,
+    #[serde(default)]
+    pub permissions: Vec<Permission>,
     pub discord_name: String,
     #[serde(default)]
     pub app_totp_enabled: bool,
+    #[serde(default)]
+    pub event_reminders: bool,
+}
+
+impl WebUser {
+    pub fn with_role(mut self, role: Option<Role>) -> Self {
+        self.permissions = role.map(|role| role.permissions()).unwrap_or_default();
+
+        self
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.permissions.contains(&permission)
+    }
 }
 
 impl From<Model> for WebUser {
@@ -159,10 +253,13 @@ impl From<Model> for WebUser {
         Self {
             id: value.id,
             is_mod: value.is_mod,
+            role_id: value.role_id,
+            permissions: vec![],
             display_name: value.display_name.to_string(),
             email: value.email.to_string(),
             discord_name: value.discord_name.clone(),
             app_totp_enabled: value.totp_validated.unwrap_or(false),
+            event_reminders: value.event_reminders,
         }
     }
 }
@@ -178,6 +275,7 @@ impl Display for WebUser {
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateProfile {
     pub email: String,
@@ -196,6 +294,7 @@ impl UpdateProfile {
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ValidateTotp {
     pub code: String,
@@ -204,7 +303,7 @@ pub struct ValidateTotp {
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
 #[serde(rename_all = "camelCase")]
-#[cfg_attr(feature = "backend", derive(Responder))]
+#[cfg_attr(feature = "backend", derive(Responder, ToSchema))]
 pub struct TotpQrCode {
     pub qr_code: String,
     pub secret: String,
@@ -212,7 +311,7 @@ pub struct TotpQrCode {
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
 #[serde(rename_all = "camelCase")]
-#[cfg_attr(feature = "backend", derive(Responder))]
+#[cfg_attr(feature = "backend", derive(Responder, ToSchema))]
 pub struct GroveUser {
     #[serde(default)]
     pub id: i32,
@@ -231,3 +330,47 @@ impl From<Model> for crate::GroveUser {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bamboo_common_core_error::BambooFieldErrorCode;
+
+    use super::*;
+
+    #[test]
+    fn test_valid_emails() {
+        assert!(is_valid_email("panda@bambushain.app"));
+        assert!(is_valid_email("bamboo.panda+grove@mail.bambushain.app"));
+    }
+
+    #[test]
+    fn test_invalid_emails() {
+        assert!(!is_valid_email(""));
+        assert!(!is_valid_email("panda"));
+        assert!(!is_valid_email("@bambushain.app"));
+        assert!(!is_valid_email("panda@"));
+        assert!(!is_valid_email("panda@bambushain"));
+        assert!(!is_valid_email("panda@.app"));
+        assert!(!is_valid_email("panda@bambushain."));
+        assert!(!is_valid_email("panda@bamboo@bambushain.app"));
+        assert!(!is_valid_email("bamboo panda@bambushain.app"));
+    }
+
+    #[test]
+    fn test_validate_profile() {
+        let fields = validate_profile("panda", " ")
+            .unwrap_err()
+            .fields
+            .into_iter()
+            .map(|field| (field.field, field.code))
+            .collect::<Vec<(String, BambooFieldErrorCode)>>();
+
+        assert_eq!(
+            fields,
+            vec![
+                ("email".to_string(), BambooFieldErrorCode::InvalidFormat),
+                ("displayName".to_string(), BambooFieldErrorCode::Required),
+            ]
+        );
+    }
+}
@@ -4,8 +4,11 @@ use std::fmt::{Display, Formatter};
 #[cfg(not(target_arch = "wasm32"))]
 use actix_web::{body, http, HttpRequest, HttpResponse, Responder, ResponseError};
 use serde::{Deserialize, Serialize};
+#[cfg(not(target_arch = "wasm32"))]
+use utoipa::ToSchema;
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum BambooErrorCode {
     Crypto,
@@ -17,6 +20,7 @@ pub enum BambooErrorCode {
     Mailing,
     NotFound,
     Serialization,
+    TooManyRequests,
     Unauthorized,
     Unknown,
     Validation,
@@ -42,12 +46,61 @@ impl Display for BambooErrorCode {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum BambooFieldErrorCode {
+    Required,
+    InvalidFormat,
+    OutOfRange,
+}
+
+/// Points to a single input of the request body that failed the validation
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BambooFieldError {
+    /// The path of the field in the body, nested fields are separated by a dot
+    pub field: String,
+    pub code: BambooFieldErrorCode,
+    pub message: String,
+}
+
+impl BambooFieldError {
+    pub fn new(
+        field: impl Into<String>,
+        code: BambooFieldErrorCode,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            field: field.into(),
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn required(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(field, BambooFieldErrorCode::Required, message)
+    }
+
+    pub fn invalid_format(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(field, BambooFieldErrorCode::InvalidFormat, message)
+    }
+
+    pub fn out_of_range(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(field, BambooFieldErrorCode::OutOfRange, message)
+    }
+}
+
 #[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Debug, Serialize, Deserialize, Default)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BambooError {
     pub entity_type: String,
     pub error_type: BambooErrorCode,
     pub message: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<BambooFieldError>,
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -74,6 +127,7 @@ impl ResponseError for BambooError {
             BambooErrorCode::ExistsAlready => http::StatusCode::CONFLICT,
             BambooErrorCode::Unauthorized => http::StatusCode::UNAUTHORIZED,
             BambooErrorCode::InsufficientRights => http::StatusCode::FORBIDDEN,
+            BambooErrorCode::TooManyRequests => http::StatusCode::TOO_MANY_REQUESTS,
             BambooErrorCode::InvalidData
             | BambooErrorCode::Serialization
             | BambooErrorCode::Validation => http::StatusCode::BAD_REQUEST,
@@ -96,6 +150,7 @@ impl Responder for BambooError {
             BambooErrorCode::ExistsAlready => HttpResponse::Conflict(),
             BambooErrorCode::Unauthorized => HttpResponse::Unauthorized(),
             BambooErrorCode::InsufficientRights => HttpResponse::Forbidden(),
+            BambooErrorCode::TooManyRequests => HttpResponse::TooManyRequests(),
             BambooErrorCode::InvalidData
             | BambooErrorCode::Serialization
             | BambooErrorCode::Validation => HttpResponse::BadRequest(),
@@ -119,9 +174,15 @@ impl BambooError {
             entity_type: entity_type.into(),
             message: message.into(),
             error_type,
+            fields: vec![],
         }
     }
 
+    /// Returns the error of the first field with the given path, forms use it to mark their inputs
+    pub fn field(&self, field: &str) -> Option<&BambooFieldError> {
+        self.fields.iter().find(|error| error.field == field)
+    }
+
     pub fn crypto(entity_type: impl Into<String>, message: impl Into<String>) -> Self {
         Self::new(entity_type, message, BambooErrorCode::Crypto)
     }
@@ -158,10 +219,28 @@ impl BambooError {
         Self::new(entity_type, message, BambooErrorCode::Serialization)
     }
 
+    pub fn too_many_requests(entity_type: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(entity_type, message, BambooErrorCode::TooManyRequests)
+    }
+
     pub fn validation(entity_type: impl Into<String>, message: impl Into<String>) -> Self {
         Self::new(entity_type, message, BambooErrorCode::Validation)
     }
 
+    pub fn invalid_fields(
+        entity_type: impl Into<String>,
+        fields: impl IntoIterator<Item = BambooFieldError>,
+    ) -> Self {
+        Self {
+            fields: fields.into_iter().collect(),
+            ..Self::new(
+                entity_type,
+                "Some fields are invalid",
+                BambooErrorCode::Validation,
+            )
+        }
+    }
+
     pub fn unauthorized(entity_type: impl Into<String>, message: impl Into<String>) -> Self {
         Self::new(entity_type, message, BambooErrorCode::Unauthorized)
     }
@@ -191,6 +270,18 @@ impl From<PasswordError> for BambooError {
 
 pub type BambooErrorResult = Result<(), BambooError>;
 
+/// Fails with all field errors at once, so the user sees every wrong input and not just the first
+pub fn check_fields(
+    entity_type: impl Into<String>,
+    fields: Vec<BambooFieldError>,
+) -> BambooErrorResult {
+    if fields.is_empty() {
+        Ok(())
+    } else {
+        Err(BambooError::invalid_fields(entity_type, fields))
+    }
+}
+
 pub type BambooResult<T> = Result<T, BambooError>;
 
 #[cfg(not(target_arch = "wasm32"))]
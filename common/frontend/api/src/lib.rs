@@ -103,6 +103,7 @@ error_code!(NO_CONTENT, 204);
 error_code!(FORBIDDEN, 403);
 error_code!(NOT_FOUND, 404);
 error_code!(CONFLICT, 409);
+error_code!(TOO_MANY_REQUESTS, 429);
 error_code!(INTERNAL_SERVER_ERROR, 500);
 
 pub async fn handle_response<OUT: DeserializeOwned>(response: Response) -> BambooApiResult<OUT> {
@@ -201,6 +202,83 @@ pub async fn get_with_query<OUT: DeserializeOwned, Value: AsRef<str>>(
     handle_response(response).await
 }
 
+/// Paging, sorting and filtering for list routes
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListQuery {
+    pub page: u64,
+    pub limit: u64,
+    pub sort: Option<String>,
+    pub filter: String,
+}
+
+impl Default for ListQuery {
+    fn default() -> Self {
+        Self {
+            page: 1,
+            limit: 50,
+            sort: None,
+            filter: String::new(),
+        }
+    }
+}
+
+impl ListQuery {
+    pub fn into_query(self) -> Vec<(&'static str, String)> {
+        let mut query = vec![
+            ("page", self.page.to_string()),
+            ("limit", self.limit.to_string()),
+        ];
+        if let Some(sort) = self.sort {
+            query.push(("sort", sort));
+        }
+        if !self.filter.trim().is_empty() {
+            query.push(("filter", self.filter));
+        }
+
+        query
+    }
+}
+
+/// A page of a list route, the total is sent in the X-Total-Count header
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct Page<T> {
+    pub entries: Vec<T>,
+    pub total: u64,
+}
+
+pub async fn get_page<OUT: DeserializeOwned>(
+    uri: impl Into<String>,
+    query: ListQuery,
+) -> BambooApiResult<Page<OUT>> {
+    get_filtered_page(uri, query, vec![]).await
+}
+
+/// Loads a page of a list route that takes additional filters next to the list query
+pub async fn get_filtered_page<OUT: DeserializeOwned>(
+    uri: impl Into<String>,
+    query: ListQuery,
+    filter: Vec<(&'static str, String)>,
+) -> BambooApiResult<Page<OUT>> {
+    let uri = uri.into();
+    log::debug!("Execute get request against {uri}");
+    let response = Request::get(uri.as_str())
+        .query(query.into_query().into_iter().chain(filter))
+        .send()
+        .await
+        .map_err(|_| ApiError::send_error())?;
+
+    let total = response
+        .headers()
+        .get("X-Total-Count")
+        .and_then(|total| total.parse::<u64>().ok());
+    let entries: Vec<OUT> = handle_response(response).await?;
+
+    Ok(Page {
+        total: total.unwrap_or(entries.len() as u64),
+        entries,
+    })
+}
+
 pub async fn post<IN: Serialize, OUT: DeserializeOwned>(
     uri: impl Into<String>,
     body: &IN,
@@ -217,6 +295,43 @@ pub async fn post<IN: Serialize, OUT: DeserializeOwned>(
     handle_response(request).await
 }
 
+/// Returns none when the server answers with no content
+pub async fn post_optional<IN: Serialize, OUT: DeserializeOwned>(
+    uri: impl Into<String>,
+    body: &IN,
+) -> BambooApiResult<Option<OUT>> {
+    let uri = uri.into();
+    log::debug!("Execute post request against {uri}");
+    let request = Request::post(uri.as_str())
+        .json(body)
+        .map_err(|_| ApiError::json_serialize_error())?
+        .send()
+        .await
+        .map_err(|_| ApiError::send_error())?;
+
+    if NO_CONTENT == ErrorCode::from(request.status()) {
+        Ok(None)
+    } else {
+        handle_response(request).await.map(Some)
+    }
+}
+
+pub async fn put<IN: Serialize, OUT: DeserializeOwned>(
+    uri: impl Into<String>,
+    body: &IN,
+) -> BambooApiResult<OUT> {
+    let uri = uri.into();
+    log::debug!("Execute put request against {uri}");
+    let request = Request::put(uri.as_str())
+        .json(body)
+        .map_err(|_| ApiError::json_serialize_error())?
+        .send()
+        .await
+        .map_err(|_| ApiError::send_error())?;
+
+    handle_response(request).await
+}
+
 pub async fn delete(uri: impl Into<String>) -> BambooApiResult<()> {
     let uri = uri.into();
     log::debug!("Execute delete request against {uri}");
@@ -251,6 +366,17 @@ pub async fn put_no_body_no_content(uri: impl Into<String>) -> BambooApiResult<(
     handle_response_code(request).await
 }
 
+pub async fn post_no_body_no_content(uri: impl Into<String>) -> BambooApiResult<()> {
+    let uri = uri.into();
+    log::debug!("Execute post request against {uri}");
+    let request = Request::post(uri.as_str())
+        .send()
+        .await
+        .map_err(|_| ApiError::send_error())?;
+
+    handle_response_code(request).await
+}
+
 pub async fn upload_file(uri: impl Into<String>, file: web_sys::File) -> BambooApiResult<()> {
     let uri = uri.into();
     log::debug!("Execute put request against {uri}");
@@ -263,3 +389,19 @@ pub async fn upload_file(uri: impl Into<String>, file: web_sys::File) -> BambooA
 
     handle_response_code(request).await
 }
+
+pub async fn post_file<OUT: DeserializeOwned>(
+    uri: impl Into<String>,
+    file: web_sys::File,
+) -> BambooApiResult<OUT> {
+    let uri = uri.into();
+    log::debug!("Execute post request against {uri}");
+    let request = Request::post(uri.as_str())
+        .body(file)
+        .unwrap()
+        .send()
+        .await
+        .map_err(|_| ApiError::send_error())?;
+
+    handle_response(request).await
+}
@@ -0,0 +1,49 @@
+use stylist::css;
+use stylist::yew::Global;
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+
+use bamboo_common_core::error::{BambooError, BambooFieldErrorCode};
+
+fn field_error_message(code: BambooFieldErrorCode) -> &'static str {
+    match code {
+        BambooFieldErrorCode::Required => "Bitte fülle dieses Feld aus",
+        BambooFieldErrorCode::InvalidFormat => "Der Wert ist ungültig",
+        BambooFieldErrorCode::OutOfRange => "Der Wert liegt außerhalb des erlaubten Bereichs",
+    }
+}
+
+/// Marks the inputs of the fields the server rejected
+///
+/// The inputs map the field path from the error to the id of the input, marked inputs show the reason on hover
+#[autoprops]
+#[function_component(BambooFieldErrors)]
+pub fn bamboo_field_errors(
+    error: &Option<BambooError>,
+    inputs: &Vec<(AttrValue, AttrValue)>,
+) -> Html {
+    use_effect_with((error.clone(), inputs.clone()), |(error, inputs)| {
+        let document = gloo_utils::document();
+        for (field, id) in inputs {
+            if let Some(input) = document.get_element_by_id(id.as_str()) {
+                if let Some(field_error) = error.as_ref().and_then(|error| error.field(field)) {
+                    let _ = input.set_attribute("aria-invalid", "true");
+                    let _ = input.set_attribute("title", field_error_message(field_error.code));
+                } else {
+                    let _ = input.remove_attribute("aria-invalid");
+                    let _ = input.remove_attribute("title");
+                }
+            }
+        }
+    });
+
+    html!(
+        <Global css={css!(r#"
+input[aria-invalid="true"],
+select[aria-invalid="true"],
+textarea[aria-invalid="true"] {
+    --border-indicator-color: var(--negative-color);
+}
+        "#)} />
+    )
+}
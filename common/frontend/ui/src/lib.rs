@@ -1,3 +1,7 @@
 pub use card::*;
+pub use field_errors::*;
+pub use paging::*;
 
 pub mod card;
+pub mod field_errors;
+pub mod paging;
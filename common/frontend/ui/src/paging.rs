@@ -0,0 +1,24 @@
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+use yew_cosmo::prelude::CosmoButton;
+
+/// The buttons to switch between the pages of a list, the first page is 1
+///
+/// They are meant to be placed in a CosmoToolbarGroup
+#[autoprops]
+#[function_component(BambooPaging)]
+pub fn bamboo_paging(page: u64, limit: u64, total: u64, on_change: &Callback<u64>) -> Html {
+    let previous_page = use_callback((on_change.clone(), page), |_, (on_change, page)| {
+        on_change.emit(page.saturating_sub(1).max(1))
+    });
+    let next_page = use_callback((on_change.clone(), page), |_, (on_change, page)| {
+        on_change.emit(page + 1)
+    });
+
+    html!(
+        <>
+            <CosmoButton label="Zurück" enabled={page > 1} on_click={previous_page} />
+            <CosmoButton label="Weiter" enabled={page * limit < total} on_click={next_page} />
+        </>
+    )
+}
@@ -0,0 +1,45 @@
+use actix_web::{get, web};
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::response::{check_invalid_query, page, ListQuery};
+use bamboo_common::backend::services::DbConnection;
+use bamboo_common::core::entities::{AuditAction, AuditLog, AuditLogFilter, User};
+use bamboo_common::core::error::BambooApiResponseResult;
+
+use crate::middleware::authenticate_user::{authenticate, Username};
+
+pub(crate) async fn record_action(
+    username: &Username,
+    grove_id: Option<i32>,
+    action: AuditAction,
+    target: Option<&User>,
+    target_name: String,
+    db: &DbConnection,
+) {
+    dbal::create_audit_log(
+        AuditLog::new(
+            grove_id,
+            None,
+            format!("{username} (Bambushainteam)"),
+            action,
+            target.map(|user| user.id),
+            target_name,
+        ),
+        db,
+    )
+    .await
+}
+
+#[get("/api/audit-log", wrap = "authenticate!()")]
+pub async fn get_audit_log(
+    filter: Option<web::Query<AuditLogFilter>>,
+    query: Option<web::Query<ListQuery>>,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let filter = check_invalid_query!(filter, "audit_log")?.into_inner();
+    let query = check_invalid_query!(query, "audit_log")?;
+
+    dbal::get_audit_log(filter, &query, &db)
+        .await
+        .map(|data| page!(data))
+}
@@ -1,21 +1,27 @@
 use actix_web::{delete, get, post, put, web};
 
 use bamboo_common::backend::response::{
-    check_invalid_path, check_missing_fields, created, list, no_content, ok,
+    check_invalid_path, check_invalid_query, check_missing_fields, created, no_content, ok, page,
+    JsonBody, ListQuery,
 };
-use bamboo_common::backend::services::{DbConnection, EnvService};
-use bamboo_common::backend::utils::get_random_password;
+use bamboo_common::backend::services::{DbConnection, EnvService, InvitationPolicy};
 use bamboo_common::backend::{dbal, mailing};
 use bamboo_common::core::entities::grove::CreateGroveRequest;
-use bamboo_common::core::entities::{Grove, User};
+use bamboo_common::core::entities::{AuditAction, Grove, Invitation};
 use bamboo_common::core::error::{BambooApiResponseResult, BambooApiResult};
 
 use crate::middleware::authenticate_user::{authenticate, Username};
 use crate::path::GrovePath;
+use crate::routes::audit_log::record_action;
 
 #[get("/api/grove", wrap = "authenticate!()")]
-pub async fn get_groves(db: DbConnection) -> BambooApiResponseResult {
-    dbal::get_groves(&db).await.map(|data| list!(data))
+pub async fn get_groves(
+    query: Option<web::Query<ListQuery>>,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let query = check_invalid_query!(query, "grove")?;
+
+    dbal::get_groves(&query, &db).await.map(|data| page!(data))
 }
 
 #[get("/api/grove/{grove_id}", wrap = "authenticate!()")]
@@ -31,34 +37,36 @@ pub async fn get_grove(
 
 #[post("/api/grove", wrap = "authenticate!()")]
 pub async fn create_grove(
-    create_grove: Option<web::Json<CreateGroveRequest>>,
+    create_grove: JsonBody<CreateGroveRequest>,
     db: DbConnection,
     env_service: EnvService,
     username: web::ReqData<Username>,
 ) -> BambooApiResult<Grove> {
     let create_grove = check_missing_fields!(create_grove, "grove")?;
     let grove = dbal::create_grove(create_grove.grove_name.clone(), &db).await?;
-    let password = get_random_password();
-    let created_mod = dbal::create_user(
+    let policy = InvitationPolicy::new(&env_service);
+    let (invitation, token) = dbal::create_invitation(
         grove.id,
-        User::new(
+        Invitation::new(
             create_grove.mod_email.clone(),
             create_grove.mod_name.clone(),
-            "".into(),
             true,
         ),
-        password.clone(),
+        format!("{} (Bambushainteam)", username.as_str()),
+        policy.lifetime,
         &db,
     )
     .await?;
-    mailing::user::send_user_created(
-        created_mod.display_name.clone(),
-        format!("{} (Bambushainteam)", username.into_inner()),
-        created_mod.email.clone(),
-        password,
-        env_service,
+    mailing::user::send_invitation(&invitation, &token, &policy, env_service).await?;
+    record_action(
+        &username,
+        Some(grove.id),
+        AuditAction::GroveCreated,
+        None,
+        grove.name.clone(),
+        &db,
     )
-    .await?;
+    .await;
 
     Ok(created!(grove))
 }
@@ -67,34 +75,67 @@ pub async fn create_grove(
 pub async fn suspend_grove(
     path: Option<web::Path<GrovePath>>,
     db: DbConnection,
+    username: web::ReqData<Username>,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "grove")?;
+    let grove = dbal::get_grove_by_id(path.grove_id, &db).await?;
 
-    dbal::suspend_grove(path.grove_id, &db)
-        .await
-        .map(|_| no_content!())
+    dbal::suspend_grove(path.grove_id, &db).await?;
+    record_action(
+        &username,
+        Some(grove.id),
+        AuditAction::GroveSuspended,
+        None,
+        grove.name,
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
 }
 
 #[delete("/api/grove/{grove_id}", wrap = "authenticate!()")]
 pub async fn delete_grove(
     path: Option<web::Path<GrovePath>>,
     db: DbConnection,
+    username: web::ReqData<Username>,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "grove")?;
+    let grove = dbal::get_grove_by_id(path.grove_id, &db).await?;
 
-    dbal::delete_grove(path.grove_id, &db)
-        .await
-        .map(|_| no_content!())
+    dbal::delete_grove(path.grove_id, &db).await?;
+    record_action(
+        &username,
+        None,
+        AuditAction::GroveDeleted,
+        None,
+        grove.name,
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
 }
 
 #[put("/api/grove/{grove_id}/suspension", wrap = "authenticate!()")]
 pub async fn resume_grove(
     path: Option<web::Path<GrovePath>>,
     db: DbConnection,
+    username: web::ReqData<Username>,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "grove")?;
+    let grove = dbal::get_grove_by_id(path.grove_id, &db).await?;
 
-    dbal::resume_grove(path.grove_id, &db)
-        .await
-        .map(|_| no_content!())
+    dbal::resume_grove(path.grove_id, &db).await?;
+    record_action(
+        &username,
+        Some(grove.id),
+        AuditAction::GroveResumed,
+        None,
+        grove.name,
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
 }
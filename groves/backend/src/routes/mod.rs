@@ -3,6 +3,7 @@ use actix_web::{web, HttpResponse};
 use crate::middleware::authenticate_user::authenticate;
 use bamboo_common::backend::services::{EnvService, EnvironmentService};
 
+mod audit_log;
 mod authentication;
 mod groves;
 mod user;
@@ -23,6 +24,7 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
                 .to(HttpResponse::NoContent)
                 .wrap(authenticate!()),
         )
+        .service(audit_log::get_audit_log)
         .service(groves::get_groves)
         .service(groves::get_grove)
         .service(groves::create_grove)
@@ -1,29 +1,29 @@
 use actix_web::{delete, get, put, web};
 
-use bamboo_common::backend::response::{check_invalid_path, list, no_content};
+use bamboo_common::backend::response::{
+    check_invalid_path, check_invalid_query, no_content, page, ListQuery,
+};
 use bamboo_common::backend::services::{DbConnection, EnvService};
 use bamboo_common::backend::utils::get_random_password;
 use bamboo_common::backend::{dbal, mailing};
-use bamboo_common::core::entities::GroveUser;
+use bamboo_common::core::entities::{AuditAction, GroveUser};
 use bamboo_common::core::error::{BambooApiResponseResult, BambooError};
 
-use crate::middleware::authenticate_user::authenticate;
+use crate::middleware::authenticate_user::{authenticate, Username};
 use crate::path::{GrovePath, GroveUserPath};
+use crate::routes::audit_log::record_action;
 
 #[get("/api/grove/{grove_id}/user", wrap = "authenticate!()")]
 pub async fn get_users(
     path: Option<web::Path<GrovePath>>,
+    query: Option<web::Query<ListQuery>>,
     db: DbConnection,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "user")?;
-    dbal::get_users_filtered_for_management(path.grove_id, &db)
+    let query = check_invalid_query!(query, "user")?;
+    dbal::get_users_filtered_for_management(path.grove_id, &query, &db)
         .await
-        .map(|data| {
-            list!(data
-                .into_iter()
-                .map(GroveUser::from)
-                .collect::<Vec<GroveUser>>())
-        })
+        .map(|data| page!(data.map(GroveUser::from)))
 }
 
 #[put(
@@ -34,6 +34,7 @@ pub async fn reset_user_password(
     path: Option<web::Path<GroveUserPath>>,
     db: DbConnection,
     env_service: EnvService,
+    username: web::ReqData<Username>,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "user")?;
     let user = dbal::get_user(path.grove_id, path.user_id, &db).await?;
@@ -41,6 +42,15 @@ pub async fn reset_user_password(
     if user.is_mod {
         let password = get_random_password();
         dbal::change_password(path.grove_id, path.user_id, password.clone(), &db).await?;
+        record_action(
+            &username,
+            Some(path.grove_id),
+            AuditAction::PasswordReset,
+            Some(&user),
+            user.display_name.clone(),
+            &db,
+        )
+        .await;
         mailing::user::send_password_changed(
             user.display_name.clone(),
             user.email.clone(),
@@ -62,20 +72,42 @@ pub async fn reset_user_password(
 pub async fn make_user_mod(
     path: Option<web::Path<GroveUserPath>>,
     db: DbConnection,
+    username: web::ReqData<Username>,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "user")?;
-    dbal::change_mod_status(path.grove_id, path.user_id, true, &db)
-        .await
-        .map(|_| no_content!())
+    dbal::change_mod_status(path.grove_id, path.user_id, true, &db).await?;
+    let user = dbal::get_user(path.grove_id, path.user_id, &db).await?;
+    record_action(
+        &username,
+        Some(path.grove_id),
+        AuditAction::ModRightsGranted,
+        Some(&user),
+        user.display_name.clone(),
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
 }
 
 #[delete("/api/grove/{grove_id}/user/{user_id}/mod", wrap = "authenticate!()")]
 pub async fn remove_user_mod(
     path: Option<web::Path<GroveUserPath>>,
     db: DbConnection,
+    username: web::ReqData<Username>,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "user")?;
-    dbal::change_mod_status(path.grove_id, path.user_id, false, &db)
-        .await
-        .map(|_| no_content!())
+    dbal::change_mod_status(path.grove_id, path.user_id, false, &db).await?;
+    let user = dbal::get_user(path.grove_id, path.user_id, &db).await?;
+    record_action(
+        &username,
+        Some(path.grove_id),
+        AuditAction::ModRightsRevoked,
+        Some(&user),
+        user.display_name.clone(),
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
 }
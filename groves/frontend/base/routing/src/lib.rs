@@ -9,4 +9,6 @@ pub enum AppRoute {
     Groves,
     #[at("/app/mods/:grove_id")]
     Users { grove_id: i32 },
+    #[at("/app/audit-log")]
+    AuditLog,
 }
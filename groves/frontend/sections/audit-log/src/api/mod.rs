@@ -0,0 +1,24 @@
+use bamboo_common::core::entities::{AuditAction, AuditLog, Grove};
+use bamboo_common::frontend::api::{get, get_filtered_page, BambooApiResult, ListQuery, Page};
+
+pub async fn get_groves() -> BambooApiResult<Vec<Grove>> {
+    log::debug!("Get all groves");
+    get("/api/grove").await
+}
+
+pub async fn get_audit_log(
+    query: ListQuery,
+    grove_id: Option<i32>,
+    action: Option<AuditAction>,
+) -> BambooApiResult<Page<AuditLog>> {
+    log::debug!("Get audit log page {}", query.page);
+    let mut filter = vec![];
+    if let Some(grove_id) = grove_id {
+        filter.push(("groveId", grove_id.to_string()));
+    }
+    if let Some(action) = action {
+        filter.push(("action", format!("{action:?}")));
+    }
+
+    get_filtered_page("/api/audit-log", query, filter).await
+}
@@ -0,0 +1,4 @@
+pub use pages::audit_log::*;
+
+mod api;
+pub mod pages;
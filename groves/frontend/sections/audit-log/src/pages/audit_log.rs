@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+use yew::virtual_dom::Key;
+use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_mount};
+
+use bamboo_common::core::entities::AuditAction;
+use bamboo_common::frontend::api::ListQuery;
+use bamboo_common::frontend::ui::BambooPaging;
+
+use crate::api;
+
+#[derive(Clone, PartialEq)]
+struct AuditLogFilter {
+    query: ListQuery,
+    grove_id: Option<i32>,
+    action: Option<AuditAction>,
+}
+
+fn action_items() -> Vec<(Option<AttrValue>, AttrValue)> {
+    vec![(None, AttrValue::from("Alle Aktionen"))]
+        .into_iter()
+        .chain(AuditAction::iter().map(|action| {
+            (
+                Some(AttrValue::from(format!("{action:?}"))),
+                AttrValue::from(action.to_string()),
+            )
+        }))
+        .collect()
+}
+
+#[function_component(AuditLogPage)]
+pub fn audit_log_page() -> Html {
+    log::debug!("Render audit log");
+    let filter_state = use_state_eq(|| AuditLogFilter {
+        query: ListQuery::default(),
+        grove_id: None,
+        action: None,
+    });
+    // The filter is read when the request runs, a state would still hold the value of the last render
+    let filter_ref = use_mut_ref(|| (*filter_state).clone());
+
+    let groves_state = use_async(async move { api::get_groves().await });
+    let audit_log_state = {
+        let filter_ref = filter_ref.clone();
+
+        use_async(async move {
+            let filter = filter_ref.borrow().clone();
+            api::get_audit_log(filter.query, filter.grove_id, filter.action).await
+        })
+    };
+
+    let load = {
+        let filter_state = filter_state.clone();
+        let audit_log_state = audit_log_state.clone();
+
+        move |filter: AuditLogFilter| {
+            *filter_ref.borrow_mut() = filter.clone();
+            filter_state.set(filter);
+            audit_log_state.run();
+        }
+    };
+
+    let grove_select = {
+        let load = load.clone();
+        let filter = (*filter_state).clone();
+
+        Callback::from(move |value: Option<AttrValue>| {
+            load(AuditLogFilter {
+                query: ListQuery {
+                    page: 1,
+                    ..filter.query.clone()
+                },
+                grove_id: value.and_then(|value| value.parse::<i32>().ok()),
+                ..filter.clone()
+            })
+        })
+    };
+    let action_select = {
+        let load = load.clone();
+        let filter = (*filter_state).clone();
+
+        Callback::from(move |value: Option<AttrValue>| {
+            load(AuditLogFilter {
+                query: ListQuery {
+                    page: 1,
+                    ..filter.query.clone()
+                },
+                action: value.and_then(|value| {
+                    AuditAction::iter().find(|action| value.as_str() == format!("{action:?}"))
+                }),
+                ..filter.clone()
+            })
+        })
+    };
+    let update_filter = {
+        let load = load.clone();
+        let filter = (*filter_state).clone();
+
+        Callback::from(move |text: AttrValue| {
+            load(AuditLogFilter {
+                query: ListQuery {
+                    page: 1,
+                    filter: text.to_string(),
+                    ..filter.query.clone()
+                },
+                ..filter.clone()
+            })
+        })
+    };
+    let change_page = {
+        let load = load.clone();
+        let filter = (*filter_state).clone();
+
+        Callback::from(move |page: u64| {
+            load(AuditLogFilter {
+                query: ListQuery {
+                    page,
+                    ..filter.query.clone()
+                },
+                ..filter.clone()
+            })
+        })
+    };
+
+    {
+        let groves_state = groves_state.clone();
+        let load = load.clone();
+        let filter = (*filter_state).clone();
+
+        use_mount(move || {
+            groves_state.run();
+            load(filter);
+        });
+    }
+
+    let grove_names = groves_state
+        .data
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|grove| (grove.id, grove.name))
+        .collect::<BTreeMap<i32, String>>();
+    let grove_items = vec![(None, AttrValue::from("Alle Haine"))]
+        .into_iter()
+        .chain(grove_names.iter().map(|(id, name)| {
+            (
+                Some(AttrValue::from(id.to_string())),
+                AttrValue::from(name.clone()),
+            )
+        }))
+        .collect::<Vec<(Option<AttrValue>, AttrValue)>>();
+    let total = audit_log_state
+        .data
+        .as_ref()
+        .map(|page| page.total)
+        .unwrap_or_default();
+    let offset = (filter_state.query.page - 1) * filter_state.query.limit;
+
+    html!(
+        <>
+            <CosmoTitle title="Protokoll" />
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoDropdown width={CosmoInputWidth::Medium} label="Hain" value={filter_state.grove_id.map(|id| AttrValue::from(id.to_string()))} items={grove_items} on_select={grove_select} />
+                    <CosmoDropdown width={CosmoInputWidth::Medium} label="Aktion" value={filter_state.action.map(|action| AttrValue::from(format!("{action:?}")))} items={action_items()} on_select={action_select} />
+                    <CosmoTextBox width={CosmoInputWidth::Medium} label="Suchen" value={AttrValue::from(filter_state.query.filter.clone())} on_input={update_filter} />
+                </CosmoToolbarGroup>
+                <CosmoToolbarGroup>
+                    <BambooPaging page={filter_state.query.page} limit={filter_state.query.limit} total={total} on_change={change_page} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
+            if audit_log_state.loading {
+                <CosmoProgressRing />
+            } else if audit_log_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Laden" message="Leider konnte das Protokoll nicht geladen werden"/>
+            } else if let Some(page) = &audit_log_state.data {
+                if page.entries.is_empty() {
+                    <CosmoMessage message_type={CosmoMessageType::Information} header="Keine Einträge" message="Für diesen Filter gibt es keine Einträge im Protokoll" />
+                } else {
+                    <CosmoTable headers={vec![AttrValue::from("Zeitpunkt"), AttrValue::from("Hain"), AttrValue::from("Ausgeführt von"), AttrValue::from("Aktion"), AttrValue::from("Betroffen")]}>
+                        {for page.entries.iter().map(|entry| CosmoTableRow::from_table_cells(vec![
+                            CosmoTableCell::from_html(html!({entry.created_at.format("%d.%m.%Y %H:%M").to_string()}), None),
+                            CosmoTableCell::from_html(html!({entry.grove_id.and_then(|id| grove_names.get(&id).cloned()).unwrap_or_else(|| "-".to_string())}), None),
+                            CosmoTableCell::from_html(html!({entry.actor_name.clone()}), None),
+                            CosmoTableCell::from_html(html!({entry.action.to_string()}), None),
+                            CosmoTableCell::from_html(html!({entry.target_name.clone()}), None),
+                        ], Some(Key::from(entry.id.to_string()))))}
+                    </CosmoTable>
+                    <CosmoParagraph>{format!("Einträge {} bis {} von {}", offset + 1, offset + page.entries.len() as u64, page.total)}</CosmoParagraph>
+                }
+            }
+        </>
+    )
+}
@@ -1,10 +1,12 @@
 use bamboo_common::core::entities::grove::CreateGroveRequest;
 use bamboo_common::core::entities::Grove;
-use bamboo_common::frontend::api::{delete, get, post, put_no_body_no_content, BambooApiResult};
+use bamboo_common::frontend::api::{
+    delete, get_page, post, put_no_body_no_content, BambooApiResult, ListQuery, Page,
+};
 
-pub async fn get_groves() -> BambooApiResult<Vec<Grove>> {
-    log::debug!("Get all groves");
-    get("/api/grove").await
+pub async fn get_groves(query: ListQuery) -> BambooApiResult<Page<Grove>> {
+    log::debug!("Get page {} of the groves", query.page);
+    get_page("/api/grove", query).await
 }
 
 pub async fn create_grove(
@@ -7,7 +7,8 @@ use yew_icons::{Icon, IconId};
 use yew_router::prelude::use_navigator;
 
 use bamboo_common::core::entities::Grove;
-use bamboo_common::frontend::api::CONFLICT;
+use bamboo_common::frontend::api::{ListQuery, CONFLICT};
+use bamboo_common::frontend::ui::BambooPaging;
 use bamboo_groves_frontend_base_routing::AppRoute;
 
 use crate::api;
@@ -63,7 +64,7 @@ fn create_grove_dialog(on_saved: &Callback<Grove>, on_close: &Callback<()>) -> H
                         <CosmoMessage message="Der Hain konnte leider nicht hinzugefügt werden" message_type={CosmoMessageType::Negative} />
                     }
                 } else {
-                    <CosmoMessage message_type={CosmoMessageType::Information} header="Hain hinzufügen" message="Nachdem der Hain erstellt wurde bekommt der Mod sein Passwort per Mail" />
+                    <CosmoMessage message_type={CosmoMessageType::Information} header="Hain hinzufügen" message="Nachdem der Hain erstellt wurde bekommt der Mod eine Einladung per Mail, über die er sein Passwort selbst festlegt" />
                 }
                 <CosmoInputGroup>
                     <CosmoTextBox label="Hainname" value={(*name_state).clone()} on_input={update_name} required={true} />
@@ -85,7 +86,18 @@ pub fn groves_page() -> Html {
     let grove_to_resume_state = use_state_eq(|| None as Option<Grove>);
     let grove_to_delete_state = use_state_eq(|| None as Option<Grove>);
 
-    let groves_state = use_async(async move { api::get_groves().await });
+    let query_state = use_state_eq(ListQuery::default);
+    // The query is read when the request runs, a state would still hold the value of the last render
+    let query_ref = use_mut_ref(ListQuery::default);
+
+    let groves_state = {
+        let query_ref = query_ref.clone();
+
+        use_async(async move {
+            let query = query_ref.borrow().clone();
+            api::get_groves(query).await
+        })
+    };
     let suspend_grove_state = {
         let grove_to_suspend_state = grove_to_suspend_state.clone();
 
@@ -188,6 +200,52 @@ pub fn groves_page() -> Html {
         state.run();
     });
 
+    let load = {
+        let query_state = query_state.clone();
+        let groves_state = groves_state.clone();
+
+        move |query: ListQuery| {
+            *query_ref.borrow_mut() = query.clone();
+            query_state.set(query);
+            groves_state.run();
+        }
+    };
+    let update_filter = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+
+        Callback::from(move |filter: AttrValue| {
+            load(ListQuery {
+                page: 1,
+                filter: filter.to_string(),
+                ..query.clone()
+            })
+        })
+    };
+    let update_sort = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+
+        Callback::from(move |sort: Option<AttrValue>| {
+            load(ListQuery {
+                page: 1,
+                sort: sort.map(|sort| sort.to_string()),
+                ..query.clone()
+            })
+        })
+    };
+    let change_page = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+
+        Callback::from(move |page: u64| {
+            load(ListQuery {
+                page,
+                ..query.clone()
+            })
+        })
+    };
+
     let open_users_page = use_callback(navigator.clone(), |grove: Grove, navigator| {
         navigator.push(&AppRoute::Users { grove_id: grove.id });
     });
@@ -200,14 +258,48 @@ pub fn groves_page() -> Html {
         });
     }
 
+    let sort_items = vec![
+        (None, AttrValue::from("Nummer")),
+        (Some(AttrValue::from("name")), AttrValue::from("Name")),
+        (
+            Some(AttrValue::from("-name")),
+            AttrValue::from("Name absteigend"),
+        ),
+        (
+            Some(AttrValue::from("-isSuspended")),
+            AttrValue::from("Pausierte zuerst"),
+        ),
+        (
+            Some(AttrValue::from("isEnabled")),
+            AttrValue::from("Deaktivierte zuerst"),
+        ),
+    ];
+    let total = groves_state
+        .data
+        .as_ref()
+        .map(|page| page.total)
+        .unwrap_or_default();
+
     html!(
         <>
             <CosmoTitle title="Haine" />
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoButton label="Neuer Hain" on_click={open_create_dialog} />
+                </CosmoToolbarGroup>
+                <CosmoToolbarGroup>
+                    <CosmoTextBox width={CosmoInputWidth::Medium} label="Suchen" value={AttrValue::from(query_state.filter.clone())} on_input={update_filter} />
+                    <CosmoDropdown width={CosmoInputWidth::Medium} label="Sortierung" value={query_state.sort.clone().map(AttrValue::from)} items={sort_items} on_select={update_sort} />
+                </CosmoToolbarGroup>
+                <CosmoToolbarGroup>
+                    <BambooPaging page={query_state.page} limit={query_state.limit} total={total} on_change={change_page} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
             if groves_state.loading {
                 <CosmoProgressRing />
             } else if groves_state.error.is_some() {
                 <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Laden" message="Leider konnten die Haine nicht geladen werden"/>
-            } else if let Some(data) = groves_state.data.clone() {
+            } else if let Some(page) = groves_state.data.clone() {
                 if suspend_grove_state.error.is_some() {
                     <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Pausieren" message="Leider konnte der Hain nicht pausiert werden" />
                 }
@@ -217,13 +309,8 @@ pub fn groves_page() -> Html {
                 if delete_grove_state.error.is_some() {
                     <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Löschen" message="Leider konnte der Hain nicht gelöscht werden" />
                 }
-                <CosmoToolbar>
-                    <CosmoToolbarGroup>
-                        <CosmoButton label="Neuer Hain" on_click={open_create_dialog} />
-                    </CosmoToolbarGroup>
-                </CosmoToolbar>
                 <CosmoTable headers={vec![AttrValue::from("#"), AttrValue::from("Name"), AttrValue::from("Pausiert"), AttrValue::from("Aktiviert"), AttrValue::from("Aktionen")]}>
-                    {for data.iter().map(|grove| {
+                    {for page.entries.iter().map(|grove| {
                         let open_suspend_dialog = open_suspend_dialog.clone();
                         let open_resume_dialog = open_resume_dialog.clone();
                         let open_delete_dialog = open_delete_dialog.clone();
@@ -264,6 +351,9 @@ pub fn groves_page() -> Html {
                         ], Some(Key::from(grove.id.to_string())))
                     })}
                 </CosmoTable>
+                if !page.entries.is_empty() {
+                    <CosmoParagraph>{format!("Haine {} bis {} von {}", (query_state.page - 1) * query_state.limit + 1, (query_state.page - 1) * query_state.limit + page.entries.len() as u64, page.total)}</CosmoParagraph>
+                }
                 if *create_grove_open_toggle {
                     <CreateGroveDialog on_close={close_create_dialog} on_saved={saved_create_dialog} />
                 }
@@ -6,6 +6,7 @@ use yew_hooks::{use_async, use_mount};
 use yew_router::prelude::*;
 
 use bamboo_groves_frontend_base::routing::AppRoute;
+use bamboo_groves_frontend_section_audit_log::AuditLogPage;
 use bamboo_groves_frontend_section_groves::GrovesPage;
 use bamboo_groves_frontend_section_login::LoginPage;
 use bamboo_groves_frontend_section_users::UsersPage;
@@ -33,6 +34,14 @@ fn switch_app(route: AppRoute) -> Html {
                 <UsersPage grove_id={grove_id} />
             </>
         ),
+        AppRoute::AuditLog => html!(
+            <>
+                <Helmet>
+                    <title>{"Protokoll"}</title>
+                </Helmet>
+                <AuditLogPage />
+            </>
+        ),
     }
 }
 
@@ -52,6 +61,14 @@ fn render_users_route_sub_menu_entry(route: AppRoute) -> Html {
     )
 }
 
+fn render_audit_log_route_sub_menu_entry(route: AppRoute) -> Html {
+    let is_active = matches!(route, AppRoute::AuditLog);
+
+    html!(
+        <CosmoSubMenuItemLink<AppRoute> to={AppRoute::AuditLog} is_active={is_active} label="Protokoll" />
+    )
+}
+
 #[function_component(Layout)]
 pub fn layout() -> Html {
     log::debug!("Render app layout");
@@ -92,6 +109,7 @@ pub fn layout() -> Html {
                     <CosmoSubMenuBar>
                         <Switch<AppRoute> render={render_groves_route_sub_menu_entry} />
                         <Switch<AppRoute> render={render_users_route_sub_menu_entry} />
+                        <Switch<AppRoute> render={render_audit_log_route_sub_menu_entry} />
                     </CosmoSubMenuBar>
                 </CosmoMenuBar>
                 <CosmoPageBody>
@@ -1,9 +1,11 @@
 use bamboo_common::core::entities::{Grove, GroveUser};
-use bamboo_common::frontend::api::{delete, get, put_no_body_no_content, BambooApiResult};
+use bamboo_common::frontend::api::{
+    delete, get, get_page, put_no_body_no_content, BambooApiResult, ListQuery, Page,
+};
 
-pub async fn get_users(id: i32) -> BambooApiResult<Vec<GroveUser>> {
-    log::debug!("Get all users for grove {id}");
-    get(format!("/api/grove/{id}/user")).await
+pub async fn get_users(id: i32, query: ListQuery) -> BambooApiResult<Page<GroveUser>> {
+    log::debug!("Get page {} of the users for grove {id}", query.page);
+    get_page(format!("/api/grove/{id}/user"), query).await
 }
 
 pub async fn get_grove(id: i32) -> BambooApiResult<Grove> {
@@ -6,6 +6,8 @@ use yew_hooks::{use_async, use_mount};
 use yew_icons::{Icon, IconId};
 
 use bamboo_common::core::entities::GroveUser;
+use bamboo_common::frontend::api::ListQuery;
+use bamboo_common::frontend::ui::BambooPaging;
 
 use crate::api;
 
@@ -17,7 +19,18 @@ pub fn users_page(grove_id: i32) -> Html {
     let user_to_make_mod_state = use_state_eq(|| None as Option<GroveUser>);
     let user_to_remove_mod_state = use_state_eq(|| None as Option<GroveUser>);
 
-    let users_state = use_async(async move { api::get_users(grove_id).await });
+    let query_state = use_state_eq(ListQuery::default);
+    // The query is read when the request runs, a state would still hold the value of the last render
+    let query_ref = use_mut_ref(ListQuery::default);
+
+    let users_state = {
+        let query_ref = query_ref.clone();
+
+        use_async(async move {
+            let query = query_ref.borrow().clone();
+            api::get_users(grove_id, query).await
+        })
+    };
     let grove_state = use_async(async move { api::get_grove(grove_id).await });
     let reset_password_state = {
         let user_to_reset_password_state = user_to_reset_password_state.clone();
@@ -111,6 +124,52 @@ pub fn users_page(grove_id: i32) -> Html {
         state.run();
     });
 
+    let load = {
+        let query_state = query_state.clone();
+        let users_state = users_state.clone();
+
+        move |query: ListQuery| {
+            *query_ref.borrow_mut() = query.clone();
+            query_state.set(query);
+            users_state.run();
+        }
+    };
+    let update_filter = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+
+        Callback::from(move |filter: AttrValue| {
+            load(ListQuery {
+                page: 1,
+                filter: filter.to_string(),
+                ..query.clone()
+            })
+        })
+    };
+    let update_sort = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+
+        Callback::from(move |sort: Option<AttrValue>| {
+            load(ListQuery {
+                page: 1,
+                sort: sort.map(|sort| sort.to_string()),
+                ..query.clone()
+            })
+        })
+    };
+    let change_page = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+
+        Callback::from(move |page: u64| {
+            load(ListQuery {
+                page,
+                ..query.clone()
+            })
+        })
+    };
+
     {
         let users_state = users_state.clone();
         let grove_state = grove_state.clone();
@@ -121,6 +180,24 @@ pub fn users_page(grove_id: i32) -> Html {
         });
     }
 
+    let sort_items = vec![
+        (None, AttrValue::from("Nummer")),
+        (
+            Some(AttrValue::from("displayName")),
+            AttrValue::from("Name"),
+        ),
+        (Some(AttrValue::from("email")), AttrValue::from("Email")),
+        (
+            Some(AttrValue::from("-isMod")),
+            AttrValue::from("Mods zuerst"),
+        ),
+    ];
+    let total = users_state
+        .data
+        .as_ref()
+        .map(|page| page.total)
+        .unwrap_or_default();
+
     html!(
         <>
             if grove_state.error.is_some() {
@@ -128,11 +205,20 @@ pub fn users_page(grove_id: i32) -> Html {
             } else if let Some(data) = grove_state.data.clone() {
                 <CosmoTitle title={format!("Benutzer in {}", data.name)} />
             }
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoTextBox width={CosmoInputWidth::Medium} label="Suchen" value={AttrValue::from(query_state.filter.clone())} on_input={update_filter} />
+                    <CosmoDropdown width={CosmoInputWidth::Medium} label="Sortierung" value={query_state.sort.clone().map(AttrValue::from)} items={sort_items} on_select={update_sort} />
+                </CosmoToolbarGroup>
+                <CosmoToolbarGroup>
+                    <BambooPaging page={query_state.page} limit={query_state.limit} total={total} on_change={change_page} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
             if users_state.loading {
                 <CosmoProgressRing />
             } else if users_state.error.is_some() {
                 <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Laden" message="Leider konnten die Benutzer nicht geladen werden"/>
-            } else if let Some(data) = users_state.data.clone() {
+            } else if let Some(page) = users_state.data.clone() {
                 if reset_password_state.error.is_some() {
                     <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Zurücksetzen" message="Leider konnte das Passwort nicht zurückgesetzt werden" />
                 }
@@ -143,7 +229,7 @@ pub fn users_page(grove_id: i32) -> Html {
                     <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Entziehen" message="Leider konnten dem Benutzer die Modrecht nicht entzogen werden" />
                 }
                 <CosmoTable headers={vec![AttrValue::from("#"), AttrValue::from("Name"), AttrValue::from("Email"), AttrValue::from("Ist Mod"), AttrValue::from("Aktionen")]}>
-                    {for data.iter().map(|user| {
+                    {for page.entries.iter().map(|user| {
                         let open_reset_password_dialog = open_reset_password_dialog.clone();
                         let open_make_user_mod_dialog = open_make_user_mod_dialog.clone();
                         let open_remove_user_mod_dialog = open_remove_user_mod_dialog.clone();
@@ -175,6 +261,9 @@ pub fn users_page(grove_id: i32) -> Html {
                         ], Some(Key::from(user.id.to_string())))
                     })}
                 </CosmoTable>
+                if !page.entries.is_empty() {
+                    <CosmoParagraph>{format!("Benutzer {} bis {} von {}", (query_state.page - 1) * query_state.limit + 1, (query_state.page - 1) * query_state.limit + page.entries.len() as u64, page.total)}</CosmoParagraph>
+                }
                 if let Some(user) = (*user_to_reset_password_state).clone() {
                     <CosmoConfirm title="Passwort zurücksetzen" message={format!("Soll das Passwort von {} zurückgesetzt werden?", user.display_name.clone())} decline_label="Nicht zurücksetzen" confirm_label="Passwort zurücksetzen" confirm_type={CosmoModalType::Warning} on_confirm={confirm_reset_password_dialog.clone()} on_decline={close_reset_password_dialog.clone()} />
                 }
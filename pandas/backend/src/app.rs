@@ -4,51 +4,17 @@ use actix_web::{middleware, App, HttpServer};
 
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::migration::{IntoSchemaManagerConnection, Migrator, MigratorTrait};
+use bamboo_common::backend::response::ListQuery;
 use bamboo_common::backend::services::minio_service::MinioClient;
-use bamboo_common::backend::services::DbConnection;
+use bamboo_common::backend::services::{DbConnection, EnvService, EnvironmentService};
 
+use crate::demo;
+use crate::login_attempt;
 use crate::notifier;
+use crate::reminder;
 use crate::routes;
-
-async fn setup_google_playstore_grove(
-    db: &sea_orm::DatabaseConnection,
-) -> std::io::Result<bamboo_common::core::entities::Grove> {
-    if let Ok(grove) = dbal::get_grove_by_name("Google".to_string(), db).await {
-        Ok(grove)
-    } else {
-        dbal::create_grove("Google".to_string(), db)
-            .await
-            .map_err(std::io::Error::other)
-    }
-}
-
-async fn setup_google_playstore_user(db: &sea_orm::DatabaseConnection) -> std::io::Result<()> {
-    let email = "playstore@google.bambushain".to_string();
-    let password = "NkWHoLDmzg4aVEx".to_string();
-
-    if let Ok(user) = dbal::get_user_by_email_or_username(email.clone(), db).await {
-        dbal::change_password(user.grove_id, user.id, password, db)
-            .await
-            .map_err(std::io::Error::other)
-            .map(|_| ())
-    } else {
-        let grove = setup_google_playstore_grove(db).await?;
-        dbal::create_user(
-            grove.id,
-            bamboo_common::core::entities::User::new(
-                email,
-                "Google Playstore".to_string(),
-                "google".to_string(),
-                true,
-            ),
-            password,
-            db,
-        )
-        .await
-        .map_err(std::io::Error::other)
-        .map(|_| ())
-    }
-}
+use crate::session;
+use crate::trash;
 
 pub fn start_server() -> std::io::Result<()> {
     let mut log_builder =
@@ -90,7 +56,10 @@ pub fn start_server() -> std::io::Result<()> {
             .await
             .map_err(std::io::Error::other)?;
         log::info!("Successfully migrated database");
-        let groves = dbal::get_groves(&db).await.map_err(std::io::Error::other)?;
+        let groves = dbal::get_groves(&ListQuery::default(), &db)
+            .await
+            .map_err(std::io::Error::other)?
+            .entries;
         let minio_client = MinioClient::new(
             std::env::var("S3_BUCKET").map_err(std::io::Error::other)?,
             std::env::var("S3_ACCESS_KEY").map_err(std::io::Error::other)?,
@@ -103,10 +72,17 @@ pub fn start_server() -> std::io::Result<()> {
         )
         .map_err(std::io::Error::other)?;
 
+        let demo_service = demo::DemoService::new(demo::DemoAccount::new(&EnvService::new(
+            EnvironmentService::new(),
+        )));
+        let demo_grove = demo_service
+            .get_ref()
+            .as_ref()
+            .map(|demo| demo.grove.clone());
         if groves.is_empty()
             || groves
                 .iter()
-                .filter(|grove| grove.name == *"Google")
+                .filter(|grove| demo_grove.as_ref().is_some_and(|name| grove.name == *name))
                 .count()
                 == groves.len()
         {
@@ -124,9 +100,13 @@ pub fn start_server() -> std::io::Result<()> {
                 .map_err(std::io::Error::other)?;
         }
 
-        setup_google_playstore_user(&db).await?;
+        demo::setup_demo_account(demo_service.clone(), &db).await?;
 
-        let notifier = notifier::NotifierState::new();
+        let notifier = notifier::NotifierState::new(db.clone());
+        reminder::start_reminder_scheduler(db.clone());
+        session::start_session_cleanup(db.clone());
+        login_attempt::start_login_attempt_cleanup(db.clone());
+        trash::start_trash_purge(db.clone());
 
         HttpServer::new(move || {
             App::new()
@@ -137,6 +117,7 @@ pub fn start_server() -> std::io::Result<()> {
                 ))
                 .app_data(notifier::Notifier::new(notifier.clone()))
                 .app_data(DbConnection::new(db.clone()))
+                .app_data(demo_service.clone())
                 .configure(routes::configure_routes)
         })
         .bind(("0.0.0.0", 8070))?
@@ -0,0 +1,240 @@
+use actix_web::web;
+use chrono::{Duration, Utc};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::services::EnvService;
+use bamboo_common::core::entities::*;
+use bamboo_common::core::error::{BambooError, BambooErrorResult, BambooResult};
+
+/// Sample data for the demo grove, can be replaced with a json file through DEMO_SEED_FILE
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DemoSeed {
+    #[serde(default)]
+    characters: Vec<Character>,
+    #[serde(default)]
+    events: Vec<Event>,
+}
+
+impl DemoSeed {
+    fn sample() -> Self {
+        let today = Utc::now().date_naive();
+        let event = |title: &str, description: &str, days: i64, color: &str| Event {
+            title: title.to_string(),
+            description: description.to_string(),
+            start_date: today + Duration::days(days),
+            end_date: today + Duration::days(days),
+            all_day: true,
+            color: color.to_string(),
+            ..Default::default()
+        };
+
+        Self {
+            characters: vec![
+                Character::new(
+                    CharacterRace::Miqote,
+                    "Bamboo Panda".to_string(),
+                    "Shiva".to_string(),
+                    vec![],
+                    None,
+                ),
+                Character::new(
+                    CharacterRace::Lalafell,
+                    "Little Panda".to_string(),
+                    "Phoenix".to_string(),
+                    vec![],
+                    None,
+                ),
+            ],
+            events: vec![
+                event("Raid Abend", "Wir gehen gemeinsam raiden", 1, "#598c79"),
+                event("Hausparty", "Party in unserem Haus", 4, "#9f2637"),
+                event("Maps", "Schatzkarten mit der ganzen Gruppe", 9, "#1b2832"),
+            ],
+        }
+    }
+
+    fn load(seed_file: Option<&String>) -> Self {
+        seed_file
+            .and_then(|path| {
+                std::fs::read_to_string(path)
+                    .map_err(|err| log::error!("Failed to read demo seed file {path}: {err}"))
+                    .ok()
+            })
+            .and_then(|content| {
+                serde_json::from_str(content.as_str())
+                    .map_err(|err| log::error!("Failed to parse demo seed file: {err}"))
+                    .ok()
+            })
+            .unwrap_or_else(Self::sample)
+    }
+}
+
+/// The demo account is read once at startup and shared as app data, it is none when the demo is disabled
+pub(crate) type DemoService = web::Data<Option<DemoAccount>>;
+
+pub(crate) struct DemoAccount {
+    pub grove: String,
+    pub email: String,
+    pub password: String,
+    pub display_name: String,
+    pub reset_interval: Option<std::time::Duration>,
+    pub seed_file: Option<String>,
+}
+
+impl DemoAccount {
+    /// Returns none unless the demo account is enabled and has an email and password
+    pub fn new(env_service: &EnvService) -> Option<Self> {
+        if env_service.get_env("DEMO_ACCOUNT_ENABLED", "false") != "true" {
+            return None;
+        }
+
+        let password = env_service.get_env_opt("DEMO_PASSWORD").or_else(|| {
+            env_service
+                .get_env_opt("DEMO_PASSWORD_FILE")
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .map(|password| password.trim().to_string())
+        });
+        let email = env_service.get_env_opt("DEMO_EMAIL");
+        let (Some(email), Some(password)) = (email, password) else {
+            log::warn!("The demo account is enabled, but DEMO_EMAIL or DEMO_PASSWORD is missing");
+            return None;
+        };
+
+        Some(Self {
+            grove: env_service.get_env("DEMO_GROVE", "Demo"),
+            email,
+            password,
+            display_name: env_service.get_env("DEMO_DISPLAY_NAME", "Demo Panda"),
+            reset_interval: env_service
+                .get_env_opt("DEMO_RESET_INTERVAL_HOURS")
+                .and_then(|hours| hours.parse::<u64>().ok())
+                .filter(|hours| *hours > 0)
+                .map(|hours| std::time::Duration::from_secs(hours * 3600)),
+            seed_file: env_service.get_env_opt("DEMO_SEED_FILE"),
+        })
+    }
+
+    pub fn is_demo_login(demo: &DemoService, email: &str) -> bool {
+        demo.get_ref()
+            .as_ref()
+            .is_some_and(|demo| demo.email == email)
+    }
+
+    /// Only a grove created for the demo is used, a grove of real pandas with the same name is never touched
+    async fn get_or_create_grove(&self, db: &DatabaseConnection) -> BambooResult<Grove> {
+        match dbal::get_grove_by_name(self.grove.clone(), db).await {
+            Ok(grove) if grove.is_demo => Ok(grove),
+            Ok(_) => Err(BambooError::exists_already(
+                "grove",
+                format!(
+                    "The grove {} was not created for the demo, choose another name in DEMO_GROVE",
+                    self.grove
+                ),
+            )),
+            Err(_) => dbal::create_demo_grove(self.grove.clone(), db).await,
+        }
+    }
+
+    /// Creates the demo grove and user, when reset is true the existing data is replaced with the seed
+    pub async fn setup(&self, reset: bool, db: &DatabaseConnection) -> BambooErrorResult {
+        let grove = self.get_or_create_grove(db).await?;
+        let (user, seed) =
+            if let Ok(user) = dbal::get_user_by_email_or_username(self.email.clone(), db).await {
+                if user.grove_id != grove.id {
+                    return Err(BambooError::exists_already(
+                        "user",
+                        "The demo email belongs to a user outside the demo grove",
+                    ));
+                }
+                dbal::change_password(grove.id, user.id, self.password.clone(), db).await?;
+                // Older setups created the demo user as mod
+                dbal::change_mod_status(grove.id, user.id, false, db).await?;
+
+                (user, reset)
+            } else {
+                let user = dbal::create_user(
+                    grove.id,
+                    User::new(
+                        self.email.clone(),
+                        self.display_name.clone(),
+                        "".to_string(),
+                        false,
+                    ),
+                    self.password.clone(),
+                    db,
+                )
+                .await?;
+
+                (user, true)
+            };
+
+        if !seed {
+            return Ok(());
+        }
+
+        dbal::delete_demo_events(grove.id, db).await?;
+        dbal::delete_other_demo_users(grove.id, user.id, db).await?;
+        dbal::delete_characters(user.id, db).await?;
+        dbal::update_profile(
+            grove.id,
+            user.id,
+            self.email.clone(),
+            self.display_name.clone(),
+            "".to_string(),
+            db,
+        )
+        .await?;
+        dbal::disable_totp(grove.id, user.id, db).await?;
+
+        let seed = DemoSeed::load(self.seed_file.as_ref());
+        for character in seed.characters {
+            dbal::create_character(user.id, character, db).await?;
+        }
+        for event in seed.events {
+            dbal::create_event(event, grove.id, user.id, db).await?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) async fn setup_demo_account(
+    demo_service: DemoService,
+    db: &DatabaseConnection,
+) -> std::io::Result<()> {
+    let Some(demo) = demo_service.get_ref() else {
+        return Ok(());
+    };
+
+    log::info!("Set up the demo account {}", demo.email);
+    demo.setup(false, db).await.map_err(std::io::Error::other)?;
+
+    if let Some(reset_interval) = demo.reset_interval {
+        log::info!(
+            "The demo grove is reset every {} hours",
+            reset_interval.as_secs() / 3600
+        );
+        let db = db.clone();
+        let demo_service = demo_service.clone();
+        actix_web::rt::spawn(async move {
+            let Some(demo) = demo_service.get_ref() else {
+                return;
+            };
+            let mut interval = actix_web::rt::time::interval(reset_interval);
+            // The first tick completes immediately, the grove was just set up
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                match demo.setup(true, &db).await {
+                    Ok(_) => log::info!("Reset the demo grove"),
+                    Err(err) => log::error!("Failed to reset the demo grove {err}"),
+                }
+            }
+        });
+    }
+
+    Ok(())
+}
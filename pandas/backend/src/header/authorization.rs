@@ -25,7 +25,13 @@ impl header::Header for AuthorizationHeader {
         }?
         .to_str()
         .map_err(|_| error::ParseError::Header)
-        .map(|header| header.strip_prefix("Panda ").map(|res| res.to_string()))?;
+        .map(|header| {
+            // Scripts using personal access tokens usually only know about bearer tokens
+            header
+                .strip_prefix("Panda ")
+                .or_else(|| header.strip_prefix("Bearer "))
+                .map(|res| res.to_string())
+        })?;
 
         Ok(AuthorizationHeader { authorization })
     }
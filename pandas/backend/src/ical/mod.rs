@@ -0,0 +1,486 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+use chrono::{DateTime, Days, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use icalendar::{
+    Calendar, CalendarComponent, CalendarDateTime, Class, Component, EventLike, Property,
+};
+
+use bamboo_common::core::entities::{
+    Event, RecurrenceFrequency, RecurrenceRule, RecurrenceWeekday,
+};
+use bamboo_common::core::error::{BambooError, BambooResult};
+
+const DEFAULT_COLOR: &str = "#9f2637";
+const DEFAULT_TITLE: &str = "Ohne Titel";
+
+pub(crate) struct ImportEntry {
+    pub(crate) title: Option<String>,
+    pub(crate) uid: Option<String>,
+    pub(crate) recurrence_id: Option<NaiveDate>,
+    pub(crate) event: BambooResult<Event>,
+}
+
+fn event_uid(id: i32) -> String {
+    format!("{id}@bambushain")
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()
+}
+
+/// Parses a `DATE` or `DATE-TIME` property, floating times are treated as UTC.
+fn parse_date_time(property: &Property) -> BambooResult<(NaiveDate, Option<DateTime<Tz>>)> {
+    let value = property.value();
+    let date = parse_date(value)
+        .ok_or_else(|| BambooError::validation("event", format!("The date {value} is invalid")))?;
+    if value.len() == 8 {
+        return Ok((date, None));
+    }
+
+    let date_time = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S")
+        .map_err(|_| BambooError::validation("event", format!("The date {value} is invalid")))?;
+    let tz = if let Some(tzid) = property.params().get("TZID") {
+        Tz::from_str(tzid.value()).map_err(|_| {
+            BambooError::validation(
+                "event",
+                format!("The time zone {} is not supported", tzid.value()),
+            )
+        })?
+    } else {
+        Tz::UTC
+    };
+    let date_time = tz
+        .from_local_datetime(&date_time)
+        .earliest()
+        .ok_or_else(|| BambooError::validation("event", format!("The date {value} is invalid")))?;
+
+    Ok((date_time.date_naive(), Some(date_time)))
+}
+
+/// Returns the date of an occurrence as seen in the time zone of the series.
+fn parse_occurrence_date(property: &Property, tz: Option<Tz>) -> BambooResult<NaiveDate> {
+    let (date, date_time) = parse_date_time(property)?;
+
+    Ok(match (date_time, tz) {
+        (Some(date_time), Some(tz)) => date_time.with_timezone(&tz).date_naive(),
+        _ => date,
+    })
+}
+
+/// Creates a `DATE` property for all-day series and a `DATE-TIME` property at the start time of the series otherwise.
+fn occurrence_property(key: &str, date: NaiveDate, series: &Event) -> Property {
+    if let (false, Some(start_time), Some(time_zone)) =
+        (series.all_day, series.start_time, &series.time_zone)
+    {
+        Property::new(
+            key,
+            date.and_time(start_time)
+                .format("%Y%m%dT%H%M%S")
+                .to_string(),
+        )
+        .add_parameter("TZID", time_zone)
+        .done()
+    } else {
+        Property::new(key, format_date(date))
+            .add_parameter("VALUE", "DATE")
+            .done()
+    }
+}
+
+fn calendar_date_time(date_time: DateTime<Tz>) -> CalendarDateTime {
+    CalendarDateTime::WithTimezone {
+        date_time: date_time.naive_local(),
+        tzid: date_time.timezone().name().to_string(),
+    }
+}
+
+fn weekday_to_ical(weekday: RecurrenceWeekday) -> &'static str {
+    match chrono::Weekday::from(weekday) {
+        chrono::Weekday::Mon => "MO",
+        chrono::Weekday::Tue => "TU",
+        chrono::Weekday::Wed => "WE",
+        chrono::Weekday::Thu => "TH",
+        chrono::Weekday::Fri => "FR",
+        chrono::Weekday::Sat => "SA",
+        chrono::Weekday::Sun => "SU",
+    }
+}
+
+fn weekday_from_ical(value: &str) -> Option<RecurrenceWeekday> {
+    let weekday = match value {
+        "MO" => chrono::Weekday::Mon,
+        "TU" => chrono::Weekday::Tue,
+        "WE" => chrono::Weekday::Wed,
+        "TH" => chrono::Weekday::Thu,
+        "FR" => chrono::Weekday::Fri,
+        "SA" => chrono::Weekday::Sat,
+        "SU" => chrono::Weekday::Sun,
+        _ => return None,
+    };
+
+    Some(weekday.into())
+}
+
+fn recurrence_to_rrule(recurrence: &RecurrenceRule, event: &Event) -> String {
+    let mut parts = vec![
+        format!(
+            "FREQ={}",
+            match recurrence.frequency {
+                RecurrenceFrequency::Daily => "DAILY",
+                RecurrenceFrequency::Weekly => "WEEKLY",
+                RecurrenceFrequency::Monthly => "MONTHLY",
+            }
+        ),
+        format!("INTERVAL={}", recurrence.interval.max(1)),
+    ];
+    if recurrence.frequency == RecurrenceFrequency::Weekly && !recurrence.by_weekday.is_empty() {
+        parts.push(format!(
+            "BYDAY={}",
+            recurrence
+                .by_weekday
+                .iter()
+                .map(|weekday| weekday_to_ical(*weekday))
+                .collect::<Vec<&str>>()
+                .join(",")
+        ));
+    }
+    if let Some(until) = recurrence.until {
+        // The end of a series with a time has to be given in UTC
+        let until = match event.tz() {
+            Some(tz) if !event.all_day => tz
+                .from_local_datetime(&until.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap()))
+                .latest()
+                .map(|until| {
+                    until
+                        .with_timezone(&Utc)
+                        .format("%Y%m%dT%H%M%SZ")
+                        .to_string()
+                })
+                .unwrap_or(format_date(until)),
+            _ => format_date(until),
+        };
+        parts.push(format!("UNTIL={until}"));
+    } else if let Some(count) = recurrence.count {
+        parts.push(format!("COUNT={count}"));
+    }
+
+    parts.join(";")
+}
+
+/// The occurrences in `overridden` are part of the calendar as their own event and must not be excluded.
+fn event_to_vevent(
+    event: &Event,
+    series: Option<&Event>,
+    overridden: Option<&BTreeSet<NaiveDate>>,
+) -> icalendar::Event {
+    let mut vevent = icalendar::Event::new();
+    vevent
+        .uid(event_uid(event.series_id.unwrap_or(event.id)).as_str())
+        .summary(event.title.as_str());
+    if let (Some(start), Some(end)) = (event.start_date_time(), event.end_date_time()) {
+        vevent
+            .starts(calendar_date_time(start))
+            .ends(calendar_date_time(end));
+    } else {
+        vevent
+            .starts(event.start_date)
+            // The end date of all-day events is exclusive in RFC 5545
+            .ends(
+                event
+                    .end_date
+                    .checked_add_days(Days::new(1))
+                    .unwrap_or(event.end_date),
+            );
+    }
+    vevent
+        .class(if event.is_private {
+            Class::Private
+        } else {
+            Class::Public
+        })
+        .add_property("X-APPLE-CALENDAR-COLOR", event.color.as_str());
+
+    if !event.description.is_empty() {
+        vevent.description(event.description.as_str());
+    }
+    if let Some(series_date) = event.series_date {
+        vevent.append_property(occurrence_property(
+            "RECURRENCE-ID",
+            series_date,
+            series.unwrap_or(event),
+        ));
+    }
+    if let Some(recurrence) = &event.recurrence {
+        vevent.add_property("RRULE", recurrence_to_rrule(recurrence, event));
+        for exception in recurrence.exceptions.iter().filter(|exception| {
+            !overridden.is_some_and(|overridden| overridden.contains(exception))
+        }) {
+            vevent.append_multi_property(occurrence_property("EXDATE", *exception, event));
+        }
+    }
+
+    vevent.done()
+}
+
+pub(crate) fn events_to_calendar(name: &str, events: Vec<Event>) -> String {
+    let mut calendar = Calendar::new();
+    calendar.name(name);
+    let series = events
+        .iter()
+        .filter(|event| event.is_recurring())
+        .map(|event| (event.id, event))
+        .collect::<BTreeMap<i32, &Event>>();
+    let mut overridden = BTreeMap::<i32, BTreeSet<NaiveDate>>::new();
+    for event in events.iter() {
+        if let (Some(series_id), Some(series_date)) = (event.series_id, event.series_date) {
+            overridden.entry(series_id).or_default().insert(series_date);
+        }
+    }
+    for event in events.iter() {
+        calendar.push(event_to_vevent(
+            event,
+            event
+                .series_id
+                .and_then(|series_id| series.get(&series_id).copied()),
+            overridden.get(&event.id),
+        ));
+    }
+
+    calendar.done().to_string()
+}
+
+fn rrule_to_recurrence(rrule: &str) -> BambooResult<RecurrenceRule> {
+    let mut frequency = None;
+    let mut recurrence = RecurrenceRule::new(RecurrenceFrequency::default());
+    for part in rrule.split(';').filter(|part| !part.is_empty()) {
+        let (key, value) = part.split_once('=').ok_or_else(|| {
+            BambooError::validation(
+                "event",
+                format!("The recurrence rule part {part} is invalid"),
+            )
+        })?;
+        match key.to_uppercase().as_str() {
+            "FREQ" => {
+                frequency = Some(match value.to_uppercase().as_str() {
+                    "DAILY" => RecurrenceFrequency::Daily,
+                    "WEEKLY" => RecurrenceFrequency::Weekly,
+                    "MONTHLY" => RecurrenceFrequency::Monthly,
+                    _ => {
+                        return Err(BambooError::validation(
+                            "event",
+                            format!("The recurrence frequency {value} is not supported"),
+                        ))
+                    }
+                })
+            }
+            "INTERVAL" => {
+                recurrence.interval = value.parse().map_err(|_| {
+                    BambooError::validation("event", "The recurrence interval is invalid")
+                })?
+            }
+            "COUNT" => {
+                recurrence.count = Some(value.parse().map_err(|_| {
+                    BambooError::validation("event", "The recurrence count is invalid")
+                })?)
+            }
+            "UNTIL" => {
+                recurrence.until = Some(parse_date(value).ok_or_else(|| {
+                    BambooError::validation("event", "The recurrence end date is invalid")
+                })?)
+            }
+            "BYDAY" => {
+                for weekday in value.split(',') {
+                    recurrence.by_weekday.insert(
+                        weekday_from_ical(weekday.to_uppercase().as_str()).ok_or_else(|| {
+                            BambooError::validation(
+                                "event",
+                                format!("The recurrence weekday {weekday} is not supported"),
+                            )
+                        })?,
+                    );
+                }
+            }
+            "WKST" => {}
+            _ => {
+                return Err(BambooError::validation(
+                    "event",
+                    format!("The recurrence rule part {key} is not supported"),
+                ))
+            }
+        }
+    }
+
+    recurrence.frequency = frequency
+        .ok_or_else(|| BambooError::validation("event", "The recurrence frequency is missing"))?;
+    if recurrence.frequency != RecurrenceFrequency::Weekly && !recurrence.by_weekday.is_empty() {
+        return Err(BambooError::validation(
+            "event",
+            "Weekdays are only supported for weekly recurrences",
+        ));
+    }
+
+    Ok(recurrence)
+}
+
+fn vevent_to_event(vevent: &icalendar::Event) -> BambooResult<Event> {
+    let title = vevent
+        .get_summary()
+        .map(str::trim)
+        .filter(|summary| !summary.is_empty())
+        .unwrap_or(DEFAULT_TITLE);
+    let (start_date, start) = parse_date_time(
+        vevent
+            .properties()
+            .get("DTSTART")
+            .ok_or_else(|| BambooError::validation("event", "The start date is missing"))?,
+    )?;
+    let (end_date, end) = if let Some(end) = vevent.properties().get("DTEND") {
+        match (parse_date_time(end)?, start) {
+            ((_, Some(end)), Some(start)) => {
+                let end = end.with_timezone(&start.timezone());
+                (end.date_naive(), Some(end))
+            }
+            // The end date of all-day events is exclusive in RFC 5545
+            ((end_date, None), None) if end_date > start_date => {
+                (end_date.pred_opt().unwrap_or(end_date), None)
+            }
+            ((end_date, _), None) => (end_date, None),
+            ((_, None), Some(start)) => (start_date, Some(start)),
+        }
+    } else {
+        (start_date, start)
+    };
+    if end_date < start_date || end < start {
+        return Err(BambooError::validation(
+            "event",
+            "The end date cannot be before the start date",
+        ));
+    }
+
+    let color = vevent
+        .property_value("X-APPLE-CALENDAR-COLOR")
+        .or_else(|| vevent.property_value("COLOR"))
+        .filter(|color| color.starts_with('#'))
+        .unwrap_or(DEFAULT_COLOR);
+    let is_private = matches!(
+        vevent.get_class(),
+        Some(Class::Private) | Some(Class::Confidential)
+    );
+
+    let recurrence = if let Some(rrule) = vevent.property_value("RRULE") {
+        let mut recurrence = rrule_to_recurrence(rrule)?;
+        for exdate in vevent
+            .multi_properties()
+            .get("EXDATE")
+            .into_iter()
+            .flatten()
+        {
+            for value in exdate.value().split(',') {
+                let mut property = Property::new("EXDATE", value);
+                if let Some(tzid) = exdate.params().get("TZID") {
+                    property.add_parameter("TZID", tzid.value());
+                }
+                recurrence.exceptions.insert(parse_occurrence_date(
+                    &property,
+                    start.map(|start| start.timezone()),
+                )?);
+            }
+        }
+
+        Some(recurrence)
+    } else {
+        None
+    };
+
+    let mut event = Event {
+        title: title.to_string(),
+        description: vevent.get_description().unwrap_or_default().to_string(),
+        start_date,
+        end_date,
+        all_day: start.is_none(),
+        start_time: start.map(|start| start.time()),
+        end_time: end.map(|end| end.time()),
+        time_zone: start.map(|start| start.timezone().name().to_string()),
+        color: color.to_string(),
+        is_private,
+        recurrence,
+        ..Event::default()
+    };
+    // Colors that cannot be parsed fall back to the default color instead of failing the validation
+    event.set_color(event.color());
+
+    Ok(event)
+}
+
+pub(crate) fn calendar_to_events(data: &str) -> BambooResult<Vec<ImportEntry>> {
+    let calendar = Calendar::from_str(data).map_err(|err| {
+        log::error!("Failed to parse calendar {err}");
+        BambooError::invalid_data("event", "The file is not a valid iCalendar file")
+    })?;
+
+    Ok(calendar
+        .components
+        .iter()
+        .filter_map(CalendarComponent::as_event)
+        .map(|vevent| {
+            let event = vevent_to_event(vevent);
+            let tz = event.as_ref().ok().and_then(Event::tz);
+
+            ImportEntry {
+                title: vevent.get_summary().map(|summary| summary.to_string()),
+                uid: vevent.get_uid().map(|uid| uid.to_string()),
+                recurrence_id: vevent
+                    .properties()
+                    .get("RECURRENCE-ID")
+                    .and_then(|recurrence_id| parse_occurrence_date(recurrence_id, tz).ok()),
+                event,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_overridden_occurrences_are_not_excluded() {
+        let mut recurrence = RecurrenceRule::new(RecurrenceFrequency::Weekly);
+        recurrence.exceptions = BTreeSet::from([date(2024, 3, 17), date(2024, 3, 24)]);
+        let series = Event {
+            id: 1,
+            title: "Raid".to_string(),
+            start_date: date(2024, 3, 10),
+            end_date: date(2024, 3, 10),
+            all_day: true,
+            color: DEFAULT_COLOR.to_string(),
+            recurrence: Some(recurrence),
+            ..Event::default()
+        };
+        let occurrence = Event {
+            id: 2,
+            start_date: date(2024, 3, 18),
+            end_date: date(2024, 3, 18),
+            recurrence: None,
+            series_id: Some(1),
+            series_date: Some(date(2024, 3, 17)),
+            ..series.clone()
+        };
+
+        let calendar = events_to_calendar("Hain", vec![series, occurrence]);
+
+        assert!(!calendar.contains("EXDATE;VALUE=DATE:20240317"));
+        assert!(calendar.contains("EXDATE;VALUE=DATE:20240324"));
+        assert!(calendar.contains("RECURRENCE-ID;VALUE=DATE:20240317"));
+    }
+}
@@ -2,9 +2,17 @@ pub use app::start_server;
 
 mod app;
 pub(crate) mod cookie;
+pub(crate) mod demo;
 pub(crate) mod header;
+pub(crate) mod ical;
+pub(crate) mod login_attempt;
 pub(crate) mod middleware;
 pub(crate) mod notifier;
+pub(crate) mod passkey;
+pub(crate) mod password_reset;
 pub(crate) mod path;
+pub(crate) mod reminder;
 pub(crate) mod routes;
+pub(crate) mod session;
 pub(crate) mod sse;
+pub(crate) mod trash;
@@ -0,0 +1,65 @@
+use chrono::Duration;
+use sea_orm::DatabaseConnection;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::services::{EnvService, EnvironmentService};
+use bamboo_common::core::error::BambooResult;
+
+pub(crate) struct LoginAttemptPolicy {
+    pub max_attempts: i32,
+    pub lockout: Duration,
+    pub max_lockout: Duration,
+}
+
+impl LoginAttemptPolicy {
+    pub fn new(env_service: &EnvService) -> Self {
+        let max_attempts = env_service
+            .get_env("LOGIN_MAX_ATTEMPTS", "5")
+            .parse::<i32>()
+            .unwrap_or(5);
+        let lockout = env_service
+            .get_env("LOGIN_LOCKOUT_MINUTES", "5")
+            .parse::<i64>()
+            .unwrap_or(5);
+        let max_lockout = env_service
+            .get_env("LOGIN_MAX_LOCKOUT_MINUTES", "1440")
+            .parse::<i64>()
+            .unwrap_or(1440);
+
+        Self {
+            max_attempts,
+            lockout: Duration::minutes(lockout),
+            max_lockout: Duration::minutes(max_lockout),
+        }
+    }
+
+    pub async fn record_failed_attempt(
+        &self,
+        key: String,
+        db: &DatabaseConnection,
+    ) -> BambooResult<bool> {
+        dbal::record_failed_login_attempt(
+            key,
+            self.max_attempts,
+            self.lockout,
+            self.max_lockout,
+            db,
+        )
+        .await
+    }
+}
+
+pub(crate) fn start_login_attempt_cleanup(db: DatabaseConnection) {
+    let env_service = EnvService::new(EnvironmentService::new());
+    let policy = LoginAttemptPolicy::new(&env_service);
+
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(std::time::Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            if let Err(err) = dbal::delete_stale_login_attempts(policy.max_lockout, &db).await {
+                log::error!("Failed to remove stale login attempts {err}");
+            }
+        }
+    });
+}
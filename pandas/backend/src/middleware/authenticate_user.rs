@@ -2,9 +2,9 @@ use actix_web::{body, dev, web, Error, HttpMessage};
 use actix_web_lab::middleware::Next;
 
 use bamboo_common::backend::dbal;
-use bamboo_common::backend::services::DbConnection;
+use bamboo_common::backend::services::{DbConnection, EnvService};
 use bamboo_common::core::entities::*;
-use bamboo_common::core::error::BambooError;
+use bamboo_common::core::error::{BambooError, BambooResult};
 
 use crate::cookie;
 use crate::header;
@@ -18,23 +18,51 @@ pub(crate) struct AuthenticationState {
 
 pub(crate) type Authentication = web::ReqData<AuthenticationState>;
 
-pub(crate) async fn authenticate_user(
+/// Suspended groves are closed for everyone, disabled groves stay open for mods only
+pub(crate) fn check_grove_access(grove: &Grove, user: &User) -> BambooResult<()> {
+    if (!grove.is_enabled && !user.is_mod) || grove.is_suspended {
+        Err(BambooError::unauthorized("user", "Authorization failed"))
+    } else {
+        Ok(())
+    }
+}
+
+async fn authenticate_user(
+    scope: Option<TokenScope>,
     db: DbConnection,
+    env_service: EnvService,
     authorization: Option<web::Header<header::AuthorizationHeader>>,
     auth_cookie: Option<cookie::BambooAuthCookie>,
     req: dev::ServiceRequest,
     next: Next<impl body::MessageBody>,
 ) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
     let (token, user) = if authorization.is_some() {
-        helpers::get_user_and_token_by_header(&db, authorization).await?
+        let (token, user, personal_access_token) =
+            helpers::get_user_token_and_personal_access_token_by_header(
+                &db,
+                &env_service,
+                authorization,
+            )
+            .await?;
+
+        // Personal access tokens are only accepted on routes that name a scope
+        if let Some(personal_access_token) = personal_access_token {
+            if !scope.is_some_and(|scope| personal_access_token.has_scope(scope)) {
+                return Err(BambooError::insufficient_rights(
+                    "personal_access_token",
+                    "The token is missing the scope for this route",
+                )
+                .into());
+            }
+        }
+
+        (token, user)
     } else {
-        helpers::get_user_and_token_by_cookie(&db, auth_cookie).await?
+        helpers::get_user_and_token_by_cookie(&db, &env_service, auth_cookie).await?
     };
 
     let grove = dbal::get_grove_by_user_id(user.id, &db).await?;
-    if (!grove.is_enabled && !user.is_mod) || grove.is_suspended {
-        return Err(BambooError::unauthorized("user", "Authorization failed").into());
-    }
+    check_grove_access(&grove, &user)?;
 
     req.extensions_mut()
         .insert(AuthenticationState { token, user });
@@ -42,9 +70,73 @@ pub(crate) async fn authenticate_user(
     next.call(req).await
 }
 
+macro_rules! scope_check {
+    ($name:ident, $scope:expr) => {
+        pub(crate) async fn $name(
+            db: DbConnection,
+            env_service: EnvService,
+            authorization: Option<web::Header<header::AuthorizationHeader>>,
+            auth_cookie: Option<cookie::BambooAuthCookie>,
+            req: dev::ServiceRequest,
+            next: Next<impl body::MessageBody>,
+        ) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
+            authenticate_user(
+                $scope,
+                db,
+                env_service,
+                authorization,
+                auth_cookie,
+                req,
+                next,
+            )
+            .await
+        }
+    };
+}
+
+scope_check!(authenticate_session, None);
+scope_check!(authenticate_read_events, Some(TokenScope::ReadEvents));
+scope_check!(authenticate_write_events, Some(TokenScope::WriteEvents));
+scope_check!(
+    authenticate_read_characters,
+    Some(TokenScope::ReadCharacters)
+);
+scope_check!(
+    authenticate_write_characters,
+    Some(TokenScope::WriteCharacters)
+);
+scope_check!(authenticate_read_users, Some(TokenScope::ReadUsers));
+
 macro_rules! authenticate {
     () => {
-        actix_web_lab::middleware::from_fn(crate::middleware::authenticate_user::authenticate_user)
+        actix_web_lab::middleware::from_fn(
+            crate::middleware::authenticate_user::authenticate_session,
+        )
+    };
+    (ReadEvents) => {
+        actix_web_lab::middleware::from_fn(
+            crate::middleware::authenticate_user::authenticate_read_events,
+        )
+    };
+    (WriteEvents) => {
+        actix_web_lab::middleware::from_fn(
+            crate::middleware::authenticate_user::authenticate_write_events,
+        )
+    };
+    (ReadCharacters) => {
+        actix_web_lab::middleware::from_fn(
+            crate::middleware::authenticate_user::authenticate_read_characters,
+        )
+    };
+    (WriteCharacters) => {
+        actix_web_lab::middleware::from_fn(
+            crate::middleware::authenticate_user::authenticate_write_characters,
+        )
+    };
+    (ReadUsers) => {
+        actix_web_lab::middleware::from_fn(
+            crate::middleware::authenticate_user::authenticate_read_users,
+        )
     };
 }
 
@@ -0,0 +1,117 @@
+use actix_web::{body, dev, web, Error};
+use actix_web_lab::middleware::Next;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::services::{DbConnection, EnvService};
+use bamboo_common::core::entities::{Permission, User};
+use bamboo_common::core::error::BambooError;
+
+use crate::demo::{DemoAccount, DemoService};
+use crate::middleware::helpers;
+use crate::{cookie, header};
+
+async fn get_user(
+    db: &DbConnection,
+    env_service: &EnvService,
+    authorization: Option<web::Header<header::AuthorizationHeader>>,
+    auth_cookie: Option<cookie::BambooAuthCookie>,
+) -> Result<User, Error> {
+    let (_, user) = if authorization.is_some() {
+        helpers::get_user_and_token_by_header(db, env_service, authorization).await?
+    } else {
+        helpers::get_user_and_token_by_cookie(db, env_service, auth_cookie).await?
+    };
+
+    Ok(user)
+}
+
+pub(crate) async fn check_not_demo(
+    db: DbConnection,
+    env_service: EnvService,
+    demo_service: DemoService,
+    authorization: Option<web::Header<header::AuthorizationHeader>>,
+    auth_cookie: Option<cookie::BambooAuthCookie>,
+    req: dev::ServiceRequest,
+    next: Next<impl body::MessageBody>,
+) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
+    let user = get_user(&db, &env_service, authorization, auth_cookie).await?;
+
+    if DemoAccount::is_demo_login(&demo_service, user.email.as_str()) {
+        Err(BambooError::insufficient_rights("user", "The demo account cannot be changed").into())
+    } else {
+        next.call(req).await
+    }
+}
+
+async fn check_permission(
+    permission: Permission,
+    db: DbConnection,
+    env_service: EnvService,
+    authorization: Option<web::Header<header::AuthorizationHeader>>,
+    auth_cookie: Option<cookie::BambooAuthCookie>,
+    req: dev::ServiceRequest,
+    next: Next<impl body::MessageBody>,
+) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
+    let user = get_user(&db, &env_service, authorization, auth_cookie).await?;
+
+    if dbal::user_has_permission(&user, permission, &db).await? {
+        next.call(req).await
+    } else {
+        Err(BambooError::insufficient_rights("user", "You are missing the permission").into())
+    }
+}
+
+macro_rules! permission_check {
+    ($name:ident, $permission:expr) => {
+        pub(crate) async fn $name(
+            db: DbConnection,
+            env_service: EnvService,
+            authorization: Option<web::Header<header::AuthorizationHeader>>,
+            auth_cookie: Option<cookie::BambooAuthCookie>,
+            req: dev::ServiceRequest,
+            next: Next<impl body::MessageBody>,
+        ) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
+            check_permission(
+                $permission,
+                db,
+                env_service,
+                authorization,
+                auth_cookie,
+                req,
+                next,
+            )
+            .await
+        }
+    };
+}
+
+permission_check!(check_manage_users, Permission::ManageUsers);
+permission_check!(check_manage_events, Permission::ManageEvents);
+permission_check!(check_manage_grove, Permission::ManageGrove);
+permission_check!(check_view_audit_log, Permission::ViewAuditLog);
+
+macro_rules! not_demo {
+    () => {
+        actix_web_lab::middleware::from_fn(crate::middleware::check_permission::check_not_demo)
+    };
+}
+
+macro_rules! has_permission {
+    (ManageUsers) => {
+        actix_web_lab::middleware::from_fn(crate::middleware::check_permission::check_manage_users)
+    };
+    (ManageEvents) => {
+        actix_web_lab::middleware::from_fn(crate::middleware::check_permission::check_manage_events)
+    };
+    (ManageGrove) => {
+        actix_web_lab::middleware::from_fn(crate::middleware::check_permission::check_manage_grove)
+    };
+    (ViewAuditLog) => {
+        actix_web_lab::middleware::from_fn(
+            crate::middleware::check_permission::check_view_audit_log,
+        )
+    };
+}
+
+pub(crate) use has_permission;
+pub(crate) use not_demo;
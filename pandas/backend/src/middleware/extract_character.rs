@@ -3,7 +3,7 @@ use actix_web_lab::middleware::Next;
 
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::response::*;
-use bamboo_common::backend::services::DbConnection;
+use bamboo_common::backend::services::{DbConnection, EnvService};
 use bamboo_common::core::entities;
 
 use crate::middleware::helpers;
@@ -13,15 +13,16 @@ use crate::{cookie, header};
 pub(crate) async fn extract_character(
     path: Option<path::CharacterPath>,
     db: DbConnection,
+    env_service: EnvService,
     authorization: Option<web::Header<header::AuthorizationHeader>>,
     auth_cookie: Option<cookie::BambooAuthCookie>,
     req: dev::ServiceRequest,
     next: Next<impl body::MessageBody>,
 ) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
     let (_, user) = if authorization.is_some() {
-        helpers::get_user_and_token_by_header(&db, authorization).await?
+        helpers::get_user_and_token_by_header(&db, &env_service, authorization).await?
     } else {
-        helpers::get_user_and_token_by_cookie(&db, auth_cookie).await?
+        helpers::get_user_and_token_by_cookie(&db, &env_service, auth_cookie).await?
     };
 
     let path = check_invalid_path!(path, "character")?;
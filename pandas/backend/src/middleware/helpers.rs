@@ -2,16 +2,20 @@ use actix_web::web;
 use sea_orm::DatabaseConnection;
 
 use bamboo_common::backend::dbal;
+use bamboo_common::backend::services::EnvService;
 use bamboo_common::core::entities::*;
 use bamboo_common::core::error::{BambooError, BambooResult};
 
 use crate::cookie;
 use crate::header;
+use crate::session::SessionPolicy;
 
-pub async fn get_user_and_token_by_header(
+/// Accepts session tokens and personal access tokens, the personal access token is returned so the caller can check its scopes
+pub async fn get_user_token_and_personal_access_token_by_header(
     db: &DatabaseConnection,
+    env_service: &EnvService,
     authorization: Option<web::Header<header::AuthorizationHeader>>,
-) -> BambooResult<(String, User)> {
+) -> BambooResult<(String, User, Option<PersonalAccessToken>)> {
     let unauthorized = BambooError::unauthorized("user", "Authorization failed");
     let token = if let Some(header) = authorization {
         if let Some(authorization) = header.authorization.clone() {
@@ -23,15 +27,38 @@ pub async fn get_user_and_token_by_header(
         Err(unauthorized.clone())
     }?;
 
-    let user = dbal::get_user_by_token(token.clone(), db)
-        .await
-        .map_err(|_| unauthorized.clone())?;
+    if dbal::is_personal_access_token(token.as_str()) {
+        let (personal_access_token, user) =
+            dbal::get_user_by_valid_personal_access_token(token.clone(), db)
+                .await
+                .map_err(|_| unauthorized.clone())?;
+
+        return Ok((token, user, Some(personal_access_token)));
+    }
+
+    let policy = SessionPolicy::new(env_service);
+    let user =
+        dbal::get_user_by_valid_token(token.clone(), policy.idle_timeout, policy.max_lifetime, db)
+            .await
+            .map_err(|_| unauthorized.clone())?;
+
+    Ok((token, user, None))
+}
+
+pub async fn get_user_and_token_by_header(
+    db: &DatabaseConnection,
+    env_service: &EnvService,
+    authorization: Option<web::Header<header::AuthorizationHeader>>,
+) -> BambooResult<(String, User)> {
+    let (token, user, _) =
+        get_user_token_and_personal_access_token_by_header(db, env_service, authorization).await?;
 
     Ok((token, user))
 }
 
 pub async fn get_user_and_token_by_cookie(
     db: &DatabaseConnection,
+    env_service: &EnvService,
     auth_cookie: Option<cookie::BambooAuthCookie>,
 ) -> BambooResult<(String, User)> {
     let unauthorized = BambooError::unauthorized("user", "Authorization failed");
@@ -41,9 +68,11 @@ pub async fn get_user_and_token_by_cookie(
         Err(unauthorized.clone())
     }?;
 
-    let user = dbal::get_user_by_token(token.clone(), db)
-        .await
-        .map_err(|_| unauthorized.clone())?;
+    let policy = SessionPolicy::new(env_service);
+    let user =
+        dbal::get_user_by_valid_token(token.clone(), policy.idle_timeout, policy.max_lifetime, db)
+            .await
+            .map_err(|_| unauthorized.clone())?;
 
     Ok((token, user))
 }
@@ -2,7 +2,7 @@ use actix_web::{body, dev, web, Error, HttpMessage};
 use actix_web_lab::middleware::Next;
 
 use bamboo_common::backend::dbal;
-use bamboo_common::backend::services::DbConnection;
+use bamboo_common::backend::services::{DbConnection, EnvService};
 use bamboo_common::core::entities::Grove as DbGrove;
 
 use crate::cookie;
@@ -18,15 +18,16 @@ pub(crate) type CurrentGrove = web::ReqData<GroveState>;
 
 pub(crate) async fn identify_grove(
     db: DbConnection,
+    env_service: EnvService,
     authorization: Option<web::Header<header::AuthorizationHeader>>,
     auth_cookie: Option<cookie::BambooAuthCookie>,
     req: dev::ServiceRequest,
     next: Next<impl body::MessageBody>,
 ) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
     let (_, user) = if authorization.is_some() {
-        helpers::get_user_and_token_by_header(&db, authorization).await?
+        helpers::get_user_and_token_by_header(&db, &env_service, authorization).await?
     } else {
-        helpers::get_user_and_token_by_cookie(&db, auth_cookie).await?
+        helpers::get_user_and_token_by_cookie(&db, &env_service, auth_cookie).await?
     };
 
     let grove = dbal::get_grove_by_user_id(user.id, &db).await?;
@@ -1,5 +1,5 @@
 pub(crate) mod authenticate_user;
-pub(crate) mod check_mod;
+pub(crate) mod check_permission;
 pub(crate) mod extract_character;
 mod helpers;
 pub(crate) mod identify_grove;
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::rt::time::interval;
+use actix_web::Responder;
+use actix_web_lab::sse;
+use parking_lot::Mutex;
+use tokio::sync::mpsc::error::SendError;
+use tokio::sync::mpsc::Sender;
+
+use bamboo_common::core::entities::{SseTopic, User};
+
+use crate::sse::event;
+
+#[derive(Debug, Clone)]
+struct Client {
+    sender: Sender<sse::Event>,
+    user: User,
+    topics: Vec<SseTopic>,
+    legacy: bool,
+}
+
+pub(crate) struct TopicBroadcaster {
+    inner: Mutex<TopicBroadcasterInner>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TopicBroadcasterInner {
+    clients: Vec<Client>,
+}
+
+impl TopicBroadcaster {
+    pub fn create() -> Arc<Self> {
+        let this = Arc::new(TopicBroadcaster {
+            inner: Mutex::new(TopicBroadcasterInner::default()),
+        });
+        TopicBroadcaster::spawn_ping(Arc::clone(&this));
+
+        this
+    }
+
+    fn spawn_ping(this: Arc<Self>) {
+        actix_web::rt::spawn(async move {
+            let mut interval = interval(Duration::from_secs(10));
+
+            loop {
+                interval.tick().await;
+                this.remove_stale_clients().await;
+            }
+        });
+    }
+
+    async fn remove_stale_clients(&self) {
+        let clients = self.inner.lock().clients.clone();
+        let mut ok_clients = Vec::new();
+        for client in clients {
+            if let Err(err) = Self::send_comment(client.sender.clone(), event::Comment::Ping).await
+            {
+                log::info!("Failed to send ping {err}");
+            } else {
+                ok_clients.push(client);
+            }
+        }
+
+        self.inner.lock().clients = ok_clients;
+    }
+
+    pub async fn new_client(&self, user: User, topics: Vec<SseTopic>) -> impl Responder {
+        self.register_client(user, topics, false).await
+    }
+
+    /// Registers a client of /sse/event, it gets the event names and payloads from before the topics existed
+    pub async fn new_legacy_event_client(&self, user: User) -> impl Responder {
+        self.register_client(user, vec![SseTopic::Event], true)
+            .await
+    }
+
+    async fn register_client(
+        &self,
+        user: User,
+        topics: Vec<SseTopic>,
+        legacy: bool,
+    ) -> impl Responder {
+        log::debug!("Open channel using tokio");
+        let (tx, rx) = tokio::sync::mpsc::channel::<sse::Event>(10);
+
+        log::debug!("Send connected message");
+        if let Err(err) = Self::send_comment(tx.clone(), event::Comment::Connected).await {
+            log::error!("Failed to send message {err}")
+        }
+        self.inner.lock().clients.push(Client {
+            sender: tx,
+            user,
+            topics,
+            legacy,
+        });
+
+        sse::Sse::from_infallible_receiver(rx).with_keep_alive(Duration::from_secs(60))
+    }
+
+    pub fn send(&self, message: event::Message) {
+        let clients = self.inner.lock().clients.clone();
+        log::debug!("Has {} clients registered", clients.len());
+        for client in clients {
+            Self::send_message(client, message.clone())
+        }
+    }
+
+    fn send_message(client: Client, message: event::Message) {
+        if client.topics.contains(&message.topic) && message.audience.contains(&client.user) {
+            let event = if client.legacy {
+                let Some(event) = message.to_legacy_event() else {
+                    return;
+                };
+                event
+            } else {
+                message.clone().into()
+            };
+
+            actix_web::rt::spawn(async move {
+                log::debug!("Send {} data", message.topic);
+                log::debug!("Sending message with data {message:#?}");
+                if let Err(err) = client.sender.send(event).await {
+                    log::error!("Failed to send message {err}");
+                }
+            });
+        }
+    }
+
+    async fn send_comment(
+        client: Sender<sse::Event>,
+        evt: event::Comment,
+    ) -> Result<(), SendError<sse::Event>> {
+        client
+            .send(sse::Event::Comment(bytestring::ByteString::from(
+                serde_json::to_string(&evt).unwrap(),
+            )))
+            .await
+    }
+}
@@ -0,0 +1,172 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, DbErr, Statement};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgListener;
+
+use crate::notifier::broadcaster::TopicBroadcaster;
+use crate::sse::event::Message;
+
+const CHANNEL: &str = "bamboo_sse";
+
+// Postgres refuses notifications with a payload of 8000 bytes or more, larger messages are stored in a table
+const MAX_PAYLOAD_SIZE: usize = 7999;
+
+// Stored messages are loaded right after the notification, so they can be removed soon after
+const STORED_MESSAGE_LIFETIME: &str = "5 minutes";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum Payload {
+    Message(Message),
+    Stored(i32),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope {
+    instance_id: String,
+    payload: Payload,
+}
+
+pub(crate) struct PostgresFanOut {
+    db: DatabaseConnection,
+    instance_id: String,
+}
+
+impl PostgresFanOut {
+    pub fn create(db: DatabaseConnection, broadcaster: Arc<TopicBroadcaster>) -> Arc<Self> {
+        let this = Arc::new(Self {
+            db,
+            instance_id: uuid::Uuid::new_v4().to_string(),
+        });
+        PostgresFanOut::spawn_listener(Arc::clone(&this), broadcaster);
+
+        this
+    }
+
+    fn spawn_listener(this: Arc<Self>, broadcaster: Arc<TopicBroadcaster>) {
+        actix_web::rt::spawn(async move {
+            loop {
+                if let Err(err) = this.listen(&broadcaster).await {
+                    log::error!("Lost the connection to the notification channel {CHANNEL}: {err}");
+                }
+                actix_web::rt::time::sleep(Duration::from_secs(5)).await;
+            }
+        });
+    }
+
+    async fn listen(&self, broadcaster: &TopicBroadcaster) -> Result<(), sqlx::Error> {
+        let mut listener = PgListener::connect_with(self.db.get_postgres_connection_pool()).await?;
+        listener.listen(CHANNEL).await?;
+        log::info!(
+            "Instance {} listens on notification channel {CHANNEL}",
+            self.instance_id
+        );
+
+        loop {
+            let notification = listener.recv().await?;
+            match serde_json::from_str::<Envelope>(notification.payload()) {
+                Ok(envelope) if envelope.instance_id != self.instance_id => {
+                    let message = match envelope.payload {
+                        Payload::Message(message) => message,
+                        Payload::Stored(id) => match self.load(id).await {
+                            Ok(message) => message,
+                            Err(err) => {
+                                log::error!("Failed to load stored notification {id}: {err}");
+                                continue;
+                            }
+                        },
+                    };
+                    log::debug!(
+                        "Received {} message from instance {}",
+                        message.topic,
+                        envelope.instance_id
+                    );
+                    broadcaster.send(message);
+                }
+                Ok(_) => {}
+                Err(err) => log::warn!("Received invalid notification payload: {err}"),
+            }
+        }
+    }
+
+    async fn load(&self, id: i32) -> Result<Message, DbErr> {
+        let message = self
+            .db
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "SELECT message FROM bamboo.sse_notification WHERE id = $1",
+                [id.into()],
+            ))
+            .await?
+            .ok_or(DbErr::RecordNotFound(format!("sse notification {id}")))?
+            .try_get::<serde_json::Value>("", "message")?;
+
+        serde_json::from_value(message).map_err(|err| DbErr::Json(err.to_string()))
+    }
+
+    /// Stores a message which is too large for a notification and returns its id, expired messages are removed on the way
+    async fn store(db: &DatabaseConnection, message: &Message) -> Result<i32, DbErr> {
+        db.execute(Statement::from_string(
+            DbBackend::Postgres,
+            format!(
+                "DELETE FROM bamboo.sse_notification WHERE created_at < CURRENT_TIMESTAMP - INTERVAL '{STORED_MESSAGE_LIFETIME}'"
+            ),
+        ))
+        .await?;
+
+        let message = serde_json::to_value(message).map_err(|err| DbErr::Json(err.to_string()))?;
+        db.query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO bamboo.sse_notification (message) VALUES ($1) RETURNING id",
+            [message.into()],
+        ))
+        .await?
+        .ok_or(DbErr::RecordNotInserted)?
+        .try_get::<i32>("", "id")
+    }
+
+    async fn notify(
+        db: &DatabaseConnection,
+        instance_id: String,
+        message: Message,
+    ) -> Result<(), DbErr> {
+        let serialize = |envelope: &Envelope| {
+            serde_json::to_string(envelope).map_err(|err| DbErr::Json(err.to_string()))
+        };
+
+        let mut envelope = Envelope {
+            instance_id,
+            payload: Payload::Message(message.clone()),
+        };
+        let mut payload = serialize(&envelope)?;
+        if payload.len() > MAX_PAYLOAD_SIZE {
+            log::debug!(
+                "The {} notification is too large for postgres, store it for the other instances",
+                message.topic
+            );
+            envelope.payload = Payload::Stored(Self::store(db, &message).await?);
+            payload = serialize(&envelope)?;
+        }
+
+        db.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT pg_notify($1, $2)",
+            [CHANNEL.into(), payload.into()],
+        ))
+        .await
+        .map(|_| ())
+    }
+
+    pub fn publish(&self, message: Message) {
+        let db = self.db.clone();
+        let instance_id = self.instance_id.clone();
+        actix_web::rt::spawn(async move {
+            if let Err(err) = Self::notify(&db, instance_id, message).await {
+                log::error!("Failed to publish notification: {err}");
+            }
+        });
+    }
+}
@@ -1,4 +1,6 @@
 pub use notifier_state::{Notifier, NotifierState};
 
-mod event;
+mod broadcaster;
+mod fan_out;
 pub(crate) mod notifier_state;
+mod webhook;
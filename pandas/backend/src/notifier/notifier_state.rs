@@ -1,47 +1,216 @@
 use std::sync::Arc;
 
 use actix_web::{web, Responder};
+use sea_orm::DatabaseConnection;
 
-use bamboo_common::core::entities::{Event, User};
+use bamboo_common::core::entities::{
+    Character, CharacterHousing, Event, FreeCompany, Grove, GroveWebhook, GroveWebhookDelivery,
+    SseTopic, User,
+};
+use bamboo_common::core::error::BambooResult;
 
-use crate::notifier::event::EventBroadcaster;
+use crate::notifier::broadcaster::TopicBroadcaster;
+use crate::notifier::fan_out::PostgresFanOut;
+use crate::notifier::webhook::{WebhookDispatcher, WebhookEventType, WebhookMessage};
+use crate::sse::event::{Action, Message};
 
 #[derive(Clone)]
 pub struct NotifierState {
-    event_broadcaster: Arc<EventBroadcaster>,
+    topic_broadcaster: Arc<TopicBroadcaster>,
+    fan_out: Arc<PostgresFanOut>,
+    webhook_dispatcher: Arc<WebhookDispatcher>,
 }
 
 impl NotifierState {
-    pub fn new() -> Self {
-        let event_broadcaster = EventBroadcaster::create();
+    pub fn new(db: DatabaseConnection) -> Self {
+        let topic_broadcaster = TopicBroadcaster::create();
+        let fan_out = PostgresFanOut::create(db.clone(), topic_broadcaster.clone());
+        let webhook_dispatcher = WebhookDispatcher::create(db);
 
-        Self { event_broadcaster }
+        Self {
+            topic_broadcaster,
+            fan_out,
+            webhook_dispatcher,
+        }
+    }
+
+    fn publish(&self, message: Message) {
+        self.topic_broadcaster.send(message.clone());
+        self.fan_out.publish(message)
+    }
+
+    fn dispatch_event_webhook(&self, event_type: WebhookEventType, event: &Event) {
+        if !event.is_private {
+            self.webhook_dispatcher
+                .dispatch(event.grove_id, WebhookMessage::event(event_type, event))
+        }
     }
 
     pub fn notify_event_create(&self, event: Event) {
         log::info!("Event created, notify sources");
-        self.event_broadcaster.notify_create(event)
+        self.dispatch_event_webhook(WebhookEventType::EventCreated, &event);
+        self.publish(Message::event(Action::Created, event))
     }
 
     pub fn notify_event_update(&self, event: Event) {
         log::info!("Event updated, notify sources");
-        self.event_broadcaster.notify_update(event)
+        self.dispatch_event_webhook(WebhookEventType::EventUpdated, &event);
+        self.publish(Message::event(Action::Updated, event))
     }
 
     pub fn notify_event_delete(&self, event: Event) {
         log::info!("Event deleted, notify sources");
-        self.event_broadcaster.notify_delete(event)
+        self.dispatch_event_webhook(WebhookEventType::EventDeleted, &event);
+        self.publish(Message::event(Action::Deleted, event))
+    }
+
+    pub fn notify_event_attendance(&self, event: Event) {
+        log::info!("Event attendance changed, notify sources");
+        self.publish(Message::event(Action::AttendanceChanged, event))
     }
 
-    pub async fn new_client(&self, user: User) -> impl Responder {
+    pub fn notify_event_party(&self, event: Event) {
+        log::info!("Event party changed, notify sources");
+        self.publish(Message::event(Action::PartyChanged, event))
+    }
+
+    pub fn notify_user_create(&self, grove_id: i32, user: &User) {
+        log::info!("User created, notify sources");
+        self.webhook_dispatcher
+            .dispatch(grove_id, WebhookMessage::user(user));
+        self.publish(Message::user(
+            Action::Created,
+            grove_id,
+            user.clone().into(),
+        ))
+    }
+
+    pub fn notify_user_update(&self, grove_id: i32, user: &User) {
+        log::info!("User updated, notify sources");
+        self.publish(Message::user(
+            Action::Updated,
+            grove_id,
+            user.clone().into(),
+        ))
+    }
+
+    pub fn notify_user_delete(&self, grove_id: i32, user: &User) {
+        log::info!("User deleted, notify sources");
+        self.publish(Message::user(
+            Action::Deleted,
+            grove_id,
+            user.clone().into(),
+        ))
+    }
+
+    pub fn notify_character_create(&self, user_id: i32, character: &Character) {
+        log::info!("Character created, notify sources");
+        self.publish(Message::character(
+            Action::Created,
+            user_id,
+            character.clone(),
+        ))
+    }
+
+    pub fn notify_character_update(&self, user_id: i32, character: &Character) {
+        log::info!("Character updated, notify sources");
+        self.publish(Message::character(
+            Action::Updated,
+            user_id,
+            character.clone(),
+        ))
+    }
+
+    pub fn notify_character_delete(&self, user_id: i32, character: &Character) {
+        log::info!("Character deleted, notify sources");
+        self.publish(Message::character(
+            Action::Deleted,
+            user_id,
+            character.clone(),
+        ))
+    }
+
+    pub fn notify_free_company_create(&self, user_id: i32, free_company: &FreeCompany) {
+        log::info!("Free company created, notify sources");
+        self.publish(Message::free_company(
+            Action::Created,
+            user_id,
+            free_company.clone(),
+        ))
+    }
+
+    pub fn notify_free_company_update(&self, user_id: i32, free_company: &FreeCompany) {
+        log::info!("Free company updated, notify sources");
+        self.publish(Message::free_company(
+            Action::Updated,
+            user_id,
+            free_company.clone(),
+        ))
+    }
+
+    pub fn notify_free_company_delete(&self, user_id: i32, free_company: &FreeCompany) {
+        log::info!("Free company deleted, notify sources");
+        self.publish(Message::free_company(
+            Action::Deleted,
+            user_id,
+            free_company.clone(),
+        ))
+    }
+
+    pub fn notify_housing_create(&self, grove_id: i32, user_id: i32, housing: &CharacterHousing) {
+        log::info!("Housing created, notify sources");
+        self.webhook_dispatcher.dispatch(
+            grove_id,
+            WebhookMessage::housing(WebhookEventType::HousingCreated, housing),
+        );
+        self.publish(Message::housing(Action::Created, user_id, housing.clone()))
+    }
+
+    pub fn notify_housing_update(&self, grove_id: i32, user_id: i32, housing: &CharacterHousing) {
+        log::info!("Housing updated, notify sources");
+        self.webhook_dispatcher.dispatch(
+            grove_id,
+            WebhookMessage::housing(WebhookEventType::HousingUpdated, housing),
+        );
+        self.publish(Message::housing(Action::Updated, user_id, housing.clone()))
+    }
+
+    pub fn notify_housing_delete(&self, grove_id: i32, user_id: i32, housing: &CharacterHousing) {
+        log::info!("Housing deleted, notify sources");
+        self.webhook_dispatcher.dispatch(
+            grove_id,
+            WebhookMessage::housing(WebhookEventType::HousingDeleted, housing),
+        );
+        self.publish(Message::housing(Action::Deleted, user_id, housing.clone()))
+    }
+
+    pub fn notify_grove_enabled(&self, grove: Grove) {
+        log::info!("Grove enabled, notify sources");
+        self.publish(Message::grove(Action::Enabled, grove))
+    }
+
+    pub fn notify_grove_disabled(&self, grove: Grove) {
+        log::info!("Grove disabled, notify sources");
+        self.publish(Message::grove(Action::Disabled, grove))
+    }
+
+    pub fn notify_grove_delete(&self, grove: Grove) {
+        log::info!("Grove deleted, notify sources");
+        self.publish(Message::grove(Action::Deleted, grove))
+    }
+
+    pub async fn ping_webhook(&self, webhook: GroveWebhook) -> BambooResult<GroveWebhookDelivery> {
+        log::info!("Send ping to webhook {}", webhook.id);
+        self.webhook_dispatcher.ping(webhook).await
+    }
+
+    pub async fn new_client(&self, user: User, topics: Vec<SseTopic>) -> impl Responder {
         log::info!("Wanted new client");
-        self.event_broadcaster.new_client(user).await
+        self.topic_broadcaster.new_client(user, topics).await
     }
-}
 
-impl Default for NotifierState {
-    fn default() -> Self {
-        Self::new()
+    pub async fn new_legacy_event_client(&self, user: User) -> impl Responder {
+        self.topic_broadcaster.new_legacy_event_client(user).await
     }
 }
 
@@ -0,0 +1,613 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use sha2::Sha256;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::services::{EnvService, EnvironmentService};
+use bamboo_common::core::entities::{
+    CharacterHousing, Event, GroveWebhook, GroveWebhookDelivery, User,
+};
+use bamboo_common::core::error::*;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum WebhookEventType {
+    EventCreated,
+    EventUpdated,
+    EventDeleted,
+    UserCreated,
+    HousingCreated,
+    HousingUpdated,
+    HousingDeleted,
+    Ping,
+}
+
+impl Display for WebhookEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::EventCreated => "event.created",
+            Self::EventUpdated => "event.updated",
+            Self::EventDeleted => "event.deleted",
+            Self::UserCreated => "user.created",
+            Self::HousingCreated => "housing.created",
+            Self::HousingUpdated => "housing.updated",
+            Self::HousingDeleted => "housing.deleted",
+            Self::Ping => "ping",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct WebhookMessage {
+    event_type: WebhookEventType,
+    data: serde_json::Value,
+    title: String,
+    description: String,
+    color: Option<u32>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookPayload {
+    #[serde(rename = "type")]
+    event_type: String,
+    grove_id: i32,
+    sent_at: DateTime<Utc>,
+    data: serde_json::Value,
+}
+
+#[derive(Serialize)]
+struct DiscordEmbed {
+    title: String,
+    description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<u32>,
+    timestamp: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+struct DiscordPayload {
+    embeds: Vec<DiscordEmbed>,
+}
+
+impl WebhookMessage {
+    fn new(
+        event_type: WebhookEventType,
+        data: impl Serialize,
+        title: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            event_type,
+            data: serde_json::to_value(data).unwrap_or_default(),
+            title: title.into(),
+            description: description.into(),
+            color: None,
+        }
+    }
+
+    pub fn event(event_type: WebhookEventType, event: &Event) -> Self {
+        let title = match event_type {
+            WebhookEventType::EventCreated => format!("Neues Event: {}", event.title),
+            WebhookEventType::EventDeleted => format!("Event abgesagt: {}", event.title),
+            _ => format!("Event geändert: {}", event.title),
+        };
+        let date = if event.start_date == event.end_date {
+            event.start_date.format("%d.%m.%Y").to_string()
+        } else {
+            format!(
+                "{} bis {}",
+                event.start_date.format("%d.%m.%Y"),
+                event.end_date.format("%d.%m.%Y")
+            )
+        };
+        let time = if let (Some(start), Some(end)) = (event.start_time, event.end_time) {
+            format!(
+                ", {} bis {} Uhr ({})",
+                start.format("%H:%M"),
+                end.format("%H:%M"),
+                event.time_zone.clone().unwrap_or_default()
+            )
+        } else {
+            String::new()
+        };
+
+        Self {
+            color: u32::from_str_radix(event.color.trim_start_matches('#'), 16).ok(),
+            ..Self::new(
+                event_type,
+                event,
+                title,
+                format!("{date}{time}\n\n{}", event.description),
+            )
+        }
+    }
+
+    pub fn user(user: &User) -> Self {
+        Self::new(
+            WebhookEventType::UserCreated,
+            serde_json::json!({
+                "id": user.id,
+                "displayName": user.display_name,
+                "discordName": user.discord_name,
+            }),
+            format!("Neuer Panda: {}", user.display_name),
+            if user.discord_name.is_empty() {
+                "Willkommen im Hain".to_string()
+            } else {
+                format!("Willkommen im Hain, {}", user.discord_name)
+            },
+        )
+    }
+
+    pub fn housing(event_type: WebhookEventType, housing: &CharacterHousing) -> Self {
+        let title = match event_type {
+            WebhookEventType::HousingCreated => "Neue Unterkunft",
+            WebhookEventType::HousingDeleted => "Unterkunft entfernt",
+            _ => "Unterkunft geändert",
+        };
+
+        Self::new(
+            event_type,
+            housing,
+            title,
+            format!(
+                "{}, {} Bezirk {} Nr. {}",
+                housing.housing_type.to_string(),
+                housing.district.to_string(),
+                housing.ward,
+                housing.plot
+            ),
+        )
+    }
+
+    pub fn ping() -> Self {
+        Self::new(
+            WebhookEventType::Ping,
+            serde_json::Value::Null,
+            "Test vom Bambushain",
+            "Der Webhook funktioniert",
+        )
+    }
+
+    fn body(&self, webhook: &GroveWebhook) -> String {
+        let sent_at = Utc::now();
+        if webhook.discord_format {
+            serde_json::to_string(&DiscordPayload {
+                embeds: vec![DiscordEmbed {
+                    title: self.title.clone(),
+                    description: self.description.clone(),
+                    color: self.color,
+                    timestamp: sent_at,
+                }],
+            })
+        } else {
+            serde_json::to_string(&WebhookPayload {
+                event_type: self.event_type.to_string(),
+                grove_id: webhook.grove_id,
+                sent_at,
+                data: self.data.clone(),
+            })
+        }
+        .unwrap_or_default()
+    }
+}
+
+/// Signs the body with the secret of the webhook, receivers can use it to verify the payload came from us
+fn sign(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take keys of any size");
+    mac.update(body.as_bytes());
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Longest error message stored with a delivery
+const MAX_ERROR_LENGTH: usize = 200;
+
+/// Webhooks are entered by mods, they must not be able to reach the server itself or the internal network
+fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ipv4(ip),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [first, second, ..] = ip.octets();
+    let is_shared = first == 100 && (64..128).contains(&second);
+    let is_reserved = first == 0 || first >= 240;
+
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || is_shared
+        || is_reserved)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    let is_unique_local = first & 0xfe00 == 0xfc00;
+    let is_link_local = first & 0xffc0 == 0xfe80;
+
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || is_unique_local
+        || is_link_local)
+}
+
+/// Resolves the host of the webhook and pins the client to the public addresses, so the host cannot point to the internal network.
+/// Hosts in `allowed_hosts` are trusted by the operator and may point anywhere, e.g. to a local test server.
+async fn webhook_client(url: &str, allowed_hosts: &[String]) -> Result<reqwest::Client, String> {
+    let url = reqwest::Url::parse(url).map_err(|err| format!("The url is invalid {err}"))?;
+    let host = url.host_str().ok_or("The url has no host".to_string())?;
+    let builder = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .redirect(reqwest::redirect::Policy::none());
+
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if allowed_hosts
+        .iter()
+        .any(|allowed_host| allowed_host.eq_ignore_ascii_case(host))
+    {
+        return builder
+            .build()
+            .map_err(|err| format!("Failed to create the client {err}"));
+    }
+
+    // Hosts given as ip address are not resolved
+    let builder = match host.parse::<IpAddr>() {
+        Ok(ip) if is_public_address(ip) => builder,
+        Ok(ip) => return Err(format!("{ip} is not a public address")),
+        Err(_) => {
+            let addrs = tokio::net::lookup_host((host, url.port_or_known_default().unwrap_or(80)))
+                .await
+                .map_err(|err| format!("Failed to resolve {host} {err}"))?
+                .filter(|addr| is_public_address(addr.ip()))
+                .collect::<Vec<SocketAddr>>();
+            if addrs.is_empty() {
+                return Err(format!("{host} does not resolve to a public address"));
+            }
+
+            builder.resolve_to_addrs(host, &addrs)
+        }
+    };
+
+    builder
+        .build()
+        .map_err(|err| format!("Failed to create the client {err}"))
+}
+
+/// Controls how often and where webhooks are delivered
+struct DeliverySettings {
+    max_attempts: i32,
+    retry_delay: Duration,
+    allowed_hosts: Vec<String>,
+}
+
+impl DeliverySettings {
+    fn new(env_service: &EnvService) -> Self {
+        let max_attempts = env_service
+            .get_env("WEBHOOK_MAX_ATTEMPTS", "5")
+            .parse::<i32>()
+            .unwrap_or(5)
+            .max(1);
+        // Internal hosts are blocked unless the operator lists them, e.g. for a local test receiver
+        let allowed_hosts = env_service
+            .get_env("WEBHOOK_ALLOWED_HOSTS", "")
+            .split(',')
+            .map(|host| host.trim().to_string())
+            .filter(|host| !host.is_empty())
+            .collect();
+
+        Self {
+            max_attempts,
+            retry_delay: Duration::from_secs(1),
+            allowed_hosts,
+        }
+    }
+
+    /// Retries failed deliveries with an exponential backoff, every attempt is passed to `log_delivery`
+    async fn deliver<F, Fut>(
+        &self,
+        webhook: &GroveWebhook,
+        message: &WebhookMessage,
+        log_delivery: F,
+    ) where
+        F: Fn(GroveWebhookDelivery) -> Fut,
+        Fut: Future<Output = BambooResult<GroveWebhookDelivery>>,
+    {
+        let body = message.body(webhook);
+        for attempt in 1..=self.max_attempts {
+            let delivery = self.send(webhook, message, &body, attempt).await;
+            let success = delivery.success;
+            if let Err(err) = log_delivery(delivery).await {
+                log::error!("Failed to log webhook delivery {err}");
+            }
+            if success {
+                return;
+            }
+
+            if attempt < self.max_attempts {
+                let backoff = self.retry_delay * 2u32.pow(attempt as u32);
+                log::info!(
+                    "Delivery of webhook {} failed, retry in {} seconds",
+                    webhook.id,
+                    backoff.as_secs_f32()
+                );
+                actix_web::rt::time::sleep(backoff).await;
+            }
+        }
+    }
+
+    async fn send(
+        &self,
+        webhook: &GroveWebhook,
+        message: &WebhookMessage,
+        body: &str,
+        attempt: i32,
+    ) -> GroveWebhookDelivery {
+        let result = match webhook_client(&webhook.url, &self.allowed_hosts).await {
+            Ok(client) => client
+                .post(webhook.url.clone())
+                .header("Content-Type", "application/json")
+                .header("X-Bamboo-Event", message.event_type.to_string())
+                .header("X-Bamboo-Signature", sign(&webhook.secret, body))
+                .body(body.to_string())
+                .send()
+                .await
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err),
+        };
+
+        // The response body is never stored, it could leak whatever the receiver answers
+        let (status_code, success, error) = match result {
+            Ok(response) => {
+                let status = response.status();
+                (Some(status.as_u16() as i32), status.is_success(), None)
+            }
+            Err(err) => {
+                log::warn!("Failed to deliver webhook {} {err}", webhook.id);
+                (
+                    None,
+                    false,
+                    Some(err.chars().take(MAX_ERROR_LENGTH).collect()),
+                )
+            }
+        };
+
+        GroveWebhookDelivery {
+            id: 0,
+            webhook_id: webhook.id,
+            event_type: message.event_type.to_string(),
+            attempt,
+            status_code,
+            success,
+            error,
+            delivered_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+pub(crate) struct WebhookDispatcher {
+    db: DatabaseConnection,
+    settings: DeliverySettings,
+}
+
+impl WebhookDispatcher {
+    pub fn create(db: DatabaseConnection) -> Arc<Self> {
+        let env_service = EnvService::new(EnvironmentService::new());
+        let settings = DeliverySettings::new(&env_service);
+
+        Arc::new(Self { db, settings })
+    }
+
+    pub fn dispatch(self: &Arc<Self>, grove_id: i32, message: WebhookMessage) {
+        let this = Arc::clone(self);
+        actix_web::rt::spawn(async move {
+            let webhooks = match dbal::get_grove_webhooks(grove_id, &this.db).await {
+                Ok(webhooks) => webhooks,
+                Err(err) => {
+                    log::error!("Failed to load webhooks of grove {grove_id} {err}");
+                    return;
+                }
+            };
+
+            for webhook in webhooks {
+                let this = Arc::clone(&this);
+                let message = message.clone();
+                actix_web::rt::spawn(async move {
+                    this.settings
+                        .deliver(&webhook, &message, |delivery| {
+                            dbal::create_grove_webhook_delivery(delivery, &this.db)
+                        })
+                        .await
+                });
+            }
+        });
+    }
+
+    pub async fn ping(&self, webhook: GroveWebhook) -> BambooResult<GroveWebhookDelivery> {
+        let message = WebhookMessage::ping();
+        let body = message.body(&webhook);
+        let delivery = self.settings.send(&webhook, &message, &body, 1).await;
+
+        dbal::create_grove_webhook_delivery(delivery, &self.db).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+
+    use super::*;
+
+    #[test]
+    fn test_is_public_address() {
+        for ip in [
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fc00::1",
+            "fd12:3456::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(!is_public_address(ip.parse().unwrap()), "{ip} is internal");
+        }
+        for ip in ["1.1.1.1", "93.184.216.34", "2606:4700:4700::1111"] {
+            assert!(is_public_address(ip.parse().unwrap()), "{ip} is public");
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_webhook_client() {
+        assert!(webhook_client("http://127.0.0.1:8080/hook", &[])
+            .await
+            .is_err());
+        assert!(
+            webhook_client("http://169.254.169.254/latest/meta-data", &[])
+                .await
+                .is_err()
+        );
+        assert!(webhook_client("http://[::1]/hook", &[]).await.is_err());
+        assert!(webhook_client("http://localhost/hook", &[]).await.is_err());
+        assert!(webhook_client("https://1.1.1.1/hook", &[]).await.is_ok());
+    }
+
+    #[actix_web::test]
+    async fn test_webhook_client_allowed_hosts() {
+        let allowed_hosts = vec!["localhost".to_string(), "::1".to_string()];
+
+        assert!(webhook_client("http://localhost:8080/hook", &allowed_hosts)
+            .await
+            .is_ok());
+        assert!(webhook_client("http://[::1]/hook", &allowed_hosts)
+            .await
+            .is_ok());
+        assert!(webhook_client("http://127.0.0.1/hook", &allowed_hosts)
+            .await
+            .is_err());
+    }
+
+    #[derive(Clone, Default)]
+    struct Received {
+        requests: Arc<parking_lot::Mutex<Vec<(Instant, String, String)>>>,
+    }
+
+    async fn receive_webhook(
+        req: HttpRequest,
+        body: String,
+        received: web::Data<Received>,
+    ) -> HttpResponse {
+        let signature = req
+            .headers()
+            .get("X-Bamboo-Signature")
+            .and_then(|signature| signature.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let mut requests = received.requests.lock();
+        requests.push((Instant::now(), signature, body));
+
+        // The first two attempts fail to check the retries
+        if requests.len() < 3 {
+            HttpResponse::InternalServerError().finish()
+        } else {
+            HttpResponse::Ok().finish()
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_deliver_to_local_server() {
+        let received = Received::default();
+        let data = web::Data::new(received.clone());
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(data.clone())
+                .route("/hook", web::post().to(receive_webhook))
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let port = server.addrs()[0].port();
+        let server = server.run();
+        let handle = server.handle();
+        actix_web::rt::spawn(server);
+
+        let webhook = GroveWebhook {
+            id: 1,
+            grove_id: 1,
+            url: format!("http://127.0.0.1:{port}/hook"),
+            secret: "bamboo".to_string(),
+            discord_format: false,
+        };
+        let settings = DeliverySettings {
+            max_attempts: 5,
+            retry_delay: Duration::from_millis(10),
+            allowed_hosts: vec!["127.0.0.1".to_string()],
+        };
+        let deliveries = parking_lot::Mutex::new(vec![]);
+
+        settings
+            .deliver(&webhook, &WebhookMessage::ping(), |delivery| {
+                deliveries.lock().push(delivery.clone());
+                async { Ok(delivery) }
+            })
+            .await;
+        handle.stop(true).await;
+
+        let requests = received.requests.lock().clone();
+        assert_eq!(requests.len(), 3);
+        for (_, signature, body) in requests.iter() {
+            let mut mac = Hmac::<Sha256>::new_from_slice(b"bamboo").unwrap();
+            mac.update(body.as_bytes());
+            let expected = hex::encode(mac.finalize().into_bytes());
+
+            assert_eq!(signature, &format!("sha256={expected}"));
+            assert!(body.contains("\"type\":\"ping\""));
+        }
+        assert!(requests[1].0 - requests[0].0 >= Duration::from_millis(20));
+        assert!(requests[2].0 - requests[1].0 >= Duration::from_millis(40));
+
+        let deliveries = deliveries.into_inner();
+        assert_eq!(
+            deliveries
+                .iter()
+                .map(|delivery| (delivery.attempt, delivery.status_code, delivery.success))
+                .collect::<Vec<(i32, Option<i32>, bool)>>(),
+            vec![
+                (1, Some(500), false),
+                (2, Some(500), false),
+                (3, Some(200), true)
+            ]
+        );
+        assert!(deliveries
+            .iter()
+            .all(|delivery| delivery.webhook_id == 1 && delivery.event_type == "ping"));
+    }
+}
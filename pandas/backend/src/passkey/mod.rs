@@ -0,0 +1,17 @@
+use bamboo_common::backend::services::EnvService;
+
+pub(crate) struct RelyingParty {
+    pub id: String,
+    pub name: String,
+    pub origin: String,
+}
+
+impl RelyingParty {
+    pub fn new(env_service: &EnvService) -> Self {
+        Self {
+            id: env_service.get_env("WEBAUTHN_RP_ID", "localhost"),
+            name: env_service.get_env("WEBAUTHN_RP_NAME", "Bambushain"),
+            origin: env_service.get_env("WEBAUTHN_ORIGIN", env_service.get_frontend_url()),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use chrono::Duration;
+
+use bamboo_common::backend::services::EnvService;
+
+pub(crate) struct PasswordResetPolicy {
+    pub lifetime: Duration,
+    pub url: String,
+}
+
+impl PasswordResetPolicy {
+    pub fn new(env_service: &EnvService) -> Self {
+        let lifetime = env_service
+            .get_env("PASSWORD_RESET_LIFETIME_MINUTES", "60")
+            .parse::<i64>()
+            .unwrap_or(60);
+
+        Self {
+            lifetime: Duration::minutes(lifetime),
+            url: format!("{}/reset-password", env_service.get_frontend_url()),
+        }
+    }
+}
@@ -1,58 +1,127 @@
 use actix_web::web;
+use chrono::NaiveDate;
 use serde::Deserialize;
+use utoipa::IntoParams;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub struct CharacterPathInfo {
     pub character_id: i32,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub struct CrafterPathInfo {
     pub crafter_id: i32,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub struct CharacterHousingPathInfo {
     pub character_housing_id: i32,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub struct CustomFieldPathInfo {
     pub field_id: i32,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub struct CustomFieldOptionPathInfo {
     pub option_id: i32,
     pub field_id: i32,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub struct CustomFieldPositionPathInfo {
     pub field_id: i32,
     pub position: i32,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub struct EventPathInfo {
     pub event_id: i32,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct EventPartySlotPathInfo {
+    pub event_id: i32,
+    pub slot: i32,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct EventOccurrencePathInfo {
+    pub event_id: i32,
+    pub occurrence_date: NaiveDate,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct CalendarFeedPathInfo {
+    pub feed_secret: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub struct FighterPathInfo {
     pub fighter_id: i32,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub struct FreeCompanyPathInfo {
     pub free_company_id: i32,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct InvitationPathInfo {
+    pub invitation_id: i32,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct PasskeyPathInfo {
+    pub passkey_id: i32,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct PersonalAccessTokenPathInfo {
+    pub token_id: i32,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct RolePathInfo {
+    pub role_id: i32,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct SessionPathInfo {
+    pub session_id: i32,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
 pub struct UserPathInfo {
     pub user_id: i32,
 }
 
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct WebhookPathInfo {
+    pub webhook_id: i32,
+}
+
+pub type CalendarFeedPath = web::Path<CalendarFeedPathInfo>;
 pub type CharacterPath = web::Path<CharacterPathInfo>;
 pub type CharacterHousingPath = web::Path<CharacterHousingPathInfo>;
 pub type CrafterPath = web::Path<CrafterPathInfo>;
@@ -60,6 +129,14 @@ pub type CustomFieldPath = web::Path<CustomFieldPathInfo>;
 pub type CustomFieldOptionPath = web::Path<CustomFieldOptionPathInfo>;
 pub type CustomFieldPositionPath = web::Path<CustomFieldPositionPathInfo>;
 pub type EventPath = web::Path<EventPathInfo>;
+pub type EventPartySlotPath = web::Path<EventPartySlotPathInfo>;
+pub type EventOccurrencePath = web::Path<EventOccurrencePathInfo>;
 pub type FighterPath = web::Path<FighterPathInfo>;
 pub type FreeCompanyPath = web::Path<FreeCompanyPathInfo>;
+pub type InvitationPath = web::Path<InvitationPathInfo>;
+pub type PasskeyPath = web::Path<PasskeyPathInfo>;
+pub type PersonalAccessTokenPath = web::Path<PersonalAccessTokenPathInfo>;
+pub type RolePath = web::Path<RolePathInfo>;
+pub type SessionPath = web::Path<SessionPathInfo>;
 pub type UserPath = web::Path<UserPathInfo>;
+pub type WebhookPath = web::Path<WebhookPathInfo>;
@@ -0,0 +1,129 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use sea_orm::DatabaseConnection;
+
+use bamboo_common::backend::services::{EnvService, EnvironmentService};
+use bamboo_common::backend::{dbal, mailing};
+use bamboo_common::core::entities::Event;
+use bamboo_common::core::error::*;
+
+struct ReminderConfig {
+    lead_time: Duration,
+    time_zone: Tz,
+}
+
+impl ReminderConfig {
+    fn new(env_service: &EnvService) -> Self {
+        let lead_time = env_service
+            .get_env("EVENT_REMINDER_LEAD_TIME", "1440")
+            .parse::<i64>()
+            .unwrap_or(1440);
+        let time_zone = Tz::from_str(
+            env_service
+                .get_env("EVENT_REMINDER_TIME_ZONE", "Europe/Berlin")
+                .as_str(),
+        )
+        .unwrap_or(Tz::Europe__Berlin);
+
+        Self {
+            lead_time: Duration::minutes(lead_time),
+            time_zone,
+        }
+    }
+
+    /// All day events have no time zone, they start at midnight in the configured time zone
+    fn event_start(&self, event: &Event) -> Option<DateTime<Utc>> {
+        if let Some(start) = event.start_date_time() {
+            Some(start.with_timezone(&Utc))
+        } else {
+            self.time_zone
+                .from_local_datetime(&event.start_date.and_time(NaiveTime::MIN))
+                .earliest()
+                .map(|start| start.with_timezone(&Utc))
+        }
+    }
+
+    fn starts_at(&self, event: &Event) -> String {
+        if let Some(start) = event.start_date_time() {
+            format!(
+                "am {} um {} Uhr ({})",
+                start.format("%d.%m.%Y"),
+                start.format("%H:%M"),
+                start.timezone().name()
+            )
+        } else {
+            format!("am {}", event.start_date.format("%d.%m.%Y"))
+        }
+    }
+}
+
+async fn send_reminders(
+    config: &ReminderConfig,
+    env_service: &EnvService,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let now = Utc::now();
+    let until = now + config.lead_time;
+    let since_date = now.date_naive().pred_opt().unwrap_or(now.date_naive());
+    let until_date = until.date_naive().succ_opt().unwrap_or(until.date_naive());
+
+    for event in dbal::get_event_occurrences_between(since_date, until_date, db).await? {
+        if let Some(start) = config.event_start(&event) {
+            if start <= now || start > until {
+                continue;
+            }
+        } else {
+            continue;
+        }
+
+        for user in dbal::get_event_reminder_recipients(&event, db).await? {
+            if !dbal::mark_event_reminder_sent(event.id, event.start_date, user.id, db).await? {
+                continue;
+            }
+
+            log::info!(
+                "Send reminder for event {} on {} to user {}",
+                event.id,
+                event.start_date,
+                user.id
+            );
+            if let Err(err) = mailing::event::send_event_reminder_mail(
+                user.display_name.clone(),
+                user.email.clone(),
+                event.clone(),
+                config.starts_at(&event),
+                env_service.clone(),
+            )
+            .await
+            {
+                // The reminder is sent again on the next tick as long as the event did not start yet
+                log::error!("Failed to send event reminder {err}");
+                let _ =
+                    dbal::unmark_event_reminder_sent(event.id, event.start_date, user.id, db).await;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn start_reminder_scheduler(db: DatabaseConnection) {
+    let env_service = EnvService::new(EnvironmentService::new());
+    let config = ReminderConfig::new(&env_service);
+
+    log::info!(
+        "Start event reminders {} minutes before events",
+        config.lead_time.num_minutes()
+    );
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(std::time::Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            if let Err(err) = send_reminders(&config, &env_service, &db).await {
+                log::error!("Failed to send event reminders {err}");
+            }
+        }
+    });
+}
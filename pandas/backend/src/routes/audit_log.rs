@@ -0,0 +1,61 @@
+use actix_web::{get, web};
+use sea_orm::DatabaseConnection;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::response::*;
+use bamboo_common::backend::services::DbConnection;
+use bamboo_common::core::entities::*;
+use bamboo_common::core::error::*;
+
+use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_permission::has_permission;
+use crate::middleware::identify_grove::{grove, CurrentGrove};
+
+pub(crate) async fn record_action(
+    authentication: &Authentication,
+    grove_id: Option<i32>,
+    action: AuditAction,
+    target: Option<&User>,
+    target_name: String,
+    db: &DatabaseConnection,
+) {
+    dbal::create_audit_log(
+        AuditLog::new(
+            grove_id,
+            Some(authentication.user.id),
+            authentication.user.display_name.clone(),
+            action,
+            target.map(|user| user.id),
+            target_name,
+        ),
+        db,
+    )
+    .await
+}
+
+#[utoipa::path(
+    tag = "Audit Log",
+    params(AuditLogFilter, ListQuery),
+    responses((status = 200, description = "The audit log of the grove, the newest entries come first", body = [AuditLog], headers(("X-Total-Count" = u64, description = "The number of audit log entries matching the filter")))),
+    security(("Panda" = []))
+)]
+#[get(
+    "/api/grove/audit-log",
+    wrap = "authenticate!()",
+    wrap = "has_permission!(ViewAuditLog)",
+    wrap = "grove!()"
+)]
+pub async fn get_audit_log(
+    filter: Option<web::Query<AuditLogFilter>>,
+    query: Option<web::Query<ListQuery>>,
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let mut filter = check_invalid_query!(filter, "audit_log")?.into_inner();
+    let query = check_invalid_query!(query, "audit_log")?;
+    filter.grove_id = Some(current_grove.grove.id);
+
+    dbal::get_audit_log(filter, &query, &db)
+        .await
+        .map(|data| page!(data))
+}
@@ -1,5 +1,12 @@
+use std::net::IpAddr;
+
 use actix_web::cookie::Cookie;
-use actix_web::{delete, post, web, HttpResponse};
+use actix_web::http::header::{USER_AGENT, X_FORWARDED_FOR};
+use actix_web::http::StatusCode;
+use actix_web::{delete, post, HttpRequest, HttpResponse};
+
+use chrono::Duration;
+use sea_orm::DatabaseConnection;
 
 use bamboo_common::backend::response::*;
 use bamboo_common::backend::services::{DbConnection, EnvService};
@@ -7,61 +14,102 @@ use bamboo_common::backend::{dbal, mailing};
 use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
+use crate::demo::{DemoAccount, DemoService};
+use crate::login_attempt::LoginAttemptPolicy;
 use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::passkey::RelyingParty;
+use crate::password_reset::PasswordResetPolicy;
+use crate::session::SessionPolicy;
 
-#[post("/api/login")]
-pub async fn login(
-    body: Option<web::Json<Login>>,
-    db: DbConnection,
+fn get_user_agent(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(USER_AGENT)
+        .and_then(|user_agent| user_agent.to_str().ok())
+        .map(|user_agent| user_agent.to_string())
+}
+
+/// The forwarded header is set by the client, it is only trusted when the request comes from one of the proxies in TRUSTED_PROXIES
+pub(crate) fn get_client_ip(req: &HttpRequest) -> String {
+    let Some(peer_ip) = req.peer_addr().map(|addr| addr.ip()) else {
+        return "unknown".to_string();
+    };
+    let trusted_proxies = req
+        .app_data::<EnvService>()
+        .map(|env_service| env_service.get_env("TRUSTED_PROXIES", ""))
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|proxy| proxy.trim().parse::<IpAddr>().ok())
+        .collect::<Vec<IpAddr>>();
+    if !trusted_proxies.contains(&peer_ip) {
+        return peer_ip.to_string();
+    }
+
+    // Every proxy appends the address it got the request from, so the last untrusted entry is the client
+    req.headers()
+        .get_all(X_FORWARDED_FOR)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|ip| ip.trim().parse::<IpAddr>().ok())
+        .filter(|ip| !trusted_proxies.contains(ip))
+        .last()
+        .unwrap_or(peer_ip)
+        .to_string()
+}
+
+fn login_response(data: LoginResult) -> HttpResponse {
+    let mut response = list!(data.clone());
+    let _ = response.add_cookie(
+        &Cookie::build(crate::cookie::BAMBOO_AUTH_COOKIE, data.token.clone())
+            .path("/")
+            .http_only(true)
+            .finish(),
+    );
+
+    response
+}
+
+async fn execute_login(
+    body: &Login,
+    user_agent: Option<String>,
+    idle_timeout: Duration,
+    db: &DatabaseConnection,
     env_service: EnvService,
+    demo_service: &DemoService,
 ) -> BambooApiResponseResult {
-    let body = check_missing_fields!(body, "authentication")?;
-
-    if body.email.clone() == "playstore@google.bambushain" {
-        dbal::create_google_auth_token(body.password.clone(), &db)
-            .await
-            .map_err(|err| {
-                log::error!("Failed to login {err}");
-                BambooError::unauthorized("user", "Login data is invalid")
-            })
-            .map(|data| {
-                let mut response = list!(data.clone());
-                let _ = response.add_cookie(
-                    &Cookie::build(crate::cookie::BAMBOO_AUTH_COOKIE, data.token.clone())
-                        .path("/")
-                        .http_only(true)
-                        .finish(),
-                );
-                response
-            })
+    if DemoAccount::is_demo_login(demo_service, body.email.as_str()) {
+        dbal::create_demo_auth_token(
+            body.email.clone(),
+            body.password.clone(),
+            user_agent,
+            idle_timeout,
+            db,
+        )
+        .await
+        .map_err(|err| {
+            log::error!("Failed to login {err}");
+            BambooError::unauthorized("user", "Login data is invalid")
+        })
+        .map(login_response)
     } else if let Some(two_factor_code) = body.two_factor_code.clone() {
         dbal::validate_auth_and_create_token(
             body.email.clone(),
             body.password.clone(),
             two_factor_code,
-            &db,
+            user_agent,
+            idle_timeout,
+            db,
         )
         .await
         .map_err(|err| {
             log::error!("Failed to login {err}");
             BambooError::unauthorized("user", "Login data is invalid")
         })
-        .map(|data| {
-            let mut response = list!(data.clone());
-            let _ = response.add_cookie(
-                &Cookie::build(crate::cookie::BAMBOO_AUTH_COOKIE, data.token.clone())
-                    .path("/")
-                    .http_only(true)
-                    .finish(),
-            );
-
-            response
-        })
+        .map(login_response)
     } else {
         let data = dbal::validate_auth_and_set_two_factor_code(
             body.email.clone(),
             body.password.clone(),
-            &db,
+            db,
         )
         .await
         .map_err(|err| {
@@ -83,14 +131,199 @@ pub async fn login(
     }
 }
 
+#[utoipa::path(
+    tag = "Authentication",
+    request_body = Login,
+    responses(
+        (status = 200, description = "The login was successful, the token is also set as cookie", body = LoginResult),
+        (status = 204, description = "The two factor code was sent by mail"),
+    )
+)]
+#[post("/api/login")]
+pub async fn login(
+    body: JsonBody<Login>,
+    req: HttpRequest,
+    db: DbConnection,
+    env_service: EnvService,
+    demo_service: DemoService,
+) -> BambooApiResponseResult {
+    let body = check_missing_fields!(body, "authentication")?;
+    let policy = SessionPolicy::new(&env_service);
+    let attempt_policy = LoginAttemptPolicy::new(&env_service);
+
+    let user = dbal::get_user_by_email_or_username(body.email.clone(), &db)
+        .await
+        .ok();
+    let ip_key = format!("login:ip:{}", get_client_ip(&req));
+    let account_key = user
+        .as_ref()
+        .map(|user| format!("login:user:{}", user.id))
+        .unwrap_or(format!("login:name:{}", body.email.to_lowercase()));
+
+    dbal::check_login_attempts(ip_key.as_str(), &db).await?;
+    dbal::check_login_attempts(account_key.as_str(), &db).await?;
+
+    let result = execute_login(
+        &body,
+        get_user_agent(&req),
+        policy.idle_timeout,
+        &db,
+        env_service.clone(),
+        &demo_service,
+    )
+    .await;
+    match &result {
+        // Only a created token counts as success, a correct password alone just sends the two factor code
+        Ok(response) if response.status() == StatusCode::OK => {
+            let _ = dbal::reset_login_attempts(ip_key.as_str(), &db).await;
+            let _ = dbal::reset_login_attempts(account_key.as_str(), &db).await;
+        }
+        Ok(_) => {}
+        Err(_) => {
+            let _ = attempt_policy.record_failed_attempt(ip_key, &db).await;
+            let locked = attempt_policy
+                .record_failed_attempt(account_key, &db)
+                .await
+                .unwrap_or(false);
+            if let (true, Some(user)) = (locked, user) {
+                log::warn!(
+                    "Locked login for user {} after too many failed attempts",
+                    user.id
+                );
+                mailing::authentication::send_account_locked_mail(
+                    user.display_name,
+                    user.email,
+                    attempt_policy.lockout.num_minutes(),
+                    env_service,
+                )
+                .await;
+            }
+        }
+    }
+
+    result
+}
+
+#[utoipa::path(
+    tag = "Authentication",
+    responses((status = 201, description = "The challenge to sign with a passkey", body = PasskeyLoginOptions))
+)]
+#[post("/api/login/passkey/challenge")]
+pub async fn create_passkey_login_challenge(
+    req: HttpRequest,
+    db: DbConnection,
+    env_service: EnvService,
+) -> BambooApiResult<PasskeyLoginOptions> {
+    let relying_party = RelyingParty::new(&env_service);
+    let attempt_policy = LoginAttemptPolicy::new(&env_service);
+    let ip_key = format!("passkey-challenge:ip:{}", get_client_ip(&req));
+    dbal::check_login_attempts(ip_key.as_str(), &db).await?;
+    // Every request stores a challenge, so it is throttled like a failed login until a passkey login succeeds
+    let _ = attempt_policy.record_failed_attempt(ip_key, &db).await;
+
+    // Passkeys are registered as discoverable, so the browser offers them without being told any
+    // credential and nobody can probe for accounts
+    dbal::create_passkey_challenge(None, &db)
+        .await
+        .map(|challenge| {
+            created!(PasskeyLoginOptions {
+                challenge,
+                rp_id: relying_party.id,
+                allow_credentials: vec![],
+            })
+        })
+}
+
+#[utoipa::path(
+    tag = "Authentication",
+    request_body = PasskeyLogin,
+    responses((status = 200, description = "The login was successful, the token is also set as cookie", body = LoginResult))
+)]
+#[post("/api/login/passkey")]
+pub async fn login_with_passkey(
+    body: JsonBody<PasskeyLogin>,
+    req: HttpRequest,
+    db: DbConnection,
+    env_service: EnvService,
+) -> BambooApiResponseResult {
+    let body = check_missing_fields!(body, "passkey")?;
+    let policy = SessionPolicy::new(&env_service);
+    let relying_party = RelyingParty::new(&env_service);
+    let attempt_policy = LoginAttemptPolicy::new(&env_service);
+    let client_ip = get_client_ip(&req);
+    let ip_key = format!("login:ip:{client_ip}");
+    dbal::check_login_attempts(ip_key.as_str(), &db).await?;
+
+    let result = dbal::validate_passkey_and_create_token(
+        body.into_inner(),
+        relying_party.id.as_str(),
+        relying_party.origin.as_str(),
+        get_user_agent(&req),
+        policy.idle_timeout,
+        &db,
+    )
+    .await
+    .map_err(|err| {
+        log::error!("Failed to login with passkey {err}");
+        BambooError::unauthorized("user", "Login data is invalid")
+    });
+    if result.is_ok() {
+        let _ = dbal::reset_login_attempts(ip_key.as_str(), &db).await;
+        let _ =
+            dbal::reset_login_attempts(format!("passkey-challenge:ip:{client_ip}").as_str(), &db)
+                .await;
+    } else {
+        let _ = attempt_policy.record_failed_attempt(ip_key, &db).await;
+    }
+
+    result.map(login_response)
+}
+
+#[utoipa::path(
+    tag = "Authentication",
+    request_body = ForgotPassword,
+    responses((status = 204, description = "A reset link was mailed, if the user exists"))
+)]
 #[post("/api/forgot-password")]
 pub async fn forgot_password(
-    body: Option<web::Json<ForgotPassword>>,
+    body: JsonBody<ForgotPassword>,
+    req: HttpRequest,
     db: DbConnection,
     env_service: EnvService,
-) -> HttpResponse {
-    if let Ok(body) = check_missing_fields!(body, "user") {
-        if let Ok(user) = dbal::get_user_by_email_or_username(body.email.clone(), &db).await {
+) -> BambooApiResponseResult {
+    let attempt_policy = LoginAttemptPolicy::new(&env_service);
+    let ip_key = format!("forgot-password:ip:{}", get_client_ip(&req));
+    dbal::check_login_attempts(ip_key.as_str(), &db).await?;
+    // Every request sends mails, so it is throttled like a failed login
+    let _ = attempt_policy.record_failed_attempt(ip_key, &db).await;
+
+    let body = check_missing_fields!(body, "user")?;
+    // The account is throttled by the submitted name before the lookup, so unknown users get the same
+    // responses as known ones and nobody can probe for accounts
+    let account_key = format!("forgot-password:name:{}", body.email.trim().to_lowercase());
+    dbal::check_login_attempts(account_key.as_str(), &db).await?;
+    let _ = attempt_policy.record_failed_attempt(account_key, &db).await;
+
+    if let Ok(user) = dbal::get_user_by_email_or_username(body.email.clone(), &db).await {
+        let reset_policy = PasswordResetPolicy::new(&env_service);
+        if let Ok(token) =
+            dbal::create_password_reset_token(user.id, reset_policy.lifetime, &db).await
+        {
+            let _ = mailing::authentication::send_password_reset_mail(
+                user.display_name.clone(),
+                user.email.clone(),
+                format!("{}?token={token}", reset_policy.url),
+                reset_policy.lifetime.num_minutes(),
+                env_service.clone(),
+            )
+            .await;
+        }
+
+        let notify_mods = dbal::get_grove_by_id(user.grove_id, &db)
+            .await
+            .map(|grove| grove.notify_mods_on_forgot_password)
+            .unwrap_or(false);
+        if notify_mods {
             if let Ok(mods) = dbal::get_users_with_mod_rights(user.id, &db).await {
                 for bamboo_mod in mods {
                     mailing::authentication::send_forgot_password_mail(
@@ -105,9 +338,55 @@ pub async fn forgot_password(
         }
     }
 
-    no_content!()
+    Ok(no_content!())
+}
+
+#[utoipa::path(
+    tag = "Authentication",
+    request_body = ResetPassword,
+    responses((status = 204, description = "The password was changed"))
+)]
+#[post("/api/reset-password")]
+pub async fn reset_password(
+    body: JsonBody<ResetPassword>,
+    req: HttpRequest,
+    db: DbConnection,
+    env_service: EnvService,
+) -> BambooApiResponseResult {
+    let body = check_missing_fields!(body, "user")?;
+    let attempt_policy = LoginAttemptPolicy::new(&env_service);
+    let ip_key = format!("reset-password:ip:{}", get_client_ip(&req));
+    dbal::check_login_attempts(ip_key.as_str(), &db).await?;
+
+    match dbal::reset_password(
+        body.token.clone(),
+        body.password.clone(),
+        body.recovery_code.clone(),
+        &db,
+    )
+    .await
+    {
+        Ok(user) => {
+            let _ =
+                dbal::reset_login_attempts(format!("login:user:{}", user.id).as_str(), &db).await;
+
+            Ok(no_content!())
+        }
+        Err(err) => {
+            if err.error_type == BambooErrorCode::Unauthorized {
+                let _ = attempt_policy.record_failed_attempt(ip_key, &db).await;
+            }
+
+            Err(err)
+        }
+    }
 }
 
+#[utoipa::path(
+    tag = "Authentication",
+    responses((status = 204, description = "The token is no longer valid")),
+    security(("Panda" = []))
+)]
 #[delete("/api/login", wrap = "authenticate!()")]
 pub async fn logout(auth: Authentication, db: DbConnection) -> HttpResponse {
     let _ = dbal::delete_token(auth.token.clone(), &db).await;
@@ -7,21 +7,36 @@ use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
-use crate::path;
+use crate::{notifier, path};
 
-#[get("/api/final-fantasy/character", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "Character",
+    params(ListQuery),
+    responses((status = 200, description = "The characters of the current user", body = [Character], headers(("X-Total-Count" = u64, description = "The number of characters matching the filter")))),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
+#[get("/api/final-fantasy/character", wrap = "authenticate!(ReadCharacters)")]
 pub async fn get_characters(
+    query: Option<web::Query<ListQuery>>,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
-    dbal::get_characters(authentication.user.id, &db)
+    let query = check_invalid_query!(query, "character")?;
+
+    dbal::get_characters(authentication.user.id, &query, &db)
         .await
-        .map(|data| list!(data))
+        .map(|data| page!(data))
 }
 
+#[utoipa::path(
+    tag = "Character",
+    params(path::CharacterPathInfo),
+    responses((status = 200, description = "The character", body = Character)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
 #[get(
     "/api/final-fantasy/character/{character_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(ReadCharacters)"
 )]
 pub async fn get_character(
     path: Option<path::CharacterPath>,
@@ -35,26 +50,45 @@ pub async fn get_character(
         .map(|data| ok!(data))
 }
 
-#[post("/api/final-fantasy/character", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "Character",
+    request_body = Character,
+    responses((status = 201, description = "The character was created", body = Character)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
+#[post(
+    "/api/final-fantasy/character",
+    wrap = "authenticate!(WriteCharacters)"
+)]
 pub async fn create_character(
-    body: Option<web::Json<Character>>,
+    body: JsonBody<Character>,
+    notifier: notifier::Notifier,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResult<Character> {
     let body = check_missing_fields!(body, "character")?;
 
-    dbal::create_character(authentication.user.id, body.into_inner(), &db)
-        .await
-        .map(|data| created!(data))
+    let character = dbal::create_character(authentication.user.id, body.into_inner(), &db).await?;
+    notifier.notify_character_create(authentication.user.id, &character);
+
+    Ok(created!(character))
 }
 
+#[utoipa::path(
+    tag = "Character",
+    params(path::CharacterPathInfo),
+    request_body = Character,
+    responses((status = 204, description = "The character was updated")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[put(
     "/api/final-fantasy/character/{character_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(WriteCharacters)"
 )]
 pub async fn update_character(
-    body: Option<web::Json<Character>>,
+    body: JsonBody<Character>,
     path: Option<path::CharacterPath>,
+    notifier: notifier::Notifier,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
@@ -67,22 +101,34 @@ pub async fn update_character(
         body.into_inner(),
         &db,
     )
-    .await
-    .map(|_| no_content!())
+    .await?;
+    let character = dbal::get_character(path.character_id, authentication.user.id, &db).await?;
+    notifier.notify_character_update(authentication.user.id, &character);
+
+    Ok(no_content!())
 }
 
+#[utoipa::path(
+    tag = "Character",
+    params(path::CharacterPathInfo),
+    responses((status = 204, description = "The character was moved to the trash")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[delete(
     "/api/final-fantasy/character/{character_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(WriteCharacters)"
 )]
 pub async fn delete_character(
     path: Option<path::CharacterPath>,
+    notifier: notifier::Notifier,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "character")?;
 
-    dbal::delete_character(path.character_id, authentication.user.id, &db)
-        .await
-        .map(|_| no_content!())
+    let character = dbal::get_character(path.character_id, authentication.user.id, &db).await?;
+    dbal::delete_character(path.character_id, authentication.user.id, &db).await?;
+    notifier.notify_character_delete(authentication.user.id, &character);
+
+    Ok(no_content!())
 }
@@ -1,4 +1,4 @@
-use actix_web::{delete, get, post, put, web};
+use actix_web::{delete, get, post, put};
 
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::response::*;
@@ -8,11 +8,17 @@ use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
 use crate::middleware::extract_character::{character, CharacterData};
-use crate::path;
+use crate::{notifier, path};
 
+#[utoipa::path(
+    tag = "Character Housing",
+    params(path::CharacterPathInfo),
+    responses((status = 200, description = "The housings of the character", body = [CharacterHousing])),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
 #[get(
     "/api/final-fantasy/character/{character_id}/housing",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(ReadCharacters)",
     wrap = "character!()"
 )]
 pub async fn get_character_housings(
@@ -25,9 +31,15 @@ pub async fn get_character_housings(
         .map(|data| list!(data))
 }
 
+#[utoipa::path(
+    tag = "Character Housing",
+    params(path::CharacterPathInfo, path::CharacterHousingPathInfo),
+    responses((status = 200, description = "The housing", body = CharacterHousing)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
 #[get(
     "/api/final-fantasy/character/{character_id}/housing/{character_housing_id}",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(ReadCharacters)",
     wrap = "character!()"
 )]
 pub async fn get_character_housing(
@@ -48,33 +60,60 @@ pub async fn get_character_housing(
     .map(|character_housing| ok!(character_housing))
 }
 
+#[utoipa::path(
+    tag = "Character Housing",
+    params(path::CharacterPathInfo),
+    request_body = CharacterHousing,
+    responses((status = 200, description = "The housing was created", body = CharacterHousing)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[post(
     "/api/final-fantasy/character/{character_id}/housing",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(WriteCharacters)",
     wrap = "character!()"
 )]
 pub async fn create_character_housing(
-    body: Option<web::Json<CharacterHousing>>,
+    body: JsonBody<CharacterHousing>,
     character: CharacterData,
+    notifier: notifier::Notifier,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResult<CharacterHousing> {
     let body = check_missing_fields!(body, "character_housing")?;
 
-    dbal::create_character_housing(authentication.user.id, character.id, body.into_inner(), &db)
-        .await
-        .map(|data| ok!(data))
+    let housing = dbal::create_character_housing(
+        authentication.user.id,
+        character.id,
+        body.into_inner(),
+        &db,
+    )
+    .await?;
+    notifier.notify_housing_create(
+        authentication.user.grove_id,
+        authentication.user.id,
+        &housing,
+    );
+
+    Ok(ok!(housing))
 }
 
+#[utoipa::path(
+    tag = "Character Housing",
+    params(path::CharacterPathInfo, path::CharacterHousingPathInfo),
+    request_body = CharacterHousing,
+    responses((status = 204, description = "The housing was updated")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[put(
     "/api/final-fantasy/character/{character_id}/housing/{character_housing_id}",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(WriteCharacters)",
     wrap = "character!()"
 )]
 pub async fn update_character_housing(
-    body: Option<web::Json<CharacterHousing>>,
+    body: JsonBody<CharacterHousing>,
     path: Option<path::CharacterHousingPath>,
     character: CharacterData,
+    notifier: notifier::Notifier,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
@@ -88,29 +127,62 @@ pub async fn update_character_housing(
         body.into_inner(),
         &db,
     )
-    .await
-    .map(|_| no_content!())
+    .await?;
+    let housing = dbal::get_character_housing(
+        path.character_housing_id,
+        authentication.user.id,
+        character.id,
+        &db,
+    )
+    .await?;
+    notifier.notify_housing_update(
+        authentication.user.grove_id,
+        authentication.user.id,
+        &housing,
+    );
+
+    Ok(no_content!())
 }
 
+#[utoipa::path(
+    tag = "Character Housing",
+    params(path::CharacterPathInfo, path::CharacterHousingPathInfo),
+    responses((status = 204, description = "The housing was deleted")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[delete(
     "/api/final-fantasy/character/{character_id}/housing/{character_housing_id}",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(WriteCharacters)",
     wrap = "character!()"
 )]
 pub async fn delete_character_housing(
     path: Option<path::CharacterHousingPath>,
     character: CharacterData,
+    notifier: notifier::Notifier,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "character_housing")?;
 
+    let housing = dbal::get_character_housing(
+        path.character_housing_id,
+        authentication.user.id,
+        character.id,
+        &db,
+    )
+    .await?;
     dbal::delete_character_housing(
         path.character_housing_id,
         authentication.user.id,
         character.id,
         &db,
     )
-    .await
-    .map(|_| no_content!())
+    .await?;
+    notifier.notify_housing_delete(
+        authentication.user.grove_id,
+        authentication.user.id,
+        &housing,
+    );
+
+    Ok(no_content!())
 }
@@ -1,4 +1,4 @@
-use actix_web::{delete, get, post, put, web};
+use actix_web::{delete, get, post, put};
 
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::response::*;
@@ -10,9 +10,15 @@ use crate::middleware::authenticate_user::{authenticate, Authentication};
 use crate::middleware::extract_character::{character, CharacterData};
 use crate::path;
 
+#[utoipa::path(
+    tag = "Crafter",
+    params(path::CharacterPathInfo),
+    responses((status = 200, description = "The crafters of the character", body = [Crafter])),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
 #[get(
     "/api/final-fantasy/character/{character_id}/crafter",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(ReadCharacters)",
     wrap = "character!()"
 )]
 pub async fn get_crafters(
@@ -25,9 +31,15 @@ pub async fn get_crafters(
         .map(|data| list!(data))
 }
 
+#[utoipa::path(
+    tag = "Crafter",
+    params(path::CharacterPathInfo, path::CrafterPathInfo),
+    responses((status = 200, description = "The crafter", body = Crafter)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
 #[get(
     "/api/final-fantasy/character/{character_id}/crafter/{crafter_id}",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(ReadCharacters)",
     wrap = "character!()"
 )]
 pub async fn get_crafter(
@@ -43,13 +55,20 @@ pub async fn get_crafter(
         .map(|crafter| ok!(crafter))
 }
 
+#[utoipa::path(
+    tag = "Crafter",
+    params(path::CharacterPathInfo),
+    request_body = Crafter,
+    responses((status = 200, description = "The crafter was created", body = Crafter)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[post(
     "/api/final-fantasy/character/{character_id}/crafter",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(WriteCharacters)",
     wrap = "character!()"
 )]
 pub async fn create_crafter(
-    body: Option<web::Json<Crafter>>,
+    body: JsonBody<Crafter>,
     character: CharacterData,
     authentication: Authentication,
     db: DbConnection,
@@ -61,13 +80,20 @@ pub async fn create_crafter(
         .map(|data| ok!(data))
 }
 
+#[utoipa::path(
+    tag = "Crafter",
+    params(path::CharacterPathInfo, path::CrafterPathInfo),
+    request_body = Crafter,
+    responses((status = 204, description = "The crafter was updated")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[put(
     "/api/final-fantasy/character/{character_id}/crafter/{crafter_id}",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(WriteCharacters)",
     wrap = "character!()"
 )]
 pub async fn update_crafter(
-    body: Option<web::Json<Crafter>>,
+    body: JsonBody<Crafter>,
     path: Option<path::CrafterPath>,
     character: CharacterData,
     authentication: Authentication,
@@ -87,9 +113,15 @@ pub async fn update_crafter(
     .map(|_| no_content!())
 }
 
+#[utoipa::path(
+    tag = "Crafter",
+    params(path::CharacterPathInfo, path::CrafterPathInfo),
+    responses((status = 204, description = "The crafter was deleted")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[delete(
     "/api/final-fantasy/character/{character_id}/crafter/{crafter_id}",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(WriteCharacters)",
     wrap = "character!()"
 )]
 pub async fn delete_crafter(
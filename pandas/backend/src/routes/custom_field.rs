@@ -1,4 +1,4 @@
-use actix_web::{delete, get, post, put, web};
+use actix_web::{delete, get, post, put};
 
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::response::*;
@@ -9,7 +9,15 @@ use bamboo_common::core::error::*;
 use crate::middleware::authenticate_user::{authenticate, Authentication};
 use crate::path;
 
-#[get("/api/final-fantasy/character/custom-field", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "Custom Field",
+    responses((status = 200, description = "The custom fields of the current user", body = [CustomCharacterField])),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
+#[get(
+    "/api/final-fantasy/character/custom-field",
+    wrap = "authenticate!(ReadCharacters)"
+)]
 pub async fn get_custom_fields(
     authentication: Authentication,
     db: DbConnection,
@@ -19,9 +27,15 @@ pub async fn get_custom_fields(
         .map(|data| list!(data))
 }
 
+#[utoipa::path(
+    tag = "Custom Field",
+    params(path::CustomFieldPathInfo),
+    responses((status = 200, description = "The custom field", body = CustomCharacterField)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
 #[get(
     "/api/final-fantasy/character/custom-field/{field_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(ReadCharacters)"
 )]
 pub async fn get_custom_field(
     path: Option<path::CustomFieldPath>,
@@ -35,9 +49,18 @@ pub async fn get_custom_field(
         .map(|data| ok!(data))
 }
 
-#[post("/api/final-fantasy/character/custom-field", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "Custom Field",
+    request_body = CustomField,
+    responses((status = 201, description = "The custom field was created", body = CustomCharacterField)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
+#[post(
+    "/api/final-fantasy/character/custom-field",
+    wrap = "authenticate!(WriteCharacters)"
+)]
 pub async fn create_custom_field(
-    body: Option<web::Json<CustomField>>,
+    body: JsonBody<CustomField>,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResult<CustomCharacterField> {
@@ -48,13 +71,20 @@ pub async fn create_custom_field(
         .map(|data| created!(data))
 }
 
+#[utoipa::path(
+    tag = "Custom Field",
+    params(path::CustomFieldPathInfo),
+    request_body = CustomField,
+    responses((status = 204, description = "The custom field was updated")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[put(
     "/api/final-fantasy/character/custom-field/{field_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(WriteCharacters)"
 )]
 pub async fn update_custom_field(
     path: Option<path::CustomFieldPath>,
-    body: Option<web::Json<CustomField>>,
+    body: JsonBody<CustomField>,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
@@ -71,9 +101,15 @@ pub async fn update_custom_field(
     .map(|_| no_content!())
 }
 
+#[utoipa::path(
+    tag = "Custom Field",
+    params(path::CustomFieldPathInfo),
+    responses((status = 204, description = "The custom field was deleted")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[delete(
     "/api/final-fantasy/character/custom-field/{field_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(WriteCharacters)"
 )]
 pub async fn delete_custom_field(
     path: Option<path::CustomFieldPath>,
@@ -87,13 +123,20 @@ pub async fn delete_custom_field(
         .map(|_| no_content!())
 }
 
+#[utoipa::path(
+    tag = "Custom Field",
+    params(path::CustomFieldPathInfo),
+    request_body = String,
+    responses((status = 201, description = "The option was created", body = CustomCharacterFieldOption)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[post(
     "/api/final-fantasy/character/custom-field/{field_id}/option",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(WriteCharacters)"
 )]
 pub async fn create_custom_field_option(
     path: Option<path::CustomFieldPath>,
-    body: Option<web::Json<String>>,
+    body: JsonBody<String>,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResult<CustomCharacterFieldOption> {
@@ -110,9 +153,15 @@ pub async fn create_custom_field_option(
     .map(|data| created!(data))
 }
 
+#[utoipa::path(
+    tag = "Custom Field",
+    params(path::CustomFieldPathInfo),
+    responses((status = 200, description = "The options of the custom field", body = [CustomCharacterFieldOption])),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
 #[get(
     "/api/final-fantasy/character/custom-field/{field_id}/option",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(ReadCharacters)"
 )]
 pub async fn get_custom_field_options(
     path: Option<path::CustomFieldPath>,
@@ -126,13 +175,20 @@ pub async fn get_custom_field_options(
         .map(|data| list!(data))
 }
 
+#[utoipa::path(
+    tag = "Custom Field",
+    params(path::CustomFieldOptionPathInfo),
+    request_body = String,
+    responses((status = 204, description = "The option was updated")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[put(
     "/api/final-fantasy/character/custom-field/{field_id}/option/{option_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(WriteCharacters)"
 )]
 pub async fn update_custom_field_option(
     path: Option<path::CustomFieldOptionPath>,
-    body: Option<web::Json<String>>,
+    body: JsonBody<String>,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
@@ -150,9 +206,15 @@ pub async fn update_custom_field_option(
     .map(|_| no_content!())
 }
 
+#[utoipa::path(
+    tag = "Custom Field",
+    params(path::CustomFieldOptionPathInfo),
+    responses((status = 204, description = "The option was deleted")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[delete(
     "/api/final-fantasy/character/custom-field/{field_id}/option/{option_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(WriteCharacters)"
 )]
 pub async fn delete_custom_field_option(
     path: Option<path::CustomFieldOptionPath>,
@@ -165,9 +227,15 @@ pub async fn delete_custom_field_option(
         .map(|_| no_content!())
 }
 
+#[utoipa::path(
+    tag = "Custom Field",
+    params(path::CustomFieldPositionPathInfo),
+    responses((status = 204, description = "The custom field was moved")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[put(
     "/api/final-fantasy/character/custom-field/{field_id}/{position}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(WriteCharacters)"
 )]
 pub async fn move_custom_field(
     path: Option<path::CustomFieldPositionPath>,
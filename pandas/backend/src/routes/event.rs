@@ -1,46 +1,113 @@
-use actix_web::{delete, get, post, put, web};
+use actix_web::web::Bytes;
+use actix_web::{delete, get, post, put, web, HttpResponse};
 use chrono::NaiveDate;
 use date_range::DateRange;
 use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
 
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::response::*;
 use bamboo_common::backend::services::DbConnection;
-use bamboo_common::core::entities::Event;
+use bamboo_common::core::entities::{Event, EventAttendance, EventImport, EventImportError};
 use bamboo_common::core::error::*;
 
-use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::ical;
+use crate::middleware::authenticate_user::{authenticate, check_grove_access, Authentication};
+use crate::middleware::check_permission::has_permission;
 use crate::middleware::identify_grove::{grove, CurrentGrove};
 use crate::notifier;
 use crate::path;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GetEventsQuery {
     pub start: NaiveDate,
     pub end: NaiveDate,
 }
 
-#[get("/api/bamboo-grove/event", wrap = "authenticate!()", wrap = "grove!()")]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EventAttendanceQuery {
+    pub date: Option<NaiveDate>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPartySlotBody {
+    pub fighter_id: i32,
+}
+
+#[utoipa::path(
+    tag = "Calendar",
+    params(GetEventsQuery, ListQuery),
+    responses((status = 200, description = "The events between start and end", body = [Event], headers(("X-Total-Count" = u64, description = "The number of events matching the filter")))),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_events"]))
+)]
+#[get(
+    "/api/bamboo-grove/event",
+    wrap = "authenticate!(ReadEvents)",
+    wrap = "grove!()"
+)]
 pub async fn get_events(
     query: Option<web::Query<GetEventsQuery>>,
+    list_query: Option<web::Query<ListQuery>>,
     current_grove: CurrentGrove,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
     let query = check_invalid_query!(query, "event")?;
+    let list_query = check_invalid_query!(list_query, "event")?;
 
     let range = DateRange::new(query.start, query.end).map_err(|_| {
         BambooError::invalid_data("event", "The start date cannot be after the end date")
     })?;
 
-    dbal::get_events(current_grove.grove.id, range, authentication.user.id, &db)
-        .await
-        .map(|data| list!(data))
+    dbal::get_events(
+        current_grove.grove.id,
+        range,
+        authentication.user.id,
+        &list_query,
+        &db,
+    )
+    .await
+    .map(|data| page!(data))
+}
+
+#[utoipa::path(
+    tag = "Calendar",
+    params(path::CalendarFeedPathInfo),
+    responses((status = 200, description = "The events of the grove as iCalendar", body = String, content_type = "text/calendar"))
+)]
+#[get("/api/bamboo-grove/event/feed/{feed_secret}.ics")]
+pub async fn get_calendar_feed(
+    path: Option<path::CalendarFeedPath>,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "event")?;
+
+    let user = dbal::get_user_by_calendar_feed_secret(path.feed_secret.clone(), &db).await?;
+    let grove = dbal::get_grove_by_user_id(user.id, &db).await?;
+    check_grove_access(&grove, &user)?;
+    let events = dbal::get_calendar_feed_events(grove.id, user.id, &db).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/calendar; charset=utf-8")
+        .body(ical::events_to_calendar(grove.name.as_str(), events)))
 }
 
-#[post("/api/bamboo-grove/event", wrap = "authenticate!()", wrap = "grove!()")]
+#[utoipa::path(
+    tag = "Calendar",
+    request_body = Event,
+    responses((status = 201, description = "The event was created", body = Event)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_events"]))
+)]
+#[post(
+    "/api/bamboo-grove/event",
+    wrap = "authenticate!(WriteEvents)",
+    wrap = "grove!()"
+)]
 pub async fn create_event(
-    body: Option<web::Json<Event>>,
+    body: JsonBody<Event>,
     notifier: notifier::Notifier,
     current_grove: CurrentGrove,
     authentication: Authentication,
@@ -60,14 +127,75 @@ pub async fn create_event(
     Ok(created!(data))
 }
 
+#[utoipa::path(
+    tag = "Calendar",
+    request_body(content = String, content_type = "text/calendar"),
+    responses((status = 201, description = "The imported events and the entries that could not be imported", body = EventImport)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_events"]))
+)]
+#[post(
+    "/api/bamboo-grove/event/import",
+    wrap = "authenticate!(WriteEvents)",
+    wrap = "grove!()",
+    wrap = "has_permission!(ManageEvents)"
+)]
+pub async fn import_events(
+    body: Bytes,
+    notifier: notifier::Notifier,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<EventImport> {
+    let data = std::str::from_utf8(&body).map_err(|_| {
+        BambooError::invalid_data("event", "The file is not a valid iCalendar file")
+    })?;
+    let entries = ical::calendar_to_events(data)?;
+    let titles = entries
+        .iter()
+        .map(|entry| entry.title.clone())
+        .collect::<Vec<Option<String>>>();
+
+    let results = dbal::import_events(
+        current_grove.grove.id,
+        authentication.user.id,
+        entries
+            .into_iter()
+            .map(|entry| (entry.uid, entry.recurrence_id, entry.event))
+            .collect(),
+        &db,
+    )
+    .await?;
+
+    let mut events = vec![];
+    let mut errors = vec![];
+    for (idx, (result, title)) in results.into_iter().zip(titles).enumerate() {
+        match result {
+            Ok(event) => events.push(event),
+            Err(err) => errors.push(EventImportError::new(idx + 1, title, err)),
+        }
+    }
+    for event in events.iter() {
+        notifier.notify_event_create(event.clone());
+    }
+
+    Ok(created!(EventImport { events, errors }))
+}
+
+#[utoipa::path(
+    tag = "Calendar",
+    params(path::EventPathInfo),
+    request_body = Event,
+    responses((status = 204, description = "The event was updated")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_events"]))
+)]
 #[put(
     "/api/bamboo-grove/event/{event_id}",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(WriteEvents)",
     wrap = "grove!()"
 )]
 pub async fn update_event(
     path: Option<path::EventPath>,
-    body: Option<web::Json<Event>>,
+    body: JsonBody<Event>,
     notifier: notifier::Notifier,
     current_grove: CurrentGrove,
     authentication: Authentication,
@@ -76,6 +204,20 @@ pub async fn update_event(
     let path = check_invalid_path!(path, "event")?;
     let body = check_missing_fields!(body, "event")?;
 
+    let event = dbal::get_event(
+        path.event_id,
+        current_grove.grove.id,
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+    if event.is_occurrence_override() && body.is_recurring() {
+        return Err(BambooError::validation(
+            "event",
+            "A single occurrence of a series cannot recur",
+        ));
+    }
+
     dbal::update_event(
         current_grove.grove.id,
         path.event_id,
@@ -96,9 +238,15 @@ pub async fn update_event(
     Ok(no_content!())
 }
 
+#[utoipa::path(
+    tag = "Calendar",
+    params(path::EventPathInfo),
+    responses((status = 204, description = "The event was moved to the trash")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_events"]))
+)]
 #[delete(
     "/api/bamboo-grove/event/{event_id}",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(WriteEvents)",
     wrap = "grove!()"
 )]
 pub async fn delete_event(
@@ -122,3 +270,321 @@ pub async fn delete_event(
 
     Ok(no_content!())
 }
+
+#[utoipa::path(
+    tag = "Calendar",
+    params(path::EventOccurrencePathInfo),
+    request_body = Event,
+    responses((status = 201, description = "The occurrence was split off the series", body = Event)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_events"]))
+)]
+#[post(
+    "/api/bamboo-grove/event/{event_id}/occurrence/{occurrence_date}",
+    wrap = "authenticate!(WriteEvents)",
+    wrap = "grove!()"
+)]
+pub async fn create_event_occurrence(
+    path: Option<path::EventOccurrencePath>,
+    body: JsonBody<Event>,
+    notifier: notifier::Notifier,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<Event> {
+    let path = check_invalid_path!(path, "event")?;
+    let body = check_missing_fields!(body, "event")?;
+
+    let data = dbal::create_event_occurrence(
+        current_grove.grove.id,
+        path.event_id,
+        path.occurrence_date,
+        body.into_inner(),
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+    let series = dbal::get_event(
+        path.event_id,
+        current_grove.grove.id,
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+    notifier.notify_event_update(series);
+    notifier.notify_event_create(data.clone());
+
+    Ok(created!(data))
+}
+
+#[utoipa::path(
+    tag = "Calendar",
+    params(path::EventOccurrencePathInfo),
+    responses((status = 204, description = "The occurrence was removed from the series")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_events"]))
+)]
+#[delete(
+    "/api/bamboo-grove/event/{event_id}/occurrence/{occurrence_date}",
+    wrap = "authenticate!(WriteEvents)",
+    wrap = "grove!()"
+)]
+pub async fn delete_event_occurrence(
+    path: Option<path::EventOccurrencePath>,
+    notifier: notifier::Notifier,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "event")?;
+
+    let series = dbal::delete_event_occurrence(
+        current_grove.grove.id,
+        path.event_id,
+        path.occurrence_date,
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+    notifier.notify_event_update(series);
+
+    Ok(no_content!())
+}
+
+/// Returns the occurrence of the event on the given date, so that listeners know which occurrence changed.
+fn event_occurrence(event: Event, date: NaiveDate) -> Event {
+    event
+        .occurrences(date, date)
+        .into_iter()
+        .find(|occurrence| occurrence.start_date == date)
+        .unwrap_or(event)
+}
+
+#[utoipa::path(
+    tag = "Calendar",
+    params(path::EventPathInfo, EventAttendanceQuery),
+    responses((status = 200, description = "The attendances of the event", body = [EventAttendance])),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_events"]))
+)]
+#[get(
+    "/api/bamboo-grove/event/{event_id}/attendance",
+    wrap = "authenticate!(ReadEvents)",
+    wrap = "grove!()"
+)]
+pub async fn get_event_attendances(
+    path: Option<path::EventPath>,
+    query: Option<web::Query<EventAttendanceQuery>>,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "event")?;
+    let query = check_invalid_query!(query, "event")?;
+
+    let event = dbal::get_event(
+        path.event_id,
+        current_grove.grove.id,
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+
+    dbal::get_event_attendances(event.id, query.date.unwrap_or(event.start_date), &db)
+        .await
+        .map(|data| list!(data))
+}
+
+#[utoipa::path(
+    tag = "Calendar",
+    params(path::EventPathInfo, EventAttendanceQuery),
+    request_body = EventAttendance,
+    responses((status = 204, description = "The attendance was saved")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_events"]))
+)]
+#[put(
+    "/api/bamboo-grove/event/{event_id}/attendance",
+    wrap = "authenticate!(WriteEvents)",
+    wrap = "grove!()"
+)]
+pub async fn set_event_attendance(
+    path: Option<path::EventPath>,
+    query: Option<web::Query<EventAttendanceQuery>>,
+    body: JsonBody<EventAttendance>,
+    notifier: notifier::Notifier,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "event")?;
+    let query = check_invalid_query!(query, "event")?;
+    let body = check_missing_fields!(body, "event")?;
+
+    let event = dbal::get_event(
+        path.event_id,
+        current_grove.grove.id,
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+    let date = query.date.unwrap_or(event.start_date);
+
+    dbal::set_event_attendance(&event, date, body.into_inner(), authentication.user.id, &db)
+        .await?;
+    notifier.notify_event_attendance(event_occurrence(event, date));
+
+    Ok(no_content!())
+}
+
+#[utoipa::path(
+    tag = "Calendar",
+    params(path::EventPathInfo, EventAttendanceQuery),
+    responses((status = 204, description = "The attendance was removed")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_events"]))
+)]
+#[delete(
+    "/api/bamboo-grove/event/{event_id}/attendance",
+    wrap = "authenticate!(WriteEvents)",
+    wrap = "grove!()"
+)]
+pub async fn delete_event_attendance(
+    path: Option<path::EventPath>,
+    query: Option<web::Query<EventAttendanceQuery>>,
+    notifier: notifier::Notifier,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "event")?;
+    let query = check_invalid_query!(query, "event")?;
+
+    let event = dbal::get_event(
+        path.event_id,
+        current_grove.grove.id,
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+    let date = query.date.unwrap_or(event.start_date);
+
+    dbal::delete_event_attendance(event.id, date, authentication.user.id, &db).await?;
+    notifier.notify_event_attendance(event_occurrence(event, date));
+
+    Ok(no_content!())
+}
+
+#[utoipa::path(
+    tag = "Calendar",
+    params(path::EventPathInfo, EventAttendanceQuery),
+    responses((status = 200, description = "The party slots of the event", body = [EventPartySlot])),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_events"]))
+)]
+#[get(
+    "/api/bamboo-grove/event/{event_id}/party",
+    wrap = "authenticate!(ReadEvents)",
+    wrap = "grove!()"
+)]
+pub async fn get_event_party_slots(
+    path: Option<path::EventPath>,
+    query: Option<web::Query<EventAttendanceQuery>>,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "event")?;
+    let query = check_invalid_query!(query, "event")?;
+
+    let event = dbal::get_event(
+        path.event_id,
+        current_grove.grove.id,
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+
+    dbal::get_event_party_slots(event.id, query.date.unwrap_or(event.start_date), &db)
+        .await
+        .map(|data| list!(data))
+}
+
+#[utoipa::path(
+    tag = "Calendar",
+    params(path::EventPartySlotPathInfo, EventAttendanceQuery),
+    request_body = EventPartySlotBody,
+    responses((status = 204, description = "The fighter was put into the slot")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_events"]))
+)]
+#[put(
+    "/api/bamboo-grove/event/{event_id}/party/{slot}",
+    wrap = "authenticate!(WriteEvents)",
+    wrap = "grove!()"
+)]
+pub async fn set_event_party_slot(
+    path: Option<path::EventPartySlotPath>,
+    query: Option<web::Query<EventAttendanceQuery>>,
+    body: JsonBody<EventPartySlotBody>,
+    notifier: notifier::Notifier,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "event")?;
+    let query = check_invalid_query!(query, "event")?;
+    let body = check_missing_fields!(body, "event")?;
+
+    let event = dbal::get_event(
+        path.event_id,
+        current_grove.grove.id,
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+    let date = query.date.unwrap_or(event.start_date);
+
+    dbal::set_event_party_slot(
+        &event,
+        date,
+        path.slot,
+        body.fighter_id,
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+    notifier.notify_event_party(event_occurrence(event, date));
+
+    Ok(no_content!())
+}
+
+#[utoipa::path(
+    tag = "Calendar",
+    params(path::EventPartySlotPathInfo, EventAttendanceQuery),
+    responses((status = 204, description = "The slot was cleared")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_events"]))
+)]
+#[delete(
+    "/api/bamboo-grove/event/{event_id}/party/{slot}",
+    wrap = "authenticate!(WriteEvents)",
+    wrap = "grove!()"
+)]
+pub async fn delete_event_party_slot(
+    path: Option<path::EventPartySlotPath>,
+    query: Option<web::Query<EventAttendanceQuery>>,
+    notifier: notifier::Notifier,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "event")?;
+    let query = check_invalid_query!(query, "event")?;
+
+    let event = dbal::get_event(
+        path.event_id,
+        current_grove.grove.id,
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+    let date = query.date.unwrap_or(event.start_date);
+
+    dbal::delete_event_party_slot(event.id, date, path.slot, authentication.user.id, &db).await?;
+    notifier.notify_event_party(event_occurrence(event, date));
+
+    Ok(no_content!())
+}
@@ -1,4 +1,4 @@
-use actix_web::{delete, get, post, put, web};
+use actix_web::{delete, get, post, put};
 
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::response::*;
@@ -10,9 +10,15 @@ use crate::middleware::authenticate_user::{authenticate, Authentication};
 use crate::middleware::extract_character::{character, CharacterData};
 use crate::path;
 
+#[utoipa::path(
+    tag = "Fighter",
+    params(path::CharacterPathInfo),
+    responses((status = 200, description = "The fighters of the character", body = [Fighter])),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
 #[get(
     "/api/final-fantasy/character/{character_id}/fighter",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(ReadCharacters)",
     wrap = "character!()"
 )]
 pub async fn get_fighters(
@@ -25,9 +31,15 @@ pub async fn get_fighters(
         .map(|data| list!(data))
 }
 
+#[utoipa::path(
+    tag = "Fighter",
+    params(path::CharacterPathInfo, path::FighterPathInfo),
+    responses((status = 200, description = "The fighter", body = Fighter)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
 #[get(
     "/api/final-fantasy/character/{character_id}/fighter/{fighter_id}",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(ReadCharacters)",
     wrap = "character!()"
 )]
 pub async fn get_fighter(
@@ -43,13 +55,20 @@ pub async fn get_fighter(
         .map(|fighter| ok!(fighter))
 }
 
+#[utoipa::path(
+    tag = "Fighter",
+    params(path::CharacterPathInfo),
+    request_body = Fighter,
+    responses((status = 200, description = "The fighter was created", body = Fighter)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[post(
     "/api/final-fantasy/character/{character_id}/fighter",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(WriteCharacters)",
     wrap = "character!()"
 )]
 pub async fn create_fighter(
-    body: Option<web::Json<Fighter>>,
+    body: JsonBody<Fighter>,
     character: CharacterData,
     authentication: Authentication,
     db: DbConnection,
@@ -61,13 +80,20 @@ pub async fn create_fighter(
         .map(|data| ok!(data))
 }
 
+#[utoipa::path(
+    tag = "Fighter",
+    params(path::CharacterPathInfo, path::FighterPathInfo),
+    request_body = Fighter,
+    responses((status = 204, description = "The fighter was updated")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[put(
     "/api/final-fantasy/character/{character_id}/fighter/{fighter_id}",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(WriteCharacters)",
     wrap = "character!()"
 )]
 pub async fn update_fighter(
-    body: Option<web::Json<Fighter>>,
+    body: JsonBody<Fighter>,
     path: Option<path::FighterPath>,
     character: CharacterData,
     authentication: Authentication,
@@ -87,9 +113,15 @@ pub async fn update_fighter(
     .map(|_| no_content!())
 }
 
+#[utoipa::path(
+    tag = "Fighter",
+    params(path::CharacterPathInfo, path::FighterPathInfo),
+    responses((status = 204, description = "The fighter was deleted")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[delete(
     "/api/final-fantasy/character/{character_id}/fighter/{fighter_id}",
-    wrap = "authenticate!()",
+    wrap = "authenticate!(WriteCharacters)",
     wrap = "character!()"
 )]
 pub async fn delete_fighter(
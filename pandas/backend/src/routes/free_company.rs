@@ -7,21 +7,39 @@ use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
-use crate::path;
+use crate::{notifier, path};
 
-#[get("/api/final-fantasy/free-company", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "Free Company",
+    params(ListQuery),
+    responses((status = 200, description = "The free companies of the current user", body = [FreeCompany], headers(("X-Total-Count" = u64, description = "The number of free companies matching the filter")))),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
+#[get(
+    "/api/final-fantasy/free-company",
+    wrap = "authenticate!(ReadCharacters)"
+)]
 pub async fn get_free_companies(
+    query: Option<web::Query<ListQuery>>,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
-    dbal::get_free_companies(authentication.user.id, &db)
+    let query = check_invalid_query!(query, "free_company")?;
+
+    dbal::get_free_companies(authentication.user.id, &query, &db)
         .await
-        .map(|data| list!(data))
+        .map(|data| page!(data))
 }
 
+#[utoipa::path(
+    tag = "Free Company",
+    params(path::FreeCompanyPathInfo),
+    responses((status = 200, description = "The free company", body = FreeCompany)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_characters"]))
+)]
 #[get(
     "/api/final-fantasy/free-company/{free_company_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(ReadCharacters)"
 )]
 pub async fn get_free_company(
     path: Option<path::FreeCompanyPath>,
@@ -35,26 +53,46 @@ pub async fn get_free_company(
         .map(|data| ok!(data.unwrap()))
 }
 
-#[post("/api/final-fantasy/free-company", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "Free Company",
+    request_body = FreeCompany,
+    responses((status = 201, description = "The free company was created", body = FreeCompany)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
+#[post(
+    "/api/final-fantasy/free-company",
+    wrap = "authenticate!(WriteCharacters)"
+)]
 pub async fn create_free_company(
-    body: Option<web::Json<FreeCompany>>,
+    body: JsonBody<FreeCompany>,
+    notifier: notifier::Notifier,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResult<FreeCompany> {
     let body = check_missing_fields!(body, "free_company")?;
 
-    dbal::create_free_company(authentication.user.id, body.name.clone(), &db)
-        .await
-        .map(|data| created!(data))
+    let free_company =
+        dbal::create_free_company(authentication.user.id, body.name.clone(), &db).await?;
+    notifier.notify_free_company_create(authentication.user.id, &free_company);
+
+    Ok(created!(free_company))
 }
 
+#[utoipa::path(
+    tag = "Free Company",
+    params(path::FreeCompanyPathInfo),
+    request_body = FreeCompany,
+    responses((status = 204, description = "The free company was updated")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[put(
     "/api/final-fantasy/free-company/{free_company_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(WriteCharacters)"
 )]
 pub async fn update_free_company(
-    body: Option<web::Json<FreeCompany>>,
+    body: JsonBody<FreeCompany>,
     path: Option<path::FreeCompanyPath>,
+    notifier: notifier::Notifier,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
@@ -67,22 +105,40 @@ pub async fn update_free_company(
         body.name.clone(),
         &db,
     )
-    .await
-    .map(|_| no_content!())
+    .await?;
+    if let Some(free_company) =
+        dbal::get_free_company(Some(path.free_company_id), authentication.user.id, &db).await?
+    {
+        notifier.notify_free_company_update(authentication.user.id, &free_company);
+    }
+
+    Ok(no_content!())
 }
 
+#[utoipa::path(
+    tag = "Free Company",
+    params(path::FreeCompanyPathInfo),
+    responses((status = 204, description = "The free company was deleted")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
 #[delete(
     "/api/final-fantasy/free-company/{free_company_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(WriteCharacters)"
 )]
 pub async fn delete_free_company(
     path: Option<path::FreeCompanyPath>,
+    notifier: notifier::Notifier,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "free_company")?;
 
-    dbal::delete_free_company(path.free_company_id, authentication.user.id, &db)
-        .await
-        .map(|_| no_content!())
+    let free_company =
+        dbal::get_free_company(Some(path.free_company_id), authentication.user.id, &db).await?;
+    dbal::delete_free_company(path.free_company_id, authentication.user.id, &db).await?;
+    if let Some(free_company) = free_company {
+        notifier.notify_free_company_delete(authentication.user.id, &free_company);
+    }
+
+    Ok(no_content!())
 }
@@ -1,61 +1,283 @@
-use actix_web::{delete, get, put};
+use actix_web::{delete, get, post, put};
 
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::response::*;
 use bamboo_common::backend::services::DbConnection;
-use bamboo_common::core::entities::Grove;
+use bamboo_common::core::entities::{
+    AuditAction, CreatedGroveWebhook, Grove, GroveWebhook, GroveWebhookDelivery,
+};
 use bamboo_common::core::error::*;
 
-use crate::middleware::authenticate_user::authenticate;
-use crate::middleware::check_mod::is_mod;
+use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_permission::has_permission;
 use crate::middleware::identify_grove::{grove, CurrentGrove};
+use crate::routes::audit_log::record_action;
+use crate::{notifier, path};
 
+#[utoipa::path(
+    tag = "Grove",
+    responses((status = 200, description = "The grove of the current user", body = Grove)),
+    security(("Panda" = []))
+)]
 #[get("/api/grove", wrap = "authenticate!()", wrap = "grove!()")]
 pub async fn get_grove(current_grove: CurrentGrove) -> BambooApiResult<Grove> {
     Ok(ok!(current_grove.grove.clone()))
 }
 
+#[utoipa::path(
+    tag = "Grove",
+    responses((status = 204, description = "The grove was disabled")),
+    security(("Panda" = []))
+)]
 #[delete(
     "/api/grove/enabled",
     wrap = "authenticate!()",
     wrap = "grove!()",
-    wrap = "is_mod!()"
+    wrap = "has_permission!(ManageGrove)"
 )]
 pub async fn disable_grove(
+    notifier: notifier::Notifier,
     current_grove: CurrentGrove,
+    authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
-    dbal::disable_grove(current_grove.grove.id, &db)
-        .await
-        .map(|_| no_content!())
+    dbal::disable_grove(current_grove.grove.id, &db).await?;
+    let grove = dbal::get_grove_by_id(current_grove.grove.id, &db).await?;
+    record_action(
+        &authentication,
+        Some(grove.id),
+        AuditAction::GroveDisabled,
+        None,
+        grove.name.clone(),
+        &db,
+    )
+    .await;
+    notifier.notify_grove_disabled(grove);
+
+    Ok(no_content!())
 }
 
+#[utoipa::path(
+    tag = "Grove",
+    responses((status = 204, description = "The grove was enabled")),
+    security(("Panda" = []))
+)]
 #[put(
     "/api/grove/enabled",
     wrap = "authenticate!()",
     wrap = "grove!()",
-    wrap = "is_mod!()"
+    wrap = "has_permission!(ManageGrove)"
 )]
 pub async fn enable_grove(
+    notifier: notifier::Notifier,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::enable_grove(current_grove.grove.id, &db).await?;
+    let grove = dbal::get_grove_by_id(current_grove.grove.id, &db).await?;
+    record_action(
+        &authentication,
+        Some(grove.id),
+        AuditAction::GroveEnabled,
+        None,
+        grove.name.clone(),
+        &db,
+    )
+    .await;
+    notifier.notify_grove_enabled(grove);
+
+    Ok(no_content!())
+}
+
+#[utoipa::path(
+    tag = "Grove",
+    responses((status = 204, description = "Mods get notified about forgotten passwords")),
+    security(("Panda" = []))
+)]
+#[put(
+    "/api/grove/forgot-password-mods",
+    wrap = "authenticate!()",
+    wrap = "grove!()",
+    wrap = "has_permission!(ManageGrove)"
+)]
+pub async fn enable_forgot_password_mods(
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::set_grove_notify_mods_on_forgot_password(current_grove.grove.id, true, &db)
+        .await
+        .map(|_| no_content!())
+}
+
+#[utoipa::path(
+    tag = "Grove",
+    responses((status = 204, description = "Mods no longer get notified about forgotten passwords")),
+    security(("Panda" = []))
+)]
+#[delete(
+    "/api/grove/forgot-password-mods",
+    wrap = "authenticate!()",
+    wrap = "grove!()",
+    wrap = "has_permission!(ManageGrove)"
+)]
+pub async fn disable_forgot_password_mods(
     current_grove: CurrentGrove,
     db: DbConnection,
 ) -> BambooApiResponseResult {
-    dbal::enable_grove(current_grove.grove.id, &db)
+    dbal::set_grove_notify_mods_on_forgot_password(current_grove.grove.id, false, &db)
         .await
         .map(|_| no_content!())
 }
 
+#[utoipa::path(
+    tag = "Grove",
+    responses((status = 204, description = "The grove and all its users were deleted")),
+    security(("Panda" = []))
+)]
 #[delete(
     "/api/grove",
     wrap = "authenticate!()",
     wrap = "grove!()",
-    wrap = "is_mod!()"
+    wrap = "has_permission!(ManageGrove)"
 )]
 pub async fn delete_grove(
+    notifier: notifier::Notifier,
     current_grove: CurrentGrove,
+    authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
-    dbal::delete_grove(current_grove.grove.id, &db)
+    dbal::delete_grove(current_grove.grove.id, &db).await?;
+    // The grove is gone, the entry stays visible in the cross grove audit log
+    record_action(
+        &authentication,
+        None,
+        AuditAction::GroveDeleted,
+        None,
+        current_grove.grove.name.clone(),
+        &db,
+    )
+    .await;
+    notifier.notify_grove_delete(current_grove.grove.clone());
+
+    Ok(no_content!())
+}
+
+#[utoipa::path(
+    tag = "Grove",
+    responses((status = 200, description = "The webhooks of the grove", body = [GroveWebhook])),
+    security(("Panda" = []))
+)]
+#[get(
+    "/api/grove/webhook",
+    wrap = "authenticate!()",
+    wrap = "grove!()",
+    wrap = "has_permission!(ManageGrove)"
+)]
+pub async fn get_webhooks(
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::get_grove_webhooks(current_grove.grove.id, &db)
+        .await
+        .map(|data| list!(data))
+}
+
+#[utoipa::path(
+    tag = "Grove",
+    request_body = GroveWebhook,
+    responses((status = 201, description = "The webhook was created, the secret is only returned once", body = CreatedGroveWebhook)),
+    security(("Panda" = []))
+)]
+#[post(
+    "/api/grove/webhook",
+    wrap = "authenticate!()",
+    wrap = "grove!()",
+    wrap = "has_permission!(ManageGrove)"
+)]
+pub async fn create_webhook(
+    body: JsonBody<GroveWebhook>,
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResult<CreatedGroveWebhook> {
+    let body = check_missing_fields!(body, "webhook")?;
+
+    dbal::create_grove_webhook(current_grove.grove.id, body.into_inner(), &db)
+        .await
+        .map(|data| created!(CreatedGroveWebhook::new(data)))
+}
+
+#[utoipa::path(
+    tag = "Grove",
+    params(path::WebhookPathInfo),
+    responses((status = 204, description = "The webhook was deleted")),
+    security(("Panda" = []))
+)]
+#[delete(
+    "/api/grove/webhook/{webhook_id}",
+    wrap = "authenticate!()",
+    wrap = "grove!()",
+    wrap = "has_permission!(ManageGrove)"
+)]
+pub async fn delete_webhook(
+    path: Option<path::WebhookPath>,
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "webhook")?;
+
+    dbal::delete_grove_webhook(path.webhook_id, current_grove.grove.id, &db)
         .await
         .map(|_| no_content!())
 }
+
+#[utoipa::path(
+    tag = "Grove",
+    params(path::WebhookPathInfo),
+    responses((status = 200, description = "The latest deliveries of the webhook", body = [GroveWebhookDelivery])),
+    security(("Panda" = []))
+)]
+#[get(
+    "/api/grove/webhook/{webhook_id}/delivery",
+    wrap = "authenticate!()",
+    wrap = "grove!()",
+    wrap = "has_permission!(ManageGrove)"
+)]
+pub async fn get_webhook_deliveries(
+    path: Option<path::WebhookPath>,
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "webhook")?;
+
+    dbal::get_grove_webhook_deliveries(path.webhook_id, current_grove.grove.id, &db)
+        .await
+        .map(|data| list!(data))
+}
+
+#[utoipa::path(
+    tag = "Grove",
+    params(path::WebhookPathInfo),
+    responses((status = 201, description = "The delivery of the ping", body = GroveWebhookDelivery)),
+    security(("Panda" = []))
+)]
+#[post(
+    "/api/grove/webhook/{webhook_id}/ping",
+    wrap = "authenticate!()",
+    wrap = "grove!()",
+    wrap = "has_permission!(ManageGrove)"
+)]
+pub async fn ping_webhook(
+    path: Option<path::WebhookPath>,
+    notifier: notifier::Notifier,
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResult<GroveWebhookDelivery> {
+    let path = check_invalid_path!(path, "webhook")?;
+
+    let webhook = dbal::get_grove_webhook(path.webhook_id, current_grove.grove.id, &db).await?;
+    notifier
+        .ping_webhook(webhook)
+        .await
+        .map(|data| created!(data))
+}
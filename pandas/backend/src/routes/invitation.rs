@@ -0,0 +1,187 @@
+use actix_web::{delete, get, post, HttpRequest};
+
+use bamboo_common::backend::response::*;
+use bamboo_common::backend::services::{DbConnection, EnvService, InvitationPolicy};
+use bamboo_common::backend::{dbal, mailing};
+use bamboo_common::core::entities::*;
+use bamboo_common::core::error::*;
+
+use crate::login_attempt::LoginAttemptPolicy;
+use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_permission::has_permission;
+use crate::middleware::identify_grove::{grove, CurrentGrove};
+use crate::routes::audit_log::record_action;
+use crate::routes::authentication::get_client_ip;
+use crate::{notifier, path};
+
+#[utoipa::path(
+    tag = "Invitation",
+    responses((status = 200, description = "The open invitations of the grove", body = [Invitation])),
+    security(("Panda" = []))
+)]
+#[get(
+    "/api/invitation",
+    wrap = "authenticate!()",
+    wrap = "has_permission!(ManageUsers)",
+    wrap = "grove!()"
+)]
+pub async fn get_invitations(
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::get_invitations(current_grove.grove.id, &db)
+        .await
+        .map(|data| list!(data))
+}
+
+#[utoipa::path(
+    tag = "Invitation",
+    request_body = Invitation,
+    responses((status = 201, description = "The invitation was created and mailed", body = Invitation)),
+    security(("Panda" = []))
+)]
+#[post(
+    "/api/invitation",
+    wrap = "authenticate!()",
+    wrap = "has_permission!(ManageUsers)",
+    wrap = "grove!()"
+)]
+pub async fn create_invitation(
+    body: JsonBody<Invitation>,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    env_service: EnvService,
+    db: DbConnection,
+) -> BambooApiResult<Invitation> {
+    let body = check_missing_fields!(body, "invitation")?;
+    if body.is_mod && !authentication.user.is_mod {
+        return Err(BambooError::insufficient_rights(
+            "invitation",
+            "Only mods can invite other mods",
+        ));
+    }
+
+    let policy = InvitationPolicy::new(&env_service);
+    let (invitation, token) = dbal::create_invitation(
+        current_grove.grove.id,
+        body.into_inner(),
+        authentication.user.display_name.clone(),
+        policy.lifetime,
+        &db,
+    )
+    .await?;
+    mailing::user::send_invitation(&invitation, &token, &policy, env_service).await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::InvitationCreated,
+        None,
+        invitation.email.clone(),
+        &db,
+    )
+    .await;
+
+    Ok(created!(invitation))
+}
+
+#[utoipa::path(
+    tag = "Invitation",
+    params(path::InvitationPathInfo),
+    responses((status = 204, description = "The invitation was renewed and mailed again")),
+    security(("Panda" = []))
+)]
+#[post(
+    "/api/invitation/{invitation_id}/resend",
+    wrap = "authenticate!()",
+    wrap = "has_permission!(ManageUsers)",
+    wrap = "grove!()"
+)]
+pub async fn resend_invitation(
+    path: Option<path::InvitationPath>,
+    current_grove: CurrentGrove,
+    env_service: EnvService,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "invitation")?;
+    let policy = InvitationPolicy::new(&env_service);
+    let (invitation, token) = dbal::renew_invitation(
+        current_grove.grove.id,
+        path.invitation_id,
+        policy.lifetime,
+        &db,
+    )
+    .await?;
+    mailing::user::send_invitation(&invitation, &token, &policy, env_service).await?;
+
+    Ok(no_content!())
+}
+
+#[utoipa::path(
+    tag = "Invitation",
+    params(path::InvitationPathInfo),
+    responses((status = 204, description = "The invitation was revoked")),
+    security(("Panda" = []))
+)]
+#[delete(
+    "/api/invitation/{invitation_id}",
+    wrap = "authenticate!()",
+    wrap = "has_permission!(ManageUsers)",
+    wrap = "grove!()"
+)]
+pub async fn delete_invitation(
+    path: Option<path::InvitationPath>,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "invitation")?;
+
+    let invitation = dbal::get_invitation(current_grove.grove.id, path.invitation_id, &db).await?;
+    dbal::delete_invitation(current_grove.grove.id, path.invitation_id, &db).await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::InvitationRevoked,
+        None,
+        invitation.email,
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
+}
+
+#[utoipa::path(
+    tag = "Invitation",
+    request_body = AcceptInvitation,
+    responses((status = 204, description = "The user was created"))
+)]
+#[post("/api/invitation/accept")]
+pub async fn accept_invitation(
+    body: JsonBody<AcceptInvitation>,
+    req: HttpRequest,
+    notifier: notifier::Notifier,
+    env_service: EnvService,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let body = check_missing_fields!(body, "invitation")?;
+    let attempt_policy = LoginAttemptPolicy::new(&env_service);
+    let ip_key = format!("accept-invitation:ip:{}", get_client_ip(&req));
+    dbal::check_login_attempts(ip_key.as_str(), &db).await?;
+
+    let body = body.into_inner();
+    match dbal::accept_invitation(body.token, body.display_name, body.password, &db).await {
+        Ok(user) => {
+            notifier.notify_user_create(user.grove_id, &user);
+
+            Ok(no_content!())
+        }
+        Err(err) => {
+            if err.error_type == BambooErrorCode::Unauthorized {
+                let _ = attempt_policy.record_failed_attempt(ip_key, &db).await;
+            }
+
+            Err(err)
+        }
+    }
+}
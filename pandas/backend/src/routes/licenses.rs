@@ -1,5 +1,9 @@
 use actix_web::{get, HttpResponse};
 
+#[utoipa::path(
+    tag = "Licenses",
+    responses((status = 200, description = "The licenses of all dependencies", body = [DependencyDetails]))
+)]
 #[get("/api/licenses")]
 pub async fn get_licenses() -> HttpResponse {
     HttpResponse::Ok()
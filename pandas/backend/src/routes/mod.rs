@@ -4,6 +4,7 @@ use bamboo_common::backend::services::{EnvService, EnvironmentService};
 
 use crate::middleware::authenticate_user::authenticate;
 
+mod audit_log;
 mod authentication;
 mod character;
 mod character_housing;
@@ -13,10 +14,14 @@ mod event;
 mod fighter;
 mod free_company;
 mod grove;
+mod invitation;
 mod licenses;
 mod my;
+mod openapi;
+mod role;
 mod sse;
 mod support;
+mod trash;
 mod user;
 
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
@@ -27,7 +32,10 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
 
     cfg.app_data(environment_service)
         .service(authentication::login)
+        .service(authentication::create_passkey_login_challenge)
+        .service(authentication::login_with_passkey)
         .service(authentication::forgot_password)
+        .service(authentication::reset_password)
         .service(authentication::logout)
         .route(
             "/api/login",
@@ -35,28 +43,62 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
                 .to(HttpResponse::NoContent)
                 .wrap(authenticate!()),
         )
+        .service(invitation::get_invitations)
+        .service(invitation::create_invitation)
+        .service(invitation::accept_invitation)
+        .service(invitation::resend_invitation)
+        .service(invitation::delete_invitation)
+        .service(role::get_roles)
+        .service(role::create_role)
+        .service(role::update_role)
+        .service(role::delete_role)
         .service(user::get_users)
-        .service(user::create_user)
         .service(user::get_user)
         .service(user::delete_user)
         .service(user::update_user_profile)
         .service(user::add_mod_user)
         .service(user::remove_mod_user)
+        .service(user::change_user_role)
         .service(user::change_password)
         .service(user::disable_totp)
         .service(user::get_profile_picture)
+        .service(event::get_calendar_feed)
+        .service(event::import_events)
         .service(event::get_events)
         .service(event::create_event)
         .service(event::update_event)
         .service(event::delete_event)
+        .service(event::create_event_occurrence)
+        .service(event::delete_event_occurrence)
+        .service(event::get_event_attendances)
+        .service(event::set_event_attendance)
+        .service(event::delete_event_attendance)
+        .service(event::get_event_party_slots)
+        .service(event::set_event_party_slot)
+        .service(event::delete_event_party_slot)
         .service(my::get_profile)
         .service(my::update_profile)
         .service(my::change_password)
         .service(my::enable_totp)
         .service(my::disable_totp)
         .service(my::validate_totp)
+        .service(my::regenerate_recovery_codes)
         .service(my::leave)
         .service(my::upload_profile_picture)
+        .service(my::enable_event_reminders)
+        .service(my::disable_event_reminders)
+        .service(my::get_calendar_feed)
+        .service(my::rotate_calendar_feed)
+        .service(my::get_sessions)
+        .service(my::delete_other_sessions)
+        .service(my::delete_session)
+        .service(my::create_passkey_challenge)
+        .service(my::get_passkeys)
+        .service(my::create_passkey)
+        .service(my::delete_passkey)
+        .service(my::get_personal_access_tokens)
+        .service(my::create_personal_access_token)
+        .service(my::delete_personal_access_token)
         .service(custom_field::get_custom_fields)
         .service(custom_field::create_custom_field)
         .service(custom_field::get_custom_field)
@@ -95,11 +137,27 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
         .service(support::send_support_request)
         .service(support::report_glitchtip_error)
         .service(licenses::get_licenses)
+        .service(audit_log::get_audit_log)
         .service(grove::get_grove)
         .service(grove::disable_grove)
         .service(grove::enable_grove)
+        .service(grove::enable_forgot_password_mods)
+        .service(grove::disable_forgot_password_mods)
         .service(grove::delete_grove)
+        .service(grove::get_webhooks)
+        .service(grove::create_webhook)
+        .service(grove::delete_webhook)
+        .service(grove::get_webhook_deliveries)
+        .service(grove::ping_webhook)
+        .service(trash::get_trash)
+        .service(trash::restore_character)
+        .service(trash::restore_event)
+        .service(trash::restore_user)
+        .service(sse::sse_client)
         .service(sse::event_sse_client)
+        .service(openapi::get_openapi)
+        .service(openapi::get_api_docs)
+        .service(openapi::get_redoc)
         .service(
             actix_web_lab::web::spa()
                 .index_file(format!("{frontend_base_path}/dist/index.html"))
@@ -1,17 +1,27 @@
 use actix_web::web::Bytes;
-use actix_web::{delete, get, post, put, web};
+use actix_web::{delete, get, post, put};
 
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::response::*;
-use bamboo_common::backend::services::{DbConnection, MinioService};
+use bamboo_common::backend::services::{DbConnection, EnvService, MinioService};
 use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_permission::not_demo;
+use crate::passkey::RelyingParty;
+use crate::{notifier, path};
 
-#[put("/api/my/password", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "Profile",
+    operation_id = "change_my_password",
+    request_body = ChangeMyPassword,
+    responses((status = 204, description = "The password was changed")),
+    security(("Panda" = []))
+)]
+#[put("/api/my/password", wrap = "authenticate!()", wrap = "not_demo!()")]
 pub async fn change_password(
-    body: Option<web::Json<ChangeMyPassword>>,
+    body: JsonBody<ChangeMyPassword>,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
@@ -28,9 +38,17 @@ pub async fn change_password(
     .map_err(|err| err.into())
 }
 
-#[put("/api/my/profile", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "Profile",
+    operation_id = "update_my_profile",
+    request_body = UpdateProfile,
+    responses((status = 204, description = "The profile was updated")),
+    security(("Panda" = []))
+)]
+#[put("/api/my/profile", wrap = "authenticate!()", wrap = "not_demo!()")]
 pub async fn update_profile(
-    body: Option<web::Json<UpdateProfile>>,
+    body: JsonBody<UpdateProfile>,
+    notifier: notifier::Notifier,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
@@ -43,11 +61,19 @@ pub async fn update_profile(
         body.discord_name.clone(),
         &db,
     )
-    .await
-    .map(|_| no_content!())
+    .await?;
+    let user = dbal::get_user(authentication.user.grove_id, authentication.user.id, &db).await?;
+    notifier.notify_user_update(user.grove_id, &user);
+
+    Ok(no_content!())
 }
 
-#[post("/api/my/totp", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "Profile",
+    responses((status = 200, description = "The secret to set up the authenticator app", body = TotpQrCode)),
+    security(("Panda" = []))
+)]
+#[post("/api/my/totp", wrap = "authenticate!()", wrap = "not_demo!()")]
 pub async fn enable_totp(
     authentication: Authentication,
     db: DbConnection,
@@ -75,12 +101,22 @@ pub async fn enable_totp(
         })?
 }
 
-#[put("/api/my/totp/validate", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "Profile",
+    request_body = ValidateTotp,
+    responses((status = 200, description = "Two factor authentication is enabled, the recovery codes are only shown once", body = RecoveryCodes)),
+    security(("Panda" = []))
+)]
+#[put(
+    "/api/my/totp/validate",
+    wrap = "authenticate!()",
+    wrap = "not_demo!()"
+)]
 pub async fn validate_totp(
-    body: Option<web::Json<ValidateTotp>>,
+    body: JsonBody<ValidateTotp>,
     authentication: Authentication,
     db: DbConnection,
-) -> BambooApiResponseResult {
+) -> BambooApiResult<RecoveryCodes> {
     if authentication.user.totp_validated.unwrap_or(false) {
         Err(BambooError::invalid_data("user", "Already validated"))
     } else {
@@ -92,26 +128,63 @@ pub async fn validate_totp(
             body.code.clone(),
             &db,
         )
-        .await
-        .map(|data| {
-            if data {
-                Ok(no_content!())
-            } else {
-                Err(BambooError::insufficient_rights(
-                    "user",
-                    "The code is invalid",
-                ))
-            }
-        })?
+        .await?
+        .map(|codes| ok!(RecoveryCodes { codes }))
+        .ok_or(BambooError::insufficient_rights(
+            "user",
+            "The code is invalid",
+        ))
     }
 }
 
-#[get("/api/my/profile", wrap = "authenticate!()")]
-pub async fn get_profile(authentication: Authentication) -> BambooApiResult<WebUser> {
-    Ok(ok!(authentication.user.clone().into()))
+#[utoipa::path(
+    tag = "Profile",
+    request_body = RegenerateRecoveryCodes,
+    responses((status = 201, description = "The new recovery codes, the old ones stop working", body = RecoveryCodes)),
+    security(("Panda" = []))
+)]
+#[post(
+    "/api/my/totp/recovery-code",
+    wrap = "authenticate!()",
+    wrap = "not_demo!()"
+)]
+pub async fn regenerate_recovery_codes(
+    body: JsonBody<RegenerateRecoveryCodes>,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<RecoveryCodes> {
+    let body = check_missing_fields!(body, "user")?;
+
+    dbal::regenerate_my_recovery_codes(authentication.user.id, body.password.clone(), &db)
+        .await
+        .map(|codes| created!(RecoveryCodes { codes }))
 }
 
-#[delete("/api/my/totp", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "Profile",
+    operation_id = "get_my_profile",
+    responses((status = 200, description = "The profile of the current user", body = WebUser)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_users"]))
+)]
+#[get("/api/my/profile", wrap = "authenticate!(ReadUsers)")]
+pub async fn get_profile(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<WebUser> {
+    let role = dbal::get_user_role(&authentication.user, &db).await?;
+
+    Ok(ok!(
+        WebUser::from(authentication.user.clone()).with_role(role)
+    ))
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    operation_id = "disable_my_totp",
+    responses((status = 204, description = "Two factor authentication was disabled")),
+    security(("Panda" = []))
+)]
+#[delete("/api/my/totp", wrap = "authenticate!()", wrap = "not_demo!()")]
 pub async fn disable_totp(
     authentication: Authentication,
     db: DbConnection,
@@ -121,21 +194,309 @@ pub async fn disable_totp(
         .map(|_| no_content!())
 }
 
-#[delete("/api/my", wrap = "authenticate!()")]
-pub async fn leave(authentication: Authentication, db: DbConnection) -> BambooApiResponseResult {
-    dbal::delete_user(authentication.user.grove_id, authentication.user.id, &db)
-        .await
-        .map(|_| no_content!())
+#[utoipa::path(
+    tag = "Profile",
+    responses((status = 204, description = "The user left the grove and was moved to the trash")),
+    security(("Panda" = []))
+)]
+#[delete("/api/my", wrap = "authenticate!()", wrap = "not_demo!()")]
+pub async fn leave(
+    notifier: notifier::Notifier,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::delete_user(authentication.user.grove_id, authentication.user.id, &db).await?;
+    notifier.notify_user_delete(authentication.user.grove_id, &authentication.user);
+
+    Ok(no_content!())
 }
 
-#[put("/api/my/picture", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "Profile",
+    request_body(content = Binary, content_type = "image/*"),
+    responses((status = 204, description = "The profile picture was changed")),
+    security(("Panda" = []))
+)]
+#[put("/api/my/picture", wrap = "authenticate!()", wrap = "not_demo!()")]
 pub async fn upload_profile_picture(
+    notifier: notifier::Notifier,
     authentication: Authentication,
     minio: MinioService,
     body: Bytes,
 ) -> BambooApiResponseResult {
     minio
         .upload_profile_picture(authentication.user.id, &body)
+        .await?;
+    notifier.notify_user_update(authentication.user.grove_id, &authentication.user);
+
+    Ok(no_content!())
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    responses((status = 204, description = "Event reminders are sent by mail")),
+    security(("Panda" = []))
+)]
+#[put("/api/my/event-reminder", wrap = "authenticate!()")]
+pub async fn enable_event_reminders(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::set_my_event_reminders(authentication.user.id, true, &db)
+        .await
+        .map(|_| no_content!())
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    responses((status = 204, description = "Event reminders are no longer sent")),
+    security(("Panda" = []))
+)]
+#[delete("/api/my/event-reminder", wrap = "authenticate!()")]
+pub async fn disable_event_reminders(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::set_my_event_reminders(authentication.user.id, false, &db)
+        .await
+        .map(|_| no_content!())
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    operation_id = "get_my_calendar_feed",
+    responses((status = 200, description = "The secret of the calendar feed", body = CalendarFeed)),
+    security(("Panda" = []))
+)]
+#[get("/api/my/calendar-feed", wrap = "authenticate!()")]
+pub async fn get_calendar_feed(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<CalendarFeed> {
+    dbal::get_my_calendar_feed_secret(authentication.user.id, &db)
+        .await
+        .map(|secret| ok!(CalendarFeed { secret }))
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    responses((status = 201, description = "The new secret, the old feed address stops working", body = CalendarFeed)),
+    security(("Panda" = []))
+)]
+#[post("/api/my/calendar-feed", wrap = "authenticate!()")]
+pub async fn rotate_calendar_feed(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<CalendarFeed> {
+    dbal::rotate_my_calendar_feed_secret(authentication.user.id, &db)
+        .await
+        .map(|secret| created!(CalendarFeed { secret }))
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    responses((status = 200, description = "The active sessions of the current user", body = [Session])),
+    security(("Panda" = []))
+)]
+#[get("/api/my/session", wrap = "authenticate!()")]
+pub async fn get_sessions(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let current_token = dbal::hash_token(authentication.token.as_str());
+
+    dbal::get_sessions(authentication.user.id, &db)
+        .await
+        .map(|tokens| {
+            list!(tokens
+                .into_iter()
+                .map(|token| Session::from_token(token, current_token.as_str()))
+                .collect::<Vec<Session>>())
+        })
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    responses((status = 204, description = "All other sessions were logged out")),
+    security(("Panda" = []))
+)]
+#[delete("/api/my/session", wrap = "authenticate!()")]
+pub async fn delete_other_sessions(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::delete_other_sessions(authentication.user.id, authentication.token.clone(), &db)
+        .await
+        .map(|_| no_content!())
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    params(path::SessionPathInfo),
+    responses((status = 204, description = "The session was logged out")),
+    security(("Panda" = []))
+)]
+#[delete("/api/my/session/{session_id}", wrap = "authenticate!()")]
+pub async fn delete_session(
+    path: Option<path::SessionPath>,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "session")?;
+
+    dbal::delete_session(path.session_id, authentication.user.id, &db)
+        .await
+        .map(|_| no_content!())
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    responses((status = 201, description = "The options to create a passkey", body = PasskeyRegistrationOptions)),
+    security(("Panda" = []))
+)]
+#[post("/api/my/passkey/challenge", wrap = "authenticate!()")]
+pub async fn create_passkey_challenge(
+    authentication: Authentication,
+    db: DbConnection,
+    env_service: EnvService,
+) -> BambooApiResult<PasskeyRegistrationOptions> {
+    let relying_party = RelyingParty::new(&env_service);
+    let exclude_credentials = dbal::get_passkeys(authentication.user.id, &db)
+        .await?
+        .into_iter()
+        .map(|passkey| passkey.credential_id)
+        .collect();
+
+    dbal::create_passkey_challenge(Some(authentication.user.id), &db)
+        .await
+        .map(|challenge| {
+            created!(PasskeyRegistrationOptions {
+                challenge,
+                rp_id: relying_party.id,
+                rp_name: relying_party.name,
+                user_id: authentication.user.id.to_string(),
+                user_name: authentication.user.email.clone(),
+                user_display_name: authentication.user.display_name.clone(),
+                exclude_credentials,
+            })
+        })
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    responses((status = 200, description = "The passkeys of the current user", body = [Passkey])),
+    security(("Panda" = []))
+)]
+#[get("/api/my/passkey", wrap = "authenticate!()")]
+pub async fn get_passkeys(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::get_passkeys(authentication.user.id, &db)
+        .await
+        .map(|passkeys| list!(passkeys))
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    request_body = PasskeyRegistration,
+    responses((status = 201, description = "The passkey was added", body = Passkey)),
+    security(("Panda" = []))
+)]
+#[post("/api/my/passkey", wrap = "authenticate!()", wrap = "not_demo!()")]
+pub async fn create_passkey(
+    body: JsonBody<PasskeyRegistration>,
+    authentication: Authentication,
+    db: DbConnection,
+    env_service: EnvService,
+) -> BambooApiResult<Passkey> {
+    let body = check_missing_fields!(body, "passkey")?;
+    let relying_party = RelyingParty::new(&env_service);
+
+    dbal::create_passkey(
+        authentication.user.id,
+        body.into_inner(),
+        relying_party.id.as_str(),
+        relying_party.origin.as_str(),
+        &db,
+    )
+    .await
+    .map(|passkey| created!(passkey))
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    params(path::PasskeyPathInfo),
+    responses((status = 204, description = "The passkey was removed")),
+    security(("Panda" = []))
+)]
+#[delete("/api/my/passkey/{passkey_id}", wrap = "authenticate!()")]
+pub async fn delete_passkey(
+    path: Option<path::PasskeyPath>,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "passkey")?;
+
+    dbal::delete_passkey(path.passkey_id, authentication.user.id, &db)
+        .await
+        .map(|_| no_content!())
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    responses((status = 200, description = "The personal access tokens of the current user", body = [PersonalAccessToken])),
+    security(("Panda" = []))
+)]
+#[get("/api/my/token", wrap = "authenticate!()")]
+pub async fn get_personal_access_tokens(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::get_personal_access_tokens(authentication.user.id, &db)
+        .await
+        .map(|personal_access_tokens| list!(personal_access_tokens))
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    request_body = PersonalAccessToken,
+    responses((status = 201, description = "The token was created, the raw token is only returned once", body = CreatedPersonalAccessToken)),
+    security(("Panda" = []))
+)]
+#[post("/api/my/token", wrap = "authenticate!()", wrap = "not_demo!()")]
+pub async fn create_personal_access_token(
+    body: JsonBody<PersonalAccessToken>,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<CreatedPersonalAccessToken> {
+    let body = check_missing_fields!(body, "personal_access_token")?;
+
+    dbal::create_personal_access_token(authentication.user.id, body.into_inner(), &db)
+        .await
+        .map(|(personal_access_token, token)| {
+            created!(CreatedPersonalAccessToken::new(
+                personal_access_token,
+                token
+            ))
+        })
+}
+
+#[utoipa::path(
+    tag = "Profile",
+    params(path::PersonalAccessTokenPathInfo),
+    responses((status = 204, description = "The token was removed")),
+    security(("Panda" = []))
+)]
+#[delete("/api/my/token/{token_id}", wrap = "authenticate!()")]
+pub async fn delete_personal_access_token(
+    path: Option<path::PersonalAccessTokenPath>,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "personal_access_token")?;
+
+    dbal::delete_personal_access_token(path.token_id, authentication.user.id, &db)
         .await
         .map(|_| no_content!())
 }
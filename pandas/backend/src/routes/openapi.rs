@@ -0,0 +1,355 @@
+use actix_web::{get, HttpResponse};
+use utoipa::openapi::path::ParameterIn;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::{
+    ContentBuilder, KnownFormat, ObjectBuilder, Ref, RefOr, ResponseBuilder, Schema, SchemaFormat,
+    SchemaType,
+};
+use utoipa::{Modify, OpenApi, ToSchema};
+
+use bamboo_common::core::entities::*;
+use bamboo_common::core::error::{
+    BambooError, BambooErrorCode, BambooFieldError, BambooFieldErrorCode,
+};
+
+use super::event::EventPartySlotBody;
+
+/// Raw bytes which are sent as is, like profile pictures
+pub struct Binary;
+
+impl<'s> ToSchema<'s> for Binary {
+    fn schema() -> (&'s str, RefOr<Schema>) {
+        (
+            "Binary",
+            ObjectBuilder::new()
+                .schema_type(SchemaType::String)
+                .format(Some(SchemaFormat::KnownFormat(KnownFormat::Binary)))
+                .into(),
+        )
+    }
+}
+
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "Panda",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "Authorization",
+                "The token of the login, sent as Panda <token>",
+            ))),
+        );
+        components.add_security_scheme(
+            "PersonalAccessToken",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some(
+                        "A personal access token, it is only accepted on routes covered by its scopes",
+                    ))
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// Every route answers errors with a BambooError, instead of repeating them on each handler they are added here
+struct ErrorResponses;
+
+impl ErrorResponses {
+    const ERRORS: [(&'static str, &'static str, &'static str); 5] = [
+        ("400", "BadRequest", "The request body or query is invalid"),
+        ("401", "Unauthorized", "The token is missing or invalid"),
+        (
+            "403",
+            "Forbidden",
+            "The user lacks the rights for this action",
+        ),
+        ("404", "NotFound", "The requested entity was not found"),
+        ("500", "InternalServerError", "An unexpected error occurred"),
+    ];
+}
+
+impl Modify for ErrorResponses {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        for (_, name, description) in Self::ERRORS {
+            components.responses.insert(
+                name.to_string(),
+                ResponseBuilder::new()
+                    .description(description)
+                    .content(
+                        "application/json",
+                        ContentBuilder::new()
+                            .schema(Ref::from_schema_name("BambooError"))
+                            .build(),
+                    )
+                    .into(),
+            );
+        }
+
+        for path_item in openapi.paths.paths.values_mut() {
+            for operation in path_item.operations.values_mut() {
+                let parameters = operation.parameters.clone().unwrap_or_default();
+                let has_input = operation.request_body.is_some()
+                    || parameters
+                        .iter()
+                        .any(|parameter| parameter.parameter_in == ParameterIn::Query);
+                let has_path = parameters
+                    .iter()
+                    .any(|parameter| parameter.parameter_in == ParameterIn::Path);
+                let is_secured = operation
+                    .security
+                    .as_ref()
+                    .is_some_and(|security| !security.is_empty());
+
+                for (status, name, _) in Self::ERRORS {
+                    let applies = match status {
+                        "400" => has_input,
+                        "401" | "403" => is_secured,
+                        "404" => has_path,
+                        _ => true,
+                    };
+                    if applies {
+                        operation
+                            .responses
+                            .responses
+                            .entry(status.to_string())
+                            .or_insert_with(|| Ref::from_response_name(name).into());
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Bambushain",
+        description = "Bambushain Server API",
+        contact(
+            name = "Panda Helferlein",
+            url = "https://pandas.bambushain.app",
+            email = "panda.helferlein@bambushain.app"
+        )
+    ),
+    servers(
+        (url = "https://pandas.bambushain.app", description = "Production"),
+        (url = "https://testsystem.bambushain.app", description = "Test")
+    ),
+    paths(
+        super::authentication::login,
+        super::authentication::create_passkey_login_challenge,
+        super::authentication::login_with_passkey,
+        super::authentication::forgot_password,
+        super::authentication::reset_password,
+        super::authentication::logout,
+        super::invitation::get_invitations,
+        super::invitation::create_invitation,
+        super::invitation::accept_invitation,
+        super::invitation::resend_invitation,
+        super::invitation::delete_invitation,
+        super::role::get_roles,
+        super::role::create_role,
+        super::role::update_role,
+        super::role::delete_role,
+        super::user::get_users,
+        super::user::get_user,
+        super::user::delete_user,
+        super::user::update_user_profile,
+        super::user::add_mod_user,
+        super::user::remove_mod_user,
+        super::user::change_user_role,
+        super::user::change_password,
+        super::user::disable_totp,
+        super::user::get_profile_picture,
+        super::event::get_calendar_feed,
+        super::event::import_events,
+        super::event::get_events,
+        super::event::create_event,
+        super::event::update_event,
+        super::event::delete_event,
+        super::event::create_event_occurrence,
+        super::event::delete_event_occurrence,
+        super::event::get_event_attendances,
+        super::event::set_event_attendance,
+        super::event::delete_event_attendance,
+        super::event::get_event_party_slots,
+        super::event::set_event_party_slot,
+        super::event::delete_event_party_slot,
+        super::my::get_profile,
+        super::my::update_profile,
+        super::my::change_password,
+        super::my::enable_totp,
+        super::my::disable_totp,
+        super::my::validate_totp,
+        super::my::regenerate_recovery_codes,
+        super::my::leave,
+        super::my::upload_profile_picture,
+        super::my::enable_event_reminders,
+        super::my::disable_event_reminders,
+        super::my::get_calendar_feed,
+        super::my::rotate_calendar_feed,
+        super::my::get_sessions,
+        super::my::delete_other_sessions,
+        super::my::delete_session,
+        super::my::create_passkey_challenge,
+        super::my::get_passkeys,
+        super::my::create_passkey,
+        super::my::delete_passkey,
+        super::my::get_personal_access_tokens,
+        super::my::create_personal_access_token,
+        super::my::delete_personal_access_token,
+        super::custom_field::get_custom_fields,
+        super::custom_field::create_custom_field,
+        super::custom_field::get_custom_field,
+        super::custom_field::update_custom_field,
+        super::custom_field::move_custom_field,
+        super::custom_field::delete_custom_field,
+        super::custom_field::get_custom_field_options,
+        super::custom_field::create_custom_field_option,
+        super::custom_field::update_custom_field_option,
+        super::custom_field::delete_custom_field_option,
+        super::character::get_characters,
+        super::character::create_character,
+        super::character::get_character,
+        super::character::update_character,
+        super::character::delete_character,
+        super::free_company::get_free_companies,
+        super::free_company::create_free_company,
+        super::free_company::get_free_company,
+        super::free_company::update_free_company,
+        super::free_company::delete_free_company,
+        super::crafter::get_crafters,
+        super::crafter::create_crafter,
+        super::crafter::get_crafter,
+        super::crafter::update_crafter,
+        super::crafter::delete_crafter,
+        super::fighter::get_fighters,
+        super::fighter::create_fighter,
+        super::fighter::get_fighter,
+        super::fighter::update_fighter,
+        super::fighter::delete_fighter,
+        super::character_housing::get_character_housings,
+        super::character_housing::create_character_housing,
+        super::character_housing::get_character_housing,
+        super::character_housing::update_character_housing,
+        super::character_housing::delete_character_housing,
+        super::support::send_support_request,
+        super::support::report_glitchtip_error,
+        super::licenses::get_licenses,
+        super::audit_log::get_audit_log,
+        super::grove::get_grove,
+        super::grove::disable_grove,
+        super::grove::enable_grove,
+        super::grove::enable_forgot_password_mods,
+        super::grove::disable_forgot_password_mods,
+        super::grove::delete_grove,
+        super::grove::get_webhooks,
+        super::grove::create_webhook,
+        super::grove::delete_webhook,
+        super::grove::get_webhook_deliveries,
+        super::grove::ping_webhook,
+        super::trash::get_trash,
+        super::trash::restore_character,
+        super::trash::restore_event,
+        super::trash::restore_user,
+        super::sse::sse_client,
+        super::sse::event_sse_client,
+    ),
+    components(schemas(
+        AcceptInvitation, AssignRole, AttendanceStatus, AuditAction, AuditLog,
+        BambooError, BambooErrorCode, BambooFieldError, BambooFieldErrorCode, Binary, CalendarFeed,
+        ChangeMyPassword, Character, CharacterHousing, CharacterRace, Crafter, CrafterJob, CreatedGroveWebhook, CreatedPersonalAccessToken,
+        CustomCharacterField, CustomCharacterFieldOption, CustomCharacterFieldValue, CustomField,
+        DependencyDetails, Event, EventAttendance, EventImport, EventImportError, EventPartySlot,
+        EventPartySlotBody, Fighter, FighterJob, ForgotPassword, FreeCompany, GlitchTipErrorRequest,
+        Grove, GroveWebhook, GroveWebhookDelivery, HousingDistrict, HousingType, Invitation, Login,
+        LoginResult, PartyTemplate, Passkey, PasskeyLogin,
+        PasskeyLoginOptions, PasskeyRegistration, PasskeyRegistrationOptions, Permission,
+        PersonalAccessToken, RecoveryCodes, RecurrenceFrequency, RecurrenceRule, RecurrenceWeekday,
+        RegenerateRecoveryCodes, ResetPassword, Role, Session, SupportRequest, TokenScope,
+        TotpQrCode, TrashEntry, TrashEntryType, UpdateProfile, ValidateTotp, WebUser
+    )),
+    modifiers(&SecuritySchemes, &ErrorResponses)
+)]
+pub struct ApiDoc;
+
+#[get("/api/openapi.json")]
+pub async fn get_openapi() -> HttpResponse {
+    HttpResponse::Ok().json(ApiDoc::openapi())
+}
+
+#[get("/api/docs")]
+pub async fn get_api_docs() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(include_str!("openapi.html"))
+}
+
+#[get("/api/docs/redoc.standalone.js")]
+pub async fn get_redoc() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/javascript; charset=utf-8")
+        .body(include_str!(
+            "../../../../docs/assets/js/redoc.standalone.js"
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../docs/openapi.json");
+
+    /// Run with UPDATE_OPENAPI=1 to write the generated spec to docs/openapi.json
+    #[test]
+    fn checked_in_spec_matches_routes() {
+        let generated = ApiDoc::openapi()
+            .to_pretty_json()
+            .expect("the spec should serialize");
+        if std::env::var("UPDATE_OPENAPI").is_ok() {
+            std::fs::write(SPEC_PATH, format!("{generated}\n"))
+                .expect("the spec should be written");
+            return;
+        }
+
+        let checked_in = std::fs::read_to_string(SPEC_PATH).unwrap_or_default();
+        assert!(
+            checked_in.trim_end() == generated,
+            "docs/openapi.json is outdated, regenerate it with UPDATE_OPENAPI=1 cargo test -p bamboo-pandas-backend"
+        );
+    }
+
+    #[test]
+    fn all_references_resolve() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).expect("the spec should serialize");
+        let mut refs = vec![];
+        collect_refs(&spec, &mut refs);
+
+        for reference in refs {
+            let target = reference
+                .strip_prefix('#')
+                .and_then(|pointer| spec.pointer(pointer));
+            assert!(target.is_some(), "{reference} does not resolve");
+        }
+    }
+
+    fn collect_refs(value: &serde_json::Value, refs: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                if let Some(serde_json::Value::String(reference)) = map.get("$ref") {
+                    refs.push(reference.clone());
+                }
+                map.values().for_each(|value| collect_refs(value, refs));
+            }
+            serde_json::Value::Array(values) => {
+                values.iter().for_each(|value| collect_refs(value, refs))
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use actix_web::{delete, get, post, put};
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::response::*;
+use bamboo_common::backend::services::DbConnection;
+use bamboo_common::core::entities::*;
+use bamboo_common::core::error::*;
+
+use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_permission::has_permission;
+use crate::middleware::identify_grove::{grove, CurrentGrove};
+use crate::path;
+use crate::routes::audit_log::record_action;
+
+fn check_role_grantable(own_role: Option<&Role>, role: &Role) -> BambooErrorResult {
+    let grantable = role
+        .permissions()
+        .into_iter()
+        .all(|permission| own_role.is_some_and(|own_role| own_role.has_permission(permission)));
+    if grantable {
+        Ok(())
+    } else {
+        Err(BambooError::insufficient_rights(
+            "role",
+            "You cannot grant permissions you don't have",
+        ))
+    }
+}
+
+// Otherwise a panda could give their own role or another account every permission
+pub(crate) async fn check_permissions_grantable(
+    authentication: &Authentication,
+    role: &Role,
+    db: &DbConnection,
+) -> BambooErrorResult {
+    let own_role = dbal::get_user_role(&authentication.user, db).await?;
+
+    check_role_grantable(own_role.as_ref(), role)
+}
+
+#[utoipa::path(
+    tag = "Role",
+    responses((status = 200, description = "The roles of the grove", body = [Role])),
+    security(("Panda" = []))
+)]
+#[get("/api/role", wrap = "authenticate!()", wrap = "grove!()")]
+pub async fn get_roles(current_grove: CurrentGrove, db: DbConnection) -> BambooApiResponseResult {
+    dbal::get_roles(current_grove.grove.id, &db)
+        .await
+        .map(|data| list!(data))
+}
+
+#[utoipa::path(
+    tag = "Role",
+    request_body = Role,
+    responses((status = 201, description = "The role was created", body = Role)),
+    security(("Panda" = []))
+)]
+#[post(
+    "/api/role",
+    wrap = "authenticate!()",
+    wrap = "has_permission!(ManageUsers)",
+    wrap = "grove!()"
+)]
+pub async fn create_role(
+    body: JsonBody<Role>,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<Role> {
+    let body = check_missing_fields!(body, "role")?;
+    check_permissions_grantable(&authentication, &body, &db).await?;
+
+    let data = dbal::create_role(current_grove.grove.id, body.into_inner(), &db).await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::RoleCreated,
+        None,
+        data.name.clone(),
+        &db,
+    )
+    .await;
+
+    Ok(created!(data))
+}
+
+#[utoipa::path(
+    tag = "Role",
+    params(path::RolePathInfo),
+    request_body = Role,
+    responses((status = 204, description = "The role was updated")),
+    security(("Panda" = []))
+)]
+#[put(
+    "/api/role/{role_id}",
+    wrap = "authenticate!()",
+    wrap = "has_permission!(ManageUsers)",
+    wrap = "grove!()"
+)]
+pub async fn update_role(
+    path: Option<path::RolePath>,
+    body: JsonBody<Role>,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "role")?;
+    let body = check_missing_fields!(body, "role")?;
+    check_permissions_grantable(&authentication, &body, &db).await?;
+
+    let name = body.name.clone();
+    dbal::update_role(current_grove.grove.id, path.role_id, body.into_inner(), &db).await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::RoleUpdated,
+        None,
+        name,
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
+}
+
+#[utoipa::path(
+    tag = "Role",
+    params(path::RolePathInfo),
+    responses((status = 204, description = "The role was deleted")),
+    security(("Panda" = []))
+)]
+#[delete(
+    "/api/role/{role_id}",
+    wrap = "authenticate!()",
+    wrap = "has_permission!(ManageUsers)",
+    wrap = "grove!()"
+)]
+pub async fn delete_role(
+    path: Option<path::RolePath>,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "role")?;
+
+    let role = dbal::get_role(current_grove.grove.id, path.role_id, &db).await?;
+    dbal::delete_role(current_grove.grove.id, path.role_id, &db).await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::RoleDeleted,
+        None,
+        role.name,
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manage_users_cannot_grant_manage_grove() {
+        let own_role = Role::new("Helper".to_string(), &[Permission::ManageUsers]);
+        let role = Role::new(
+            "Admin".to_string(),
+            &[Permission::ManageUsers, Permission::ManageGrove],
+        );
+
+        assert!(check_role_grantable(Some(&own_role), &role).is_err());
+    }
+
+    #[test]
+    fn test_permissions_held_can_be_granted() {
+        let own_role = Role::new(
+            "Helper".to_string(),
+            &[Permission::ManageUsers, Permission::ViewAuditLog],
+        );
+        let role = Role::new("Auditor".to_string(), &[Permission::ViewAuditLog]);
+
+        assert!(check_role_grantable(Some(&own_role), &role).is_ok());
+        assert!(check_role_grantable(None, &role).is_err());
+        assert!(check_role_grantable(None, &Role::new("Member".to_string(), &[])).is_ok());
+    }
+}
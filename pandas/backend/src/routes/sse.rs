@@ -1,13 +1,72 @@
-use actix_web::{get, Responder};
+use std::str::FromStr;
+
+use actix_web::{get, web, Responder};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use bamboo_common::core::entities::SseTopic;
+use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
 use crate::notifier::Notifier;
 
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SseQuery {
+    pub topics: Option<String>,
+}
+
+/// Parses the comma separated topics, no topics at all means every topic
+fn parse_topics(topics: Option<String>) -> BambooResult<Vec<SseTopic>> {
+    let Some(topics) = topics else {
+        return Ok(SseTopic::all());
+    };
+
+    topics
+        .split(',')
+        .map(|topic| {
+            SseTopic::from_str(topic.trim()).map_err(|_| {
+                BambooError::invalid_data("sse", format!("The topic {} is unknown", topic.trim()))
+            })
+        })
+        .collect()
+}
+
+#[utoipa::path(
+    tag = "Server Sent Events",
+    params(SseQuery),
+    responses(
+        (status = 200, description = "A stream of change notifications for the topics", body = String, content_type = "text/event-stream"),
+        (status = 400, description = "One of the topics is unknown", body = BambooError),
+    ),
+    security(("Panda" = []))
+)]
+#[get("/sse", wrap = "authenticate!()")]
+pub async fn sse_client(
+    query: Option<web::Query<SseQuery>>,
+    notifier: Notifier,
+    authentication: Authentication,
+) -> BambooResult<impl Responder> {
+    let topics = parse_topics(query.and_then(|query| query.topics.clone()))?;
+
+    log::debug!("Register new sse client for topics {topics:?}");
+    Ok(notifier
+        .new_client(authentication.user.clone(), topics)
+        .await)
+}
+
+#[utoipa::path(
+    tag = "Server Sent Events",
+    responses((status = 200, description = "A stream of event change notifications named created, updated and deleted", body = String, content_type = "text/event-stream")),
+    security(("Panda" = []))
+)]
 #[get("/sse/event", wrap = "authenticate!()")]
 pub async fn event_sse_client(
     notifier: Notifier,
     authentication: Authentication,
 ) -> impl Responder {
     log::debug!("Register new event sse client");
-    notifier.new_client(authentication.user.clone()).await
+    notifier
+        .new_legacy_event_client(authentication.user.clone())
+        .await
 }
@@ -13,11 +13,17 @@ use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
 
+#[utoipa::path(
+    tag = "Support",
+    request_body = SupportRequest,
+    responses((status = 204, description = "The support request was sent")),
+    security(("Panda" = []))
+)]
 #[post("/api/support", wrap = "authenticate!()")]
 pub async fn send_support_request(
     authentication: Authentication,
     env_service: EnvService,
-    body: Option<web::Json<SupportRequest>>,
+    body: JsonBody<SupportRequest>,
 ) -> BambooApiResponseResult {
     let body = check_missing_fields!(body, "support")?;
 
@@ -30,6 +36,12 @@ pub async fn send_support_request(
     .map(|_| no_content!())
 }
 
+#[utoipa::path(
+    tag = "Support",
+    request_body = GlitchTipErrorRequest,
+    responses((status = 204, description = "The error was reported")),
+    security(("Panda" = []))
+)]
 #[post("/api/glitchtip", wrap = "authenticate!()")]
 pub async fn report_glitchtip_error(
     body: Option<web::Json<GlitchTipErrorRequest>>,
@@ -0,0 +1,137 @@
+use actix_web::{get, put, web};
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::response::*;
+use bamboo_common::backend::services::{DbConnection, EnvService};
+use bamboo_common::core::entities::*;
+use bamboo_common::core::error::*;
+
+use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_permission::has_permission;
+use crate::middleware::identify_grove::{grove, CurrentGrove};
+use crate::routes::audit_log::record_action;
+use crate::trash::TrashPolicy;
+use crate::{notifier, path};
+
+#[utoipa::path(
+    tag = "Trash",
+    params(ListQuery),
+    responses((status = 200, description = "The deleted characters and events of the current user, pandas with the permission to manage users also see deleted users. The newest entries come first", body = [TrashEntry], headers(("X-Total-Count" = u64, description = "The number of trash entries matching the filter")))),
+    security(("Panda" = []))
+)]
+#[get("/api/trash", wrap = "authenticate!()", wrap = "grove!()")]
+pub async fn get_trash(
+    query: Option<web::Query<ListQuery>>,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    env_service: EnvService,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let query = check_invalid_query!(query, "trash")?;
+    let policy = TrashPolicy::new(&env_service);
+    let include_users =
+        dbal::user_has_permission(&authentication.user, Permission::ManageUsers, &db).await?;
+
+    dbal::get_trash(
+        current_grove.grove.id,
+        authentication.user.id,
+        include_users,
+        policy.retention,
+        &query,
+        &db,
+    )
+    .await
+    .map(|data| page!(data))
+}
+
+#[utoipa::path(
+    tag = "Trash",
+    params(path::CharacterPathInfo),
+    responses((status = 200, description = "The character was restored", body = Character)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_characters"]))
+)]
+#[put(
+    "/api/trash/character/{character_id}",
+    wrap = "authenticate!(WriteCharacters)"
+)]
+pub async fn restore_character(
+    path: Option<path::CharacterPath>,
+    notifier: notifier::Notifier,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<Character> {
+    let path = check_invalid_path!(path, "character")?;
+
+    let character = dbal::restore_character(path.character_id, authentication.user.id, &db).await?;
+    notifier.notify_character_create(authentication.user.id, &character);
+
+    Ok(ok!(character))
+}
+
+#[utoipa::path(
+    tag = "Trash",
+    params(path::EventPathInfo),
+    responses((status = 200, description = "The event was restored", body = Event)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["write_events"]))
+)]
+#[put(
+    "/api/trash/event/{event_id}",
+    wrap = "authenticate!(WriteEvents)",
+    wrap = "grove!()"
+)]
+pub async fn restore_event(
+    path: Option<path::EventPath>,
+    notifier: notifier::Notifier,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<Event> {
+    let path = check_invalid_path!(path, "event")?;
+
+    let event = dbal::restore_event(
+        current_grove.grove.id,
+        path.event_id,
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+    notifier.notify_event_create(event.clone());
+
+    Ok(ok!(event))
+}
+
+#[utoipa::path(
+    tag = "Trash",
+    params(path::UserPathInfo),
+    responses((status = 200, description = "The user was restored", body = WebUser)),
+    security(("Panda" = []))
+)]
+#[put(
+    "/api/trash/user/{user_id}",
+    wrap = "authenticate!()",
+    wrap = "has_permission!(ManageUsers)",
+    wrap = "grove!()"
+)]
+pub async fn restore_user(
+    path: Option<path::UserPath>,
+    notifier: notifier::Notifier,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<WebUser> {
+    let path = check_invalid_path!(path, "user")?;
+
+    let user = dbal::restore_user(current_grove.grove.id, path.user_id, &db).await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::UserRestored,
+        Some(&user),
+        user.display_name.clone(),
+        &db,
+    )
+    .await;
+    notifier.notify_user_create(current_grove.grove.id, &user);
+
+    Ok(ok!(user.into()))
+}
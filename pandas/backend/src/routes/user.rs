@@ -1,4 +1,4 @@
-use actix_web::{delete, get, post, put, web, Responder};
+use actix_web::{delete, get, put, web, Responder};
 
 use bamboo_common::backend::response::*;
 use bamboo_common::backend::services::{DbConnection, EnvService, MinioService};
@@ -8,77 +8,93 @@ use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
-use crate::middleware::check_mod::is_mod;
+use crate::middleware::check_permission::has_permission;
 use crate::middleware::identify_grove::{grove, CurrentGrove};
-use crate::path;
+use crate::routes::audit_log::record_action;
+use crate::routes::role::check_permissions_grantable;
+use crate::{notifier, path};
 
-#[get("/api/user", wrap = "authenticate!()", wrap = "grove!()")]
-pub async fn get_users(current_grove: CurrentGrove, db: DbConnection) -> BambooApiResponseResult {
-    dbal::get_users(current_grove.grove.id, &db)
-        .await
-        .map(|users| {
-            list!(users
-                .into_iter()
-                .map(WebUser::from)
-                .collect::<Vec<WebUser>>())
-        })
+// Pandas with a role must not be able to hand out mod rights
+fn check_can_change_mods(authentication: &Authentication) -> BambooErrorResult {
+    if authentication.user.is_mod {
+        Ok(())
+    } else {
+        Err(BambooError::insufficient_rights(
+            "user",
+            "Only mods can change mod rights",
+        ))
+    }
 }
 
-#[get("/api/user/{user_id}", wrap = "authenticate!()", wrap = "grove!()")]
-pub async fn get_user(
-    path: Option<path::UserPath>,
+// Pandas with a role must not be able to take over a mod account
+fn check_target_is_manageable(authentication: &Authentication, target: &User) -> BambooErrorResult {
+    if target.is_mod && !authentication.user.is_mod {
+        Err(BambooError::insufficient_rights(
+            "user",
+            "Only mods can change other mods",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[utoipa::path(
+    tag = "User",
+    params(ListQuery),
+    responses((status = 200, description = "The users of the grove", body = [WebUser], headers(("X-Total-Count" = u64, description = "The number of users matching the filter")))),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_users"]))
+)]
+#[get("/api/user", wrap = "authenticate!(ReadUsers)", wrap = "grove!()")]
+pub async fn get_users(
+    query: Option<web::Query<ListQuery>>,
     current_grove: CurrentGrove,
     db: DbConnection,
-) -> BambooApiResult<WebUser> {
-    let path = check_invalid_path!(path, "user")?;
+) -> BambooApiResponseResult {
+    let query = check_invalid_query!(query, "user")?;
 
-    dbal::get_user(current_grove.grove.id, path.user_id, &db)
+    dbal::get_users(current_grove.grove.id, &query, &db)
         .await
-        .map(|data| ok!(data.into()))
+        .map(|users| page!(users.map(WebUser::from)))
 }
 
-#[post(
-    "/api/user",
-    wrap = "authenticate!()",
-    wrap = "is_mod!()",
+#[utoipa::path(
+    tag = "User",
+    params(path::UserPathInfo),
+    responses((status = 200, description = "The user", body = WebUser)),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_users"]))
+)]
+#[get(
+    "/api/user/{user_id}",
+    wrap = "authenticate!(ReadUsers)",
     wrap = "grove!()"
 )]
-pub async fn create_user(
-    body: Option<web::Json<User>>,
+pub async fn get_user(
+    path: Option<path::UserPath>,
     current_grove: CurrentGrove,
-    authentication: Authentication,
-    env_service: EnvService,
     db: DbConnection,
 ) -> BambooApiResult<WebUser> {
-    let body = check_missing_fields!(body, "user")?;
-    let new_password = get_random_password();
-    let user = dbal::create_user(
-        current_grove.grove.id,
-        body.into_inner(),
-        new_password.clone(),
-        &db,
-    )
-    .await?;
-    mailing::user::send_user_created(
-        user.display_name.clone(),
-        authentication.user.display_name.clone(),
-        user.email.clone(),
-        new_password,
-        env_service,
-    )
-    .await?;
+    let path = check_invalid_path!(path, "user")?;
 
-    Ok(created!(user.into()))
+    dbal::get_user(current_grove.grove.id, path.user_id, &db)
+        .await
+        .map(|data| ok!(data.into()))
 }
 
+#[utoipa::path(
+    tag = "User",
+    params(path::UserPathInfo),
+    responses((status = 204, description = "The user was moved to the trash")),
+    security(("Panda" = []))
+)]
 #[delete(
     "/api/user/{user_id}",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "has_permission!(ManageUsers)",
     wrap = "grove!()"
 )]
 pub async fn delete_user(
     path: Option<path::UserPath>,
+    notifier: notifier::Notifier,
     current_grove: CurrentGrove,
     authentication: Authentication,
     db: DbConnection,
@@ -91,24 +107,44 @@ pub async fn delete_user(
         ));
     }
 
-    dbal::delete_user(current_grove.grove.id, path.user_id, &db)
-        .await
-        .map(|_| no_content!())
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    check_target_is_manageable(&authentication, &user)?;
+    dbal::delete_user(current_grove.grove.id, path.user_id, &db).await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::UserDeleted,
+        Some(&user),
+        user.display_name.clone(),
+        &db,
+    )
+    .await;
+    notifier.notify_user_delete(current_grove.grove.id, &user);
+
+    Ok(no_content!())
 }
 
+#[utoipa::path(
+    tag = "User",
+    params(path::UserPathInfo),
+    responses((status = 204, description = "The user is a mod now")),
+    security(("Panda" = []))
+)]
 #[put(
     "/api/user/{user_id}/mod",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "has_permission!(ManageUsers)",
     wrap = "grove!()"
 )]
 pub async fn add_mod_user(
     path: Option<path::UserPath>,
+    notifier: notifier::Notifier,
     current_grove: CurrentGrove,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "user")?;
+    check_can_change_mods(&authentication)?;
     if path.user_id == authentication.user.id {
         return Err(BambooError::validation(
             "user",
@@ -116,24 +152,43 @@ pub async fn add_mod_user(
         ));
     }
 
-    dbal::change_mod_status(current_grove.grove.id, path.user_id, true, &db)
-        .await
-        .map(|_| no_content!())
+    dbal::change_mod_status(current_grove.grove.id, path.user_id, true, &db).await?;
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::ModRightsGranted,
+        Some(&user),
+        user.display_name.clone(),
+        &db,
+    )
+    .await;
+    notifier.notify_user_update(current_grove.grove.id, &user);
+
+    Ok(no_content!())
 }
 
+#[utoipa::path(
+    tag = "User",
+    params(path::UserPathInfo),
+    responses((status = 204, description = "The user is no mod anymore")),
+    security(("Panda" = []))
+)]
 #[delete(
     "/api/user/{user_id}/mod",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "has_permission!(ManageUsers)",
     wrap = "grove!()"
 )]
 pub async fn remove_mod_user(
     path: Option<path::UserPath>,
+    notifier: notifier::Notifier,
     current_grove: CurrentGrove,
     authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "user")?;
+    check_can_change_mods(&authentication)?;
     if path.user_id == authentication.user.id {
         return Err(BambooError::validation(
             "user",
@@ -141,15 +196,92 @@ pub async fn remove_mod_user(
         ));
     }
 
-    dbal::change_mod_status(current_grove.grove.id, path.user_id, false, &db)
-        .await
-        .map(|_| no_content!())
+    dbal::change_mod_status(current_grove.grove.id, path.user_id, false, &db).await?;
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::ModRightsRevoked,
+        Some(&user),
+        user.display_name.clone(),
+        &db,
+    )
+    .await;
+    notifier.notify_user_update(current_grove.grove.id, &user);
+
+    Ok(no_content!())
+}
+
+#[utoipa::path(
+    tag = "User",
+    params(path::UserPathInfo),
+    request_body = AssignRole,
+    responses((status = 204, description = "The role of the user was changed")),
+    security(("Panda" = []))
+)]
+#[put(
+    "/api/user/{user_id}/role",
+    wrap = "authenticate!()",
+    wrap = "has_permission!(ManageUsers)",
+    wrap = "grove!()"
+)]
+pub async fn change_user_role(
+    path: Option<path::UserPath>,
+    body: JsonBody<AssignRole>,
+    notifier: notifier::Notifier,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "user")?;
+    let body = check_missing_fields!(body, "user")?;
+    if path.user_id == authentication.user.id {
+        return Err(BambooError::validation(
+            "user",
+            "You cannot change your own role",
+        ));
+    }
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    check_target_is_manageable(&authentication, &user)?;
+    // Neither the new nor the old role may hold permissions the panda changing it doesn't have
+    if let Some(current_role) = dbal::get_user_role(&user, &db).await? {
+        check_permissions_grantable(&authentication, &current_role, &db).await?;
+    }
+    if let Some(role_id) = body.role_id {
+        let role = dbal::get_role(current_grove.grove.id, role_id, &db).await?;
+        if role.name == MOD_ROLE_NAME {
+            check_can_change_mods(&authentication)?;
+        }
+        check_permissions_grantable(&authentication, &role, &db).await?;
+    }
+
+    dbal::change_role(current_grove.grove.id, path.user_id, body.role_id, &db).await?;
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::RoleAssigned,
+        Some(&user),
+        user.display_name.clone(),
+        &db,
+    )
+    .await;
+    notifier.notify_user_update(current_grove.grove.id, &user);
+
+    Ok(no_content!())
 }
 
+#[utoipa::path(
+    tag = "User",
+    operation_id = "change_user_password",
+    params(path::UserPathInfo),
+    responses((status = 204, description = "A new password was generated and mailed to the user")),
+    security(("Panda" = []))
+)]
 #[put(
     "/api/user/{user_id}/password",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "has_permission!(ManageUsers)",
     wrap = "grove!()"
 )]
 pub async fn change_password(
@@ -168,6 +300,7 @@ pub async fn change_password(
     }
 
     let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    check_target_is_manageable(&authentication, &user)?;
     let new_password = get_random_password();
     dbal::change_password(
         current_grove.grove.id,
@@ -176,6 +309,15 @@ pub async fn change_password(
         &db,
     )
     .await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::PasswordReset,
+        Some(&user),
+        user.display_name.clone(),
+        &db,
+    )
+    .await;
 
     mailing::user::send_password_changed(
         user.display_name.clone(),
@@ -188,20 +330,31 @@ pub async fn change_password(
     .map(|_| no_content!())
 }
 
+#[utoipa::path(
+    tag = "User",
+    params(path::UserPathInfo),
+    request_body = UpdateProfile,
+    responses((status = 204, description = "The profile was updated")),
+    security(("Panda" = []))
+)]
 #[put(
     "/api/user/{user_id}/profile",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "has_permission!(ManageUsers)",
     wrap = "grove!()"
 )]
 pub async fn update_user_profile(
     path: Option<path::UserPath>,
-    body: Option<web::Json<UpdateProfile>>,
+    body: JsonBody<UpdateProfile>,
+    notifier: notifier::Notifier,
     current_grove: CurrentGrove,
+    authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "user")?;
     let body = check_missing_fields!(body, "user")?;
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    check_target_is_manageable(&authentication, &user)?;
 
     dbal::update_profile(
         current_grove.grove.id,
@@ -211,14 +364,33 @@ pub async fn update_user_profile(
         body.discord_name.clone(),
         &db,
     )
-    .await
-    .map(|_| no_content!())
+    .await?;
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::ProfileUpdated,
+        Some(&user),
+        user.display_name.clone(),
+        &db,
+    )
+    .await;
+    notifier.notify_user_update(current_grove.grove.id, &user);
+
+    Ok(no_content!())
 }
 
+#[utoipa::path(
+    tag = "User",
+    operation_id = "disable_user_totp",
+    params(path::UserPathInfo),
+    responses((status = 204, description = "Two factor authentication was disabled")),
+    security(("Panda" = []))
+)]
 #[delete(
     "/api/user/{user_id}/totp",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "has_permission!(ManageUsers)",
     wrap = "grove!()"
 )]
 pub async fn disable_totp(
@@ -235,12 +407,29 @@ pub async fn disable_totp(
         ));
     }
 
-    dbal::disable_totp(current_grove.grove.id, path.user_id, &db)
-        .await
-        .map(|_| no_content!())
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    check_target_is_manageable(&authentication, &user)?;
+    dbal::disable_totp(current_grove.grove.id, path.user_id, &db).await?;
+    record_action(
+        &authentication,
+        Some(current_grove.grove.id),
+        AuditAction::TotpDisabled,
+        Some(&user),
+        user.display_name.clone(),
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
 }
 
-#[get("/api/user/{user_id}/picture", wrap = "authenticate!()")]
+#[utoipa::path(
+    tag = "User",
+    params(path::UserPathInfo),
+    responses((status = 200, description = "The profile picture of the user", body = Binary, content_type = "image/*")),
+    security(("Panda" = []), ("PersonalAccessToken" = ["read_users"]))
+)]
+#[get("/api/user/{user_id}/picture", wrap = "authenticate!(ReadUsers)")]
 pub async fn get_profile_picture(
     path: Option<path::UserPath>,
     minio: MinioService,
@@ -0,0 +1,50 @@
+use chrono::Duration;
+use sea_orm::DatabaseConnection;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::services::{EnvService, EnvironmentService};
+
+pub(crate) struct SessionPolicy {
+    pub idle_timeout: Duration,
+    pub max_lifetime: Duration,
+}
+
+impl SessionPolicy {
+    pub fn new(env_service: &EnvService) -> Self {
+        let idle_timeout = env_service
+            .get_env("SESSION_IDLE_TIMEOUT_DAYS", "30")
+            .parse::<i64>()
+            .unwrap_or(30);
+        let max_lifetime = env_service
+            .get_env("SESSION_MAX_LIFETIME_DAYS", "90")
+            .parse::<i64>()
+            .unwrap_or(90);
+
+        Self {
+            idle_timeout: Duration::days(idle_timeout),
+            max_lifetime: Duration::days(max_lifetime),
+        }
+    }
+}
+
+pub(crate) fn start_session_cleanup(db: DatabaseConnection) {
+    let env_service = EnvService::new(EnvironmentService::new());
+    let policy = SessionPolicy::new(&env_service);
+
+    log::info!(
+        "Sessions expire after {} days without use and {} days at the latest",
+        policy.idle_timeout.num_days(),
+        policy.max_lifetime.num_days()
+    );
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(std::time::Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            match dbal::delete_expired_tokens(&db).await {
+                Ok(0) => {}
+                Ok(count) => log::info!("Removed {count} expired sessions"),
+                Err(err) => log::error!("Failed to remove expired sessions {err}"),
+            }
+        }
+    });
+}
@@ -3,53 +3,128 @@ use std::fmt::Display;
 use actix_web_lab::sse;
 use serde::{Deserialize, Serialize};
 
+use bamboo_common::core::entities::{
+    Character, CharacterHousing, Event, FreeCompany, Grove, SseTopic, User, WebUser,
+};
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
-pub enum EventAction {
+pub enum Action {
     Created,
     Updated,
     Deleted,
+    AttendanceChanged,
+    PartyChanged,
+    Enabled,
+    Disabled,
 }
 
-impl Display for EventAction {
+impl Display for Action {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         f.write_str(match self {
             Self::Created => "created",
             Self::Updated => "updated",
             Self::Deleted => "deleted",
+            Self::AttendanceChanged => "attendance",
+            Self::PartyChanged => "party",
+            Self::Enabled => "enabled",
+            Self::Disabled => "disabled",
         })
     }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct Event {
-    pub event: bamboo_common::core::entities::Event,
-    pub action: EventAction,
+pub enum Audience {
+    Grove(i32),
+    User(i32),
 }
 
-impl From<Event> for sse::Event {
-    fn from(value: Event) -> Self {
-        let mut data = sse::Data::new_json(value.event.clone()).unwrap();
-        data.set_event(value.action.to_string());
+impl Audience {
+    pub fn contains(&self, user: &User) -> bool {
+        match self {
+            Self::Grove(grove_id) => *grove_id == user.grove_id,
+            Self::User(user_id) => *user_id == user.id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Message {
+    pub topic: SseTopic,
+    pub action: Action,
+    pub audience: Audience,
+    pub data: serde_json::Value,
+}
+
+impl From<Message> for sse::Event {
+    fn from(value: Message) -> Self {
+        let mut data = sse::Data::new_json(value.data).unwrap();
+        data.set_event(format!("{}.{}", value.topic, value.action));
 
         sse::Event::Data(data)
     }
 }
 
-impl Event {
-    fn new(action: EventAction, event: bamboo_common::core::entities::Event) -> Self {
-        Self { event, action }
+impl Message {
+    /// Clients of the legacy /sse/event stream only know the bare action names created, updated and deleted
+    pub fn to_legacy_event(&self) -> Option<sse::Event> {
+        match self.action {
+            Action::Created | Action::Updated | Action::Deleted => {
+                let mut data = sse::Data::new_json(self.data.clone()).ok()?;
+                data.set_event(self.action.to_string());
+
+                Some(sse::Event::Data(data))
+            }
+            _ => None,
+        }
+    }
+
+    fn new(topic: SseTopic, action: Action, audience: Audience, data: impl Serialize) -> Self {
+        Self {
+            topic,
+            action,
+            audience,
+            data: serde_json::to_value(data).unwrap_or_default(),
+        }
+    }
+
+    pub fn event(action: Action, event: Event) -> Self {
+        let audience = if event.is_private {
+            Audience::User(event.user_id.unwrap_or_default())
+        } else {
+            Audience::Grove(event.grove_id)
+        };
+
+        Self::new(SseTopic::Event, action, audience, event)
+    }
+
+    pub fn user(action: Action, grove_id: i32, user: WebUser) -> Self {
+        Self::new(SseTopic::User, action, Audience::Grove(grove_id), user)
+    }
+
+    pub fn character(action: Action, user_id: i32, character: Character) -> Self {
+        Self::new(
+            SseTopic::Character,
+            action,
+            Audience::User(user_id),
+            character,
+        )
     }
 
-    pub fn created(event: bamboo_common::core::entities::Event) -> Self {
-        Self::new(EventAction::Created, event)
+    pub fn free_company(action: Action, user_id: i32, free_company: FreeCompany) -> Self {
+        Self::new(
+            SseTopic::FreeCompany,
+            action,
+            Audience::User(user_id),
+            free_company,
+        )
     }
 
-    pub fn updated(event: bamboo_common::core::entities::Event) -> Self {
-        Self::new(EventAction::Updated, event)
+    pub fn housing(action: Action, user_id: i32, housing: CharacterHousing) -> Self {
+        Self::new(SseTopic::Housing, action, Audience::User(user_id), housing)
     }
 
-    pub fn deleted(event: bamboo_common::core::entities::Event) -> Self {
-        Self::new(EventAction::Deleted, event)
+    pub fn grove(action: Action, grove: Grove) -> Self {
+        Self::new(SseTopic::Grove, action, Audience::Grove(grove.id), grove)
     }
 }
 
@@ -0,0 +1,54 @@
+use chrono::Duration;
+use sea_orm::DatabaseConnection;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::services::{EnvService, EnvironmentService};
+
+pub(crate) struct TrashPolicy {
+    pub retention: Duration,
+}
+
+impl TrashPolicy {
+    pub fn new(env_service: &EnvService) -> Self {
+        let configured = env_service.get_env("TRASH_RETENTION_DAYS", "30");
+        // A retention below one day would purge entries before anyone could restore them
+        let retention = match configured.parse::<i64>() {
+            Ok(days) if days >= 1 => days,
+            Ok(days) => {
+                log::warn!("TRASH_RETENTION_DAYS must be at least 1, got {days}, using 1 day");
+                1
+            }
+            Err(err) => {
+                log::error!(
+                    "TRASH_RETENTION_DAYS is not a number, got {configured}, using 30 days: {err}"
+                );
+                30
+            }
+        };
+
+        Self {
+            retention: Duration::days(retention),
+        }
+    }
+}
+
+pub(crate) fn start_trash_purge(db: DatabaseConnection) {
+    let env_service = EnvService::new(EnvironmentService::new());
+    let policy = TrashPolicy::new(&env_service);
+
+    log::info!(
+        "Deleted characters, events and users are purged after {} days",
+        policy.retention.num_days()
+    );
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(std::time::Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            match dbal::purge_trash(policy.retention, &db).await {
+                Ok(0) => {}
+                Ok(count) => log::info!("Purged {count} entries from the trash"),
+                Err(err) => log::error!("Failed to purge the trash {err}"),
+            }
+        }
+    });
+}
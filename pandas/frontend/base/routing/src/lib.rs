@@ -31,6 +31,10 @@ pub enum AppRoute {
     Licenses,
     #[at("/login")]
     Login,
+    #[at("/reset-password")]
+    ResetPassword,
+    #[at("/invitation")]
+    Invitation,
 }
 
 #[derive(Clone, Routable, PartialEq)]
@@ -51,8 +55,14 @@ pub enum SupportRoute {
 pub enum ModAreaRoute {
     #[at("/mod-area")]
     UserManagement,
+    #[at("/mod-area/invitations")]
+    Invitations,
+    #[at("/mod-area/roles")]
+    Roles,
     #[at("/mod-area/grove")]
     GroveManagement,
+    #[at("/mod-area/audit-log")]
+    AuditLog,
 }
 
 #[derive(Clone, Routable, PartialEq)]
@@ -61,6 +71,8 @@ pub enum BambooGroveRoute {
     Calendar,
     #[at("/bamboo/user")]
     User,
+    #[at("/bamboo/trash")]
+    Trash,
 }
 
 #[derive(Clone, Routable, PartialEq)]
@@ -1,4 +1,6 @@
 pub use bamboo_common::frontend::api;
 pub use bamboo_pandas_frontend_base_error as error;
 pub use bamboo_pandas_frontend_base_routing as routing;
+pub use bamboo_pandas_frontend_base_sse as sse;
 pub use bamboo_pandas_frontend_base_storage as storage;
+pub use bamboo_pandas_frontend_base_webauthn as webauthn;
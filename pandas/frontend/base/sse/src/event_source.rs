@@ -0,0 +1,60 @@
+use gloo_events::EventListener;
+use serde::de::DeserializeOwned;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{EventSource, MessageEvent};
+use yew::Callback;
+
+use bamboo_common::core::entities::SseTopic;
+
+pub struct TopicEventSource {
+    event_source: Option<EventSource>,
+    listeners: Vec<EventListener>,
+}
+
+impl TopicEventSource {
+    pub fn new(topics: &[SseTopic]) -> Self {
+        let topics = topics
+            .iter()
+            .map(|topic| topic.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+        let event_source = EventSource::new(format!("/sse?topics={topics}").as_str())
+            .map_err(|err| {
+                log::warn!("Failed to start event source, automatic updates disabled: {err:?}");
+            })
+            .ok();
+
+        Self {
+            event_source,
+            listeners: vec![],
+        }
+    }
+
+    pub fn register_handler<T: DeserializeOwned + 'static>(
+        &mut self,
+        topic: SseTopic,
+        action: impl Into<String>,
+        callback: Callback<T>,
+    ) {
+        if let Some(source) = self.event_source.clone() {
+            let event = format!("{topic}.{}", action.into());
+            self.listeners
+                .push(EventListener::new(&source, event, move |evt| {
+                    log::debug!("New message received");
+                    let evt = evt.dyn_ref::<MessageEvent>().unwrap_throw();
+                    if let Some(data) = evt.data().as_string() {
+                        log::debug!("The data received: {data:?}");
+                        if let Ok(data) = serde_json::from_str::<T>(data.as_str()) {
+                            callback.emit(data);
+                        }
+                    }
+                }));
+        }
+    }
+
+    pub fn close(&self) {
+        if let Some(source) = self.event_source.clone() {
+            source.close();
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub use event_source::TopicEventSource;
+
+pub mod event_source;
@@ -0,0 +1,197 @@
+use std::fmt::{Display, Formatter};
+
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use base64::Engine;
+use js_sys::{Array, Function, Object, Promise, Reflect, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use bamboo_common::core::entities::{
+    PasskeyLogin, PasskeyLoginOptions, PasskeyRegistration, PasskeyRegistrationOptions,
+};
+
+const TIMEOUT: u32 = 300_000;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PasskeyError {
+    NotSupported,
+    Cancelled,
+    Failed(String),
+}
+
+impl Display for PasskeyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PasskeyError::NotSupported => f.write_str("passkeys are not supported"),
+            PasskeyError::Cancelled => f.write_str("the ceremony was cancelled"),
+            PasskeyError::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
+impl From<JsValue> for PasskeyError {
+    fn from(value: JsValue) -> Self {
+        let name = Reflect::get(&value, &"name".into())
+            .ok()
+            .and_then(|name| name.as_string())
+            .unwrap_or_default();
+        match name.as_str() {
+            "NotAllowedError" | "AbortError" => PasskeyError::Cancelled,
+            "NotSupportedError" => PasskeyError::NotSupported,
+            _ => PasskeyError::Failed(format!("{value:?}")),
+        }
+    }
+}
+
+pub fn is_supported() -> bool {
+    Reflect::has(&js_sys::global(), &"PublicKeyCredential".into()).unwrap_or(false)
+}
+
+fn to_buffer(value: &str) -> Result<Uint8Array, PasskeyError> {
+    BASE64_URL_SAFE_NO_PAD
+        .decode(value)
+        .map(|bytes| Uint8Array::from(bytes.as_slice()))
+        .map_err(|err| PasskeyError::Failed(err.to_string()))
+}
+
+fn to_base64(buffer: &JsValue) -> String {
+    BASE64_URL_SAFE_NO_PAD.encode(Uint8Array::new(buffer).to_vec())
+}
+
+fn object(entries: &[(&str, JsValue)]) -> Result<Object, PasskeyError> {
+    let object = Object::new();
+    for (key, value) in entries {
+        Reflect::set(&object, &(*key).into(), value)?;
+    }
+
+    Ok(object)
+}
+
+fn credential_descriptors(credential_ids: &[String]) -> Result<Array, PasskeyError> {
+    let descriptors = Array::new();
+    for credential_id in credential_ids {
+        let descriptor = object(&[
+            ("type", "public-key".into()),
+            ("id", to_buffer(credential_id)?.into()),
+        ])?;
+        descriptors.push(&descriptor);
+    }
+
+    Ok(descriptors)
+}
+
+fn get(target: &JsValue, key: &str) -> Result<JsValue, PasskeyError> {
+    Ok(Reflect::get(target, &key.into())?)
+}
+
+fn call(
+    target: &JsValue,
+    method: &str,
+    argument: Option<&JsValue>,
+) -> Result<JsValue, PasskeyError> {
+    let function = get(target, method)?
+        .dyn_into::<Function>()
+        .map_err(|_| PasskeyError::NotSupported)?;
+    Ok(match argument {
+        Some(argument) => function.call1(target, argument)?,
+        None => function.call0(target)?,
+    })
+}
+
+async fn credentials(method: &str, options: Object) -> Result<JsValue, PasskeyError> {
+    if !is_supported() {
+        return Err(PasskeyError::NotSupported);
+    }
+
+    let credentials = get(&get(&js_sys::global(), "navigator")?, "credentials")?;
+    let promise = call(&credentials, method, Some(&options.into()))?
+        .dyn_into::<Promise>()
+        .map_err(|_| PasskeyError::NotSupported)?;
+
+    let credential = JsFuture::from(promise).await?;
+    if credential.is_null() {
+        Err(PasskeyError::Cancelled)
+    } else {
+        Ok(credential)
+    }
+}
+
+pub async fn create_passkey(
+    name: String,
+    options: PasskeyRegistrationOptions,
+) -> Result<PasskeyRegistration, PasskeyError> {
+    let algorithms = Array::new();
+    for algorithm in [-7, -8] {
+        let parameter = object(&[("type", "public-key".into()), ("alg", algorithm.into())])?;
+        algorithms.push(&parameter);
+    }
+
+    let public_key = object(&[
+        ("challenge", to_buffer(options.challenge.as_str())?.into()),
+        (
+            "rp",
+            object(&[
+                ("id", options.rp_id.into()),
+                ("name", options.rp_name.into()),
+            ])?
+            .into(),
+        ),
+        (
+            "user",
+            object(&[
+                ("id", Uint8Array::from(options.user_id.as_bytes()).into()),
+                ("name", options.user_name.into()),
+                ("displayName", options.user_display_name.into()),
+            ])?
+            .into(),
+        ),
+        ("pubKeyCredParams", algorithms.into()),
+        (
+            "excludeCredentials",
+            credential_descriptors(&options.exclude_credentials)?.into(),
+        ),
+        (
+            "authenticatorSelection",
+            object(&[
+                ("residentKey", "required".into()),
+                ("userVerification", "required".into()),
+            ])?
+            .into(),
+        ),
+        ("attestation", "none".into()),
+        ("timeout", TIMEOUT.into()),
+    ])?;
+
+    let credential = credentials("create", object(&[("publicKey", public_key.into())])?).await?;
+    let response = get(&credential, "response")?;
+
+    Ok(PasskeyRegistration {
+        name,
+        credential_id: to_base64(&get(&credential, "rawId")?),
+        client_data_json: to_base64(&get(&response, "clientDataJSON")?),
+        authenticator_data: to_base64(&call(&response, "getAuthenticatorData", None)?),
+    })
+}
+
+pub async fn get_passkey(options: PasskeyLoginOptions) -> Result<PasskeyLogin, PasskeyError> {
+    let public_key = object(&[
+        ("challenge", to_buffer(options.challenge.as_str())?.into()),
+        ("rpId", options.rp_id.into()),
+        (
+            "allowCredentials",
+            credential_descriptors(&options.allow_credentials)?.into(),
+        ),
+        ("userVerification", "required".into()),
+        ("timeout", TIMEOUT.into()),
+    ])?;
+
+    let credential = credentials("get", object(&[("publicKey", public_key.into())])?).await?;
+    let response = get(&credential, "response")?;
+
+    Ok(PasskeyLogin {
+        credential_id: to_base64(&get(&credential, "rawId")?),
+        client_data_json: to_base64(&get(&response, "clientDataJSON")?),
+        authenticator_data: to_base64(&get(&response, "authenticatorData")?),
+        signature: to_base64(&get(&response, "signature")?),
+    })
+}
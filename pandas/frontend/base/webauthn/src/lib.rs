@@ -0,0 +1,3 @@
+pub use ceremony::*;
+
+pub mod ceremony;
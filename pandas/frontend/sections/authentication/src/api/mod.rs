@@ -12,14 +12,10 @@ pub async fn get_my_profile() -> BambooApiResult<WebUser> {
 
 pub async fn login(login_data: Login) -> BambooApiResult<either::Either<LoginResult, ()>> {
     log::debug!("Execute login");
-    if login_data.two_factor_code.is_some()
-        || login_data.email.clone() == "playstore@google.bambushain"
-    {
-        let result = api::post("/api/login", &login_data).await?;
-        Ok(either::Left(result))
-    } else {
-        api::post_no_content("/api/login", &login_data).await?;
-        Ok(either::Right(()))
+    // Demo accounts get their token right away, everyone else has to enter a two factor code first
+    match api::post_optional("/api/login", &login_data).await? {
+        Some(result) => Ok(either::Left(result)),
+        None => Ok(either::Right(())),
     }
 }
 
@@ -27,3 +23,23 @@ pub async fn forgot_password(data: ForgotPassword) -> BambooApiResult<()> {
     log::debug!("Request new password");
     api::post_no_content("/api/forgot-password", &data).await
 }
+
+pub async fn reset_password(data: ResetPassword) -> BambooApiResult<()> {
+    log::debug!("Reset password");
+    api::post_no_content("/api/reset-password", &data).await
+}
+
+pub async fn accept_invitation(data: AcceptInvitation) -> BambooApiResult<()> {
+    log::debug!("Accept invitation");
+    api::post_no_content("/api/invitation/accept", &data).await
+}
+
+pub async fn create_passkey_login_challenge() -> BambooApiResult<PasskeyLoginOptions> {
+    log::debug!("Create passkey login challenge");
+    api::post_no_body("/api/login/passkey/challenge").await
+}
+
+pub async fn login_with_passkey(login_data: PasskeyLogin) -> BambooApiResult<LoginResult> {
+    log::debug!("Execute passkey login");
+    api::post("/api/login/passkey", &login_data).await
+}
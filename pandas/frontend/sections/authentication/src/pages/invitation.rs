@@ -0,0 +1,105 @@
+use serde::Deserialize;
+use yew::prelude::*;
+use yew_cosmo::prelude::*;
+use yew_hooks::use_async;
+use yew_icons::Icon;
+use yew_router::hooks::{use_location, use_navigator};
+
+use bamboo_common::core::entities::AcceptInvitation;
+use bamboo_common::frontend::api::{CONFLICT, TOO_MANY_REQUESTS};
+use bamboo_pandas_frontend_base::routing::AppRoute;
+
+use crate::api;
+use crate::pages::login::LoginLayout;
+
+#[derive(Deserialize, Default)]
+struct InvitationQuery {
+    token: String,
+}
+
+#[function_component(AcceptInvitationPage)]
+pub fn accept_invitation_page() -> Html {
+    let navigator = use_navigator().expect("Navigator should be available");
+    let token = use_location()
+        .and_then(|location| location.query::<InvitationQuery>().ok())
+        .unwrap_or_default()
+        .token;
+
+    let display_name_state = use_state_eq(|| AttrValue::from(""));
+    let password_state = use_state_eq(|| AttrValue::from(""));
+    let repeated_password_state = use_state_eq(|| AttrValue::from(""));
+
+    let accept_state = {
+        let display_name_state = display_name_state.clone();
+        let password_state = password_state.clone();
+        let repeated_password_state = repeated_password_state.clone();
+
+        let token = token.clone();
+
+        use_async(async move {
+            if *password_state != *repeated_password_state {
+                return Err("Die Passwörter stimmen nicht überein");
+            }
+
+            api::accept_invitation(AcceptInvitation {
+                token,
+                display_name: (*display_name_state).to_string(),
+                password: (*password_state).to_string(),
+            })
+            .await
+            .map_err(|err| {
+                if err.code == CONFLICT {
+                    "Der Name ist leider schon vergeben, bitte such dir einen anderen aus"
+                } else if err.code == TOO_MANY_REQUESTS {
+                    "Zu viele Fehlversuche, bitte warte ein paar Minuten und versuch es dann erneut"
+                } else {
+                    "Die Einladung ist ungültig oder abgelaufen, bitte frag einen Mod nach einer neuen"
+                }
+            })
+        })
+    };
+
+    let on_display_name_update =
+        use_callback(display_name_state.clone(), |value, state| state.set(value));
+    let on_password_update = use_callback(password_state.clone(), |value, state| state.set(value));
+    let on_repeated_password_update =
+        use_callback(repeated_password_state.clone(), |value, state| {
+            state.set(value)
+        });
+    let accept_submit = use_callback(accept_state.clone(), |_, state| state.run());
+    let login_click = use_callback(navigator, |_, navigator| navigator.push(&AppRoute::Login));
+
+    html!(
+        <LoginLayout title="Willkommen im Bambushain" message={html!(
+            if token.is_empty() {
+                <>
+                    <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {"Der Link ist ungültig, bitte frag einen Mod nach einer neuen Einladung"}
+                </>
+            } else if let Some(error) = accept_state.error {
+                <>
+                    <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {error}
+                </>
+            } else if accept_state.data.is_some() {
+                {"Dein Account ist fertig, du kannst dich jetzt mit deiner Emailadresse und deinem Passwort anmelden"}
+            } else {
+                <>
+                    <Icon icon_id={IconId::LucideUserPlus} /> {"Such dir einen Namen und ein Passwort aus"}
+                </>
+            }
+        )}>
+            if accept_state.data.is_some() || token.is_empty() {
+                <CosmoButtonContainer>
+                    <CosmoButton state={CosmoButtonType::Primary} label="Zur Anmeldung" on_click={login_click} />
+                </CosmoButtonContainer>
+            } else {
+                <CosmoForm on_submit={accept_submit} buttons={html!(
+                    <CosmoButton state={CosmoButtonType::Primary} label="Einladung annehmen" is_submit={true} />
+                )}>
+                    <CosmoTextBox id="display-name" required={true} value={(*display_name_state).clone()} on_input={on_display_name_update} label="Name" />
+                    <CosmoTextBox id="password" input_type={CosmoTextBoxType::Password} required={true} value={(*password_state).clone()} on_input={on_password_update} label="Passwort" />
+                    <CosmoTextBox id="repeated-password" input_type={CosmoTextBoxType::Password} required={true} value={(*repeated_password_state).clone()} on_input={on_repeated_password_update} label="Passwort wiederholen" />
+                </CosmoForm>
+            }
+        </LoginLayout>
+    )
+}
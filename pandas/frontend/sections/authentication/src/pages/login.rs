@@ -6,11 +6,92 @@ use yew_icons::Icon;
 use yew_router::hooks::use_navigator;
 
 use bamboo_common::core::entities::{ForgotPassword, Login};
+use bamboo_common::frontend::api::TOO_MANY_REQUESTS;
 use bamboo_pandas_frontend_base::routing::{AppRoute, LegalRoute};
-use bamboo_pandas_frontend_base::storage;
+use bamboo_pandas_frontend_base::webauthn::PasskeyError;
+use bamboo_pandas_frontend_base::{storage, webauthn};
 
 use crate::api;
 
+#[derive(Properties, PartialEq, Clone)]
+pub(crate) struct LoginLayoutProps {
+    pub title: AttrValue,
+    pub message: Html,
+    #[prop_or_default]
+    pub children: Html,
+}
+
+#[function_component(LoginLayout)]
+pub(crate) fn login_layout(props: &LoginLayoutProps) -> Html {
+    let login_around_style = use_style!(
+        r#"
+position: fixed;
+left: 0;
+right: 0;
+top: 0;
+bottom: 0;
+display: flex;
+justify-content: center;
+align-items: center;
+height: 100vh;
+width: 100vw;
+background: url("/static/background-login.webp");
+background-size: cover;
+background-position-y: bottom;
+
+font-family: var(--font-family);
+color: var(--black);
+
+--black: #ffffff;
+--white: transparent;
+
+input {
+    --primary-color: var(--control-border-color);
+}
+    "#
+    );
+
+    let login_container_style = use_style!(
+        r#"
+background: rgba(255, 255, 255, 0.25);
+padding: 2rem 4rem;
+backdrop-filter: blur(24px) saturate(90%);
+box-sizing: border-box;
+margin-top: 1.25rem;
+min-width: 35.625rem;
+max-width: 40rem;
+border-radius: var(--border-radius);
+"#
+    );
+    let login_message_style = use_style!(
+        r#"
+font-size: 1.5rem;
+color: #fff;
+font-weight: var(--font-weight-light);
+font-family: var(--font-family);
+display: flex;
+gap: 0.5rem;
+align-items: center;
+    "#
+    );
+
+    html!(
+        <div class={login_around_style}>
+            <div class={classes!(login_container_style, "login-page")}>
+                <CosmoTitle title={props.title.clone()} />
+                <p class={login_message_style}>
+                    {props.message.clone()}
+                </p>
+                {props.children.clone()}
+                <div style="display: flex; gap: 1rem">
+                    <CosmoAnchorLink<AppRoute> to={AppRoute::LegalRoot}>{"Impressum"}</CosmoAnchorLink<AppRoute>>
+                    <CosmoAnchorLink<LegalRoute> to={LegalRoute::DataProtection}>{"Datenschutzerklärung"}</CosmoAnchorLink<LegalRoute>>
+                </div>
+            </div>
+        </div>
+    )
+}
+
 #[function_component(LoginContent)]
 fn login_content() -> Html {
     let navigator = use_navigator().expect("Navigator should be available");
@@ -29,6 +110,8 @@ fn login_content() -> Html {
 
         let two_factor_code_requested_toggle = two_factor_code_requested_toggle.clone();
 
+        let navigator = navigator.clone();
+
         use_async(async move {
             let two_factor_code = if (*two_factor_code_state).is_empty() {
                 None
@@ -52,6 +135,9 @@ fn login_content() -> Html {
                     two_factor_code_requested_toggle.set(true);
                     Ok(())
                 }
+                Err(err) if err.code == TOO_MANY_REQUESTS => {
+                    Err("Zu viele Fehlversuche, bitte warte ein paar Minuten und versuch es dann erneut")
+                }
                 Err(_) => {
                     if *two_factor_code_requested_toggle {
                         Err("Der Zwei Faktor Code ist ungültig")
@@ -62,6 +148,30 @@ fn login_content() -> Html {
             }
         })
     };
+    let passkey_login = use_async(async move {
+        let options = api::create_passkey_login_challenge()
+            .await
+            .map_err(|_| "Die Anmeldung mit Passkey ist fehlgeschlagen")?;
+        let login_data = webauthn::get_passkey(options)
+            .await
+            .map_err(|err| match err {
+                PasskeyError::Cancelled => "Die Anmeldung mit Passkey wurde abgebrochen",
+                PasskeyError::NotSupported => "Dein Browser unterstützt leider keine Passkeys",
+                PasskeyError::Failed(err) => {
+                    log::error!("Failed to get passkey {err}");
+                    "Die Anmeldung mit Passkey ist fehlgeschlagen"
+                }
+            })?;
+
+        match api::login_with_passkey(login_data).await {
+            Ok(result) => {
+                storage::set_token(result.token);
+                navigator.push(&AppRoute::BambooGroveRoot);
+                Ok(())
+            }
+            Err(_) => Err("Der Passkey ist ungültig oder nicht mehr hinterlegt"),
+        }
+    });
     let forgot_password = {
         let email_state = email_state.clone();
 
@@ -93,6 +203,9 @@ fn login_content() -> Html {
             }
         },
     );
+    let passkey_login_click = use_callback(passkey_login.clone(), |_, passkey_login| {
+        passkey_login.run();
+    });
     let forgot_password_click = use_callback(
         forgot_password_toggle.clone(),
         |_, forgot_password_toggle| {
@@ -100,107 +213,54 @@ fn login_content() -> Html {
         },
     );
 
-    let login_around_style = use_style!(
-        r#"
-position: fixed;
-left: 0;
-right: 0;
-top: 0;
-bottom: 0;
-display: flex;
-justify-content: center;
-align-items: center;
-height: 100vh;
-width: 100vw;
-background: url("/static/background-login.webp");
-background-size: cover;
-background-position-y: bottom;
-
-font-family: var(--font-family);
-color: var(--black);
-
---black: #ffffff;
---white: transparent;
-
-input {
-    --primary-color: var(--control-border-color);
-}
-    "#
-    );
-
-    let login_container_style = use_style!(
-        r#"
-background: rgba(255, 255, 255, 0.25);
-padding: 2rem 4rem;
-backdrop-filter: blur(24px) saturate(90%);
-box-sizing: border-box;
-margin-top: 1.25rem;
-min-width: 35.625rem;
-max-width: 40rem;
-border-radius: var(--border-radius);
-"#
-    );
-    let login_message_style = use_style!(
-        r#"
-font-size: 1.5rem;
-color: #fff;
-font-weight: var(--font-weight-light);
-font-family: var(--font-family);
-display: flex;
-gap: 0.5rem;
-align-items: center;
-    "#
-    );
-
     html!(
-        <div class={login_around_style}>
-            <div class={classes!(login_container_style, "login-page")}>
-                <CosmoTitle title="Anmelden" />
-                <p class={login_message_style}>
-                    if *forgot_password_toggle {
-                        {"Gib deine Emailadresse oder deinen Namen ein, wenn du im Hain bist, schicken wir allen Mods eine Email mit der Bitte dein Passwort zurückzusetzen"}
-                    } else if forgot_password.error.is_some() {
-                        {"Leider konnten wir die Mods nicht erreichen, bitte wende dich direkt an einen Mod wenn du kannst oder an "}<CosmoAnchor href="mailto:panda.helferlein@bambushain.app">{"panda.helferlein@bambushain.app"}</CosmoAnchor>
-                    } else if forgot_password.data.is_some() {
-                        {"Wir haben den Mods geschrieben, bitte warte bis sich jemand bei dir meldet"}
-                    } else if let Some(error) = &login.error {
-                        <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {error}
-                    } else {
-                        <Icon icon_id={IconId::LucideLogIn} /> {"Melde dich an und betrete den Bambushain"}
-                    }
-                </p>
-                if !*two_factor_code_requested_toggle && !*forgot_password_toggle {
-                    <CosmoForm on_submit={login_submit} buttons={html!(
-                        <>
-                            <CosmoButton state={CosmoButtonType::Default} label="Passwort vergessen" on_click={forgot_password_click} />
-                            <CosmoButton state={CosmoButtonType::Primary} label="Anmelden" is_submit={true} />
-                        </>
-                    )}>
-                        <CosmoTextBox id="email" required={true} value={(*email_state).clone()} on_input={on_email_update} label="Email oder Name" />
-                        <CosmoTextBox id="password" input_type={CosmoTextBoxType::Password} required={true} value={(*password_state).clone()} on_input={on_password_update} label="Passwort" />
-                    </CosmoForm>
-                } else if *forgot_password_toggle {
-                    <CosmoForm on_submit={login_submit} buttons={html!(
-                        <>
-                            <CosmoButton state={CosmoButtonType::Default} label="Zurück" on_click={forgot_password_click} />
-                            <CosmoButton state={CosmoButtonType::Primary} label="Abschicken" is_submit={true} />
-                        </>
-                    )}>
-                        <CosmoTextBox id="email" required={true} value={(*email_state).clone()} on_input={on_email_update} label="Email oder Name" />
-                    </CosmoForm>
+        <LoginLayout title="Anmelden" message={html!(
+            <>
+                if *forgot_password_toggle {
+                    {"Gib deine Emailadresse oder deinen Namen ein, wenn du im Hain bist, schicken wir dir eine Email mit einem Link zum Zurücksetzen deines Passworts"}
+                } else if forgot_password.error.as_ref().is_some_and(|err| err.code == TOO_MANY_REQUESTS) {
+                    {"Du hast schon zu oft nach einem neuen Passwort gefragt, bitte warte ein paar Minuten"}
+                } else if forgot_password.error.is_some() {
+                    {"Leider konnten wir dir keine Email schicken, bitte wende dich direkt an einen Mod wenn du kannst oder an "}<CosmoAnchor href="mailto:panda.helferlein@bambushain.app">{"panda.helferlein@bambushain.app"}</CosmoAnchor>
+                } else if forgot_password.data.is_some() {
+                    {"Wenn wir dich gefunden haben, haben wir dir eine Email mit einem Link zum Zurücksetzen deines Passworts geschickt"}
+                } else if let Some(error) = login.error.or(passkey_login.error) {
+                    <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {error}
                 } else {
-                    <CosmoForm on_submit={login_submit} buttons={html!(<CosmoButton state={CosmoButtonType::Primary} label="Anmelden" is_submit={true} />)}>
-                        <CosmoTextBox required={true} readonly={true} id="email" value={(*email_state).clone()} on_input={on_email_update} label="Email" />
-                        <CosmoTextBox required={true} readonly={true} id="password" input_type={CosmoTextBoxType::Password} value={(*password_state).clone()} on_input={on_password_update} label="Passwort" />
-                        <CosmoTextBox required={true} id="twofactor" value={(*two_factor_code_state).clone()} on_input={on_two_factor_code_update} label="Zwei Faktor Code" />
-                    </CosmoForm>
+                    <Icon icon_id={IconId::LucideLogIn} /> {"Melde dich an und betrete den Bambushain"}
                 }
-                <div style="display: flex; gap: 1rem">
-                    <CosmoAnchorLink<AppRoute> to={AppRoute::LegalRoot}>{"Impressum"}</CosmoAnchorLink<AppRoute>>
-                    <CosmoAnchorLink<LegalRoute> to={LegalRoute::DataProtection}>{"Datenschutzerklärung"}</CosmoAnchorLink<LegalRoute>>
-                </div>
-            </div>
-        </div>
+            </>
+        )}>
+            if !*two_factor_code_requested_toggle && !*forgot_password_toggle {
+                <CosmoForm on_submit={login_submit} buttons={html!(
+                    <>
+                        <CosmoButton state={CosmoButtonType::Default} label="Passwort vergessen" on_click={forgot_password_click} />
+                        if webauthn::is_supported() {
+                            <CosmoButton state={CosmoButtonType::Default} label="Mit Passkey anmelden" on_click={passkey_login_click} />
+                        }
+                        <CosmoButton state={CosmoButtonType::Primary} label="Anmelden" is_submit={true} />
+                    </>
+                )}>
+                    <CosmoTextBox id="email" required={true} value={(*email_state).clone()} on_input={on_email_update} label="Email oder Name" />
+                    <CosmoTextBox id="password" input_type={CosmoTextBoxType::Password} required={true} value={(*password_state).clone()} on_input={on_password_update} label="Passwort" />
+                </CosmoForm>
+            } else if *forgot_password_toggle {
+                <CosmoForm on_submit={login_submit} buttons={html!(
+                    <>
+                        <CosmoButton state={CosmoButtonType::Default} label="Zurück" on_click={forgot_password_click} />
+                        <CosmoButton state={CosmoButtonType::Primary} label="Abschicken" is_submit={true} />
+                    </>
+                )}>
+                    <CosmoTextBox id="email" required={true} value={(*email_state).clone()} on_input={on_email_update} label="Email oder Name" />
+                </CosmoForm>
+            } else {
+                <CosmoForm on_submit={login_submit} buttons={html!(<CosmoButton state={CosmoButtonType::Primary} label="Anmelden" is_submit={true} />)}>
+                    <CosmoTextBox required={true} readonly={true} id="email" value={(*email_state).clone()} on_input={on_email_update} label="Email" />
+                    <CosmoTextBox required={true} readonly={true} id="password" input_type={CosmoTextBoxType::Password} value={(*password_state).clone()} on_input={on_password_update} label="Passwort" />
+                    <CosmoTextBox required={true} id="twofactor" value={(*two_factor_code_state).clone()} on_input={on_two_factor_code_update} label="Zwei Faktor Code" />
+                </CosmoForm>
+            }
+        </LoginLayout>
     )
 }
 
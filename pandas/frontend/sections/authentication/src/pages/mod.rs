@@ -1,3 +1,7 @@
+pub use invitation::*;
 pub use login::*;
+pub use reset_password::*;
 
+mod invitation;
 mod login;
+mod reset_password;
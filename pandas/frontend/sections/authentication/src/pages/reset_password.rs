@@ -0,0 +1,107 @@
+use serde::Deserialize;
+use yew::prelude::*;
+use yew_cosmo::prelude::*;
+use yew_hooks::use_async;
+use yew_icons::Icon;
+use yew_router::hooks::{use_location, use_navigator};
+
+use bamboo_common::core::entities::ResetPassword;
+use bamboo_common::frontend::api::TOO_MANY_REQUESTS;
+use bamboo_pandas_frontend_base::routing::AppRoute;
+
+use crate::api;
+use crate::pages::login::LoginLayout;
+
+#[derive(Deserialize, Default)]
+struct ResetPasswordQuery {
+    token: String,
+}
+
+#[function_component(ResetPasswordPage)]
+pub fn reset_password_page() -> Html {
+    let navigator = use_navigator().expect("Navigator should be available");
+    let token = use_location()
+        .and_then(|location| location.query::<ResetPasswordQuery>().ok())
+        .unwrap_or_default()
+        .token;
+
+    let password_state = use_state_eq(|| AttrValue::from(""));
+    let repeated_password_state = use_state_eq(|| AttrValue::from(""));
+    let recovery_code_state = use_state_eq(|| AttrValue::from(""));
+
+    let reset_state = {
+        let password_state = password_state.clone();
+        let repeated_password_state = repeated_password_state.clone();
+        let recovery_code_state = recovery_code_state.clone();
+
+        let token = token.clone();
+
+        use_async(async move {
+            if *password_state != *repeated_password_state {
+                return Err("Die Passwörter stimmen nicht überein");
+            }
+
+            api::reset_password(ResetPassword {
+                token,
+                password: (*password_state).to_string(),
+                recovery_code: Some((*recovery_code_state).to_string())
+                    .filter(|code| !code.trim().is_empty()),
+            })
+            .await
+            .map_err(|err| {
+                if err.code == TOO_MANY_REQUESTS {
+                    "Zu viele Fehlversuche, bitte warte ein paar Minuten und versuch es dann erneut"
+                } else {
+                    "Der Link ist ungültig oder abgelaufen oder der Wiederherstellungscode ist falsch"
+                }
+            })
+        })
+    };
+
+    let on_password_update = use_callback(password_state.clone(), |value, state| state.set(value));
+    let on_repeated_password_update =
+        use_callback(repeated_password_state.clone(), |value, state| {
+            state.set(value)
+        });
+    let on_recovery_code_update =
+        use_callback(recovery_code_state.clone(), |value, state| state.set(value));
+    let reset_submit = use_callback(reset_state.clone(), |_, state| state.run());
+    let login_click = use_callback(navigator, |_, navigator| navigator.push(&AppRoute::Login));
+
+    html!(
+        <LoginLayout title="Passwort zurücksetzen" message={html!(
+            if token.is_empty() {
+                <>
+                    <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {"Der Link ist ungültig, bitte fordere einen neuen an"}
+                </>
+            } else if let Some(error) = reset_state.error {
+                <>
+                    <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {error}
+                </>
+            } else if reset_state.data.is_some() {
+                {"Dein Passwort wurde geändert, du kannst dich jetzt mit deinem neuen Passwort anmelden. Eine Zwei-Faktor-App musst du danach neu einrichten"}
+            } else {
+                <>
+                    <Icon icon_id={IconId::LucideKey} /> {"Gib dein neues Passwort ein"}
+                </>
+            }
+        )}>
+            if reset_state.data.is_some() || token.is_empty() {
+                <CosmoButtonContainer>
+                    <CosmoButton state={CosmoButtonType::Primary} label="Zur Anmeldung" on_click={login_click} />
+                </CosmoButtonContainer>
+            } else {
+                <CosmoForm on_submit={reset_submit} buttons={html!(
+                    <>
+                        <CosmoButton state={CosmoButtonType::Default} label="Zur Anmeldung" on_click={login_click} />
+                        <CosmoButton state={CosmoButtonType::Primary} label="Passwort ändern" is_submit={true} />
+                    </>
+                )}>
+                    <CosmoTextBox id="password" input_type={CosmoTextBoxType::Password} required={true} value={(*password_state).clone()} on_input={on_password_update} label="Neues Passwort" />
+                    <CosmoTextBox id="repeated-password" input_type={CosmoTextBoxType::Password} required={true} value={(*repeated_password_state).clone()} on_input={on_repeated_password_update} label="Passwort wiederholen" />
+                    <CosmoTextBox id="recovery-code" value={(*recovery_code_state).clone()} on_input={on_recovery_code_update} label="Wiederherstellungscode, nur mit Zwei-Faktor-App" />
+                </CosmoForm>
+            }
+        </LoginLayout>
+    )
+}
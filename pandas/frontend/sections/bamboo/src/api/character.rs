@@ -0,0 +1,28 @@
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::BambooApiResult;
+
+use crate::api::get;
+
+pub async fn get_my_characters() -> BambooApiResult<Vec<Character>> {
+    log::debug!("Get my characters");
+    get("/api/final-fantasy/character").await
+}
+
+pub async fn get_my_fighters() -> BambooApiResult<Vec<(Character, Fighter)>> {
+    log::debug!("Get fighters of my characters");
+    let mut fighters = vec![];
+    for character in get_my_characters().await? {
+        let character_fighters: Vec<Fighter> = get(format!(
+            "/api/final-fantasy/character/{}/fighter",
+            character.id
+        ))
+        .await?;
+        fighters.extend(
+            character_fighters
+                .into_iter()
+                .map(|fighter| (character.clone(), fighter)),
+        );
+    }
+
+    Ok(fighters)
+}
@@ -1,5 +1,6 @@
 use std::rc::Rc;
 
+use chrono::NaiveDate;
 use date_range::DateRange;
 
 use bamboo_common::core::entities::*;
@@ -32,3 +33,110 @@ pub async fn delete_event(id: i32) -> BambooApiResult<()> {
     log::debug!("Delete event {id}");
     delete(format!("/api/bamboo-grove/event/{id}")).await
 }
+
+pub async fn create_event_occurrence(
+    id: i32,
+    occurrence_date: NaiveDate,
+    event: Event,
+) -> BambooApiResult<Event> {
+    log::debug!("Create occurrence {occurrence_date} of event {id}");
+    post(
+        format!(
+            "/api/bamboo-grove/event/{id}/occurrence/{}",
+            occurrence_date.format("%F")
+        ),
+        &event,
+    )
+    .await
+}
+
+pub async fn delete_event_occurrence(id: i32, occurrence_date: NaiveDate) -> BambooApiResult<()> {
+    log::debug!("Delete occurrence {occurrence_date} of event {id}");
+    delete(format!(
+        "/api/bamboo-grove/event/{id}/occurrence/{}",
+        occurrence_date.format("%F")
+    ))
+    .await
+}
+
+pub async fn get_event_attendances(
+    id: i32,
+    event_date: NaiveDate,
+) -> BambooApiResult<Vec<EventAttendance>> {
+    log::debug!("Get attendances of event {id} on {event_date}");
+    get_with_query(
+        format!("/api/bamboo-grove/event/{id}/attendance"),
+        vec![("date", event_date.format("%F").to_string().as_str())],
+    )
+    .await
+}
+
+pub async fn set_event_attendance(
+    id: i32,
+    event_date: NaiveDate,
+    attendance: EventAttendance,
+) -> BambooApiResult<()> {
+    log::debug!("Set attendance of event {id} on {event_date}");
+    put_no_content(
+        format!(
+            "/api/bamboo-grove/event/{id}/attendance?date={}",
+            event_date.format("%F")
+        ),
+        &attendance,
+    )
+    .await
+}
+
+pub async fn get_event_party_slots(
+    id: i32,
+    event_date: NaiveDate,
+) -> BambooApiResult<Vec<EventPartySlot>> {
+    log::debug!("Get party slots of event {id} on {event_date}");
+    get_with_query(
+        format!("/api/bamboo-grove/event/{id}/party"),
+        vec![("date", event_date.format("%F").to_string().as_str())],
+    )
+    .await
+}
+
+pub async fn set_event_party_slot(
+    id: i32,
+    event_date: NaiveDate,
+    party_slot: EventPartySlot,
+) -> BambooApiResult<()> {
+    log::debug!(
+        "Take party slot {} of event {id} on {event_date}",
+        party_slot.slot
+    );
+    put_no_content(
+        format!(
+            "/api/bamboo-grove/event/{id}/party/{}?date={}",
+            party_slot.slot,
+            event_date.format("%F")
+        ),
+        &party_slot,
+    )
+    .await
+}
+
+pub async fn delete_event_party_slot(
+    id: i32,
+    event_date: NaiveDate,
+    slot: i32,
+) -> BambooApiResult<()> {
+    log::debug!("Leave party slot {slot} of event {id} on {event_date}");
+    delete(format!(
+        "/api/bamboo-grove/event/{id}/party/{slot}?date={}",
+        event_date.format("%F")
+    ))
+    .await
+}
+
+pub async fn delete_event_attendance(id: i32, event_date: NaiveDate) -> BambooApiResult<()> {
+    log::debug!("Delete attendance of event {id} on {event_date}");
+    delete(format!(
+        "/api/bamboo-grove/event/{id}/attendance?date={}",
+        event_date.format("%F")
+    ))
+    .await
+}
@@ -1,6 +1,10 @@
 pub(crate) use bamboo_pandas_frontend_base::api::*;
+pub(crate) use character::*;
 pub(crate) use event::*;
+pub(crate) use trash::*;
 pub use user::*;
 
+pub(crate) mod character;
 pub(crate) mod event;
+pub(crate) mod trash;
 pub mod user;
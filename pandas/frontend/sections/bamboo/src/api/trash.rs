@@ -0,0 +1,20 @@
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::{BambooApiResult, ListQuery, Page};
+
+use crate::api::{get_page, put_no_body_no_content};
+
+pub async fn get_trash(query: ListQuery) -> BambooApiResult<Page<TrashEntry>> {
+    log::debug!("Get trash page {}", query.page);
+    get_page("/api/trash", query).await
+}
+
+pub async fn restore_trash_entry(entry: &TrashEntry) -> BambooApiResult<()> {
+    log::debug!("Restore {:?} {}", entry.entry_type, entry.id);
+    let entry_type = match entry.entry_type {
+        TrashEntryType::Character => "character",
+        TrashEntryType::Event => "event",
+        TrashEntryType::User => "user",
+    };
+
+    put_no_body_no_content(format!("/api/trash/{entry_type}/{}", entry.id)).await
+}
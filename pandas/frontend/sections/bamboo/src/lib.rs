@@ -1,4 +1,5 @@
 pub use pages::calendar::*;
+pub use pages::trash::*;
 pub use pages::user::*;
 
 pub mod api;
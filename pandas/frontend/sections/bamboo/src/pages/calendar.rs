@@ -1,18 +1,16 @@
 #![allow(clippy::clone_on_copy)]
 
 use std::ops::Deref;
-use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::str::FromStr;
 
+use bounce::use_atom_value;
 use chrono::prelude::*;
 use chrono::{Days, Months};
+use chrono_tz::{Tz, TZ_VARIANTS};
 use date_range::DateRange;
-use futures::channel::mpsc;
-use futures::stream::Stream;
-use gloo_events::EventListener;
+use strum::IntoEnumIterator;
 use stylist::yew::use_style;
-use wasm_bindgen::{JsCast, UnwrapThrowExt};
-use web_sys::{EventSource, EventTarget, MessageEvent};
+use yew::html_nested;
 use yew::prelude::*;
 use yew_autoprops::autoprops;
 use yew_cosmo::prelude::*;
@@ -20,9 +18,15 @@ use yew_hooks::prelude::{use_bool_toggle, use_effect_update_with_deps, use_list,
 use yew_hooks::{use_async, use_mount};
 use yew_icons::Icon;
 
-use bamboo_common::core::entities::Event;
+use bamboo_common::core::entities::{
+    AttendanceStatus, Character, CrafterJob, Event, EventAttendance, EventPartySlot, Fighter,
+    FighterJob, FighterRole, PartyTemplate, RecurrenceFrequency, RecurrenceRule, RecurrenceWeekday,
+    SseTopic,
+};
 use bamboo_common::frontend::api::ApiError;
-use bamboo_pandas_frontend_base::error;
+use bamboo_common::frontend::ui::BambooFieldErrors;
+use bamboo_pandas_frontend_base::sse::TopicEventSource;
+use bamboo_pandas_frontend_base::{error, storage};
 
 use crate::api;
 
@@ -41,93 +45,332 @@ impl ToString for ColorYiqResult {
     }
 }
 
-struct CalendarEventSourceEvent {
-    receiver: mpsc::UnboundedReceiver<()>,
-    #[allow(dead_code)]
-    listener: EventListener,
-}
-
-impl CalendarEventSourceEvent {
-    pub fn new(target: EventTarget, event: String, callback: Callback<Event>) -> Self {
-        let (sender, receiver) = mpsc::unbounded();
-
-        let listener = EventListener::new(&target, event, move |evt| {
-            log::debug!("New message received");
-            let evt = evt.dyn_ref::<MessageEvent>().unwrap_throw();
-            let data = evt.data();
-            if let Some(data) = data.as_string() {
-                log::debug!("The data received: {data:?}");
-                if let Ok(event) = serde_json::from_str::<Event>(data.as_str()) {
-                    log::debug!("Decoded the message {:#?}", event.clone());
-                    callback.emit(event);
-                }
-            }
-
-            sender.unbounded_send(()).unwrap_throw();
-        });
+fn color_yiq(color: Color) -> ColorYiqResult {
+    let yiq =
+        ((color.red() as u32 * 299) + (color.green() as u32 * 587) + (color.blue() as u32 * 114))
+            / 1000;
 
-        Self { receiver, listener }
+    if yiq >= 128 {
+        ColorYiqResult::Dark
+    } else {
+        ColorYiqResult::Light
     }
 }
 
-impl Stream for CalendarEventSourceEvent {
-    type Item = ();
+#[derive(PartialEq, Clone)]
+enum RecurrenceEnd {
+    Never,
+    Until,
+    Count,
+}
+
+impl RecurrenceEnd {
+    fn from_rule(recurrence: &RecurrenceRule) -> Self {
+        if recurrence.until.is_some() {
+            RecurrenceEnd::Until
+        } else if recurrence.count.is_some() {
+            RecurrenceEnd::Count
+        } else {
+            RecurrenceEnd::Never
+        }
+    }
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        Pin::new(&mut self.receiver).poll_next(cx)
+    fn value(&self) -> AttrValue {
+        AttrValue::from(match self {
+            RecurrenceEnd::Never => "never",
+            RecurrenceEnd::Until => "until",
+            RecurrenceEnd::Count => "count",
+        })
     }
 }
 
-struct CalendarEventSource {
-    event_source: Option<web_sys::EventSource>,
-    listeners: Vec<CalendarEventSourceEvent>,
+#[derive(PartialEq, Clone)]
+struct EventTimes {
+    start_time: NaiveTime,
+    end_time: NaiveTime,
+    time_zone: Tz,
 }
 
-impl CalendarEventSource {
+impl EventTimes {
     fn new() -> Self {
-        let event_source = if let Ok(event_source) = EventSource::new("/sse/event").map_err(|err| {
-            log::warn!(
-                "Failed to start event source, automatic calendar updates disabled: {err:?}"
-            );
-        }) {
-            Some(event_source)
-        } else {
-            None
-        };
-
         Self {
-            event_source,
-            listeners: vec![],
+            start_time: NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            time_zone: browser_time_zone(),
         }
     }
 
-    fn register_handler(&mut self, event: impl Into<String>, callback: Callback<Event>) {
-        if let Some(source) = self.event_source.clone() {
-            self.listeners.push(CalendarEventSourceEvent::new(
-                source.into(),
-                event.into(),
-                callback,
-            ));
+    fn from_event(event: &Event) -> Option<Self> {
+        if event.all_day {
+            None
+        } else {
+            Some(Self {
+                start_time: event.start_time?,
+                end_time: event.end_time?,
+                time_zone: event.tz()?,
+            })
         }
     }
 
-    fn close(&self) {
-        if let Some(source) = self.event_source.clone() {
-            source.close();
+    fn apply(times: &Option<Self>, event: Event) -> Event {
+        if let Some(times) = times {
+            event.with_times(times.start_time, times.end_time, times.time_zone)
+        } else {
+            event
         }
     }
 }
 
-fn color_yiq(color: Color) -> ColorYiqResult {
-    let yiq =
-        ((color.red() as u32 * 299) + (color.green() as u32 * 587) + (color.blue() as u32 * 114))
-            / 1000;
+fn browser_time_zone() -> Tz {
+    let options = js_sys::Intl::DateTimeFormat::new(&js_sys::Array::new(), &js_sys::Object::new())
+        .resolved_options();
 
-    if yiq >= 128 {
-        ColorYiqResult::Dark
-    } else {
-        ColorYiqResult::Light
+    js_sys::Reflect::get(&options, &"timeZone".into())
+        .ok()
+        .and_then(|time_zone| time_zone.as_string())
+        .and_then(|time_zone| Tz::from_str(time_zone.as_str()).ok())
+        .unwrap_or(Tz::UTC)
+}
+
+#[autoprops]
+#[function_component(TimeInputs)]
+fn time_inputs(times: &Option<EventTimes>, on_change: &Callback<Option<EventTimes>>) -> Html {
+    let time_zones = use_memo((), |_| {
+        TZ_VARIANTS
+            .iter()
+            .map(|tz| (Some(AttrValue::from(tz.name())), AttrValue::from(tz.name())))
+            .collect::<Vec<(Option<AttrValue>, AttrValue)>>()
+    });
+
+    let all_day_checked = use_callback(
+        (on_change.clone(), times.clone()),
+        |value: bool, (on_change, times)| {
+            on_change.emit(if value {
+                None
+            } else {
+                Some(times.clone().unwrap_or(EventTimes::new()))
+            })
+        },
+    );
+    let start_time_input = use_callback(
+        (on_change.clone(), times.clone()),
+        |value: NaiveTime, (on_change, times)| {
+            if let Some(mut times) = times.clone() {
+                times.start_time = value;
+                on_change.emit(Some(times));
+            }
+        },
+    );
+    let end_time_input = use_callback(
+        (on_change.clone(), times.clone()),
+        |value: NaiveTime, (on_change, times)| {
+            if let Some(mut times) = times.clone() {
+                times.end_time = value;
+                on_change.emit(Some(times));
+            }
+        },
+    );
+    let time_zone_select = use_callback(
+        (on_change.clone(), times.clone()),
+        |value: Option<AttrValue>, (on_change, times)| {
+            if let (Some(mut times), Some(time_zone)) = (
+                times.clone(),
+                value.and_then(|value| Tz::from_str(value.as_str()).ok()),
+            ) {
+                times.time_zone = time_zone;
+                on_change.emit(Some(times));
+            }
+        },
+    );
+
+    let mut time_inputs = vec![];
+    if let Some(times) = times.clone() {
+        time_inputs.push(CosmoInputGroupChildren::from(html_nested!(
+            <CosmoTimePicker id="event-start-time" width={CosmoInputWidth::Medium} label="Beginn" required={true} value={times.start_time} on_input={start_time_input} />
+        )));
+        time_inputs.push(CosmoInputGroupChildren::from(html_nested!(
+            <CosmoTimePicker id="event-end-time" width={CosmoInputWidth::Medium} label="Ende" required={true} value={times.end_time} on_input={end_time_input} />
+        )));
+        time_inputs.push(CosmoInputGroupChildren::from(html_nested!(
+            <CosmoDropdown id="event-time-zone" width={CosmoInputWidth::Medium} label="Zeitzone" required={true} value={Some(AttrValue::from(times.time_zone.name()))} items={(*time_zones).clone()} on_select={time_zone_select} />
+        )));
     }
+
+    html!(
+        <CosmoInputGroup>
+            <CosmoSwitch label="Ganztägig" checked={times.is_none()} on_check={all_day_checked} />
+            {for time_inputs}
+        </CosmoInputGroup>
+    )
+}
+
+#[autoprops]
+#[function_component(RecurrenceInputs)]
+fn recurrence_inputs(
+    start_date: &NaiveDate,
+    recurrence: &Option<RecurrenceRule>,
+    on_change: &Callback<Option<RecurrenceRule>>,
+) -> Html {
+    let recurring_checked = use_callback(
+        (on_change.clone(), recurrence.clone()),
+        |value: bool, (on_change, recurrence)| {
+            on_change.emit(if value {
+                Some(
+                    recurrence
+                        .clone()
+                        .unwrap_or(RecurrenceRule::new(RecurrenceFrequency::Weekly)),
+                )
+            } else {
+                None
+            })
+        },
+    );
+    let frequency_select = use_callback(
+        (on_change.clone(), recurrence.clone()),
+        |value: Option<AttrValue>, (on_change, recurrence)| {
+            if let (Some(mut recurrence), Some(value)) = (recurrence.clone(), value) {
+                if let Some(frequency) = RecurrenceFrequency::iter()
+                    .find(|frequency| value.as_str() == format!("{frequency:?}"))
+                {
+                    recurrence.frequency = frequency;
+                    on_change.emit(Some(recurrence));
+                }
+            }
+        },
+    );
+    let interval_input = use_callback(
+        (on_change.clone(), recurrence.clone()),
+        |value: i64, (on_change, recurrence)| {
+            if let Some(mut recurrence) = recurrence.clone() {
+                recurrence.interval = value.max(1) as u32;
+                on_change.emit(Some(recurrence));
+            }
+        },
+    );
+    let weekday_select = use_callback(
+        (on_change.clone(), recurrence.clone()),
+        |value: AttrValue, (on_change, recurrence)| {
+            if let Some(mut recurrence) = recurrence.clone() {
+                if let Some(weekday) = RecurrenceWeekday::iter()
+                    .find(|weekday| value.as_str() == format!("{weekday:?}"))
+                {
+                    recurrence.by_weekday.insert(weekday);
+                    on_change.emit(Some(recurrence));
+                }
+            }
+        },
+    );
+    let weekday_deselect = use_callback(
+        (on_change.clone(), recurrence.clone()),
+        |value: AttrValue, (on_change, recurrence)| {
+            if let Some(mut recurrence) = recurrence.clone() {
+                recurrence
+                    .by_weekday
+                    .retain(|weekday| value.as_str() != format!("{weekday:?}"));
+                on_change.emit(Some(recurrence));
+            }
+        },
+    );
+    let end_select = use_callback(
+        (on_change.clone(), recurrence.clone(), *start_date),
+        |value: Option<AttrValue>, (on_change, recurrence, start_date)| {
+            if let Some(mut recurrence) = recurrence.clone() {
+                let value = value.unwrap_or(RecurrenceEnd::Never.value());
+                recurrence.until = if value == RecurrenceEnd::Until.value() {
+                    Some(recurrence.until.unwrap_or(*start_date))
+                } else {
+                    None
+                };
+                recurrence.count = if value == RecurrenceEnd::Count.value() {
+                    Some(recurrence.count.unwrap_or(1))
+                } else {
+                    None
+                };
+                on_change.emit(Some(recurrence));
+            }
+        },
+    );
+    let until_input = use_callback(
+        (on_change.clone(), recurrence.clone()),
+        |value: NaiveDate, (on_change, recurrence)| {
+            if let Some(mut recurrence) = recurrence.clone() {
+                recurrence.until = Some(value);
+                on_change.emit(Some(recurrence));
+            }
+        },
+    );
+    let count_input = use_callback(
+        (on_change.clone(), recurrence.clone()),
+        |value: i64, (on_change, recurrence)| {
+            if let Some(mut recurrence) = recurrence.clone() {
+                recurrence.count = Some(value.max(1) as u32);
+                on_change.emit(Some(recurrence));
+            }
+        },
+    );
+
+    let mut recurrence_inputs = vec![];
+    if let Some(recurrence) = recurrence.clone() {
+        let frequencies = RecurrenceFrequency::iter()
+            .map(|frequency| {
+                (
+                    Some(AttrValue::from(format!("{frequency:?}"))),
+                    AttrValue::from(frequency.to_string()),
+                )
+            })
+            .collect::<Vec<(Option<AttrValue>, AttrValue)>>();
+        let ends = vec![
+            (Some(RecurrenceEnd::Never.value()), AttrValue::from("Nie")),
+            (
+                Some(RecurrenceEnd::Until.value()),
+                AttrValue::from("An einem Datum"),
+            ),
+            (
+                Some(RecurrenceEnd::Count.value()),
+                AttrValue::from("Nach einer Anzahl an Terminen"),
+            ),
+        ];
+
+        recurrence_inputs.push(CosmoInputGroupChildren::from(html_nested!(
+            <CosmoDropdown width={CosmoInputWidth::Medium} label="Wiederholung" required={true} value={Some(AttrValue::from(format!("{:?}", recurrence.frequency)))} items={frequencies} on_select={frequency_select} />
+        )));
+        recurrence_inputs.push(CosmoInputGroupChildren::from(html_nested!(
+            <CosmoNumberBox id="event-recurrence-interval" width={CosmoInputWidth::Medium} label="Intervall" required={true} value={recurrence.interval as i64} on_input={interval_input} />
+        )));
+        if recurrence.frequency == RecurrenceFrequency::Weekly {
+            let weekdays = RecurrenceWeekday::iter()
+                .map(|weekday| {
+                    CosmoModernSelectItem::new(
+                        weekday.to_string(),
+                        format!("{weekday:?}"),
+                        recurrence.by_weekday.contains(&weekday),
+                    )
+                })
+                .collect::<Vec<CosmoModernSelectItem>>();
+            recurrence_inputs.push(CosmoInputGroupChildren::from(html_nested!(
+                <CosmoModernSelect width={CosmoInputWidth::Medium} label="Wochentage" items={weekdays} on_select={weekday_select} on_deselect={Some(weekday_deselect)} />
+            )));
+        }
+        recurrence_inputs.push(CosmoInputGroupChildren::from(html_nested!(
+            <CosmoDropdown width={CosmoInputWidth::Medium} label="Endet" required={true} value={Some(RecurrenceEnd::from_rule(&recurrence).value())} items={ends} on_select={end_select} />
+        )));
+        if let Some(until) = recurrence.until {
+            recurrence_inputs.push(CosmoInputGroupChildren::from(html_nested!(
+                <CosmoDatePicker id="event-recurrence-until" width={CosmoInputWidth::Medium} label="Endet am" min={*start_date} value={until} on_input={until_input} />
+            )));
+        } else if let Some(count) = recurrence.count {
+            recurrence_inputs.push(CosmoInputGroupChildren::from(html_nested!(
+                <CosmoNumberBox id="event-recurrence-count" width={CosmoInputWidth::Medium} label="Anzahl Termine" value={count as i64} on_input={count_input} />
+            )));
+        }
+    }
+
+    html!(
+        <CosmoInputGroup>
+            <CosmoSwitch label="Wiederholen" checked={recurrence.is_some()} on_check={recurring_checked} />
+            {for recurrence_inputs}
+        </CosmoInputGroup>
+    )
 }
 
 #[autoprops]
@@ -147,6 +390,10 @@ fn add_event_dialog(
     let is_private_state = use_state_eq(|| false);
     let unreported_error_toggle = use_state_eq(|| false);
 
+    let recurrence_state = use_state_eq(|| None as Option<RecurrenceRule>);
+    let times_state = use_state_eq(|| None as Option<EventTimes>);
+    let party_template_state = use_state_eq(|| None as Option<PartyTemplate>);
+
     let bamboo_error_state = use_state_eq(ApiError::default);
 
     {
@@ -178,6 +425,10 @@ fn add_event_dialog(
         let is_private_state = is_private_state.clone();
         let unreported_error_toggle = unreported_error_toggle.clone();
 
+        let recurrence_state = recurrence_state.clone();
+        let times_state = times_state.clone();
+        let party_template_state = party_template_state.clone();
+
         let bamboo_error_state = bamboo_error_state.clone();
 
         let start_date = *start_date;
@@ -185,13 +436,18 @@ fn add_event_dialog(
         let on_added = on_added.clone();
 
         use_async(async move {
-            api::create_event(Event::new(
-                (*title_state).to_string(),
-                (*description_state).to_string(),
-                start_date,
-                (*end_date_state).clone(),
-                *color_state,
-                *is_private_state,
+            api::create_event(EventTimes::apply(
+                &times_state,
+                Event::new(
+                    (*title_state).to_string(),
+                    (*description_state).to_string(),
+                    start_date,
+                    (*end_date_state).clone(),
+                    *color_state,
+                    *is_private_state,
+                    (*recurrence_state).clone(),
+                )
+                .with_party_template(*party_template_state),
             ))
             .await
             .map(|evt| {
@@ -217,6 +473,11 @@ fn add_event_dialog(
     let color_input = use_callback(color_state.clone(), |value, state| state.set(value));
     let is_private_checked =
         use_callback(is_private_state.clone(), |value, state| state.set(value));
+    let recurrence_change = use_callback(recurrence_state.clone(), |value, state| state.set(value));
+    let times_change = use_callback(times_state.clone(), |value, state| state.set(value));
+    let party_template_select = use_callback(party_template_state.clone(), |value, state| {
+        state.set(party_template_from_value(value))
+    });
     let report_unknown_error = use_callback(
         (bamboo_error_state.clone(), unreported_error_toggle.clone()),
         |_, (bamboo_error_state, unreported_error_toggle)| {
@@ -230,6 +491,7 @@ fn add_event_dialog(
     );
 
     let form_submit = use_callback(save_state.clone(), |_, state| state.run());
+    let field_errors = save_state.error.as_ref().map(|err| &err.bamboo_error);
 
     html!(
         <>
@@ -239,19 +501,25 @@ fn add_event_dialog(
                     <CosmoButton label="Event speichern" is_submit={true} />
                 </>
             )}>
-                if save_state.error.is_some() && *unreported_error_toggle {
+                if field_errors.is_some_and(|error| !error.fields.is_empty()) {
+                    <CosmoMessage message_type={CosmoMessageType::Negative} message="Bitte prüfe die markierten Felder" header="Fehler beim Speichern" />
+                } else if save_state.error.is_some() && *unreported_error_toggle {
                     <CosmoMessage message_type={CosmoMessageType::Negative} message="Das Event konnte leider nicht erstellt werden" header="Fehler beim Speichern" actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
                 } else if save_state.error.is_some() {
                     <CosmoMessage message_type={CosmoMessageType::Negative} message="Das Event konnte leider nicht erstellt werden" header="Fehler beim Speichern" />
                 }
+                <BambooFieldErrors error={field_errors.cloned()} inputs={event_field_inputs()} />
                 <CosmoInputGroup>
-                    <CosmoTextBox width={CosmoInputWidth::Medium} label="Titel" value={(*title_state).clone()} on_input={title_input} />
+                    <CosmoTextBox id="event-title" width={CosmoInputWidth::Medium} label="Titel" value={(*title_state).clone()} on_input={title_input} />
                     <CosmoTextArea width={CosmoInputWidth::Medium} label="Beschreibung" value={(*description_state).clone()} on_input={description_input} />
-                    <CosmoColorPicker width={CosmoInputWidth::Medium} label="Farbe" value={*color_state} on_input={color_input} />
+                    <CosmoColorPicker id="event-color" width={CosmoInputWidth::Medium} label="Farbe" value={*color_state} on_input={color_input} />
                     <CosmoDatePicker width={CosmoInputWidth::Medium} label="Von" value={*start_date} readonly={true} on_input={|_| {}} />
-                    <CosmoDatePicker width={CosmoInputWidth::Medium} label="Bis" min={*start_date} value={(*end_date_state).clone()} on_input={end_date_input} />
+                    <CosmoDatePicker id="event-end-date" width={CosmoInputWidth::Medium} label="Bis" min={*start_date} value={(*end_date_state).clone()} on_input={end_date_input} />
+                    <CosmoDropdown width={CosmoInputWidth::Medium} label="Gruppe" value={party_template_value(*party_template_state)} items={party_template_items()} on_select={party_template_select} />
                     <CosmoSwitch label="Nur für mich" checked={*is_private_state} on_check={is_private_checked} />
                 </CosmoInputGroup>
+                <TimeInputs times={(*times_state).clone()} on_change={times_change} />
+                <RecurrenceInputs start_date={*start_date} recurrence={(*recurrence_state).clone()} on_change={recurrence_change} />
             </CosmoModal>
         </>
     )
@@ -272,6 +540,11 @@ fn edit_event_dialog(
 
     let end_date_state = use_state_eq(|| event.end_date);
 
+    let recurrence_state = use_state_eq(|| event.recurrence.clone());
+    let times_state = use_state_eq(|| EventTimes::from_event(event));
+    let party_template_state = use_state_eq(|| event.party_template);
+    let edit_series_state = use_state_eq(|| false);
+
     let delete_event_open_state = use_state_eq(|| false);
     let unreported_error_toggle = use_state_eq(|| false);
 
@@ -295,6 +568,11 @@ fn edit_event_dialog(
 
         let end_date_state = end_date_state.clone();
 
+        let recurrence_state = recurrence_state.clone();
+        let times_state = times_state.clone();
+        let party_template_state = party_template_state.clone();
+        let edit_series_state = edit_series_state.clone();
+
         let unreported_error_toggle = unreported_error_toggle.clone();
 
         let bamboo_error_state = bamboo_error_state.clone();
@@ -304,22 +582,42 @@ fn edit_event_dialog(
         let on_updated = on_updated.clone();
 
         use_async(async move {
-            let mut evt = Event::new(
-                (*title_state).to_string(),
-                (*description_state).to_string(),
-                event.start_date,
-                *end_date_state,
-                *color_state,
-                event.is_private,
+            let mut evt = EventTimes::apply(
+                &times_state,
+                Event::new(
+                    (*title_state).to_string(),
+                    (*description_state).to_string(),
+                    event.start_date,
+                    *end_date_state,
+                    *color_state,
+                    event.is_private,
+                    None,
+                )
+                .with_party_template(*party_template_state),
             );
-            evt.id = event.id;
 
-            api::update_event(event.id, evt.clone())
-                .await
-                .map(|_| {
-                    on_updated.emit(evt);
-                    unreported_error_toggle.set(false)
-                })
+            let result = if event.is_recurring() && !*edit_series_state {
+                api::create_event_occurrence(event.id, event.start_date, evt)
+                    .await
+                    .map(|occurrence| on_updated.emit(occurrence))
+            } else {
+                let series_start_date = event.series_start_date.unwrap_or(event.start_date);
+                evt.id = event.id;
+                evt.start_date = series_start_date;
+                evt.end_date = series_start_date + (*end_date_state - event.start_date);
+                evt.series_id = event.series_id;
+                evt.series_date = event.series_date;
+                if !event.is_occurrence_override() {
+                    evt.recurrence = (*recurrence_state).clone();
+                }
+
+                api::update_event(event.id, evt.clone())
+                    .await
+                    .map(|_| on_updated.emit(evt))
+            };
+
+            result
+                .map(|_| unreported_error_toggle.set(false))
                 .map_err(|err| {
                     log::error!("Failed to update event {} {err}", event.id);
                     unreported_error_toggle.set(true);
@@ -333,19 +631,26 @@ fn edit_event_dialog(
 
         let event = event.clone();
 
+        let edit_series_state = edit_series_state.clone();
+
         let unreported_error_toggle = unreported_error_toggle.clone();
 
         let bamboo_error_state = bamboo_error_state.clone();
 
+        let on_updated = on_updated.clone();
         let on_deleted = on_deleted.clone();
 
         use_async(async move {
-            api::delete_event(id)
-                .await
-                .map(|_| {
-                    on_deleted.emit(event);
-                    unreported_error_toggle.set(false)
-                })
+            let result = if event.is_recurring() && !*edit_series_state {
+                api::delete_event_occurrence(id, event.start_date)
+                    .await
+                    .map(|_| on_updated.emit(event))
+            } else {
+                api::delete_event(id).await.map(|_| on_deleted.emit(event))
+            };
+
+            result
+                .map(|_| unreported_error_toggle.set(false))
                 .map_err(|err| {
                     log::error!("Failed to update event {id} {err}");
                     unreported_error_toggle.set(true);
@@ -360,6 +665,13 @@ fn edit_event_dialog(
     let description_input =
         use_callback(description_state.clone(), |value, state| state.set(value));
     let color_input = use_callback(color_state.clone(), |value, state| state.set(value));
+    let recurrence_change = use_callback(recurrence_state.clone(), |value, state| state.set(value));
+    let times_change = use_callback(times_state.clone(), |value, state| state.set(value));
+    let party_template_select = use_callback(party_template_state.clone(), |value, state| {
+        state.set(party_template_from_value(value))
+    });
+    let edit_series_checked =
+        use_callback(edit_series_state.clone(), |value, state| state.set(value));
     let report_unknown_error = use_callback(
         (bamboo_error_state.clone(), unreported_error_toggle.clone()),
         |_, (bamboo_error_state, unreported_error_toggle)| {
@@ -379,40 +691,574 @@ fn edit_event_dialog(
     log::debug!("Color {}", event.color().hex());
     log::debug!("Color string {}", event.color.clone());
 
+    let edit_occurrence = event.is_recurring() && !*edit_series_state;
+    let field_errors = save_state.error.as_ref().map(|err| &err.bamboo_error);
+    let (delete_label, delete_message) = if edit_occurrence {
+        (
+            "Termin löschen",
+            format!(
+                "Soll der Termin von {} am {} wirklich gelöscht werden?",
+                event.title.clone(),
+                event.start_date.format("%d.%m.%Y")
+            ),
+        )
+    } else if event.is_recurring() {
+        (
+            "Serie löschen",
+            format!(
+                "Soll die Serie {} mit allen Terminen wirklich gelöscht werden? Du kannst sie im Papierkorb wiederherstellen.",
+                event.title.clone()
+            ),
+        )
+    } else {
+        (
+            "Event löschen",
+            format!(
+                "Soll das Event {} wirklich gelöscht werden? Du kannst es im Papierkorb wiederherstellen.",
+                event.title.clone()
+            ),
+        )
+    };
+
     html!(
         <>
             <CosmoModal title="Event bearbeiten" on_form_submit={form_submit} is_form={true} buttons={html!(
                 <>
-                    <CosmoButton state={CosmoButtonType::Negative} label="Event löschen" on_click={open_delete} />
+                    <CosmoButton state={CosmoButtonType::Negative} label={delete_label} on_click={open_delete} />
                     <CosmoButton label="Abbrechen" on_click={on_cancel.clone()} />
                     <CosmoButton label="Event speichern" is_submit={true} />
                 </>
             )}>
-                if save_state.error.is_some() && *unreported_error_toggle {
+                if field_errors.is_some_and(|error| !error.fields.is_empty()) {
+                    <CosmoMessage message_type={CosmoMessageType::Negative} message="Bitte prüfe die markierten Felder" header="Fehler beim Speichern" />
+                } else if save_state.error.is_some() && *unreported_error_toggle {
                         <CosmoMessage message_type={CosmoMessageType::Negative} message="Das Event konnte leider nicht geändert werden" header="Fehler beim Speichern" actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
                 } else if save_state.error.is_some() {
                     <CosmoMessage message_type={CosmoMessageType::Negative} message="Das Event konnte leider nicht geändert werden" header="Fehler beim Speichern" />
                 }
+                <BambooFieldErrors error={field_errors.cloned()} inputs={event_field_inputs()} />
                 if delete_state.error.is_some() && *unreported_error_toggle {
                     <CosmoMessage message_type={CosmoMessageType::Negative} message="Das Event konnte leider nicht gelöscht werden" header="Fehler beim Löschen" actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
                 } else if delete_state.error.is_some() {
                     <CosmoMessage message_type={CosmoMessageType::Negative} message="Das Event konnte leider nicht gelöscht werden" header="Fehler beim Löschen" />
                 }
+                if event.is_recurring() {
+                    <CosmoInputGroup>
+                        <CosmoSwitch label="Ganze Serie bearbeiten" checked={*edit_series_state} on_check={edit_series_checked} />
+                    </CosmoInputGroup>
+                }
                 <CosmoInputGroup>
-                    <CosmoTextBox width={CosmoInputWidth::Medium} label="Titel" value={(*title_state).clone()} on_input={title_input} />
+                    <CosmoTextBox id="event-title" width={CosmoInputWidth::Medium} label="Titel" value={(*title_state).clone()} on_input={title_input} />
                     <CosmoTextArea width={CosmoInputWidth::Medium} label="Beschreibung" value={(*description_state).clone()} on_input={description_input} />
-                    <CosmoColorPicker width={CosmoInputWidth::Medium} label="Farbe" value={*color_state} on_input={color_input} />
+                    <CosmoColorPicker id="event-color" width={CosmoInputWidth::Medium} label="Farbe" value={*color_state} on_input={color_input} />
                     <CosmoDatePicker width={CosmoInputWidth::Medium} label="Von" value={event.start_date} readonly={true} on_input={|_| {}} />
-                    <CosmoDatePicker width={CosmoInputWidth::Medium} label="Bis" min={event.start_date} value={*end_date_state} on_input={end_date_input} />
+                    <CosmoDatePicker id="event-end-date" width={CosmoInputWidth::Medium} label="Bis" min={event.start_date} value={*end_date_state} on_input={end_date_input} />
+                    <CosmoDropdown width={CosmoInputWidth::Medium} label="Gruppe" value={party_template_value(*party_template_state)} items={party_template_items()} on_select={party_template_select} />
                 </CosmoInputGroup>
+                <TimeInputs times={(*times_state).clone()} on_change={times_change} />
+                if !edit_occurrence && !event.is_occurrence_override() {
+                    <RecurrenceInputs start_date={event.series_start_date.unwrap_or(event.start_date)} recurrence={(*recurrence_state).clone()} on_change={recurrence_change} />
+                }
+                <EventAttendanceDetails event={event.clone()} />
+                if event.party_template.is_some() {
+                    <EventPartyDetails event={event.clone()} />
+                }
             </CosmoModal>
             if *delete_event_open_state {
-                <CosmoConfirm confirm_type={CosmoModalType::Warning} title="Event löschen" message={format!("Soll das Event {} wirklich gelöscht werden?", event.title.clone())} confirm_label="Event löschen" decline_label="Nicht löschen" on_confirm={delete_confirm} on_decline={delete_decline} />
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} title={delete_label} message={delete_message} confirm_label={delete_label} decline_label="Nicht löschen" on_confirm={delete_confirm} on_decline={delete_decline} />
             }
         </>
     )
 }
 
+fn event_field_inputs() -> Vec<(AttrValue, AttrValue)> {
+    [
+        ("title", "event-title"),
+        ("color", "event-color"),
+        ("endDate", "event-end-date"),
+        ("startTime", "event-start-time"),
+        ("endTime", "event-end-time"),
+        ("timeZone", "event-time-zone"),
+        ("recurrence.interval", "event-recurrence-interval"),
+        ("recurrence.until", "event-recurrence-until"),
+        ("recurrence.count", "event-recurrence-count"),
+    ]
+    .into_iter()
+    .map(|(field, id)| (AttrValue::from(field), AttrValue::from(id)))
+    .collect()
+}
+
+fn party_template_items() -> Vec<(Option<AttrValue>, AttrValue)> {
+    vec![(None, AttrValue::from("Keine Gruppe"))]
+        .into_iter()
+        .chain(PartyTemplate::iter().map(|template| {
+            (
+                party_template_value(Some(template)),
+                AttrValue::from(format!("{template} ({} Plätze)", template.slots().len())),
+            )
+        }))
+        .collect()
+}
+
+fn party_template_value(template: Option<PartyTemplate>) -> Option<AttrValue> {
+    template.map(|template| AttrValue::from(format!("{template:?}")))
+}
+
+fn party_template_from_value(value: Option<AttrValue>) -> Option<PartyTemplate> {
+    value.and_then(|value| {
+        PartyTemplate::iter().find(|template| value.as_str() == format!("{template:?}"))
+    })
+}
+
+fn attendance_job_value(attendance: &EventAttendance) -> Option<AttrValue> {
+    if let Some(job) = attendance.fighter_job {
+        Some(AttrValue::from(format!("fighter:{job:?}")))
+    } else {
+        attendance
+            .crafter_job
+            .map(|job| AttrValue::from(format!("crafter:{job:?}")))
+    }
+}
+
+#[autoprops]
+#[function_component(EventAttendanceDetails)]
+fn event_attendance_details(event: &Event) -> Html {
+    let current_user = use_atom_value::<storage::CurrentUser>();
+
+    let my_attendance_state = use_state_eq(|| None as Option<EventAttendance>);
+    let event_source_state = use_mut_ref(|| TopicEventSource::new(&[SseTopic::Event]));
+
+    let attendances_state = {
+        let id = event.id;
+        let event_date = event.start_date;
+
+        let my_attendance_state = my_attendance_state.clone();
+
+        let user_id = current_user.profile.id;
+
+        use_async(async move {
+            api::get_event_attendances(id, event_date)
+                .await
+                .inspect(|attendances| {
+                    my_attendance_state.set(
+                        attendances
+                            .iter()
+                            .find(|attendance| attendance.user_id == user_id)
+                            .cloned(),
+                    )
+                })
+        })
+    };
+    let characters_state = use_async(async { api::get_my_characters().await });
+    let save_state = {
+        let id = event.id;
+        let event_date = event.start_date;
+
+        let my_attendance_state = my_attendance_state.clone();
+        let attendances_state = attendances_state.clone();
+
+        use_async(async move {
+            let result = if let Some(attendance) = (*my_attendance_state).clone() {
+                api::set_event_attendance(id, event_date, attendance).await
+            } else {
+                api::delete_event_attendance(id, event_date).await
+            };
+            attendances_state.run();
+
+            result
+        })
+    };
+
+    let status_select = use_callback(
+        my_attendance_state.clone(),
+        |value: Option<AttrValue>, state| {
+            let status = value.and_then(|value| {
+                AttendanceStatus::iter().find(|status| value.as_str() == format!("{status:?}"))
+            });
+            state.set(status.map(|status| {
+                let mut attendance = (**state).clone().unwrap_or_default();
+                attendance.status = status;
+                attendance
+            }))
+        },
+    );
+    let character_select = use_callback(
+        my_attendance_state.clone(),
+        |value: Option<AttrValue>, state| {
+            if let Some(mut attendance) = (**state).clone() {
+                attendance.character_id = value.and_then(|value| value.parse().ok());
+                state.set(Some(attendance));
+            }
+        },
+    );
+    let job_select = use_callback(
+        my_attendance_state.clone(),
+        |value: Option<AttrValue>, state| {
+            if let Some(mut attendance) = (**state).clone() {
+                let value = value.unwrap_or_default();
+                attendance.fighter_job =
+                    FighterJob::iter().find(|job| value.as_str() == format!("fighter:{job:?}"));
+                attendance.crafter_job =
+                    CrafterJob::iter().find(|job| value.as_str() == format!("crafter:{job:?}"));
+                state.set(Some(attendance));
+            }
+        },
+    );
+    let on_save = use_callback(save_state.clone(), |_, state| state.run());
+
+    {
+        let attendances_state = attendances_state.clone();
+        let characters_state = characters_state.clone();
+        let event_source_state = event_source_state.clone();
+
+        let event = event.clone();
+
+        use_mount(move || {
+            let reload_state = attendances_state.clone();
+            event_source_state.borrow_mut().register_handler(
+                SseTopic::Event,
+                "attendance",
+                Callback::from(move |changed: Event| {
+                    if changed.id == event.id && changed.start_date == event.start_date {
+                        log::debug!("The attendances of the event changed, reload them");
+                        reload_state.run();
+                    }
+                }),
+            );
+            attendances_state.run();
+            characters_state.run();
+        })
+    }
+    {
+        let event_source_state = event_source_state.clone();
+        use_unmount(move || event_source_state.borrow().close());
+    }
+
+    let statuses = vec![(None, AttrValue::from("Keine Rückmeldung"))]
+        .into_iter()
+        .chain(AttendanceStatus::iter().map(|status| {
+            (
+                Some(AttrValue::from(format!("{status:?}"))),
+                AttrValue::from(status.to_string()),
+            )
+        }))
+        .collect::<Vec<(Option<AttrValue>, AttrValue)>>();
+    let characters = vec![(None, AttrValue::from("Kein Charakter"))]
+        .into_iter()
+        .chain(
+            characters_state
+                .data
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|character| {
+                    (
+                        Some(AttrValue::from(character.id.to_string())),
+                        AttrValue::from(character.name),
+                    )
+                }),
+        )
+        .collect::<Vec<(Option<AttrValue>, AttrValue)>>();
+    let jobs = vec![(None, AttrValue::from("Kein Job"))]
+        .into_iter()
+        .chain(FighterJob::iter().map(|job| {
+            (
+                Some(AttrValue::from(format!("fighter:{job:?}"))),
+                AttrValue::from(job.to_string()),
+            )
+        }))
+        .chain(CrafterJob::iter().map(|job| {
+            (
+                Some(AttrValue::from(format!("crafter:{job:?}"))),
+                AttrValue::from(job.to_string()),
+            )
+        }))
+        .collect::<Vec<(Option<AttrValue>, AttrValue)>>();
+
+    let attendances = attendances_state.data.clone().unwrap_or_default();
+    let accepted = attendances
+        .iter()
+        .filter(|attendance| attendance.status == AttendanceStatus::Accepted)
+        .collect::<Vec<&EventAttendance>>();
+    let role_summary = FighterRole::iter()
+        .map(|role| {
+            format!(
+                "{role}: {}",
+                accepted
+                    .iter()
+                    .filter(|attendance| attendance.role() == Some(role))
+                    .count()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let mut attendance_inputs = vec![];
+    if let Some(attendance) = (*my_attendance_state).clone() {
+        attendance_inputs.push(CosmoInputGroupChildren::from(html_nested!(
+            <CosmoDropdown width={CosmoInputWidth::Medium} label="Charakter" value={attendance.character_id.map(|id| AttrValue::from(id.to_string()))} items={characters} on_select={character_select} />
+        )));
+        attendance_inputs.push(CosmoInputGroupChildren::from(html_nested!(
+            <CosmoDropdown width={CosmoInputWidth::Medium} label="Job" value={attendance_job_value(&attendance)} items={jobs} on_select={job_select} />
+        )));
+    }
+
+    html!(
+        <>
+            <CosmoHeader level={CosmoHeaderLevel::H3} header="Teilnahme" />
+            if attendances_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} message="Die Teilnahmen konnten leider nicht geladen werden" header="Fehler beim Laden" />
+            } else {
+                <CosmoParagraph>{format!("{} Zusagen ({role_summary})", accepted.len())}</CosmoParagraph>
+                <ul>
+                    {for attendances.iter().map(|attendance| {
+                        let mut details = vec![attendance.status.to_string()];
+                        if let Some(character_name) = attendance.character_name.clone() {
+                            details.push(character_name);
+                        }
+                        if let Some(job) = attendance.fighter_job {
+                            details.push(job.to_string());
+                        } else if let Some(job) = attendance.crafter_job {
+                            details.push(job.to_string());
+                        }
+
+                        html!(
+                            <li key={attendance.id}>{format!("{}: {}", attendance.display_name, details.join(", "))}</li>
+                        )
+                    })}
+                </ul>
+            }
+            if save_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} message="Deine Teilnahme konnte leider nicht gespeichert werden" header="Fehler beim Speichern" />
+            }
+            <CosmoInputGroup>
+                <CosmoDropdown width={CosmoInputWidth::Medium} label="Meine Teilnahme" value={(*my_attendance_state).clone().map(|attendance| AttrValue::from(format!("{:?}", attendance.status)))} items={statuses} on_select={status_select} />
+                {for attendance_inputs}
+            </CosmoInputGroup>
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoButton label="Teilnahme speichern" on_click={on_save} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
+        </>
+    )
+}
+
+#[autoprops]
+#[function_component(EventPartyDetails)]
+fn event_party_details(event: &Event) -> Html {
+    let current_user = use_atom_value::<storage::CurrentUser>();
+
+    let selected_slot_state = use_state_eq(|| None as Option<i32>);
+    let selected_fighter_state = use_state_eq(|| None as Option<i32>);
+    let event_source_state = use_mut_ref(|| TopicEventSource::new(&[SseTopic::Event]));
+
+    let user_id = current_user.profile.id;
+
+    let party_slots_state = {
+        let id = event.id;
+        let event_date = event.start_date;
+
+        let selected_slot_state = selected_slot_state.clone();
+        let selected_fighter_state = selected_fighter_state.clone();
+
+        use_async(async move {
+            api::get_event_party_slots(id, event_date)
+                .await
+                .inspect(|party_slots| {
+                    let my_slot = party_slots
+                        .iter()
+                        .find(|party_slot| party_slot.user_id == user_id);
+                    selected_slot_state.set(my_slot.map(|party_slot| party_slot.slot));
+                    selected_fighter_state.set(my_slot.map(|party_slot| party_slot.fighter_id));
+                })
+        })
+    };
+    let fighters_state = use_async(async { api::get_my_fighters().await });
+    let save_state = {
+        let id = event.id;
+        let event_date = event.start_date;
+
+        let selected_slot_state = selected_slot_state.clone();
+        let selected_fighter_state = selected_fighter_state.clone();
+        let party_slots_state = party_slots_state.clone();
+
+        use_async(async move {
+            let my_slot = party_slots_state
+                .data
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .find(|party_slot| party_slot.user_id == user_id);
+            let result = match (*selected_slot_state, *selected_fighter_state) {
+                (Some(slot), Some(fighter_id)) => {
+                    api::set_event_party_slot(
+                        id,
+                        event_date,
+                        EventPartySlot::new(event_date, slot, fighter_id),
+                    )
+                    .await
+                }
+                (None, _) => {
+                    if let Some(my_slot) = my_slot {
+                        api::delete_event_party_slot(id, event_date, my_slot.slot).await
+                    } else {
+                        Ok(())
+                    }
+                }
+                _ => Ok(()),
+            };
+            party_slots_state.run();
+
+            result
+        })
+    };
+
+    let slot_select = use_callback(
+        (selected_slot_state.clone(), selected_fighter_state.clone()),
+        |value: Option<AttrValue>, (slot_state, fighter_state)| {
+            slot_state.set(value.and_then(|value| value.parse().ok()));
+            fighter_state.set(None);
+        },
+    );
+    let fighter_select = use_callback(
+        selected_fighter_state.clone(),
+        |value: Option<AttrValue>, state| state.set(value.and_then(|value| value.parse().ok())),
+    );
+    let on_save = use_callback(save_state.clone(), |_, state| state.run());
+
+    {
+        let party_slots_state = party_slots_state.clone();
+        let fighters_state = fighters_state.clone();
+        let event_source_state = event_source_state.clone();
+
+        let event = event.clone();
+
+        use_mount(move || {
+            let reload_state = party_slots_state.clone();
+            event_source_state.borrow_mut().register_handler(
+                SseTopic::Event,
+                "party",
+                Callback::from(move |changed: Event| {
+                    if changed.id == event.id && changed.start_date == event.start_date {
+                        log::debug!("The party of the event changed, reload it");
+                        reload_state.run();
+                    }
+                }),
+            );
+            party_slots_state.run();
+            fighters_state.run();
+        })
+    }
+    {
+        let event_source_state = event_source_state.clone();
+        use_unmount(move || event_source_state.borrow().close());
+    }
+
+    let roles = event
+        .party_template
+        .map(|template| template.slots())
+        .unwrap_or_default();
+    let party_slots = party_slots_state.data.clone().unwrap_or_default();
+    let slot_label = |slot: usize, role: FighterRole| {
+        format!(
+            "{role} {}",
+            roles[..=slot].iter().filter(|r| **r == role).count()
+        )
+    };
+
+    let free_slots = vec![(None, AttrValue::from("Kein Platz"))]
+        .into_iter()
+        .chain(
+            roles
+                .iter()
+                .enumerate()
+                .filter(|(slot, _)| {
+                    party_slots
+                        .iter()
+                        .find(|party_slot| party_slot.slot == *slot as i32)
+                        .map(|party_slot| party_slot.user_id == user_id)
+                        .unwrap_or(true)
+                })
+                .map(|(slot, role)| {
+                    (
+                        Some(AttrValue::from(slot.to_string())),
+                        AttrValue::from(slot_label(slot, *role)),
+                    )
+                }),
+        )
+        .collect::<Vec<(Option<AttrValue>, AttrValue)>>();
+    let selected_role = (*selected_slot_state)
+        .and_then(|slot| usize::try_from(slot).ok())
+        .and_then(|slot| roles.get(slot).copied());
+    let fighters = vec![(None, AttrValue::from("Kein Kämpfer"))]
+        .into_iter()
+        .chain(
+            fighters_state
+                .data
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(_, fighter)| Some(fighter.job.role()) == selected_role)
+                .map(|(character, fighter): (Character, Fighter)| {
+                    (
+                        Some(AttrValue::from(fighter.id.to_string())),
+                        AttrValue::from(format!("{}: {}", character.name, fighter.job.to_string())),
+                    )
+                }),
+        )
+        .collect::<Vec<(Option<AttrValue>, AttrValue)>>();
+
+    let mut party_inputs = vec![];
+    if selected_role.is_some() {
+        party_inputs.push(CosmoInputGroupChildren::from(html_nested!(
+            <CosmoDropdown width={CosmoInputWidth::Medium} label="Kämpfer" value={(*selected_fighter_state).map(|id| AttrValue::from(id.to_string()))} items={fighters} on_select={fighter_select} />
+        )));
+    }
+
+    html!(
+        <>
+            <CosmoHeader level={CosmoHeaderLevel::H3} header={format!("Gruppe: {}", event.party_template.map(|template| template.to_string()).unwrap_or_default())} />
+            if party_slots_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} message="Die Gruppe konnte leider nicht geladen werden" header="Fehler beim Laden" />
+            } else {
+                <ul>
+                    {for roles.iter().enumerate().map(|(slot, role)| {
+                        let member = party_slots
+                            .iter()
+                            .find(|party_slot| party_slot.slot == slot as i32)
+                            .map(|party_slot| {
+                                format!(
+                                    "{} ({}, {})",
+                                    party_slot.display_name,
+                                    party_slot.character_name,
+                                    party_slot.fighter_job.map(|job| job.to_string()).unwrap_or_default()
+                                )
+                            })
+                            .unwrap_or("Frei".into());
+
+                        html!(
+                            <li key={slot}>{format!("{}: {member}", slot_label(slot, *role))}</li>
+                        )
+                    })}
+                </ul>
+            }
+            if save_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} message="Dein Platz konnte leider nicht gespeichert werden" header="Fehler beim Speichern" />
+            }
+            <CosmoInputGroup>
+                <CosmoDropdown width={CosmoInputWidth::Medium} label="Mein Platz" value={(*selected_slot_state).map(|slot| AttrValue::from(slot.to_string()))} items={free_slots} on_select={slot_select} />
+                {for party_inputs}
+            </CosmoInputGroup>
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoButton label="Platz speichern" on_click={on_save} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
+        </>
+    )
+}
+
 #[autoprops]
 #[function_component(EventEntry)]
 fn event_entry(event: &Event, on_updated: &Callback<Event>, on_deleted: &Callback<Event>) -> Html {
@@ -509,7 +1355,11 @@ cursor: pointer;"#,
                 <EditEventDialog event={event.clone()} on_updated={on_updated} on_deleted={on_deleted} on_cancel={on_cancel} />
             }
             <span class={classes} data-description={event.description.clone()}>
-                {event.title.clone()}
+                if let Some(start) = event.start_date_time() {
+                    {format!("{} {}", start.with_timezone(&Local).format("%H:%M"), event.title.clone())}
+                } else {
+                    {event.title.clone()}
+                }
                 <a onclick={move |_| edit_open_toggle.set(true)}>
                     <Icon icon_id={IconId::LucidePencil} width="16px" height="16px" class={classes!(edit_style, "panda-calendar-edit")} />
                 </a>
@@ -627,7 +1477,7 @@ z-index: 1;
             <div class={classes!(style)}>
                 <Icon onclick={move |_| add_event_open_toggle.set(true)} icon_id={IconId::LucideCalendarPlus} class={classes!(add_style, "panda-calendar-add")} />
                 {for events.iter().map(move |evt| html!(
-                    <EventEntry on_updated={on_updated.clone()} on_deleted={on_deleted.clone()} key={evt.id} event={evt.clone()} />
+                    <EventEntry on_updated={on_updated.clone()} on_deleted={on_deleted.clone()} key={format!("{}-{}", evt.id, evt.start_date)} event={evt.clone()} />
                 ))}
             </div>
         </>
@@ -687,7 +1537,7 @@ fn calendar_data(date: &NaiveDate) -> Html {
     let bamboo_error_state = use_state_eq(ApiError::default);
 
     let events_list = use_list(vec![] as Vec<Event>);
-    let calendar_event_source_state = use_mut_ref(CalendarEventSource::new);
+    let calendar_event_source_state = use_mut_ref(|| TopicEventSource::new(&[SseTopic::Event]));
 
     let events_state = {
         let range = DateRange::new(calendar_start_date, calendar_end_date).unwrap();
@@ -717,16 +1567,21 @@ fn calendar_data(date: &NaiveDate) -> Html {
     let event_created = use_callback(
         (
             events_list.clone(),
+            events_state.clone(),
             calendar_start_date.clone(),
             calendar_end_date.clone(),
         ),
-        |event: Event, (events_list, since, until)| {
+        |event: Event, (events_list, events_state, since, until)| {
             log::debug!(
                 "Someone created a new event, adding it to the list if it is in current range"
             );
             log::debug!("Got event {event:?}");
-            if (event.start_date >= *since && event.start_date <= *until)
-                || (event.end_date >= *since && event.end_date <= *until)
+            let (start_date, end_date) = event.dates_in(&Local);
+            if event.is_recurring() || event.is_occurrence_override() {
+                log::debug!("The event is part of a series, reload the events");
+                events_state.run();
+            } else if (start_date >= *since && start_date <= *until)
+                || (end_date >= *since && end_date <= *until)
             {
                 log::debug!("The event is in range, lets add it to the list");
                 events_list.push(event.clone());
@@ -736,14 +1591,19 @@ fn calendar_data(date: &NaiveDate) -> Html {
     let event_updated = use_callback(
         (
             events_list.clone(),
+            events_state.clone(),
             calendar_start_date.clone(),
             calendar_end_date.clone(),
         ),
-        |event: Event, (events_list, since, until)| {
+        |event: Event, (events_list, events_state, since, until)| {
             log::debug!("Someone updated an event, if we have it loaded, lets update it");
             log::debug!("Got event {event:?}");
-            if (event.start_date >= *since && event.start_date <= *until)
-                || (event.end_date >= *since && event.end_date <= *until)
+            let (start_date, end_date) = event.dates_in(&Local);
+            if event.is_recurring() || event.is_occurrence_override() {
+                log::debug!("The event is part of a series, reload the events");
+                events_state.run();
+            } else if (start_date >= *since && start_date <= *until)
+                || (end_date >= *since && end_date <= *until)
             {
                 log::debug!("The event is in range");
 
@@ -825,11 +1685,11 @@ fn calendar_data(date: &NaiveDate) -> Html {
         let event_deleted = event_deleted.clone();
 
         use_mount(move || {
-            log::debug!("Start event source for calendar on /sse/event");
+            log::debug!("Start event source for calendar on /sse");
             let mut source = calendar_event_source_state.borrow_mut();
-            source.register_handler("created", event_created.clone());
-            source.register_handler("updated", event_updated.clone());
-            source.register_handler("deleted", event_deleted.clone());
+            source.register_handler(SseTopic::Event, "created", event_created.clone());
+            source.register_handler(SseTopic::Event, "updated", event_updated.clone());
+            source.register_handler(SseTopic::Event, "deleted", event_deleted.clone());
             event_source_connected_toggle.set(true);
             events_state.run();
         })
@@ -851,7 +1711,10 @@ fn calendar_data(date: &NaiveDate) -> Html {
             events_list
                 .current()
                 .iter()
-                .filter(move |event| event.start_date <= day && event.end_date >= day)
+                .filter(move |event| {
+                    let (start_date, end_date) = event.dates_in(&Local);
+                    start_date <= day && end_date >= day
+                })
                 .cloned()
                 .collect::<Vec<Event>>()
         }
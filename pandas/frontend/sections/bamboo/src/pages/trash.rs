@@ -0,0 +1,186 @@
+use std::ops::Deref;
+
+use yew::prelude::*;
+use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_bool_toggle, use_mount};
+
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::{ApiError, ListQuery, NOT_FOUND};
+use bamboo_common::frontend::ui::BambooPaging;
+use bamboo_pandas_frontend_base::error;
+
+use crate::api;
+
+#[function_component(TrashPage)]
+pub fn trash_page() -> Html {
+    log::debug!("Render trash page");
+    let bamboo_error_state = use_state_eq(ApiError::default);
+    let unreported_error_toggle = use_bool_toggle(false);
+    // The entry is read when the request runs, a state would still hold the value of the last render
+    let restore_entry_ref = use_mut_ref(|| None as Option<TrashEntry>);
+
+    let query_state = use_state_eq(ListQuery::default);
+    // The query is read when the request runs, a state would still hold the value of the last render
+    let query_ref = use_mut_ref(ListQuery::default);
+
+    let trash_state = {
+        let query_ref = query_ref.clone();
+
+        use_async(async move {
+            let query = query_ref.borrow().clone();
+            api::get_trash(query).await
+        })
+    };
+
+    let restore_state = {
+        let bamboo_error_state = bamboo_error_state.clone();
+        let unreported_error_toggle = unreported_error_toggle.clone();
+        let restore_entry_ref = restore_entry_ref.clone();
+        let trash_state = trash_state.clone();
+
+        use_async(async move {
+            unreported_error_toggle.set(false);
+            let entry = restore_entry_ref.borrow().clone();
+            let result = if let Some(entry) = entry {
+                api::restore_trash_entry(&entry)
+                    .await
+                    .map_err(|err| {
+                        log::warn!("Failed to restore the trash entry {err}");
+                        bamboo_error_state.set(err.clone());
+                        unreported_error_toggle.set(true);
+                        err
+                    })
+                    .map(|_| entry)
+            } else {
+                Err(ApiError::default())
+            };
+            trash_state.run();
+
+            result
+        })
+    };
+
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "bamboo_trash",
+                "trash_page",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+
+    let load = {
+        let query_state = query_state.clone();
+        let trash_state = trash_state.clone();
+
+        move |query: ListQuery| {
+            *query_ref.borrow_mut() = query.clone();
+            query_state.set(query);
+            trash_state.run();
+        }
+    };
+    let update_filter = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+
+        Callback::from(move |filter: AttrValue| {
+            load(ListQuery {
+                page: 1,
+                filter: filter.to_string(),
+                ..query.clone()
+            })
+        })
+    };
+    let change_page = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+
+        Callback::from(move |page: u64| {
+            load(ListQuery {
+                page,
+                ..query.clone()
+            })
+        })
+    };
+
+    {
+        let trash_state = trash_state.clone();
+
+        use_mount(move || trash_state.run());
+    }
+
+    let total = trash_state
+        .data
+        .as_ref()
+        .map(|page| page.total)
+        .unwrap_or_default();
+
+    html!(
+        <>
+            <CosmoTitle title="Papierkorb" />
+            <CosmoParagraph>
+                {"Gelöschte Charaktere und Events landen erst im Papierkorb. Bis sie endgültig gelöscht werden, kannst du sie hier mit allen Daten wiederherstellen. Mods sehen hier auch gelöschte Pandas."}
+            </CosmoParagraph>
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoTextBox width={CosmoInputWidth::Medium} label="Suchen" value={AttrValue::from(query_state.filter.clone())} on_input={update_filter} />
+                </CosmoToolbarGroup>
+                <CosmoToolbarGroup>
+                    <BambooPaging page={query_state.page} limit={query_state.limit} total={total} on_change={change_page} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
+            if let Some(err) = &restore_state.error {
+                if err.code == NOT_FOUND {
+                    <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Wiederherstellen" message="Der Eintrag ist nicht mehr im Papierkorb" />
+                } else if *unreported_error_toggle {
+                    <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Wiederherstellen" message="Der Eintrag konnte leider nicht wiederhergestellt werden" actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                } else {
+                    <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Wiederherstellen" message="Der Eintrag konnte leider nicht wiederhergestellt werden" />
+                }
+            } else if let Some(entry) = &restore_state.data {
+                <CosmoMessage message_type={CosmoMessageType::Positive} header="Wiederhergestellt" message={format!("{} {} wurde wiederhergestellt", entry.entry_type, entry.name)} />
+            }
+            if trash_state.loading {
+                <CosmoProgressRing />
+            } else if trash_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Laden" message="Der Papierkorb konnte nicht geladen werden" />
+            } else if let Some(page) = &trash_state.data {
+                if page.entries.is_empty() && query_state.filter.trim().is_empty() {
+                    <CosmoMessage message_type={CosmoMessageType::Information} header="Papierkorb ist leer" message="Es gibt keine gelöschten Einträge, die du wiederherstellen kannst" />
+                } else if page.entries.is_empty() {
+                    <CosmoMessage message_type={CosmoMessageType::Information} header="Keine Einträge" message="Für diese Suche gibt es keine Einträge im Papierkorb" />
+                } else {
+                    <CosmoTable headers={vec![AttrValue::from("Art"), AttrValue::from("Name"), AttrValue::from("Gelöscht am"), AttrValue::from("Endgültig gelöscht am"), AttrValue::from("Aktionen")]}>
+                        {for page.entries.iter().map(|entry| {
+                            let restore = {
+                                let restore_state = restore_state.clone();
+                                let restore_entry_ref = restore_entry_ref.clone();
+                                let entry = entry.clone();
+
+                                Callback::from(move |_| {
+                                    *restore_entry_ref.borrow_mut() = Some(entry.clone());
+                                    restore_state.run();
+                                })
+                            };
+
+                            CosmoTableRow::from_table_cells(vec![
+                                CosmoTableCell::from_html(html!(entry.entry_type.to_string()), None),
+                                CosmoTableCell::from_html(html!(entry.name.clone()), None),
+                                CosmoTableCell::from_html(html!(entry.deleted_at.format("%d.%m.%Y %H:%M").to_string()), None),
+                                CosmoTableCell::from_html(html!(entry.purge_at.format("%d.%m.%Y").to_string()), None),
+                                CosmoTableCell::from_html(html!(
+                                    <CosmoToolbarGroup>
+                                        <CosmoButton label="Wiederherstellen" on_click={restore} />
+                                    </CosmoToolbarGroup>
+                                ), None),
+                            ], Some(format!("{:?}-{}", entry.entry_type, entry.id).into()))
+                        })}
+                    </CosmoTable>
+                }
+            }
+        </>
+    )
+}
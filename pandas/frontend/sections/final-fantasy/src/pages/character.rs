@@ -7,11 +7,12 @@ use yew::prelude::*;
 use yew::virtual_dom::{Key, VChild};
 use yew_autoprops::autoprops;
 use yew_cosmo::prelude::*;
-use yew_hooks::{use_async, use_bool_toggle, use_map, use_mount};
+use yew_hooks::{use_async, use_bool_toggle, use_map, use_mount, use_unmount};
 
 use bamboo_common::core::entities::*;
 use bamboo_common::frontend::api::{ApiError, CONFLICT, NOT_FOUND};
 use bamboo_pandas_frontend_base::error;
+use bamboo_pandas_frontend_base::sse::TopicEventSource;
 
 use crate::api;
 use crate::pages::crafter::CrafterDetails;
@@ -344,7 +345,7 @@ fn character_details(
                         match err.code {
                             CONFLICT => {
                                 error_message_state
-                                    .set("Ein Charakter mit diesem Namen existiert bereits für diese Welt".into());
+                                    .set("Ein Charakter mit diesem Namen existiert bereits für diese Welt oder liegt im Papierkorb".into());
                                 unreported_error_toggle.set(false);
                             }
                             NOT_FOUND => {
@@ -480,7 +481,7 @@ fn character_details(
                 CharacterActions::Delete => {
                     let character = character.clone();
                     html!(
-                        <CosmoConfirm confirm_type={CosmoModalType::Warning} on_confirm={on_modal_delete} on_decline={on_modal_close} confirm_label="Character löschen" decline_label="Character behalten" title="Character löschen" message={format!("Soll der Character {} wirklich gelöscht werden?", character.name)} />
+                        <CosmoConfirm confirm_type={CosmoModalType::Warning} on_confirm={on_modal_delete} on_decline={on_modal_close} confirm_label="Character löschen" decline_label="Character behalten" title="Character löschen" message={format!("Soll der Character {} wirklich gelöscht werden? Du kannst ihn im Papierkorb wiederherstellen.", character.name)} />
                     )
                 }
                 CharacterActions::Closed => html!(),
@@ -506,6 +507,9 @@ pub fn character_page() -> Html {
 
     let error_message_state = use_state_eq(|| AttrValue::from(""));
 
+    let event_source_state =
+        use_mut_ref(|| TopicEventSource::new(&[SseTopic::Character, SseTopic::FreeCompany]));
+
     let characters_state = {
         let bamboo_error_state = bamboo_error_state.clone();
 
@@ -558,7 +562,7 @@ pub fn character_page() -> Html {
                     .map_err(|err| {
                         error_message_state.set(
                             if err.code == CONFLICT {
-                                "Ein Charakter mit diesem Namen existiert bereits für diese Welt"
+                                "Ein Charakter mit diesem Namen existiert bereits für diese Welt oder liegt im Papierkorb"
                             } else {
                                 bamboo_error_state.set(err.clone());
                                 error_message_form_state.set("character_page".into());
@@ -622,13 +626,39 @@ pub fn character_page() -> Html {
         let custom_fields_state = custom_fields_state.clone();
         let free_companies_state = free_companies_state.clone();
         let characters_state = characters_state.clone();
+        let event_source_state = event_source_state.clone();
 
         use_mount(move || {
+            let mut source = event_source_state.borrow_mut();
+            for action in ["created", "updated", "deleted"] {
+                let characters_state = characters_state.clone();
+                source.register_handler(
+                    SseTopic::Character,
+                    action,
+                    Callback::from(move |_: Character| {
+                        log::debug!("Your characters changed in another tab, reload them");
+                        characters_state.run();
+                    }),
+                );
+                let free_companies_state = free_companies_state.clone();
+                source.register_handler(
+                    SseTopic::FreeCompany,
+                    action,
+                    Callback::from(move |_: FreeCompany| {
+                        log::debug!("Your free companies changed in another tab, reload them");
+                        free_companies_state.run();
+                    }),
+                );
+            }
             free_companies_state.run();
             custom_fields_state.run();
             characters_state.run();
         });
     }
+    {
+        let event_source_state = event_source_state.clone();
+        use_unmount(move || event_source_state.borrow().close());
+    }
 
     if characters_state.loading {
         html!(
@@ -7,6 +7,7 @@ use yew_autoprops::autoprops;
 use yew_cosmo::prelude::*;
 use yew_hooks::{use_async, use_bool_toggle, use_effect_update, use_mount};
 
+use bamboo_common::core::entities::character_housing::{MAX_PLOT, MAX_WARD};
 use bamboo_common::core::entities::*;
 use bamboo_common::frontend::api::{ApiError, CONFLICT, NOT_FOUND};
 use bamboo_common::frontend::ui::{BambooCard, BambooCardList};
@@ -22,6 +23,19 @@ enum HousingActions {
     Closed,
 }
 
+fn housing_field_error_message(err: &ApiError) -> String {
+    match (
+        err.bamboo_error.field("ward").is_some(),
+        err.bamboo_error.field("plot").is_some(),
+    ) {
+        (true, true) => format!(
+            "Der Bezirk muss zwischen 1 und {MAX_WARD} und die Nummer zwischen 1 und {MAX_PLOT} liegen"
+        ),
+        (true, false) => format!("Der Bezirk muss zwischen 1 und {MAX_WARD} liegen"),
+        _ => format!("Die Nummer muss zwischen 1 und {MAX_PLOT} liegen"),
+    }
+}
+
 #[autoprops]
 #[function_component(ModifyHousingModal)]
 fn modify_housing_modal(
@@ -59,7 +73,7 @@ fn modify_housing_modal(
             )
         })
         .collect::<Vec<CosmoModernSelectItem>>();
-    let wards = (1..31i16)
+    let wards = (1..=MAX_WARD)
         .map(|ward| {
             CosmoModernSelectItem::new(
                 ward.to_string(),
@@ -68,7 +82,7 @@ fn modify_housing_modal(
             )
         })
         .collect::<Vec<CosmoModernSelectItem>>();
-    let plots = (1..61i16)
+    let plots = (1..=MAX_PLOT)
         .map(|plot| {
             CosmoModernSelectItem::new(
                 plot.to_string(),
@@ -213,6 +227,9 @@ pub fn housing_details(character: &Character) -> Html {
                             unreported_error_toggle.set(false);
                             error_message_state
                                 .set("Eine Unterkunft an dieser Adresse existiert bereits".into());
+                        } else if !err.bamboo_error.fields.is_empty() {
+                            unreported_error_toggle.set(false);
+                            error_message_state.set(housing_field_error_message(&err).into());
                         } else {
                             unreported_error_toggle.set(true);
                             error_message_state
@@ -268,6 +285,10 @@ pub fn housing_details(character: &Character) -> Html {
                                 error_message_state
                                     .set("Die Unterkunft konnte nicht gefunden werden".into());
                             }
+                            _ if !err.bamboo_error.fields.is_empty() => {
+                                unreported_error_toggle.set(false);
+                                error_message_state.set(housing_field_error_message(&err).into());
+                            }
                             _ => {
                                 unreported_error_toggle.set(true);
                                 error_message_state
@@ -29,9 +29,18 @@ pub async fn disable_totp() -> BambooApiResult<()> {
     api::delete("/api/my/totp").await
 }
 
-pub async fn validate_totp(code: String, password: String) -> BambooApiResult<()> {
+pub async fn validate_totp(code: String, password: String) -> BambooApiResult<RecoveryCodes> {
     log::debug!("Validate totp for current user");
-    api::put_no_content("/api/my/totp/validate", &ValidateTotp { code, password }).await
+    api::put("/api/my/totp/validate", &ValidateTotp { code, password }).await
+}
+
+pub async fn regenerate_recovery_codes(password: String) -> BambooApiResult<RecoveryCodes> {
+    log::debug!("Regenerate recovery codes for current user");
+    api::post(
+        "/api/my/totp/recovery-code",
+        &RegenerateRecoveryCodes { password },
+    )
+    .await
 }
 
 pub async fn leave() -> BambooApiResult<()> {
@@ -43,3 +52,75 @@ pub async fn upload_profile_picture(file: web_sys::File) -> BambooApiResult<()>
     log::debug!("Change profile picture");
     api::upload_file("/api/my/picture", file).await
 }
+
+pub async fn set_event_reminders(event_reminders: bool) -> BambooApiResult<()> {
+    if event_reminders {
+        log::debug!("Enable event reminders for current user");
+        api::put_no_body_no_content("/api/my/event-reminder").await
+    } else {
+        log::debug!("Disable event reminders for current user");
+        api::delete("/api/my/event-reminder").await
+    }
+}
+
+pub async fn get_calendar_feed() -> BambooApiResult<CalendarFeed> {
+    log::debug!("Get calendar feed of current user");
+    api::get("/api/my/calendar-feed").await
+}
+
+pub async fn rotate_calendar_feed() -> BambooApiResult<CalendarFeed> {
+    log::debug!("Rotate calendar feed of current user");
+    api::post_no_body("/api/my/calendar-feed").await
+}
+
+pub async fn get_sessions() -> BambooApiResult<Vec<Session>> {
+    log::debug!("Get sessions of current user");
+    api::get("/api/my/session").await
+}
+
+pub async fn delete_session(id: i32) -> BambooApiResult<()> {
+    log::debug!("Revoke session {id} of current user");
+    api::delete(format!("/api/my/session/{id}")).await
+}
+
+pub async fn delete_other_sessions() -> BambooApiResult<()> {
+    log::debug!("Revoke all other sessions of current user");
+    api::delete("/api/my/session").await
+}
+
+pub async fn get_passkeys() -> BambooApiResult<Vec<Passkey>> {
+    log::debug!("Get passkeys of current user");
+    api::get("/api/my/passkey").await
+}
+
+pub async fn create_passkey_challenge() -> BambooApiResult<PasskeyRegistrationOptions> {
+    log::debug!("Create passkey challenge for current user");
+    api::post_no_body("/api/my/passkey/challenge").await
+}
+
+pub async fn create_passkey(registration: PasskeyRegistration) -> BambooApiResult<Passkey> {
+    log::debug!("Register passkey for current user");
+    api::post("/api/my/passkey", &registration).await
+}
+
+pub async fn delete_passkey(id: i32) -> BambooApiResult<()> {
+    log::debug!("Delete passkey {id} of current user");
+    api::delete(format!("/api/my/passkey/{id}")).await
+}
+
+pub async fn get_personal_access_tokens() -> BambooApiResult<Vec<PersonalAccessToken>> {
+    log::debug!("Get personal access tokens of current user");
+    api::get("/api/my/token").await
+}
+
+pub async fn create_personal_access_token(
+    personal_access_token: PersonalAccessToken,
+) -> BambooApiResult<CreatedPersonalAccessToken> {
+    log::debug!("Create personal access token for current user");
+    api::post("/api/my/token", &personal_access_token).await
+}
+
+pub async fn delete_personal_access_token(id: i32) -> BambooApiResult<()> {
+    log::debug!("Delete personal access token {id} of current user");
+    api::delete(format!("/api/my/token/{id}")).await
+}
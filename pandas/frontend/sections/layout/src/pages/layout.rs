@@ -2,6 +2,7 @@ use std::ops::Deref;
 
 use bounce::helmet::Helmet;
 use bounce::{use_atom_setter, use_atom_value};
+use strum::IntoEnumIterator;
 use stylist::yew::use_style;
 use yew::prelude::*;
 use yew_autoprops::autoprops;
@@ -10,14 +11,22 @@ use yew_hooks::{use_async, use_bool_toggle, use_mount, use_update, UseAsyncHandl
 use yew_router::prelude::*;
 
 use bamboo_common::core::entities::user::UpdateProfile;
+use bamboo_common::core::entities::{
+    CalendarFeed, Permission, PersonalAccessToken, TokenScope, WebUser,
+};
 use bamboo_common::frontend::api::{ApiError, CONFLICT, FORBIDDEN, NOT_FOUND};
+use bamboo_common::frontend::ui::BambooFieldErrors;
 use bamboo_pandas_frontend_base::routing::{
     AppRoute, BambooGroveRoute, FinalFantasyRoute, LegalRoute, LicensesRoute, ModAreaRoute,
     SupportRoute,
 };
-use bamboo_pandas_frontend_base::{error, storage};
-use bamboo_pandas_frontend_section_authentication::LoginPage;
+use bamboo_pandas_frontend_base::webauthn::PasskeyError;
+use bamboo_pandas_frontend_base::{error, storage, webauthn};
+use bamboo_pandas_frontend_section_authentication::{
+    AcceptInvitationPage, LoginPage, ResetPasswordPage,
+};
 use bamboo_pandas_frontend_section_bamboo::CalendarPage;
+use bamboo_pandas_frontend_section_bamboo::TrashPage;
 use bamboo_pandas_frontend_section_bamboo::UsersPage;
 use bamboo_pandas_frontend_section_final_fantasy::CharacterPage;
 use bamboo_pandas_frontend_section_final_fantasy::SettingsPage;
@@ -25,7 +34,10 @@ use bamboo_pandas_frontend_section_legal::{DataProtectionPage, ImprintPage};
 use bamboo_pandas_frontend_section_licenses::{
     BambooGrovePage, FontsPage, ImagesPage, SoftwareLicensesPage,
 };
-use bamboo_pandas_frontend_section_mod_area::{GroveManagementPage, UserManagementPage};
+use bamboo_pandas_frontend_section_mod_area::{
+    AuditLogPage, GroveManagementPage, InvitationManagementPage, RoleManagementPage,
+    UserManagementPage,
+};
 use bamboo_pandas_frontend_section_support::ContactPage;
 
 use crate::api;
@@ -40,16 +52,43 @@ pub fn switch(route: AppRoute) -> Html {
                 <LoginPage />
             </>
         ),
+        AppRoute::ResetPassword => html!(
+            <>
+                <Helmet>
+                    <title>{"Passwort zurücksetzen"}</title>
+                </Helmet>
+                <ResetPasswordPage />
+            </>
+        ),
+        AppRoute::Invitation => html!(
+            <>
+                <Helmet>
+                    <title>{"Einladung annehmen"}</title>
+                </Helmet>
+                <AcceptInvitationPage />
+            </>
+        ),
         _ => html!(<Layout />),
     }
 }
 
-fn switch_sub_menu(route: AppRoute) -> Html {
+fn can_open_mod_area_route(profile: &WebUser, route: &ModAreaRoute) -> bool {
     match route {
+        ModAreaRoute::UserManagement | ModAreaRoute::Invitations | ModAreaRoute::Roles => {
+            profile.has_permission(Permission::ManageUsers)
+        }
+        ModAreaRoute::GroveManagement => profile.has_permission(Permission::ManageGrove),
+        ModAreaRoute::AuditLog => profile.has_permission(Permission::ViewAuditLog),
+    }
+}
+
+fn switch_sub_menu(profile: WebUser) -> impl Fn(AppRoute) -> Html {
+    move |route| match route {
         AppRoute::BambooGroveRoot | AppRoute::BambooGrove => html!(
             <CosmoSubMenuBar>
                 <Switch<BambooGroveRoute> render={render_sub_menu_entry("Event Kalender", BambooGroveRoute::Calendar)} />
                 <Switch<BambooGroveRoute> render={render_sub_menu_entry("Pandas", BambooGroveRoute::User)} />
+                <Switch<BambooGroveRoute> render={render_sub_menu_entry("Papierkorb", BambooGroveRoute::Trash)} />
             </CosmoSubMenuBar>
         ),
         AppRoute::FinalFantasyRoot | AppRoute::FinalFantasy => html!(
@@ -65,8 +104,17 @@ fn switch_sub_menu(route: AppRoute) -> Html {
         ),
         AppRoute::ModAreaRoot | AppRoute::ModArea => html!(
             <CosmoSubMenuBar>
-                <Switch<ModAreaRoute> render={render_sub_menu_entry("Benutzerverwaltung", ModAreaRoute::UserManagement)} />
-                <Switch<ModAreaRoute> render={render_sub_menu_entry("Hainverwaltung", ModAreaRoute::GroveManagement)} />
+                if profile.has_permission(Permission::ManageUsers) {
+                    <Switch<ModAreaRoute> render={render_sub_menu_entry("Benutzerverwaltung", ModAreaRoute::UserManagement)} />
+                    <Switch<ModAreaRoute> render={render_sub_menu_entry("Einladungen", ModAreaRoute::Invitations)} />
+                    <Switch<ModAreaRoute> render={render_sub_menu_entry("Rollen", ModAreaRoute::Roles)} />
+                }
+                if profile.has_permission(Permission::ManageGrove) {
+                    <Switch<ModAreaRoute> render={render_sub_menu_entry("Hainverwaltung", ModAreaRoute::GroveManagement)} />
+                }
+                if profile.has_permission(Permission::ViewAuditLog) {
+                    <Switch<ModAreaRoute> render={render_sub_menu_entry("Protokoll", ModAreaRoute::AuditLog)} />
+                }
             </CosmoSubMenuBar>
         ),
         AppRoute::LegalRoot | AppRoute::Legal => html!(
@@ -129,6 +177,14 @@ fn switch_bamboo_grove(route: BambooGroveRoute) -> Html {
                 <UsersPage />
             </>
         ),
+        BambooGroveRoute::Trash => html!(
+            <>
+                <Helmet>
+                    <title>{"Papierkorb"}</title>
+                </Helmet>
+                <TrashPage />
+            </>
+        ),
     }
 }
 
@@ -145,7 +201,28 @@ fn switch_support(route: SupportRoute) -> Html {
     }
 }
 
-fn switch_mod_area(route: ModAreaRoute) -> Html {
+fn switch_mod_area(profile: WebUser) -> impl Fn(ModAreaRoute) -> Html {
+    move |route| {
+        if !can_open_mod_area_route(&profile, &route) {
+            return if let Some(route) = [
+                ModAreaRoute::UserManagement,
+                ModAreaRoute::GroveManagement,
+                ModAreaRoute::AuditLog,
+            ]
+            .into_iter()
+            .find(|route| can_open_mod_area_route(&profile, route))
+            {
+                html!(<Redirect<ModAreaRoute> to={route} />)
+            } else {
+                html!(<Redirect<AppRoute> to={AppRoute::BambooGroveRoot} />)
+            };
+        }
+
+        switch_mod_area_route(route)
+    }
+}
+
+fn switch_mod_area_route(route: ModAreaRoute) -> Html {
     match route {
         ModAreaRoute::UserManagement => html!(
             <>
@@ -155,6 +232,22 @@ fn switch_mod_area(route: ModAreaRoute) -> Html {
                 <UserManagementPage />
             </>
         ),
+        ModAreaRoute::Invitations => html!(
+            <>
+                <Helmet>
+                    <title>{"Einladungen"}</title>
+                </Helmet>
+                <InvitationManagementPage />
+            </>
+        ),
+        ModAreaRoute::Roles => html!(
+            <>
+                <Helmet>
+                    <title>{"Rollen"}</title>
+                </Helmet>
+                <RoleManagementPage />
+            </>
+        ),
         ModAreaRoute::GroveManagement => html!(
             <>
                 <Helmet>
@@ -163,6 +256,14 @@ fn switch_mod_area(route: ModAreaRoute) -> Html {
                 <GroveManagementPage />
             </>
         ),
+        ModAreaRoute::AuditLog => html!(
+            <>
+                <Helmet>
+                    <title>{"Protokoll"}</title>
+                </Helmet>
+                <AuditLogPage />
+            </>
+        ),
     }
 }
 
@@ -224,7 +325,7 @@ fn switch_licenses(route: LicensesRoute) -> Html {
     }
 }
 
-fn switch_app(is_mod: bool, grove_is_enabled: bool) -> impl Fn(AppRoute) -> Html {
+fn switch_app(profile: WebUser, grove_is_enabled: bool) -> impl Fn(AppRoute) -> Html {
     move |route| {
         if grove_is_enabled {
             match route {
@@ -256,13 +357,13 @@ fn switch_app(is_mod: bool, grove_is_enabled: bool) -> impl Fn(AppRoute) -> Html
                     </>
                 ),
                 AppRoute::ModAreaRoot | AppRoute::ModArea => {
-                    if is_mod {
+                    if !profile.permissions.is_empty() {
                         html!(
                             <>
                                 <Helmet>
                                     <title>{"Mod Area"}</title>
                                 </Helmet>
-                                <Switch<ModAreaRoute> render={switch_mod_area} />
+                                <Switch<ModAreaRoute> render={switch_mod_area(profile.clone())} />
                             </>
                         )
                     } else {
@@ -287,7 +388,7 @@ fn switch_app(is_mod: bool, grove_is_enabled: bool) -> impl Fn(AppRoute) -> Html
                         <Switch<LicensesRoute> render={switch_licenses} />
                     </>
                 ),
-                AppRoute::Login => html!(),
+                AppRoute::Login | AppRoute::ResetPassword | AppRoute::Invitation => html!(),
             }
         } else {
             match route {
@@ -312,7 +413,7 @@ fn switch_app(is_mod: bool, grove_is_enabled: bool) -> impl Fn(AppRoute) -> Html
                         <Helmet>
                             <title>{"Mod Area"}</title>
                         </Helmet>
-                        <Switch<ModAreaRoute> render={switch_mod_area} />
+                        <Switch<ModAreaRoute> render={switch_mod_area(profile.clone())} />
                     </>
                 ),
             }
@@ -349,7 +450,7 @@ fn render_sub_menu_entry<Route: Routable + Clone + 'static>(
 
 fn switch_top_bar(route: AppRoute) -> Html {
     match route {
-        AppRoute::Login => html!(),
+        AppRoute::Login | AppRoute::ResetPassword | AppRoute::Invitation => html!(),
         AppRoute::LegalRoot | AppRoute::Legal | AppRoute::LicensesRoot | AppRoute::Licenses => {
             html!(
                 <TopBarLegal />
@@ -425,14 +526,14 @@ fn app_layout() -> Html {
                             <Switch<AppRoute> render={render_main_menu_entry("Final Fantasy", AppRoute::FinalFantasyRoot, AppRoute::FinalFantasy)} />
                             <Switch<AppRoute> render={render_main_menu_entry("Bambussupport", AppRoute::SupportRoot, AppRoute::Support)} />
                         }
-                        if profile.is_mod {
+                        if !profile.permissions.is_empty() {
                             <Switch<AppRoute> render={render_main_menu_entry("Mod Area", AppRoute::ModAreaRoot, AppRoute::ModArea)} />
                         }
                     </CosmoMainMenu>
-                    <Switch<AppRoute> render={switch_sub_menu} />
+                    <Switch<AppRoute> render={switch_sub_menu(profile.clone())} />
                 </CosmoMenuBar>
                 <CosmoPageBody>
-                    <Switch<AppRoute> render={switch_app(profile.is_mod, *grove_is_enabled)} />
+                    <Switch<AppRoute> render={switch_app(profile.clone(), *grove_is_enabled)} />
                </CosmoPageBody>
             </>
         }
@@ -450,10 +551,10 @@ fn legal_layout() -> Html {
                     <Switch<AppRoute> render={render_main_menu_entry("Rechtliches", AppRoute::LegalRoot, AppRoute::Legal)} />
                     <Switch<AppRoute> render={render_main_menu_entry("Lizenzen", AppRoute::LicensesRoot, AppRoute::Licenses)} />
                 </CosmoMainMenu>
-                <Switch<AppRoute> render={switch_sub_menu} />
+                <Switch<AppRoute> render={switch_sub_menu(WebUser::default())} />
             </CosmoMenuBar>
             <CosmoPageBody>
-                <Switch<AppRoute> render={switch_app(false, true)} />
+                <Switch<AppRoute> render={switch_app(WebUser::default(), true)} />
            </CosmoPageBody>
         </>
     )
@@ -563,6 +664,7 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
 
     let disable_totp_open_toggle = use_bool_toggle(false);
     let app_two_factor_open_toggle = use_bool_toggle(false);
+    let recovery_codes_open_toggle = use_bool_toggle(false);
     let unreported_error_toggle = use_bool_toggle(false);
 
     let bamboo_error_state = use_state_eq(ApiError::default);
@@ -574,12 +676,16 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
         use_state_eq(|| AttrValue::from(profile_atom.profile.display_name.clone()));
     let discord_name_state =
         use_state_eq(|| AttrValue::from(profile_atom.profile.discord_name.clone()));
+    let event_reminders_state = use_state_eq(|| profile_atom.profile.event_reminders);
 
     let update_email = use_callback(email_state.clone(), |value, state| state.set(value));
     let update_display_name =
         use_callback(display_name_state.clone(), |value, state| state.set(value));
     let update_discord_name =
         use_callback(discord_name_state.clone(), |value, state| state.set(value));
+    let update_event_reminders = use_callback(event_reminders_state.clone(), |value, state| {
+        state.set(value)
+    });
     let select_profile_picture = use_callback(profile_picture_state.clone(), |value, state| {
         state.set(Some(value))
     });
@@ -606,24 +712,33 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
         let email_state = email_state.clone();
         let display_name_state = display_name_state.clone();
         let discord_name_state = discord_name_state.clone();
+        let event_reminders_state = event_reminders_state.clone();
 
         let profile_picture_state = profile_picture_state.clone();
 
+        let event_reminders = profile_atom.profile.event_reminders;
+
         let on_close = on_close.clone();
 
         use_async(async move {
-            let result = api::update_my_profile(UpdateProfile::new(
+            let mut result = api::update_my_profile(UpdateProfile::new(
                 (*email_state).to_string(),
                 (*display_name_state).to_string(),
                 (*discord_name_state).to_string(),
             ))
-            .await
-            .map(|_| unreported_error_toggle.set(false))
-            .map_err(|err| {
-                unreported_error_toggle.set(true);
-                bamboo_error_state.set(err.clone());
-                err
-            });
+            .await;
+            if result.is_ok() && *event_reminders_state != event_reminders {
+                result = api::set_event_reminders(*event_reminders_state).await;
+                if let Ok(profile) = api::get_my_profile().await {
+                    profile_atom_setter(profile.into());
+                }
+            }
+            let result = result
+                .map(|_| unreported_error_toggle.set(false))
+                .inspect_err(|err| {
+                    unreported_error_toggle.set(true);
+                    bamboo_error_state.set(err.clone());
+                });
             if result.is_ok() {
                 if let Some(profile_picture) = (*profile_picture_state).clone() {
                     let profile_result = api::upload_profile_picture(profile_picture)
@@ -690,11 +805,24 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
         app_two_factor_open_toggle.clone(),
         |_, app_two_factor_open_toggle| app_two_factor_open_toggle.set(true),
     );
+    let on_open_recovery_codes = use_callback(recovery_codes_open_toggle.clone(), |_, state| {
+        state.set(true)
+    });
     let on_save = use_callback(save_state.clone(), |_, save_state| save_state.run());
     let on_disable_totp = use_callback(disable_totp_state.clone(), |_, disable_totp_state| {
         disable_totp_state.run()
     });
     let on_close = on_close.clone();
+    let field_inputs = vec![
+        (
+            AttrValue::from("email"),
+            AttrValue::from("my-profile-email"),
+        ),
+        (
+            AttrValue::from("displayName"),
+            AttrValue::from("my-profile-display-name"),
+        ),
+    ];
 
     html!(
         <>
@@ -706,6 +834,7 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
                     <CosmoButton on_click={on_close} label="Schließen" />
                     if profile_atom.profile.app_totp_enabled {
                         <CosmoButton on_click={on_open_disable_totp} label="App Zwei Faktor deaktivieren" />
+                        <CosmoButton on_click={on_open_recovery_codes} label="Wiederherstellungscodes" />
                     } else {
                         <CosmoButton on_click={on_enable_app_two_factor} label="App Zwei Faktor aktivieren" />
                     }
@@ -716,7 +845,9 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
                     if err.code == NOT_FOUND {
                         <CosmoMessage message_type={CosmoMessageType::Negative} message="Bitte versuch es erneut um einen Fehler auszuschließen" header="Du wurdest scheinbar gelöscht" />
                     } else if err.code == CONFLICT {
-                        <CosmoMessage message_type={CosmoMessageType::Negative} message="Die Email oder der Name ist leider schon vergeben" header="Leider schon vergeben" />
+                        <CosmoMessage message_type={CosmoMessageType::Negative} message="Die Email oder der Name ist leider schon vergeben, eventuell von einem Panda im Papierkorb" header="Leider schon vergeben" />
+                    } else if !err.bamboo_error.fields.is_empty() {
+                        <CosmoMessage message_type={CosmoMessageType::Negative} message="Bitte prüfe die markierten Felder" header="Fehler beim Speichern" />
                     } else if *unreported_error_toggle {
                         <CosmoMessage message_type={CosmoMessageType::Negative} message="Dein Profil konnte leider nicht gespeichert werden" header="Fehler beim Speichern" actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
                     } else {
@@ -732,19 +863,24 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
                         <CosmoMessage message_type={CosmoMessageType::Negative} message="Zwei Faktor per App konnte leider nicht deaktiviert werden" header="Fehler beim Deaktivieren" />
                     }
                 }
+                <BambooFieldErrors error={save_state.error.clone().map(|err| err.bamboo_error)} inputs={field_inputs} />
                 <CosmoInputGroup>
-                    <CosmoTextBox label="Email" input_type={CosmoTextBoxType::Email} required={true} on_input={update_email} value={(*email_state).clone()} />
-                    <CosmoTextBox label="Name" required={true} on_input={update_display_name} value={(*display_name_state).clone()} />
+                    <CosmoTextBox id="my-profile-email" label="Email" input_type={CosmoTextBoxType::Email} required={true} on_input={update_email} value={(*email_state).clone()} />
+                    <CosmoTextBox id="my-profile-display-name" label="Name" required={true} on_input={update_display_name} value={(*display_name_state).clone()} />
                     <CosmoTextBox label="Discord Name (optional)" on_input={update_discord_name} value={(*discord_name_state).clone()} />
                     <CosmoFilePicker label="Profilbild (optional)" on_select={select_profile_picture} />
+                    <CosmoSwitch label="Erinnerungen an Events per Mail" checked={*event_reminders_state} on_check={update_event_reminders} />
                 </CosmoInputGroup>
             </CosmoModal>
             if *disable_totp_open_toggle {
-                <CosmoConfirm confirm_type={CosmoModalType::Warning} message="Möchtest du deine Zwei Faktor Authentifizierung per App deaktivieren? Du bekommst dann wieder eine Email." title="Zwei Faktor Authentifizierung deaktivieren" on_decline={on_close_disable_totp} on_confirm={on_disable_totp} confirm_label="Deaktivieren" decline_label="Nicht deaktivieren" />
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} message="Möchtest du deine Zwei Faktor Authentifizierung per App deaktivieren? Du bekommst dann wieder eine Email und deine Wiederherstellungscodes werden ungültig." title="Zwei Faktor Authentifizierung deaktivieren" on_decline={on_close_disable_totp} on_confirm={on_disable_totp} confirm_label="Deaktivieren" decline_label="Nicht deaktivieren" />
             }
             if *app_two_factor_open_toggle {
                 <EnableTotpDialog on_close={move |_| app_two_factor_open_toggle.set(false)} />
             }
+            if *recovery_codes_open_toggle {
+                <RecoveryCodesDialog on_close={move |_| recovery_codes_open_toggle.set(false)} />
+            }
         </>
     )
 }
@@ -788,30 +924,30 @@ fn enable_totp_dialog(on_close: &Callback<()>) -> Html {
         let code_state = code_state.clone();
         let current_password_state = current_password_state.clone();
 
-        let on_close = on_close.clone();
-
         let profile_atom = profile_atom.clone();
 
         use_async(async move {
-            if let Err(err) = api::validate_totp(
+            match api::validate_totp(
                 (*code_state).to_string(),
                 (*current_password_state).to_string(),
             )
             .await
             {
-                log::error!("Failed to validate token: {err}");
-                unreported_error_toggle.set(true);
-                bamboo_error_state.set(err.clone());
+                Ok(recovery_codes) => {
+                    unreported_error_toggle.set(false);
+                    if let Ok(profile) = api::get_my_profile().await {
+                        profile_atom(profile.into())
+                    }
 
-                Err(err)
-            } else {
-                on_close.emit(());
-                unreported_error_toggle.set(false);
-                if let Ok(profile) = api::get_my_profile().await {
-                    profile_atom(profile.into())
+                    Ok(recovery_codes)
                 }
+                Err(err) => {
+                    log::error!("Failed to validate token: {err}");
+                    unreported_error_toggle.set(true);
+                    bamboo_error_state.set(err.clone());
 
-                Ok(())
+                    Err(err)
+                }
             }
         })
     };
@@ -832,9 +968,15 @@ fn enable_totp_dialog(on_close: &Callback<()>) -> Html {
         },
     );
     let on_form_submit = use_callback(
-        (enable_totp_state.clone(), validate_totp_state.clone()),
-        |_, (enable_totp_state, validate_totp_state)| {
-            if enable_totp_state.data.is_some() {
+        (
+            enable_totp_state.clone(),
+            validate_totp_state.clone(),
+            on_close.clone(),
+        ),
+        |_, (enable_totp_state, validate_totp_state, on_close)| {
+            if validate_totp_state.data.is_some() {
+                on_close.emit(());
+            } else if enable_totp_state.data.is_some() {
                 validate_totp_state.run();
             } else {
                 enable_totp_state.run();
@@ -856,12 +998,19 @@ object-fit: scale-down;
                 <title>{"Zwei Faktor per App aktivieren"}</title>
             </Helmet>
             <CosmoModal title="Zwei Faktor per App aktivieren" is_form={true} on_form_submit={on_form_submit} buttons={html!(
-                <>
-                    <CosmoButton on_click={on_close.clone()} label="Abbrechen" />
-                    <CosmoButton is_submit={true} label="App einrichten" />
-                </>
+                if validate_totp_state.data.is_some() {
+                    <CosmoButton is_submit={true} label="Fertig" />
+                } else {
+                    <>
+                        <CosmoButton on_click={on_close.clone()} label="Abbrechen" />
+                        <CosmoButton is_submit={true} label="App einrichten" />
+                    </>
+                }
             )}>
-                if let Some(data) = &enable_totp_state.data {
+                if let Some(recovery_codes) = &validate_totp_state.data {
+                    <CosmoParagraph>{"Zwei Faktor per App ist jetzt aktiv. Falls du dein Handy verlierst, kannst du dich mit einem dieser Wiederherstellungscodes anstelle des Zwei Faktor Codes anmelden. Jeder Code funktioniert nur einmal, bewahre sie gut auf, sie werden dir nur jetzt angezeigt."}</CosmoParagraph>
+                    <RecoveryCodeList codes={recovery_codes.codes.clone()} />
+                } else if let Some(data) = &enable_totp_state.data {
                     <img class={img_style} src={format!("data:image/png;base64,{}", data.qr_code.clone())} alt={data.secret.clone()} />
                     if let Some(err) = &validate_totp_state.error {
                         if err.code == FORBIDDEN {
@@ -894,6 +1043,607 @@ Anschließend kommt ein QR Code, den musst du scannen und danach einen Code aus
     )
 }
 
+#[autoprops]
+#[function_component(RecoveryCodeList)]
+fn recovery_code_list(codes: &Vec<String>) -> Html {
+    let list_style = use_style!(
+        r#"
+display: grid;
+grid-template-columns: repeat(2, 1fr);
+gap: 0.5rem 2rem;
+padding: 0;
+list-style: none;
+font-family: monospace;
+font-size: 1.25rem;
+"#
+    );
+
+    html!(
+        <ul class={list_style}>
+            {for codes.iter().map(|code| html!(<li>{code}</li>))}
+        </ul>
+    )
+}
+
+#[autoprops]
+#[function_component(RecoveryCodesDialog)]
+fn recovery_codes_dialog(on_close: &Callback<()>) -> Html {
+    log::debug!("Open dialog to regenerate the recovery codes");
+    let password_state = use_state_eq(|| AttrValue::from(""));
+
+    let regenerate_state = {
+        let password_state = password_state.clone();
+
+        use_async(
+            async move { api::regenerate_recovery_codes((*password_state).to_string()).await },
+        )
+    };
+
+    let update_password = use_callback(password_state.clone(), |value, state| state.set(value));
+    let on_form_submit = use_callback(
+        (regenerate_state.clone(), on_close.clone()),
+        |_, (regenerate_state, on_close)| {
+            if regenerate_state.data.is_some() {
+                on_close.emit(());
+            } else {
+                regenerate_state.run();
+            }
+        },
+    );
+
+    html!(
+        <>
+            <Helmet>
+                <title>{"Wiederherstellungscodes"}</title>
+            </Helmet>
+            <CosmoModal title="Wiederherstellungscodes" is_form={true} on_form_submit={on_form_submit} buttons={html!(
+                if regenerate_state.data.is_some() {
+                    <CosmoButton is_submit={true} label="Fertig" />
+                } else {
+                    <>
+                        <CosmoButton on_click={on_close.clone()} label="Abbrechen" />
+                        <CosmoButton is_submit={true} label="Neue Codes erzeugen" />
+                    </>
+                }
+            )}>
+                if let Some(recovery_codes) = &regenerate_state.data {
+                    <CosmoParagraph>{"Deine alten Wiederherstellungscodes funktionieren nicht mehr. Bewahre die neuen Codes gut auf, sie werden dir nur jetzt angezeigt."}</CosmoParagraph>
+                    <RecoveryCodeList codes={recovery_codes.codes.clone()} />
+                } else {
+                    if let Some(err) = &regenerate_state.error {
+                        if err.code == FORBIDDEN {
+                            <CosmoMessage header="Passwort falsch" message="Das von dir eingegebene Passwort ist ungültig, versuch es nochmal" message_type={CosmoMessageType::Negative} />
+                        } else {
+                            <CosmoMessage header="Fehler beim Erzeugen" message="Leider konnten keine neuen Wiederherstellungscodes erzeugt werden" message_type={CosmoMessageType::Negative} />
+                        }
+                    }
+                    <CosmoParagraph>{"Wenn du neue Wiederherstellungscodes erzeugst, funktionieren deine alten Codes nicht mehr. Bitte bestätige das mit deinem Passwort."}</CosmoParagraph>
+                    <CosmoInputGroup>
+                        <CosmoTextBox input_type={CosmoTextBoxType::Password} label="Aktuelles Passwort" required={true} on_input={update_password} value={(*password_state).clone()} />
+                    </CosmoInputGroup>
+                }
+            </CosmoModal>
+        </>
+    )
+}
+
+#[autoprops]
+#[function_component(CalendarFeedDialog)]
+fn calendar_feed_dialog(on_close: &Callback<()>) -> Html {
+    log::debug!("Open dialog to subscribe to the calendar");
+    let unreported_error_toggle = use_bool_toggle(false);
+    let rotate_feed_open_toggle = use_bool_toggle(false);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+
+    let feed_state = use_state_eq(|| None as Option<CalendarFeed>);
+
+    let load_feed_state = {
+        let feed_state = feed_state.clone();
+
+        use_async(async move {
+            api::get_calendar_feed()
+                .await
+                .map(|data| feed_state.set(Some(data)))
+        })
+    };
+    let rotate_feed_state = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+        let rotate_feed_open_toggle = rotate_feed_open_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        let feed_state = feed_state.clone();
+
+        use_async(async move {
+            rotate_feed_open_toggle.set(false);
+            api::rotate_calendar_feed()
+                .await
+                .map(|data| {
+                    unreported_error_toggle.set(false);
+                    feed_state.set(Some(data))
+                })
+                .inspect_err(|err| {
+                    unreported_error_toggle.set(true);
+                    bamboo_error_state.set(err.clone());
+                })
+        })
+    };
+
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "layout",
+                "calendar_feed_dialog",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+    let on_rotate = use_callback(
+        (
+            feed_state.clone(),
+            rotate_feed_state.clone(),
+            rotate_feed_open_toggle.clone(),
+        ),
+        |_, (feed_state, rotate_feed_state, rotate_feed_open_toggle)| {
+            if feed_state.is_some() {
+                rotate_feed_open_toggle.set(true);
+            } else {
+                rotate_feed_state.run();
+            }
+        },
+    );
+    let on_rotate_confirm = use_callback(rotate_feed_state.clone(), |_, state| state.run());
+    let on_rotate_decline = use_callback(rotate_feed_open_toggle.clone(), |_, toggle| {
+        toggle.set(false)
+    });
+
+    {
+        let load_feed_state = load_feed_state.clone();
+
+        use_mount(move || load_feed_state.run());
+    }
+
+    let feed_url = (*feed_state).clone().map(|feed| {
+        AttrValue::from(format!(
+            "{}/api/bamboo-grove/event/feed/{}.ics",
+            gloo_utils::window().location().origin().unwrap_or_default(),
+            feed.secret
+        ))
+    });
+
+    html!(
+        <>
+            <Helmet>
+                <title>{"Kalender abonnieren"}</title>
+            </Helmet>
+            <CosmoModal title="Kalender abonnieren" buttons={html!(
+                <>
+                    <CosmoButton on_click={on_close.clone()} label="Schließen" />
+                    if feed_url.is_some() {
+                        <CosmoButton on_click={on_rotate} label="Neue Adresse erzeugen" />
+                    } else {
+                        <CosmoButton on_click={on_rotate} label="Adresse erzeugen" />
+                    }
+                </>
+            )}>
+                if rotate_feed_state.error.is_some() {
+                    if *unreported_error_toggle {
+                        <CosmoMessage header="Fehler beim Erzeugen" message="Die Adresse für deinen Kalender konnte leider nicht erzeugt werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                    } else {
+                        <CosmoMessage header="Fehler beim Erzeugen" message="Die Adresse für deinen Kalender konnte leider nicht erzeugt werden" message_type={CosmoMessageType::Negative} />
+                    }
+                }
+                if load_feed_state.loading || rotate_feed_state.loading {
+                    <CosmoProgressRing />
+                } else if let Some(feed_url) = feed_url {
+                    <CosmoParagraph>{"Mit der folgenden Adresse kannst du den Event Kalender in Thunderbird, Outlook oder auf deinem Handy abonnieren. Private Events siehst nur du. Gib die Adresse daher nicht weiter, wenn sie in falsche Hände gerät kannst du eine neue Adresse erzeugen."}</CosmoParagraph>
+                    <CosmoInputGroup>
+                        <CosmoTextBox label="Adresse" readonly={true} value={feed_url} on_input={|_| {}} />
+                    </CosmoInputGroup>
+                } else {
+                    <CosmoParagraph>{"Du hast noch keine Adresse um den Event Kalender zu abonnieren. Sobald du eine Adresse erzeugst, kannst du den Kalender in Thunderbird, Outlook oder auf deinem Handy abonnieren."}</CosmoParagraph>
+                }
+            </CosmoModal>
+            if *rotate_feed_open_toggle {
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} on_confirm={on_rotate_confirm} on_decline={on_rotate_decline} title="Neue Adresse erzeugen" message="Wenn du eine neue Adresse erzeugst, funktioniert die alte Adresse nicht mehr.\nDu musst den Kalender dann überall neu abonnieren." confirm_label="Neue Adresse erzeugen" decline_label="Abbrechen" />
+            }
+        </>
+    )
+}
+
+fn format_session_time(time: chrono::NaiveDateTime) -> String {
+    time.and_utc()
+        .with_timezone(&chrono::Local)
+        .format("%d.%m.%Y %H:%M")
+        .to_string()
+}
+
+#[autoprops]
+#[function_component(SessionsDialog)]
+fn sessions_dialog(on_close: &Callback<()>) -> Html {
+    log::debug!("Open dialog to manage the sessions");
+    let revoke_others_open_toggle = use_bool_toggle(false);
+
+    let revoke_session_state = use_state_eq(|| None as Option<i32>);
+
+    let sessions_state = use_async(async move { api::get_sessions().await });
+    let delete_session_state = {
+        let sessions_state = sessions_state.clone();
+        let revoke_session_state = revoke_session_state.clone();
+
+        use_async(async move {
+            if let Some(id) = *revoke_session_state {
+                api::delete_session(id).await.map(|_| {
+                    revoke_session_state.set(None);
+                    sessions_state.run();
+                })
+            } else {
+                Ok(())
+            }
+        })
+    };
+    let delete_other_sessions_state = {
+        let sessions_state = sessions_state.clone();
+        let revoke_others_open_toggle = revoke_others_open_toggle.clone();
+
+        use_async(async move {
+            revoke_others_open_toggle.set(false);
+            api::delete_other_sessions()
+                .await
+                .map(|_| sessions_state.run())
+        })
+    };
+
+    let on_revoke_others = use_callback(revoke_others_open_toggle.clone(), |_, toggle| {
+        toggle.set(true)
+    });
+    let on_revoke_others_confirm =
+        use_callback(delete_other_sessions_state.clone(), |_, state| state.run());
+    let on_revoke_others_decline = use_callback(revoke_others_open_toggle.clone(), |_, toggle| {
+        toggle.set(false)
+    });
+    let on_revoke_confirm = use_callback(delete_session_state.clone(), |_, state| state.run());
+    let on_revoke_decline = use_callback(revoke_session_state.clone(), |_, state| state.set(None));
+
+    {
+        let sessions_state = sessions_state.clone();
+
+        use_mount(move || sessions_state.run());
+    }
+
+    html!(
+        <>
+            <Helmet>
+                <title>{"Angemeldete Geräte"}</title>
+            </Helmet>
+            <CosmoModal title="Angemeldete Geräte" buttons={html!(
+                <>
+                    <CosmoButton on_click={on_close.clone()} label="Schließen" />
+                    <CosmoButton on_click={on_revoke_others} label="Alle anderen abmelden" />
+                </>
+            )}>
+                <CosmoParagraph>{"Hier siehst du alle Geräte, auf denen du gerade angemeldet bist. Wenn du ein Gerät nicht kennst, melde es ab und ändere dein Passwort."}</CosmoParagraph>
+                if delete_session_state.error.is_some() || delete_other_sessions_state.error.is_some() {
+                    <CosmoMessage header="Fehler beim Abmelden" message="Das Gerät konnte leider nicht abgemeldet werden" message_type={CosmoMessageType::Negative} />
+                }
+                if sessions_state.loading {
+                    <CosmoProgressRing />
+                } else if sessions_state.error.is_some() {
+                    <CosmoMessage header="Fehler beim Laden" message="Die angemeldeten Geräte konnten leider nicht geladen werden" message_type={CosmoMessageType::Negative} />
+                } else if let Some(sessions) = &sessions_state.data {
+                    <CosmoTable headers={vec![AttrValue::from("Gerät"), AttrValue::from("Angemeldet seit"), AttrValue::from("Zuletzt aktiv"), AttrValue::from("Läuft ab"), AttrValue::from("")]}>
+                        {for sessions.iter().map(|session| {
+                            let id = session.id;
+                            let revoke_session_state = revoke_session_state.clone();
+
+                            CosmoTableRow::from_table_cells(vec![
+                                CosmoTableCell::from_html(html!(session.user_agent.clone().unwrap_or("Unbekanntes Gerät".into())), None),
+                                CosmoTableCell::from_html(html!(format_session_time(session.created_at)), None),
+                                CosmoTableCell::from_html(html!(format_session_time(session.last_used_at)), None),
+                                CosmoTableCell::from_html(html!(format_session_time(session.expires_at)), None),
+                                CosmoTableCell::from_html(if session.current {
+                                    html!({"Dieses Gerät"})
+                                } else {
+                                    html!(<CosmoButton label="Abmelden" on_click={move |_| revoke_session_state.set(Some(id))} />)
+                                }, None),
+                            ], Some(session.id.into()))
+                        })}
+                    </CosmoTable>
+                }
+            </CosmoModal>
+            if revoke_session_state.is_some() {
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} on_confirm={on_revoke_confirm} on_decline={on_revoke_decline} title="Gerät abmelden" message="Soll das Gerät wirklich abgemeldet werden?" confirm_label="Gerät abmelden" decline_label="Abbrechen" />
+            }
+            if *revoke_others_open_toggle {
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} on_confirm={on_revoke_others_confirm} on_decline={on_revoke_others_decline} title="Alle anderen abmelden" message="Du wirst auf allen anderen Geräten abgemeldet und musst dich dort neu anmelden." confirm_label="Alle anderen abmelden" decline_label="Abbrechen" />
+            }
+        </>
+    )
+}
+
+#[autoprops]
+#[function_component(PasskeysDialog)]
+fn passkeys_dialog(on_close: &Callback<()>) -> Html {
+    log::debug!("Open dialog to manage the passkeys");
+    let name_state = use_state_eq(|| AttrValue::from(""));
+
+    let delete_passkey_state = use_state_eq(|| None as Option<i32>);
+
+    let passkeys_state = use_async(async move { api::get_passkeys().await });
+    let create_passkey_state = {
+        let name_state = name_state.clone();
+        let passkeys_state = passkeys_state.clone();
+
+        use_async(async move {
+            let options = api::create_passkey_challenge()
+                .await
+                .map_err(|_| "Der Passkey konnte leider nicht hinzugefügt werden")?;
+            let registration = webauthn::create_passkey((*name_state).to_string(), options)
+                .await
+                .map_err(|err| match err {
+                    PasskeyError::Cancelled => "Das Hinzufügen wurde abgebrochen",
+                    PasskeyError::NotSupported => "Dein Browser unterstützt leider keine Passkeys",
+                    PasskeyError::Failed(err) => {
+                        log::error!("Failed to create passkey {err}");
+                        "Der Passkey konnte leider nicht hinzugefügt werden"
+                    }
+                })?;
+
+            api::create_passkey(registration)
+                .await
+                .map(|_| {
+                    name_state.set("".into());
+                    passkeys_state.run();
+                })
+                .map_err(|err| {
+                    if err.code == CONFLICT {
+                        "Der Passkey ist bereits hinterlegt"
+                    } else {
+                        "Der Passkey konnte leider nicht hinzugefügt werden"
+                    }
+                })
+        })
+    };
+    let delete_state = {
+        let passkeys_state = passkeys_state.clone();
+        let delete_passkey_state = delete_passkey_state.clone();
+
+        use_async(async move {
+            if let Some(id) = *delete_passkey_state {
+                api::delete_passkey(id).await.map(|_| {
+                    delete_passkey_state.set(None);
+                    passkeys_state.run();
+                })
+            } else {
+                Ok(())
+            }
+        })
+    };
+
+    let update_name = use_callback(name_state.clone(), |value, state| state.set(value));
+    let on_create = use_callback(create_passkey_state.clone(), |_, state| state.run());
+    let on_delete_confirm = use_callback(delete_state.clone(), |_, state| state.run());
+    let on_delete_decline = use_callback(delete_passkey_state.clone(), |_, state| state.set(None));
+
+    {
+        let passkeys_state = passkeys_state.clone();
+
+        use_mount(move || passkeys_state.run());
+    }
+
+    html!(
+        <>
+            <Helmet>
+                <title>{"Passkeys"}</title>
+            </Helmet>
+            <CosmoModal title="Passkeys" is_form={true} on_form_submit={on_create} buttons={html!(
+                <>
+                    <CosmoButton on_click={on_close.clone()} label="Schließen" />
+                    if webauthn::is_supported() {
+                        <CosmoButton label="Passkey hinzufügen" is_submit={true} />
+                    }
+                </>
+            )}>
+                <CosmoParagraph>{"Mit einem Passkey kannst du dich ohne Passwort und Zwei Faktor Code anmelden, zum Beispiel mit deinem Fingerabdruck oder der PIN deines Geräts."}</CosmoParagraph>
+                if !webauthn::is_supported() {
+                    <CosmoMessage header="Keine Passkeys möglich" message="Dein Browser unterstützt leider keine Passkeys" message_type={CosmoMessageType::Warning} />
+                }
+                if let Some(error) = create_passkey_state.error {
+                    <CosmoMessage header="Fehler beim Hinzufügen" message={error} message_type={CosmoMessageType::Negative} />
+                }
+                if delete_state.error.is_some() {
+                    <CosmoMessage header="Fehler beim Entfernen" message="Der Passkey konnte leider nicht entfernt werden" message_type={CosmoMessageType::Negative} />
+                }
+                if passkeys_state.loading {
+                    <CosmoProgressRing />
+                } else if passkeys_state.error.is_some() {
+                    <CosmoMessage header="Fehler beim Laden" message="Deine Passkeys konnten leider nicht geladen werden" message_type={CosmoMessageType::Negative} />
+                } else if let Some(passkeys) = &passkeys_state.data {
+                    <CosmoTable headers={vec![AttrValue::from("Name"), AttrValue::from("Hinzugefügt am"), AttrValue::from("Zuletzt verwendet"), AttrValue::from("")]}>
+                        {for passkeys.iter().map(|passkey| {
+                            let id = passkey.id;
+                            let delete_passkey_state = delete_passkey_state.clone();
+
+                            CosmoTableRow::from_table_cells(vec![
+                                CosmoTableCell::from_html(html!(passkey.name.clone()), None),
+                                CosmoTableCell::from_html(html!(format_session_time(passkey.created_at)), None),
+                                CosmoTableCell::from_html(html!(passkey.last_used_at.map(format_session_time).unwrap_or("Noch nie".into())), None),
+                                CosmoTableCell::from_html(html!(<CosmoButton label="Entfernen" on_click={move |_| delete_passkey_state.set(Some(id))} />), None),
+                            ], Some(passkey.id.into()))
+                        })}
+                    </CosmoTable>
+                }
+                if webauthn::is_supported() {
+                    <CosmoInputGroup>
+                        <CosmoTextBox label="Name des Passkeys" required={true} on_input={update_name} value={(*name_state).clone()} />
+                    </CosmoInputGroup>
+                }
+            </CosmoModal>
+            if delete_passkey_state.is_some() {
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} on_confirm={on_delete_confirm} on_decline={on_delete_decline} title="Passkey entfernen" message="Soll der Passkey wirklich entfernt werden? Du kannst dich danach nicht mehr damit anmelden." confirm_label="Passkey entfernen" decline_label="Abbrechen" />
+            }
+        </>
+    )
+}
+
+#[autoprops]
+#[function_component(PersonalAccessTokensDialog)]
+fn personal_access_tokens_dialog(on_close: &Callback<()>) -> Html {
+    log::debug!("Open dialog to manage the personal access tokens");
+    let name_state = use_state_eq(|| AttrValue::from(""));
+    let expiry_state = use_state_eq(|| AttrValue::from("90"));
+    let scopes_state = use_state_eq(Vec::<TokenScope>::new);
+
+    let delete_token_state = use_state_eq(|| None as Option<i32>);
+    let created_token_state = use_state_eq(|| None as Option<AttrValue>);
+
+    let tokens_state = use_async(async move { api::get_personal_access_tokens().await });
+    let create_token_state = {
+        let name_state = name_state.clone();
+        let expiry_state = expiry_state.clone();
+        let scopes_state = scopes_state.clone();
+        let created_token_state = created_token_state.clone();
+        let tokens_state = tokens_state.clone();
+
+        use_async(async move {
+            let expires_at = expiry_state
+                .parse::<i64>()
+                .ok()
+                .map(|days| chrono::Utc::now().naive_utc() + chrono::Duration::days(days));
+
+            api::create_personal_access_token(PersonalAccessToken::new(
+                (*name_state).to_string(),
+                scopes_state.as_slice(),
+                expires_at,
+            ))
+            .await
+            .map(|created| {
+                name_state.set("".into());
+                scopes_state.set(vec![]);
+                created_token_state.set(Some(created.token.into()));
+                tokens_state.run();
+            })
+        })
+    };
+    let delete_state = {
+        let tokens_state = tokens_state.clone();
+        let delete_token_state = delete_token_state.clone();
+
+        use_async(async move {
+            if let Some(id) = *delete_token_state {
+                api::delete_personal_access_token(id).await.map(|_| {
+                    delete_token_state.set(None);
+                    tokens_state.run();
+                })
+            } else {
+                Ok(())
+            }
+        })
+    };
+
+    let update_name = use_callback(name_state.clone(), |value, state| state.set(value));
+    let update_expiry = use_callback(expiry_state.clone(), |value: Option<AttrValue>, state| {
+        state.set(value.unwrap_or_default())
+    });
+    let on_create = use_callback(create_token_state.clone(), |_, state| state.run());
+    let on_delete_confirm = use_callback(delete_state.clone(), |_, state| state.run());
+    let on_delete_decline = use_callback(delete_token_state.clone(), |_, state| state.set(None));
+
+    {
+        let tokens_state = tokens_state.clone();
+
+        use_mount(move || tokens_state.run());
+    }
+
+    let mut token_inputs = vec![
+        CosmoInputGroupChildren::from(html_nested!(
+            <CosmoTextBox label="Name des Tokens" required={true} on_input={update_name} value={(*name_state).clone()} />
+        )),
+        CosmoInputGroupChildren::from(html_nested!(
+            <CosmoDropdown label="Gültig" required={true} value={Some((*expiry_state).clone())} on_select={update_expiry} items={vec![
+                (Some(AttrValue::from("30")), AttrValue::from("30 Tage")),
+                (Some(AttrValue::from("90")), AttrValue::from("90 Tage")),
+                (Some(AttrValue::from("365")), AttrValue::from("Ein Jahr")),
+                (Some(AttrValue::from("never")), AttrValue::from("Unbegrenzt")),
+            ]} />
+        )),
+    ];
+    token_inputs.extend(TokenScope::iter().map(|scope| {
+        let checked = scopes_state.contains(&scope);
+        let scopes_state = scopes_state.clone();
+        let on_check = Callback::from(move |checked: bool| {
+            let mut scopes = (*scopes_state).clone();
+            scopes.retain(|s| *s != scope);
+            if checked {
+                scopes.push(scope);
+            }
+            scopes_state.set(scopes);
+        });
+
+        CosmoInputGroupChildren::from(html_nested!(
+            <CosmoSwitch label={scope.to_string()} checked={checked} on_check={on_check} />
+        ))
+    }));
+
+    html!(
+        <>
+            <Helmet>
+                <title>{"Zugriffstokens"}</title>
+            </Helmet>
+            <CosmoModal title="Zugriffstokens" is_form={true} on_form_submit={on_create} buttons={html!(
+                <>
+                    <CosmoButton on_click={on_close.clone()} label="Schließen" />
+                    <CosmoButton label="Token erstellen" is_submit={true} />
+                </>
+            )}>
+                <CosmoParagraph>{"Mit einem Zugriffstoken können Skripte und Bots in deinem Namen auf Bambushain zugreifen. Ein Token darf nur das, was du beim Erstellen auswählst."}</CosmoParagraph>
+                if let Some(token) = (*created_token_state).clone() {
+                    <CosmoMessage header="Token erstellt" message="Kopiere dir den Token jetzt, er wird dir nur dieses eine Mal angezeigt" message_type={CosmoMessageType::Positive} />
+                    <CosmoInputGroup>
+                        <CosmoTextBox label="Token" readonly={true} value={token} on_input={|_| {}} />
+                    </CosmoInputGroup>
+                }
+                if create_token_state.error.is_some() {
+                    <CosmoMessage header="Fehler beim Erstellen" message="Der Token konnte leider nicht erstellt werden, wähle mindestens eine Berechtigung aus" message_type={CosmoMessageType::Negative} />
+                }
+                if delete_state.error.is_some() {
+                    <CosmoMessage header="Fehler beim Entfernen" message="Der Token konnte leider nicht entfernt werden" message_type={CosmoMessageType::Negative} />
+                }
+                if tokens_state.loading {
+                    <CosmoProgressRing />
+                } else if tokens_state.error.is_some() {
+                    <CosmoMessage header="Fehler beim Laden" message="Deine Zugriffstokens konnten leider nicht geladen werden" message_type={CosmoMessageType::Negative} />
+                } else if let Some(tokens) = &tokens_state.data {
+                    <CosmoTable headers={vec![AttrValue::from("Name"), AttrValue::from("Berechtigungen"), AttrValue::from("Erstellt am"), AttrValue::from("Läuft ab"), AttrValue::from("Zuletzt verwendet"), AttrValue::from("")]}>
+                        {for tokens.iter().map(|token| {
+                            let id = token.id;
+                            let delete_token_state = delete_token_state.clone();
+
+                            CosmoTableRow::from_table_cells(vec![
+                                CosmoTableCell::from_html(html!(token.name.clone()), None),
+                                CosmoTableCell::from_html(html!(token.scopes().iter().map(|scope| scope.to_string()).collect::<Vec<String>>().join(", ")), None),
+                                CosmoTableCell::from_html(html!(format_session_time(token.created_at)), None),
+                                CosmoTableCell::from_html(html!(token.expires_at.map(format_session_time).unwrap_or("Nie".into())), None),
+                                CosmoTableCell::from_html(html!(token.last_used_at.map(format_session_time).unwrap_or("Noch nie".into())), None),
+                                CosmoTableCell::from_html(html!(<CosmoButton label="Entfernen" on_click={move |_| delete_token_state.set(Some(id))} />), None),
+                            ], Some(token.id.into()))
+                        })}
+                    </CosmoTable>
+                }
+                <CosmoInputGroup>
+                    {for token_inputs}
+                </CosmoInputGroup>
+            </CosmoModal>
+            if delete_token_state.is_some() {
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} on_confirm={on_delete_confirm} on_decline={on_delete_decline} title="Token entfernen" message="Soll der Token wirklich entfernt werden? Skripte die ihn verwenden funktionieren danach nicht mehr." confirm_label="Token entfernen" decline_label="Abbrechen" />
+            }
+        </>
+    )
+}
+
 #[function_component(TopBar)]
 fn top_bar() -> Html {
     log::debug!("Render top bar");
@@ -904,6 +1654,10 @@ fn top_bar() -> Html {
     let profile_open_toggle = use_bool_toggle(false);
     let password_open_toggle = use_bool_toggle(false);
     let leave_grove_open_toggle = use_bool_toggle(false);
+    let calendar_feed_open_toggle = use_bool_toggle(false);
+    let sessions_open_toggle = use_bool_toggle(false);
+    let passkeys_open_toggle = use_bool_toggle(false);
+    let personal_access_tokens_open_toggle = use_bool_toggle(false);
 
     let profile_user_id = use_state(|| profile_atom.profile.id);
 
@@ -954,6 +1708,24 @@ fn top_bar() -> Html {
         toggle.set(false)
     });
     let leave_grove = use_callback(leave_grove_state.clone(), |_, state| state.run());
+    let open_calendar_feed = use_callback(calendar_feed_open_toggle.clone(), |_, toggle| {
+        toggle.set(true)
+    });
+    let close_calendar_feed = use_callback(calendar_feed_open_toggle.clone(), |_, toggle| {
+        toggle.set(false)
+    });
+    let open_sessions = use_callback(sessions_open_toggle.clone(), |_, toggle| toggle.set(true));
+    let close_sessions = use_callback(sessions_open_toggle.clone(), |_, toggle| toggle.set(false));
+    let open_passkeys = use_callback(passkeys_open_toggle.clone(), |_, toggle| toggle.set(true));
+    let close_passkeys = use_callback(passkeys_open_toggle.clone(), |_, toggle| toggle.set(false));
+    let open_personal_access_tokens =
+        use_callback(personal_access_tokens_open_toggle.clone(), |_, toggle| {
+            toggle.set(true)
+        });
+    let close_personal_access_tokens =
+        use_callback(personal_access_tokens_open_toggle.clone(), |_, toggle| {
+            toggle.set(false)
+        });
     let profile_updated = use_callback(
         (
             profile_user_id.clone(),
@@ -979,6 +1751,10 @@ fn top_bar() -> Html {
                 <CosmoTopBarItemLink<AppRoute> label="Rechtliches" to={AppRoute::LegalRoot} />
                 <CosmoTopBarItem label="Mein Profil" on_click={open_update_my_profile} />
                 <CosmoTopBarItem label="Passwort ändern" on_click={open_change_password} />
+                <CosmoTopBarItem label="Kalender abonnieren" on_click={open_calendar_feed} />
+                <CosmoTopBarItem label="Passkeys" on_click={open_passkeys} />
+                <CosmoTopBarItem label="Zugriffstokens" on_click={open_personal_access_tokens} />
+                <CosmoTopBarItem label="Angemeldete Geräte" on_click={open_sessions} />
                 <CosmoTopBarItem label="Hain verlassen" on_click={open_leave_grove} />
             </CosmoTopBar>
             if *profile_open_toggle {
@@ -989,8 +1765,20 @@ fn top_bar() -> Html {
                     <ChangePasswordDialog on_close={move |_| password_open_toggle.set(false)} mods={data.clone()} />
                 }
             }
+            if *calendar_feed_open_toggle {
+                <CalendarFeedDialog on_close={close_calendar_feed} />
+            }
+            if *sessions_open_toggle {
+                <SessionsDialog on_close={close_sessions} />
+            }
+            if *passkeys_open_toggle {
+                <PasskeysDialog on_close={close_passkeys} />
+            }
+            if *personal_access_tokens_open_toggle {
+                <PersonalAccessTokensDialog on_close={close_personal_access_tokens} />
+            }
             if *leave_grove_open_toggle {
-                <CosmoConfirm confirm_type={CosmoModalType::Negative} on_confirm={leave_grove} on_decline={close_leave_grove} title="Hain verlassen" message="Bist du sicher, dass du den Hain verlassen möchtest?\nWenn du den Hain verlässt werden alle deine Daten gelöscht und können nicht wiederhergestellt werden." confirm_label="Hain verlassen" decline_label="Im Hain bleiben" />
+                <CosmoConfirm confirm_type={CosmoModalType::Negative} on_confirm={leave_grove} on_decline={close_leave_grove} title="Hain verlassen" message="Bist du sicher, dass du den Hain verlassen möchtest?\nWenn du den Hain verlässt werden alle deine Daten gelöscht. Bis zur endgültigen Löschung kann ein Mod dich wiederherstellen." confirm_label="Hain verlassen" decline_label="Im Hain bleiben" />
             }
         </>
     )
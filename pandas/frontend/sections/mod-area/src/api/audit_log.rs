@@ -0,0 +1,17 @@
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::{BambooApiResult, ListQuery, Page};
+
+use crate::api::get_filtered_page;
+
+pub async fn get_audit_log(
+    query: ListQuery,
+    action: Option<AuditAction>,
+) -> BambooApiResult<Page<AuditLog>> {
+    log::debug!("Get audit log page {}", query.page);
+    let mut filter = vec![];
+    if let Some(action) = action {
+        filter.push(("action", format!("{action:?}")));
+    }
+
+    get_filtered_page("/api/grove/audit-log", query, filter).await
+}
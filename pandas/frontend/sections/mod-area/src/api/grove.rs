@@ -1,4 +1,6 @@
-use bamboo_common::core::entities::Grove;
+use bamboo_common::core::entities::{
+    CreatedGroveWebhook, EventImport, Grove, GroveWebhook, GroveWebhookDelivery,
+};
 use bamboo_common::frontend::api::BambooApiResult;
 use bamboo_pandas_frontend_base::api;
 
@@ -21,3 +23,43 @@ pub async fn enable_grove() -> BambooApiResult<()> {
     log::debug!("Disable the current grove");
     api::put_no_body_no_content("/api/grove/enabled").await
 }
+
+pub async fn enable_forgot_password_mods() -> BambooApiResult<()> {
+    log::debug!("Notify the mods when someone forgot their password");
+    api::put_no_body_no_content("/api/grove/forgot-password-mods").await
+}
+
+pub async fn disable_forgot_password_mods() -> BambooApiResult<()> {
+    log::debug!("Stop notifying the mods when someone forgot their password");
+    api::delete("/api/grove/forgot-password-mods").await
+}
+
+pub async fn import_events(file: web_sys::File) -> BambooApiResult<EventImport> {
+    log::debug!("Import events into the current grove");
+    api::post_file("/api/bamboo-grove/event/import", file).await
+}
+
+pub async fn get_webhooks() -> BambooApiResult<Vec<GroveWebhook>> {
+    log::debug!("Loading webhooks of the current grove");
+    api::get("/api/grove/webhook").await
+}
+
+pub async fn create_webhook(webhook: GroveWebhook) -> BambooApiResult<CreatedGroveWebhook> {
+    log::debug!("Create webhook for {}", webhook.url);
+    api::post("/api/grove/webhook", &webhook).await
+}
+
+pub async fn delete_webhook(id: i32) -> BambooApiResult<()> {
+    log::debug!("Delete webhook {id}");
+    api::delete(format!("/api/grove/webhook/{id}")).await
+}
+
+pub async fn get_webhook_deliveries(id: i32) -> BambooApiResult<Vec<GroveWebhookDelivery>> {
+    log::debug!("Loading deliveries of webhook {id}");
+    api::get(format!("/api/grove/webhook/{id}/delivery")).await
+}
+
+pub async fn ping_webhook(id: i32) -> BambooApiResult<GroveWebhookDelivery> {
+    log::debug!("Send ping to webhook {id}");
+    api::post_no_body(format!("/api/grove/webhook/{id}/ping")).await
+}
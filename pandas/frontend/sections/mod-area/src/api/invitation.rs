@@ -0,0 +1,24 @@
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::BambooApiResult;
+
+use crate::api::{delete, get, post, post_no_body_no_content};
+
+pub async fn get_invitations() -> BambooApiResult<Vec<Invitation>> {
+    log::debug!("Get invitations");
+    get("/api/invitation").await
+}
+
+pub async fn create_invitation(invitation: Invitation) -> BambooApiResult<Invitation> {
+    log::debug!("Invite {}", invitation.email);
+    post("/api/invitation", &invitation).await
+}
+
+pub async fn resend_invitation(id: i32) -> BambooApiResult<()> {
+    log::debug!("Resend invitation {id}");
+    post_no_body_no_content(format!("/api/invitation/{id}/resend")).await
+}
+
+pub async fn delete_invitation(id: i32) -> BambooApiResult<()> {
+    log::debug!("Revoke invitation {id}");
+    delete(format!("/api/invitation/{id}")).await
+}
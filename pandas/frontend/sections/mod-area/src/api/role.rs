@@ -0,0 +1,24 @@
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::BambooApiResult;
+
+use crate::api::{delete, get, post, put_no_content};
+
+pub async fn get_roles() -> BambooApiResult<Vec<Role>> {
+    log::debug!("Get roles");
+    get("/api/role").await
+}
+
+pub async fn create_role(role: Role) -> BambooApiResult<Role> {
+    log::debug!("Create role {}", role.name);
+    post("/api/role", &role).await
+}
+
+pub async fn update_role(id: i32, role: Role) -> BambooApiResult<()> {
+    log::debug!("Update role {id}");
+    put_no_content(format!("/api/role/{id}"), &role).await
+}
+
+pub async fn delete_role(id: i32) -> BambooApiResult<()> {
+    log::debug!("Delete role {id}");
+    delete(format!("/api/role/{id}")).await
+}
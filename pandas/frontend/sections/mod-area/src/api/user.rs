@@ -1,16 +1,11 @@
 use bamboo_common::core::entities::*;
-use bamboo_common::frontend::api::BambooApiResult;
+use bamboo_common::frontend::api::{BambooApiResult, ListQuery, Page};
 
-use crate::api::{delete, get, post, put_no_body_no_content, put_no_content};
+use crate::api::{delete, get_page, put_no_body_no_content, put_no_content};
 
-pub async fn get_users() -> BambooApiResult<Vec<WebUser>> {
-    log::debug!("Get users");
-    get("/api/user").await
-}
-
-pub async fn create_user(user: User) -> BambooApiResult<WebUser> {
-    log::debug!("Create user {}", user.email);
-    post("/api/user", &user).await
+pub async fn get_users(query: ListQuery) -> BambooApiResult<Page<WebUser>> {
+    log::debug!("Get page {} of the users", query.page);
+    get_page("/api/user", query).await
 }
 
 pub async fn make_user_mod(id: i32) -> BambooApiResult<()> {
@@ -23,6 +18,11 @@ pub async fn remove_user_mod(id: i32) -> BambooApiResult<()> {
     delete(format!("/api/user/{id}/mod")).await
 }
 
+pub async fn change_user_role(id: i32, role_id: Option<i32>) -> BambooApiResult<()> {
+    log::debug!("Change role of user {id} to {role_id:?}");
+    put_no_content(format!("/api/user/{id}/role"), &AssignRole::new(role_id)).await
+}
+
 pub async fn delete_user(id: i32) -> BambooApiResult<()> {
     log::debug!("Delete user {id}");
     delete(format!("/api/user/{id}")).await
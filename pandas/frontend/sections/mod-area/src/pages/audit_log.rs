@@ -0,0 +1,150 @@
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_mount};
+
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::ListQuery;
+use bamboo_common::frontend::ui::BambooPaging;
+
+use crate::api;
+
+fn action_items() -> Vec<(Option<AttrValue>, AttrValue)> {
+    vec![(None, AttrValue::from("Alle Aktionen"))]
+        .into_iter()
+        .chain(AuditAction::iter().map(|action| {
+            (
+                Some(AttrValue::from(format!("{action:?}"))),
+                AttrValue::from(action.to_string()),
+            )
+        }))
+        .collect()
+}
+
+#[function_component(AuditLogPage)]
+pub fn audit_log_page() -> Html {
+    let query_state = use_state_eq(ListQuery::default);
+    let action_state = use_state_eq(|| None as Option<AuditAction>);
+    // The filter is read when the request runs, a state would still hold the value of the last render
+    let filter_ref = use_mut_ref(|| (ListQuery::default(), None as Option<AuditAction>));
+
+    let audit_log_state = {
+        let filter_ref = filter_ref.clone();
+
+        use_async(async move {
+            let (query, action) = filter_ref.borrow().clone();
+            api::get_audit_log(query, action).await
+        })
+    };
+
+    let load = {
+        let query_state = query_state.clone();
+        let action_state = action_state.clone();
+        let audit_log_state = audit_log_state.clone();
+
+        move |query: ListQuery, action: Option<AuditAction>| {
+            *filter_ref.borrow_mut() = (query.clone(), action);
+            query_state.set(query);
+            action_state.set(action);
+            audit_log_state.run();
+        }
+    };
+
+    let action_select = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+
+        Callback::from(move |value: Option<AttrValue>| {
+            load(
+                ListQuery {
+                    page: 1,
+                    ..query.clone()
+                },
+                value.and_then(|value| {
+                    AuditAction::iter().find(|action| value.as_str() == format!("{action:?}"))
+                }),
+            )
+        })
+    };
+    let update_filter = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+        let action = *action_state;
+
+        Callback::from(move |filter: AttrValue| {
+            load(
+                ListQuery {
+                    page: 1,
+                    filter: filter.to_string(),
+                    ..query.clone()
+                },
+                action,
+            )
+        })
+    };
+    let change_page = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+        let action = *action_state;
+
+        Callback::from(move |page: u64| {
+            load(
+                ListQuery {
+                    page,
+                    ..query.clone()
+                },
+                action,
+            )
+        })
+    };
+
+    {
+        let load = load.clone();
+
+        use_mount(move || load(ListQuery::default(), None));
+    }
+
+    let total = audit_log_state
+        .data
+        .as_ref()
+        .map(|page| page.total)
+        .unwrap_or_default();
+    let offset = (query_state.page - 1) * query_state.limit;
+
+    html!(
+        <>
+            <CosmoTitle title="Protokoll" />
+            <CosmoParagraph>
+                {"Im Protokoll siehst du, welcher Mod wann Pandas gelöscht, Rechte geändert, Passwörter zurückgesetzt oder den Hain verändert hat."}
+            </CosmoParagraph>
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoDropdown width={CosmoInputWidth::Medium} label="Aktion" value={(*action_state).map(|action| AttrValue::from(format!("{action:?}")))} items={action_items()} on_select={action_select} />
+                    <CosmoTextBox width={CosmoInputWidth::Medium} label="Suchen" value={AttrValue::from(query_state.filter.clone())} on_input={update_filter} />
+                </CosmoToolbarGroup>
+                <CosmoToolbarGroup>
+                    <BambooPaging page={query_state.page} limit={query_state.limit} total={total} on_change={change_page} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
+            if audit_log_state.loading {
+                <CosmoProgressRing />
+            } else if audit_log_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Laden" message="Das Protokoll konnte nicht geladen werden" />
+            } else if let Some(page) = &audit_log_state.data {
+                if page.entries.is_empty() {
+                    <CosmoMessage message_type={CosmoMessageType::Information} header="Keine Einträge" message="Für diesen Filter gibt es noch keine Einträge im Protokoll" />
+                } else {
+                    <CosmoTable headers={vec![AttrValue::from("Zeitpunkt"), AttrValue::from("Mod"), AttrValue::from("Aktion"), AttrValue::from("Betroffen")]}>
+                        {for page.entries.iter().map(|entry| CosmoTableRow::from_table_cells(vec![
+                            CosmoTableCell::from_html(html!(entry.created_at.format("%d.%m.%Y %H:%M").to_string()), None),
+                            CosmoTableCell::from_html(html!(entry.actor_name.clone()), None),
+                            CosmoTableCell::from_html(html!(entry.action.to_string()), None),
+                            CosmoTableCell::from_html(html!(entry.target_name.clone()), None),
+                        ], Some(entry.id.into())))}
+                    </CosmoTable>
+                    <CosmoParagraph>{format!("Einträge {} bis {} von {}", offset + 1, offset + page.entries.len() as u64, page.total)}</CosmoParagraph>
+                }
+            }
+        </>
+    )
+}
@@ -1,19 +1,253 @@
+use bounce::use_atom_value;
 use yew::prelude::*;
+use yew_autoprops::autoprops;
 use yew_cosmo::prelude::*;
 use yew_hooks::{use_async, use_bool_toggle, use_mount};
 use yew_router::prelude::*;
 
+use bamboo_common::core::entities::{GroveWebhook, Permission};
 use bamboo_pandas_frontend_base::routing::AppRoute;
+use bamboo_pandas_frontend_base::storage;
 
 use crate::api;
 
+#[autoprops]
+#[function_component(ImportEventsDialog)]
+fn import_events_dialog(on_close: &Callback<()>) -> Html {
+    let file_state = use_state_eq(|| None as Option<web_sys::File>);
+
+    let import_state = {
+        let file_state = file_state.clone();
+
+        use_async(async move {
+            if let Some(file) = (*file_state).clone() {
+                api::import_events(file).await
+            } else {
+                Err(Default::default())
+            }
+        })
+    };
+
+    let select_file = use_callback(file_state.clone(), |value, state| state.set(Some(value)));
+    let on_import = use_callback(import_state.clone(), |_, state| state.run());
+    let on_close = on_close.clone();
+
+    html!(
+        <CosmoModal title="Termine importieren" is_form={true} on_form_submit={on_import} buttons={html!(
+            <>
+                <CosmoButton on_click={on_close} label="Schließen" />
+                <CosmoButton is_submit={true} label="Termine importieren" />
+            </>
+        )}>
+            if import_state.loading {
+                <CosmoProgressRing />
+            } else if let Some(result) = &import_state.data {
+                <CosmoMessage message_type={CosmoMessageType::Positive} header="Termine importiert" message={format!("Es wurden {} Termine importiert", result.events.len())} />
+                if !result.errors.is_empty() {
+                    <CosmoMessage message_type={CosmoMessageType::Warning} header="Nicht alle Termine konnten importiert werden" message="Die folgenden Einträge wurden übersprungen" />
+                    <ul>
+                        {for result.errors.iter().map(|error| html!(
+                            <li key={error.entry}>
+                                {format!("Eintrag {} ({}): {}", error.entry, error.title.clone().unwrap_or_default(), error.error.message)}
+                            </li>
+                        ))}
+                    </ul>
+                }
+            } else if import_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Importieren" message="Die Datei konnte nicht importiert werden, bitte prüfe ob es sich um eine gültige iCalendar Datei handelt" />
+            }
+            <CosmoInputGroup>
+                <CosmoFilePicker label="iCalendar Datei" accept=".ics,text/calendar" required={true} on_select={select_file} />
+            </CosmoInputGroup>
+        </CosmoModal>
+    )
+}
+
+#[autoprops]
+#[function_component(WebhookDeliveries)]
+fn webhook_deliveries(webhook_id: i32) -> Html {
+    let deliveries_state = use_async(async move { api::get_webhook_deliveries(webhook_id).await });
+
+    {
+        let deliveries_state = deliveries_state.clone();
+
+        use_effect_with(webhook_id, move |_| deliveries_state.run());
+    }
+
+    let reload = use_callback(deliveries_state.clone(), |_, state| state.run());
+
+    html!(
+        <>
+            <CosmoHeader level={CosmoHeaderLevel::H3} header="Zustellungen" />
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoButton label="Neu laden" on_click={reload} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
+            if deliveries_state.loading {
+                <CosmoProgressRing />
+            } else if deliveries_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Laden" message="Die Zustellungen konnten nicht geladen werden" />
+            } else if let Some(deliveries) = &deliveries_state.data {
+                <CosmoTable headers={vec![AttrValue::from("Zeitpunkt"), AttrValue::from("Ereignis"), AttrValue::from("Versuch"), AttrValue::from("Status"), AttrValue::from("Fehler")]}>
+                    {for deliveries.iter().map(|delivery| CosmoTableRow::from_table_cells(vec![
+                        CosmoTableCell::from_html(html!(delivery.delivered_at.format("%d.%m.%Y %H:%M:%S").to_string()), None),
+                        CosmoTableCell::from_html(html!(delivery.event_type.clone()), None),
+                        CosmoTableCell::from_html(html!(delivery.attempt), None),
+                        CosmoTableCell::from_html(html!({if delivery.success {
+                            "Erfolgreich".to_string()
+                        } else {
+                            delivery.status_code.map(|code| code.to_string()).unwrap_or("Fehlgeschlagen".into())
+                        }}), None),
+                        CosmoTableCell::from_html(html!(delivery.error.clone().unwrap_or_default()), None),
+                    ], Some(delivery.id.into())))}
+                </CosmoTable>
+            }
+        </>
+    )
+}
+
+#[autoprops]
+#[function_component(WebhooksDialog)]
+fn webhooks_dialog(on_close: &Callback<()>) -> Html {
+    let url_state = use_state_eq(|| AttrValue::from(""));
+    let discord_format_state = use_state_eq(|| false);
+    let selected_webhook_state = use_state_eq(|| None as Option<i32>);
+
+    let webhooks_state = use_async(async { api::get_webhooks().await });
+    let create_state = {
+        let url_state = url_state.clone();
+        let discord_format_state = discord_format_state.clone();
+
+        let webhooks_state = webhooks_state.clone();
+
+        use_async(async move {
+            api::create_webhook(GroveWebhook::new(
+                (*url_state).to_string(),
+                *discord_format_state,
+            ))
+            .await
+            .inspect(|_| {
+                url_state.set("".into());
+                webhooks_state.run();
+            })
+        })
+    };
+    let delete_state = {
+        let webhooks_state = webhooks_state.clone();
+        let selected_webhook_state = selected_webhook_state.clone();
+
+        use_async(async move {
+            if let Some(id) = *selected_webhook_state {
+                api::delete_webhook(id).await.map(|_| {
+                    selected_webhook_state.set(None);
+                    webhooks_state.run();
+                })
+            } else {
+                Ok(())
+            }
+        })
+    };
+    let ping_state = {
+        let selected_webhook_state = selected_webhook_state.clone();
+
+        use_async(async move {
+            if let Some(id) = *selected_webhook_state {
+                api::ping_webhook(id).await
+            } else {
+                Err(Default::default())
+            }
+        })
+    };
+
+    let update_url = use_callback(url_state.clone(), |value, state| state.set(value));
+    let update_discord_format = use_callback(discord_format_state.clone(), |value, state| {
+        state.set(value)
+    });
+    let on_create = use_callback(create_state.clone(), |_, state| state.run());
+    let on_delete = use_callback(delete_state.clone(), |_, state| state.run());
+    let on_ping = use_callback(ping_state.clone(), |_, state| state.run());
+    let on_close = on_close.clone();
+
+    {
+        let webhooks_state = webhooks_state.clone();
+
+        use_mount(move || webhooks_state.run());
+    }
+
+    html!(
+        <CosmoModal title="Webhooks" is_form={true} on_form_submit={on_create} buttons={html!(
+            <>
+                <CosmoButton on_click={on_close} label="Schließen" />
+                <CosmoButton is_submit={true} label="Webhook hinzufügen" />
+            </>
+        )}>
+            <CosmoParagraph>{"Sobald Events erstellt, geändert oder gelöscht werden, neue Pandas dazukommen oder sich Unterkünfte ändern, schicken wir eine Nachricht an die eingetragenen Adressen. Die Nachrichten sind mit dem Geheimnis des Webhooks per HMAC-SHA256 signiert, die Signatur steht im Header X-Bamboo-Signature. Für Discord kannst du das Discord Format auswählen."}</CosmoParagraph>
+            if create_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Speichern" message="Der Webhook konnte nicht gespeichert werden, bitte prüfe ob die Adresse mit http:// oder https:// beginnt" />
+            } else if let Some(created) = &create_state.data {
+                <CosmoMessage header="Webhook erstellt" message="Kopiere dir das Geheimnis jetzt, es wird dir nur dieses eine Mal angezeigt" message_type={CosmoMessageType::Positive} />
+                <CosmoInputGroup>
+                    <CosmoTextBox label="Geheimnis" readonly={true} value={created.secret.clone()} on_input={|_| {}} />
+                </CosmoInputGroup>
+            }
+            <CosmoInputGroup>
+                <CosmoTextBox label="Adresse" input_type={CosmoTextBoxType::Url} required={true} value={(*url_state).clone()} on_input={update_url} />
+                <CosmoSwitch label="Discord Format" checked={*discord_format_state} on_check={update_discord_format} />
+            </CosmoInputGroup>
+            if webhooks_state.loading {
+                <CosmoProgressRing />
+            } else if webhooks_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Laden" message="Die Webhooks konnten nicht geladen werden" />
+            } else if let Some(webhooks) = &webhooks_state.data {
+                <CosmoTable headers={vec![AttrValue::from("Adresse"), AttrValue::from("Format"), AttrValue::from("")]}>
+                    {for webhooks.iter().map(|webhook| {
+                        let id = webhook.id;
+                        let selected_webhook_state = selected_webhook_state.clone();
+
+                        CosmoTableRow::from_table_cells(vec![
+                            CosmoTableCell::from_html(html!(webhook.url.clone()), None),
+                            CosmoTableCell::from_html(html!({if webhook.discord_format { "Discord" } else { "JSON" }}), None),
+                            CosmoTableCell::from_html(html!(
+                                <CosmoButton label="Auswählen" on_click={move |_| selected_webhook_state.set(Some(id))} />
+                            ), None),
+                        ], Some(webhook.id.into()))
+                    })}
+                </CosmoTable>
+            }
+            if let Some(webhook_id) = *selected_webhook_state {
+                <CosmoToolbar>
+                    <CosmoToolbarGroup>
+                        <CosmoButton label="Test senden" on_click={on_ping} />
+                        <CosmoButton label="Webhook löschen" on_click={on_delete} />
+                    </CosmoToolbarGroup>
+                </CosmoToolbar>
+                if let Some(delivery) = &ping_state.data {
+                    if delivery.success {
+                        <CosmoMessage message_type={CosmoMessageType::Positive} header="Test erfolgreich" message="Der Webhook hat die Testnachricht angenommen" />
+                    } else {
+                        <CosmoMessage message_type={CosmoMessageType::Negative} header="Test fehlgeschlagen" message={delivery.error.clone().unwrap_or("Der Webhook hat die Testnachricht abgelehnt".into())} />
+                    }
+                }
+                if delete_state.error.is_some() {
+                    <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Löschen" message="Der Webhook konnte nicht gelöscht werden" />
+                }
+                <WebhookDeliveries webhook_id={webhook_id} />
+            }
+        </CosmoModal>
+    )
+}
+
 #[function_component(GroveManagementPage)]
 pub fn grove_management_page() -> Html {
     let navigator = use_navigator().expect("Navigator needs to be some");
+    let current_user = use_atom_value::<storage::CurrentUser>();
 
     let delete_grove_open_toggle = use_bool_toggle(false);
     let disable_grove_open_toggle = use_bool_toggle(false);
     let enable_grove_open_toggle = use_bool_toggle(false);
+    let import_events_open_toggle = use_bool_toggle(false);
+    let webhooks_open_toggle = use_bool_toggle(false);
 
     let grove_state = use_async(async { api::get_grove().await });
     let disable_grove_state = {
@@ -47,6 +281,25 @@ pub fn grove_management_page() -> Html {
         })
     };
 
+    let forgot_password_mods_state = {
+        let grove_state = grove_state.clone();
+
+        use_async(async move {
+            let notify_mods = grove_state
+                .data
+                .as_ref()
+                .is_some_and(|grove| grove.notify_mods_on_forgot_password);
+            let result = if notify_mods {
+                api::disable_forgot_password_mods().await
+            } else {
+                api::enable_forgot_password_mods().await
+            };
+            grove_state.run();
+
+            result
+        })
+    };
+
     let open_delete_grove = use_callback(delete_grove_open_toggle.clone(), |_, toggle| {
         toggle.set(true)
     });
@@ -57,6 +310,16 @@ pub fn grove_management_page() -> Html {
         toggle.set(true)
     });
 
+    let open_import_events = use_callback(import_events_open_toggle.clone(), |_, toggle| {
+        toggle.set(true)
+    });
+    let close_import_events = use_callback(import_events_open_toggle.clone(), |_, toggle| {
+        toggle.set(false)
+    });
+
+    let open_webhooks = use_callback(webhooks_open_toggle.clone(), |_, toggle| toggle.set(true));
+    let close_webhooks = use_callback(webhooks_open_toggle.clone(), |_, toggle| toggle.set(false));
+
     let close_delete_grove = use_callback(delete_grove_open_toggle.clone(), |_, toggle| {
         toggle.set(false)
     });
@@ -70,6 +333,8 @@ pub fn grove_management_page() -> Html {
     let disable_grove = use_callback(disable_grove_state.clone(), |_, state| state.run());
     let enable_grove = use_callback(enable_grove_state.clone(), |_, state| state.run());
     let delete_grove = use_callback(delete_grove_state.clone(), |_, state| state.run());
+    let toggle_forgot_password_mods =
+        use_callback(forgot_password_mods_state.clone(), |_, state| state.run());
 
     {
         let grove_state = grove_state.clone();
@@ -95,6 +360,32 @@ pub fn grove_management_page() -> Html {
             <>
                 <CosmoTitle title="Hainverwaltung" subtitle={grove.name.clone()} />
                 <CosmoMessage header="Willkommen in der Hainverwaltung" message="In der Hainverwaltung hast du die Möglichkeit deinen Hain zu löschen oder zu deaktivieren" message_type={CosmoMessageType::Information} />
+                if current_user.profile.has_permission(Permission::ManageEvents) {
+                    <CosmoMessage header="Termine importieren" message="Du hast die Möglichkeit Termine aus einer iCalendar Datei in den Kalender deines Hains zu importieren. So kannst du deine Termine aus anderen Kalendern übernehmen." message_type={CosmoMessageType::Information} actions={html!(
+                        <CosmoButton label="Termine importieren" on_click={open_import_events} />
+                    )} />
+                }
+                if *import_events_open_toggle {
+                    <ImportEventsDialog on_close={close_import_events} />
+                }
+                <CosmoMessage header="Webhooks" message="Mit Webhooks kannst du zum Beispiel einen Discord Kanal über neue Events, Pandas und Unterkünfte in deinem Hain informieren." message_type={CosmoMessageType::Information} actions={html!(
+                    <CosmoButton label="Webhooks verwalten" on_click={open_webhooks} />
+                )} />
+                if *webhooks_open_toggle {
+                    <WebhooksDialog on_close={close_webhooks} />
+                }
+                if forgot_password_mods_state.error.is_some() {
+                    <CosmoMessage header="Fehler beim Speichern" message="Die Einstellung konnte nicht gespeichert werden" message_type={CosmoMessageType::Negative} />
+                }
+                if grove.notify_mods_on_forgot_password {
+                    <CosmoMessage header="Mods bei Passwort vergessen informieren" message="Wenn ein Panda sein Passwort vergessen hat, bekommt er eine Email mit einem Link zum Zurücksetzen. Zusätzlich bekommen alle Mods eine Email, damit sie helfen können." message_type={CosmoMessageType::Information} actions={html!(
+                        <CosmoButton label="Mods nicht mehr informieren" on_click={toggle_forgot_password_mods} />
+                    )} />
+                } else {
+                    <CosmoMessage header="Mods bei Passwort vergessen informieren" message="Wenn ein Panda sein Passwort vergessen hat, bekommt er eine Email mit einem Link zum Zurücksetzen. Du kannst einstellen, dass zusätzlich alle Mods eine Email bekommen, damit sie helfen können." message_type={CosmoMessageType::Information} actions={html!(
+                        <CosmoButton label="Mods informieren" on_click={toggle_forgot_password_mods} />
+                    )} />
+                }
                 if grove.is_enabled {
                     <CosmoMessage header="Hain deaktivieren" message="Du hast die Möglichkeit den Hain zu deaktivieren. Sobald er deaktiviert ist können sich nur noch Mods anmelden und haben nur noch Zugriff auf die Mod Area. Den Hain zu deaktivieren ist eine gute Alternative dazu ihn direkt zu löschen." message_type={CosmoMessageType::Warning} actions={html!(
                         <CosmoButton label="Hain deaktivieren" on_click={open_disable_grove} />
@@ -110,9 +401,11 @@ pub fn grove_management_page() -> Html {
                         <CosmoConfirm confirm_type={CosmoModalType::Warning} on_confirm={enable_grove} on_decline={close_enable_grove} confirm_label="Hain aktivieren" decline_label="Hain nicht aktivieren" title="Hain aktivieren" message="Wenn du den Hain aktivierst haben wieder alle Benutzer die Möglichkeit sich anzumelden." />
                     }
                 }
-                <CosmoMessage header="Hain löschen" message="Du hast die Möglichkeit den Hain zu löschen. Sobald der Hain gelöscht ist werden alle Benutzer, Events und Charaktere gelöscht. Wir haben 14 Tage lang nach dem Löschen die Möglichkeit die Daten aus einem unserer Backups wiederherzustellen, danach sind die Daten verloren." message_type={CosmoMessageType::Negative} actions={html!(
-                    <CosmoButton label="Hain löschen" on_click={open_delete_grove} />
-                )} />
+                if current_user.profile.has_permission(Permission::ManageGrove) {
+                    <CosmoMessage header="Hain löschen" message="Du hast die Möglichkeit den Hain zu löschen. Sobald der Hain gelöscht ist werden alle Benutzer, Events und Charaktere gelöscht. Wir haben 14 Tage lang nach dem Löschen die Möglichkeit die Daten aus einem unserer Backups wiederherzustellen, danach sind die Daten verloren." message_type={CosmoMessageType::Negative} actions={html!(
+                        <CosmoButton label="Hain löschen" on_click={open_delete_grove} />
+                    )} />
+                }
                 if *delete_grove_open_toggle {
                     <CosmoConfirm confirm_type={CosmoModalType::Negative} on_confirm={delete_grove} on_decline={close_delete_grove} confirm_label="Hain löschen" decline_label="Hain nicht löschen" title="Hain löschen" message="Wenn du den Hain löscht werden alle Benutzer und ihre Daten unwiderruflich gelöscht.\nBitte überleg dir ob es reicht ihn zu deaktivieren. Falls du ihn wirklich löschen möchtest mächten wir dich darum bitten allen Benutzern Bescheid zu geben, damit sie ihre Daten sichern können." />
                 }
@@ -0,0 +1,256 @@
+use std::ops::Deref;
+
+use bounce::use_atom_value;
+use chrono::Utc;
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_bool_toggle, use_mount};
+
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::{ApiError, CONFLICT, FORBIDDEN};
+use bamboo_pandas_frontend_base::{error, storage};
+
+use crate::api;
+
+#[autoprops]
+#[function_component(CreateInvitationModal)]
+pub(crate) fn create_invitation_modal(
+    on_saved: &Callback<Invitation>,
+    on_close: &Callback<()>,
+) -> Html {
+    let current_user = use_atom_value::<storage::CurrentUser>();
+
+    let email_state = use_state_eq(|| AttrValue::from(""));
+    let display_name_state = use_state_eq(|| AttrValue::from(""));
+
+    let is_mod_toggle = use_bool_toggle(false);
+    let unreported_error_toggle = use_bool_toggle(false);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+
+    let save_state = {
+        let email_state = email_state.clone();
+        let display_name_state = display_name_state.clone();
+
+        let is_mod_toggle = is_mod_toggle.clone();
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        let on_saved = on_saved.clone();
+
+        use_async(async move {
+            api::create_invitation(Invitation::new(
+                (*email_state).to_string(),
+                (*display_name_state).to_string(),
+                *is_mod_toggle,
+            ))
+            .await
+            .inspect(|data| {
+                log::debug!("Invitation was created successfully");
+                unreported_error_toggle.set(false);
+                on_saved.emit(data.clone());
+            })
+            .map_err(|err| {
+                log::warn!("Failed to create invitation {err}");
+                unreported_error_toggle.set(true);
+                bamboo_error_state.set(err.clone());
+
+                err
+            })
+        })
+    };
+
+    let update_email = use_callback(email_state.clone(), |value, state| state.set(value));
+    let update_display_name =
+        use_callback(display_name_state.clone(), |value, state| state.set(value));
+
+    let update_is_mod = use_callback(is_mod_toggle.clone(), |checked, state| state.set(checked));
+
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "bamboo_invitation",
+                "create_invitation_modal",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+
+    let on_save = use_callback(save_state.clone(), |_, state| state.run());
+
+    // Only mods can invite other mods
+    let mod_switch = if current_user.profile.is_mod {
+        vec![CosmoInputGroupChildren::from(html_nested!(
+            <CosmoSwitch label="Moderator" on_check={update_is_mod} checked={*is_mod_toggle} />
+        ))]
+    } else {
+        vec![]
+    };
+
+    html!(
+        <CosmoModal title="Panda einladen" is_form={true} on_form_submit={on_save} buttons={
+            html!(
+                <>
+                    <CosmoButton on_click={on_close.clone()} label="Abbrechen" />
+                    <CosmoButton is_submit={true} label="Panda einladen" />
+                </>
+            )}>
+            <>
+                if let Some(err) = &save_state.error {
+                    if err.code == FORBIDDEN {
+                        <CosmoMessage message="Du musst Mod sein um andere Pandas einzuladen" message_type={CosmoMessageType::Negative} />
+                    } else if err.code == CONFLICT {
+                        <CosmoMessage message="Ein Panda mit dieser Emailadresse ist bereits im Bambushain registriert oder eingeladen" message_type={CosmoMessageType::Negative} />
+                    } else if *unreported_error_toggle {
+                        <CosmoMessage message="Der Panda konnte leider nicht eingeladen werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                    } else {
+                        <CosmoMessage message="Der Panda konnte leider nicht eingeladen werden" message_type={CosmoMessageType::Negative} />
+                    }
+                } else {
+                    <CosmoMessage message_type={CosmoMessageType::Information} header="Lade einen neuen Panda ein" message="Der Panda bekommt eine Email mit einem Link, über den er seinen Namen und sein Passwort selbst festlegt" />
+                }
+                <CosmoInputGroup>
+                    <CosmoTextBox label="Email" value={(*email_state).clone()} on_input={update_email} required={true} />
+                    <CosmoTextBox label="Name für die Begrüßung" value={(*display_name_state).clone()} on_input={update_display_name} required={true} />
+                    {for mod_switch}
+                </CosmoInputGroup>
+            </>
+        </CosmoModal>
+    )
+}
+
+#[function_component(InvitationManagementPage)]
+pub fn invitation_management_page() -> Html {
+    let create_open_toggle = use_bool_toggle(false);
+
+    let revoke_invitation_state = use_state_eq(|| None as Option<Invitation>);
+    // The id is read when the request runs, a state would still hold the value of the last render
+    let resend_invitation_ref = use_mut_ref(|| 0);
+
+    let invitations_state = use_async(async { api::get_invitations().await });
+    let resend_state = {
+        let invitations_state = invitations_state.clone();
+        let resend_invitation_ref = resend_invitation_ref.clone();
+
+        use_async(async move {
+            let id = *resend_invitation_ref.borrow();
+            let result = api::resend_invitation(id).await;
+            invitations_state.run();
+
+            result
+        })
+    };
+    let revoke_state = {
+        let invitations_state = invitations_state.clone();
+        let revoke_invitation_state = revoke_invitation_state.clone();
+
+        use_async(async move {
+            let result = if let Some(invitation) = (*revoke_invitation_state).clone() {
+                api::delete_invitation(invitation.id).await
+            } else {
+                Ok(())
+            };
+            revoke_invitation_state.set(None);
+            invitations_state.run();
+
+            result
+        })
+    };
+
+    let open_create = use_callback(create_open_toggle.clone(), |_, toggle| toggle.set(true));
+    let close_create = use_callback(create_open_toggle.clone(), |_, toggle| toggle.set(false));
+    let on_created = use_callback(
+        (create_open_toggle.clone(), invitations_state.clone()),
+        |_: Invitation, (toggle, invitations_state)| {
+            toggle.set(false);
+            invitations_state.run();
+        },
+    );
+    let revoke = use_callback(revoke_state.clone(), |_, state| state.run());
+    let close_revoke = use_callback(revoke_invitation_state.clone(), |_, state| state.set(None));
+
+    {
+        let invitations_state = invitations_state.clone();
+
+        use_mount(move || invitations_state.run());
+    }
+
+    let now = Utc::now().naive_utc();
+
+    html!(
+        <>
+            <CosmoTitle title="Einladungen" />
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoButton label="Panda einladen" on_click={open_create} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
+            if resend_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Senden" message="Die Einladung konnte nicht erneut verschickt werden" />
+            } else if resend_state.data.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Positive} header="Einladung verschickt" message="Die Einladung wurde mit einem neuen Link erneut verschickt, der alte Link ist nicht mehr gültig" />
+            }
+            if revoke_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Zurückziehen" message="Die Einladung konnte nicht zurückgezogen werden" />
+            }
+            if invitations_state.loading {
+                <CosmoProgressRing />
+            } else if invitations_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Laden" message="Die Einladungen konnten nicht geladen werden" />
+            } else if let Some(invitations) = &invitations_state.data {
+                if invitations.is_empty() {
+                    <CosmoMessage message_type={CosmoMessageType::Information} header="Keine offenen Einladungen" message="Alle eingeladenen Pandas haben ihre Einladung bereits angenommen" />
+                } else {
+                    <CosmoTable headers={vec![AttrValue::from("Name"), AttrValue::from("Email"), AttrValue::from("Moderator"), AttrValue::from("Eingeladen von"), AttrValue::from("Gültig bis"), AttrValue::from("Aktionen")]}>
+                        {for invitations.iter().map(|invitation| {
+                            let resend = {
+                                let resend_state = resend_state.clone();
+                                let resend_invitation_ref = resend_invitation_ref.clone();
+                                let id = invitation.id;
+
+                                Callback::from(move |_| {
+                                    *resend_invitation_ref.borrow_mut() = id;
+                                    resend_state.run();
+                                })
+                            };
+                            let open_revoke = {
+                                let revoke_invitation_state = revoke_invitation_state.clone();
+                                let invitation = invitation.clone();
+
+                                Callback::from(move |_| revoke_invitation_state.set(Some(invitation.clone())))
+                            };
+
+                            CosmoTableRow::from_table_cells(vec![
+                                CosmoTableCell::from_html(html!(invitation.display_name.clone()), None),
+                                CosmoTableCell::from_html(html!(invitation.email.clone()), None),
+                                CosmoTableCell::from_html(html!({if invitation.is_mod { "Ja" } else { "Nein" }}), None),
+                                CosmoTableCell::from_html(html!(invitation.invited_by.clone()), None),
+                                CosmoTableCell::from_html(html!({if invitation.expires_at > now {
+                                    invitation.expires_at.format("%d.%m.%Y %H:%M").to_string()
+                                } else {
+                                    "Abgelaufen".to_string()
+                                }}), None),
+                                CosmoTableCell::from_html(html!(
+                                    <CosmoToolbarGroup>
+                                        <CosmoButton label="Erneut senden" on_click={resend} />
+                                        <CosmoButton label="Zurückziehen" on_click={open_revoke} />
+                                    </CosmoToolbarGroup>
+                                ), None),
+                            ], Some(invitation.id.into()))
+                        })}
+                    </CosmoTable>
+                }
+            }
+            if *create_open_toggle {
+                <CreateInvitationModal on_saved={on_created} on_close={close_create} />
+            }
+            if let Some(invitation) = (*revoke_invitation_state).clone() {
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} on_confirm={revoke} on_decline={close_revoke} confirm_label="Einladung zurückziehen" decline_label="Einladung behalten" title="Einladung zurückziehen" message={format!("Soll die Einladung für {} wirklich zurückgezogen werden? Der Link in der Email funktioniert danach nicht mehr.", invitation.email)} />
+            }
+        </>
+    )
+}
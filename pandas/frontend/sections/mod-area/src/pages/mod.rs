@@ -1,2 +1,5 @@
+pub mod audit_log;
 pub mod grove;
+pub mod invitation;
+pub mod role;
 pub mod user;
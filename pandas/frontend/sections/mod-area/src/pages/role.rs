@@ -0,0 +1,219 @@
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_bool_toggle, use_mount};
+
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::CONFLICT;
+
+use crate::api;
+
+#[autoprops]
+#[function_component(RoleModal)]
+fn role_modal(role: &Option<Role>, on_saved: &Callback<()>, on_close: &Callback<()>) -> Html {
+    let name_state = use_state_eq(|| {
+        AttrValue::from(
+            role.as_ref()
+                .map(|role| role.name.clone())
+                .unwrap_or_default(),
+        )
+    });
+    let permissions_state = use_state_eq(|| {
+        role.as_ref()
+            .map(|role| role.permissions())
+            .unwrap_or_default()
+    });
+
+    let save_state = {
+        let name_state = name_state.clone();
+        let permissions_state = permissions_state.clone();
+
+        let role_id = role.as_ref().map(|role| role.id);
+
+        let on_saved = on_saved.clone();
+
+        use_async(async move {
+            let role = Role::new((*name_state).to_string(), permissions_state.as_slice());
+            if let Some(id) = role_id {
+                api::update_role(id, role).await
+            } else {
+                api::create_role(role).await.map(|_| ())
+            }
+            .inspect(|_| on_saved.emit(()))
+        })
+    };
+
+    let update_name = use_callback(name_state.clone(), |value, state| state.set(value));
+    let on_save = use_callback(save_state.clone(), |_, state| state.run());
+
+    let permission_switches = Permission::iter()
+        .map(|permission| {
+            let checked = permissions_state.contains(&permission);
+            let permissions_state = permissions_state.clone();
+            let on_check = Callback::from(move |checked: bool| {
+                let mut permissions = (*permissions_state).clone();
+                permissions.retain(|p| *p != permission);
+                if checked {
+                    permissions.push(permission);
+                }
+                permissions_state.set(permissions);
+            });
+
+            CosmoInputGroupChildren::from(html_nested!(
+                <CosmoSwitch label={permission.to_string()} checked={checked} on_check={on_check} />
+            ))
+        })
+        .collect::<Vec<CosmoInputGroupChildren>>();
+
+    html!(
+        <CosmoModal title={if role.is_some() { "Rolle bearbeiten" } else { "Rolle erstellen" }} is_form={true} on_form_submit={on_save} buttons={html!(
+            <>
+                <CosmoButton on_click={on_close.clone()} label="Abbrechen" />
+                <CosmoButton is_submit={true} label="Rolle speichern" />
+            </>
+        )}>
+            if let Some(err) = &save_state.error {
+                if err.code == CONFLICT {
+                    <CosmoMessage message="Es gibt bereits eine Rolle mit diesem Namen" message_type={CosmoMessageType::Negative} />
+                } else {
+                    <CosmoMessage message="Die Rolle konnte leider nicht gespeichert werden" message_type={CosmoMessageType::Negative} />
+                }
+            } else {
+                <CosmoMessage message_type={CosmoMessageType::Information} header="Rechte für Pandas ohne Modrechte" message="Pandas mit dieser Rolle bekommen nur die ausgewählten Rechte, Mods haben immer alle Rechte" />
+            }
+            <CosmoInputGroup>
+                <CosmoTextBox label="Name" value={(*name_state).clone()} on_input={update_name} required={true} />
+                {for permission_switches}
+            </CosmoInputGroup>
+        </CosmoModal>
+    )
+}
+
+#[function_component(RoleManagementPage)]
+pub fn role_management_page() -> Html {
+    let create_open_toggle = use_bool_toggle(false);
+
+    let edit_role_state = use_state_eq(|| None as Option<Role>);
+    let delete_role_state = use_state_eq(|| None as Option<Role>);
+
+    let roles_state = use_async(async { api::get_roles().await });
+    let delete_state = {
+        let roles_state = roles_state.clone();
+        let delete_role_state = delete_role_state.clone();
+
+        use_async(async move {
+            let result = if let Some(role) = (*delete_role_state).clone() {
+                api::delete_role(role.id).await
+            } else {
+                Ok(())
+            };
+            delete_role_state.set(None);
+            roles_state.run();
+
+            result
+        })
+    };
+
+    let open_create = use_callback(create_open_toggle.clone(), |_, toggle| toggle.set(true));
+    let close_modal = use_callback(
+        (create_open_toggle.clone(), edit_role_state.clone()),
+        |_, (toggle, edit_role_state)| {
+            toggle.set(false);
+            edit_role_state.set(None);
+        },
+    );
+    let on_saved = use_callback(
+        (
+            create_open_toggle.clone(),
+            edit_role_state.clone(),
+            roles_state.clone(),
+        ),
+        |_, (toggle, edit_role_state, roles_state)| {
+            toggle.set(false);
+            edit_role_state.set(None);
+            roles_state.run();
+        },
+    );
+    let delete = use_callback(delete_state.clone(), |_, state| state.run());
+    let close_delete = use_callback(delete_role_state.clone(), |_, state| state.set(None));
+
+    {
+        let roles_state = roles_state.clone();
+
+        use_mount(move || roles_state.run());
+    }
+
+    html!(
+        <>
+            <CosmoTitle title="Rollen" />
+            <CosmoParagraph>
+                {"Mit Rollen kannst du Pandas einzelne Rechte geben, ohne sie direkt zum Mod zu machen. Mods haben die Rolle Mod mit allen Rechten. Welche Rolle ein Panda hat, legst du in der Benutzerverwaltung fest."}
+            </CosmoParagraph>
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoButton label="Rolle erstellen" on_click={open_create} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
+            if delete_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Löschen" message="Die Rolle konnte nicht gelöscht werden" />
+            }
+            if roles_state.loading {
+                <CosmoProgressRing />
+            } else if roles_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Laden" message="Die Rollen konnten nicht geladen werden" />
+            } else if let Some(roles) = &roles_state.data {
+                if roles.is_empty() {
+                    <CosmoMessage message_type={CosmoMessageType::Information} header="Keine Rollen" message="Dein Hain hat noch keine Rollen, die Rolle Mod wird angelegt, sobald es einen Mod gibt" />
+                } else {
+                    <CosmoTable headers={vec![AttrValue::from("Name"), AttrValue::from("Rechte"), AttrValue::from("Aktionen")]}>
+                        {for roles.iter().map(|role| {
+                            let open_edit = {
+                                let edit_role_state = edit_role_state.clone();
+                                let role = role.clone();
+
+                                Callback::from(move |_| edit_role_state.set(Some(role.clone())))
+                            };
+                            let open_delete = {
+                                let delete_role_state = delete_role_state.clone();
+                                let role = role.clone();
+
+                                Callback::from(move |_| delete_role_state.set(Some(role.clone())))
+                            };
+                            // The mod role always keeps every permission
+                            let is_mod_role = role.name == MOD_ROLE_NAME;
+                            let permissions = role
+                                .permissions()
+                                .iter()
+                                .map(|permission| permission.to_string())
+                                .collect::<Vec<String>>();
+
+                            CosmoTableRow::from_table_cells(vec![
+                                CosmoTableCell::from_html(html!(role.name.clone()), None),
+                                CosmoTableCell::from_html(html!({if permissions.is_empty() {
+                                    "Keine Rechte".to_string()
+                                } else {
+                                    permissions.join(", ")
+                                }}), None),
+                                CosmoTableCell::from_html(html!(
+                                    <CosmoToolbarGroup>
+                                        <CosmoButton label="Bearbeiten" enabled={!is_mod_role} on_click={open_edit} />
+                                        <CosmoButton label="Löschen" enabled={!is_mod_role} on_click={open_delete} />
+                                    </CosmoToolbarGroup>
+                                ), None),
+                            ], Some(role.id.into()))
+                        })}
+                    </CosmoTable>
+                }
+            }
+            if *create_open_toggle {
+                <RoleModal role={None as Option<Role>} on_saved={on_saved.clone()} on_close={close_modal.clone()} />
+            } else if let Some(role) = (*edit_role_state).clone() {
+                <RoleModal role={Some(role)} on_saved={on_saved} on_close={close_modal} />
+            }
+            if let Some(role) = (*delete_role_state).clone() {
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} on_confirm={delete} on_decline={close_delete} confirm_label="Rolle löschen" decline_label="Rolle behalten" title="Rolle löschen" message={format!("Soll die Rolle {} wirklich gelöscht werden? Pandas mit dieser Rolle verlieren ihre Rechte.", role.name)} />
+            }
+        </>
+    )
+}
@@ -8,10 +8,13 @@ use yew_hooks::use_mount;
 use yew_hooks::{use_async, use_bool_toggle, use_unmount};
 
 use bamboo_common::core::entities::*;
-use bamboo_common::frontend::api::{ApiError, CONFLICT, FORBIDDEN, NOT_FOUND};
+use bamboo_common::frontend::api::{ApiError, ListQuery, CONFLICT, FORBIDDEN, NOT_FOUND};
+use bamboo_common::frontend::ui::{BambooFieldErrors, BambooPaging};
+use bamboo_pandas_frontend_base::sse::TopicEventSource;
 use bamboo_pandas_frontend_base::{error, storage};
 
 use crate::api;
+use crate::pages::invitation::CreateInvitationModal;
 
 #[derive(PartialEq, Clone)]
 enum UserConfirmActions {
@@ -23,128 +26,6 @@ enum UserConfirmActions {
     Closed,
 }
 
-#[autoprops]
-#[function_component(CreateUserModal)]
-fn create_user_modal(on_saved: &Callback<WebUser>, on_close: &Callback<()>) -> Html {
-    log::debug!("Create create user modal");
-    log::debug!("Initialize state and callbacks");
-    let email_state = use_state_eq(|| AttrValue::from(""));
-    let display_name_state = use_state_eq(|| AttrValue::from(""));
-    let discord_name_state = use_state_eq(|| AttrValue::from(""));
-
-    let is_mod_toggle = use_bool_toggle(false);
-    let unreported_error_toggle = use_bool_toggle(false);
-
-    let bamboo_error_state = use_state_eq(ApiError::default);
-
-    {
-        let email_state = email_state.clone();
-        let display_name_state = display_name_state.clone();
-        let discord_name_state = discord_name_state.clone();
-
-        let is_mod_toggle = is_mod_toggle.clone();
-
-        use_unmount(move || {
-            is_mod_toggle.set(false);
-
-            email_state.set("".into());
-            display_name_state.set("".into());
-            discord_name_state.set("".into());
-        })
-    }
-
-    let save_state = {
-        let email_state = email_state.clone();
-        let display_name_state = display_name_state.clone();
-        let discord_name_state = discord_name_state.clone();
-
-        let is_mod_toggle = is_mod_toggle.clone();
-        let unreported_error_toggle = unreported_error_toggle.clone();
-
-        let bamboo_error_state = bamboo_error_state.clone();
-
-        let on_saved = on_saved.clone();
-
-        use_async(async move {
-            api::create_user(User::new(
-                (*email_state).to_string(),
-                (*display_name_state).to_string(),
-                (*discord_name_state).to_string(),
-                *is_mod_toggle,
-            ))
-            .await
-            .map(|data| {
-                log::debug!("User was created successfully");
-                unreported_error_toggle.set(false);
-                on_saved.emit(data.clone());
-
-                data
-            })
-            .map_err(|err| {
-                log::warn!("Failed to create user {err}");
-                unreported_error_toggle.set(true);
-                bamboo_error_state.set(err.clone());
-
-                err
-            })
-        })
-    };
-
-    let update_email = use_callback(email_state.clone(), |value, state| state.set(value));
-    let update_display_name =
-        use_callback(display_name_state.clone(), |value, state| state.set(value));
-    let update_discord_name =
-        use_callback(discord_name_state.clone(), |value, state| state.set(value));
-
-    let update_is_mod = use_callback(is_mod_toggle.clone(), |checked, state| state.set(checked));
-
-    let report_unknown_error = use_callback(
-        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
-        |_, (bamboo_error_state, unreported_error_toggle)| {
-            error::report_unknown_error(
-                "bamboo_user",
-                "create_user_modal",
-                bamboo_error_state.deref().clone(),
-            );
-            unreported_error_toggle.set(false);
-        },
-    );
-
-    let on_save = use_callback(save_state.clone(), |_, state| state.run());
-
-    html!(
-        <CosmoModal title="Panda hinzufügen" is_form={true} on_form_submit={on_save} buttons={
-            html!(
-                <>
-                    <CosmoButton on_click={on_close.clone()} label="Abbrechen" />
-                    <CosmoButton is_submit={true} label="Panda hinzufügen" />
-                </>
-            )}>
-            <>
-                if let Some(err) = &save_state.error {
-                    if err.code == FORBIDDEN {
-                        <CosmoMessage message="Du musst Mod sein um andere Pandas hinzuzufügen" message_type={CosmoMessageType::Negative} />
-                    } else if err.code == CONFLICT {
-                        <CosmoMessage message="Ein Panda mit dieser Emailadresse oder Namen ist bereits in Bambushain registriert" message_type={CosmoMessageType::Negative} />
-                    } else if *unreported_error_toggle {
-                        <CosmoMessage message="Der Panda konnte leider nicht hinzugefügt werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
-                    } else {
-                        <CosmoMessage message="Der Panda konnte leider nicht hinzugefügt werden" message_type={CosmoMessageType::Negative} />
-                    }
-                } else {
-                    <CosmoMessage message_type={CosmoMessageType::Information} header="Füge einen neuen Panda hinzu" message="Das Passwort wird dem Panda per Mail geschickt" />
-                }
-                <CosmoInputGroup>
-                    <CosmoTextBox label="Email" value={(*email_state).clone()} on_input={update_email} required={true} />
-                    <CosmoTextBox label="Name" value={(*display_name_state).clone()} on_input={update_display_name} required={true} />
-                    <CosmoTextBox label="Discord Name (optional)" value={(*discord_name_state).clone()} on_input={update_discord_name} />
-                    <CosmoSwitch label="Moderator" on_check={update_is_mod} checked={*is_mod_toggle} />
-                </CosmoInputGroup>
-            </>
-        </CosmoModal>
-    )
-}
-
 #[autoprops]
 #[function_component(UpdateProfileDialog)]
 fn update_profile_dialog(
@@ -212,6 +93,16 @@ fn update_profile_dialog(
         },
     );
     let on_save = use_callback(save_state.clone(), |_, state| state.run());
+    let field_inputs = vec![
+        (
+            AttrValue::from("email"),
+            AttrValue::from("update-profile-email"),
+        ),
+        (
+            AttrValue::from("displayName"),
+            AttrValue::from("update-profile-display-name"),
+        ),
+    ];
 
     html!(
         <>
@@ -227,16 +118,19 @@ fn update_profile_dialog(
                     } else if err.code == NOT_FOUND {
                         <CosmoMessage message="Der Panda wurde nicht gefunden" message_type={CosmoMessageType::Negative} />
                     } else if err.code == CONFLICT {
-                        <CosmoMessage message="Ein Panda mit dieser Emailadresse oder Namen ist bereits in Bambushain registriert" message_type={CosmoMessageType::Negative} />
+                        <CosmoMessage message="Ein Panda mit dieser Emailadresse oder Namen ist bereits in Bambushain registriert oder liegt im Papierkorb" message_type={CosmoMessageType::Negative} />
+                    } else if !err.bamboo_error.fields.is_empty() {
+                        <CosmoMessage message="Bitte prüfe die markierten Felder" message_type={CosmoMessageType::Negative} />
                     } else if *unreported_error_toggle {
                         <CosmoMessage message="Der Panda konnte leider nicht geändert werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
                     } else {
                         <CosmoMessage message="Der Panda konnte leider nicht geändert werden" message_type={CosmoMessageType::Negative} />
                     }
                 }
+                <BambooFieldErrors error={save_state.error.clone().map(|err| err.bamboo_error)} inputs={field_inputs} />
                 <CosmoInputGroup>
-                    <CosmoTextBox label="Email" required={true} input_type={CosmoTextBoxType::Email} on_input={update_email} value={(*email_state).clone()} />
-                    <CosmoTextBox label="Name" required={true} on_input={update_display_name} value={(*display_name_state).clone()} />
+                    <CosmoTextBox id="update-profile-email" label="Email" required={true} input_type={CosmoTextBoxType::Email} on_input={update_email} value={(*email_state).clone()} />
+                    <CosmoTextBox id="update-profile-display-name" label="Name" required={true} on_input={update_display_name} value={(*display_name_state).clone()} />
                     <CosmoTextBox label="Discord Name (optional)" on_input={update_discord_name} value={(*discord_name_state).clone()} />
                 </CosmoInputGroup>
             </CosmoModal>
@@ -246,11 +140,18 @@ fn update_profile_dialog(
 
 #[autoprops]
 #[function_component(UserDetails)]
-fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<()>) -> Html {
+fn user_details(
+    user: &WebUser,
+    roles: &Vec<Role>,
+    on_delete: &Callback<()>,
+    on_update: &Callback<()>,
+) -> Html {
     log::debug!("Initialize table body state and callbacks");
     let confirm_state = use_state_eq(|| UserConfirmActions::Closed);
 
     let current_user = use_atom::<storage::CurrentUser>();
+    // The role is read when the request runs, a state would still hold the value of the last render
+    let selected_role_ref = use_mut_ref(|| user.role_id);
 
     let profile_edit_toggle = use_bool_toggle(false);
     let unreported_error_toggle = use_bool_toggle(false);
@@ -402,6 +303,43 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
         })
     };
 
+    let change_role_state = {
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let selected_role_ref = selected_role_ref.clone();
+
+        let on_update = on_update.clone();
+
+        let user_id = user.id;
+
+        use_async(async move {
+            let role_id = *selected_role_ref.borrow();
+            api::change_user_role(user_id, role_id)
+                .await
+                .map(|_| {
+                    unreported_error_toggle.set(false);
+
+                    on_update.emit(())
+                })
+                .map_err(|err| {
+                    log::error!("Failed to change role {err}");
+                    bamboo_error_state.set(err.clone());
+                    unreported_error_toggle.set(true);
+
+                    err
+                })
+        })
+    };
+
+    let select_role = use_callback(
+        (selected_role_ref.clone(), change_role_state.clone()),
+        |value: Option<AttrValue>, (selected_role_ref, change_role_state)| {
+            *selected_role_ref.borrow_mut() = value.and_then(|value| value.parse::<i32>().ok());
+            change_role_state.run();
+        },
+    );
     let make_mod_click = use_callback(confirm_state.clone(), |_, state| {
         state.set(UserConfirmActions::MakeMod)
     });
@@ -466,24 +404,50 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
         },
     );
 
+    let is_current_user = user.id == current_user.profile.id;
+    // Only mods can change other mods
+    let can_manage = !is_current_user && (current_user.profile.is_mod || !user.is_mod);
+    let role_items = vec![(None, AttrValue::from("Keine Rolle"))]
+        .into_iter()
+        .chain(roles.iter().map(|role| {
+            (
+                Some(AttrValue::from(role.id.to_string())),
+                AttrValue::from(role.name.clone()),
+            )
+        }))
+        .collect::<Vec<(Option<AttrValue>, AttrValue)>>();
+    let role_name = user
+        .role_id
+        .and_then(|role_id| roles.iter().find(|role| role.id == role_id))
+        .map(|role| role.name.clone());
+
     html!(
         <>
             <CosmoTitle title={user.display_name.clone()} subtitle={user.email.clone()} />
             <CosmoToolbar>
                 <CosmoToolbarGroup>
-                    if user.is_mod {
-                        <CosmoButton enabled={user.id != current_user.profile.id} on_click={remove_mod_click} label="Modrechte entziehen" />
-                    } else {
-                        <CosmoButton enabled={user.id != current_user.profile.id} on_click={make_mod_click} label="Zum Mod machen" />
+                    if current_user.profile.is_mod {
+                        if user.is_mod {
+                            <CosmoButton enabled={!is_current_user} on_click={remove_mod_click} label="Modrechte entziehen" />
+                        } else {
+                            <CosmoButton enabled={!is_current_user} on_click={make_mod_click} label="Zum Mod machen" />
+                        }
                     }
-                    <CosmoButton enabled={user.id != current_user.profile.id} on_click={update_profile_click} label="Panda bearbeiten" />
+                    <CosmoButton enabled={can_manage} on_click={update_profile_click} label="Panda bearbeiten" />
                 </CosmoToolbarGroup>
                 <CosmoToolbarGroup>
-                    <CosmoButton enabled={user.id != current_user.profile.id} on_click={change_password_click} label="Passwort ändern" />
-                    <CosmoButton enabled={user.id != current_user.profile.id && user.app_totp_enabled} on_click={disable_totp_click} label="Zwei Faktor deaktivieren" />
-                    <CosmoButton enabled={user.id != current_user.profile.id} on_click={delete_click} label="Aus dem Hain werfen" />
+                    <CosmoButton enabled={can_manage} on_click={change_password_click} label="Passwort ändern" />
+                    <CosmoButton enabled={can_manage && user.app_totp_enabled} on_click={disable_totp_click} label="Zwei Faktor deaktivieren" />
+                    <CosmoButton enabled={can_manage} on_click={delete_click} label="Aus dem Hain werfen" />
                 </CosmoToolbarGroup>
             </CosmoToolbar>
+            if change_role_state.error.is_some() {
+                if *unreported_error_toggle {
+                    <CosmoMessage header="Fehler beim Ändern der Rolle" message="Die Rolle des Pandas konnte nicht geändert werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
+                } else {
+                    <CosmoMessage header="Fehler beim Ändern der Rolle" message="Die Rolle des Pandas konnte nicht geändert werden" message_type={CosmoMessageType::Negative} />
+                }
+            }
             if let Some(err) = &delete_state.error {
                 if err.code == FORBIDDEN {
                     <CosmoMessage header="Fehler beim Rauswerfen" message="Du musst Mod sein um Pandas aus dem Hain zu werfen" message_type={CosmoMessageType::Negative} />
@@ -560,7 +524,17 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
                         {"Nein"}
                     }
                 </CosmoKeyValueListItem>
+                if !user.is_mod {
+                    <CosmoKeyValueListItem title="Rolle">
+                        {role_name.unwrap_or("Keine Rolle".to_string())}
+                    </CosmoKeyValueListItem>
+                }
             </CosmoKeyValueList>
+            if can_manage && !user.is_mod {
+                <CosmoInputGroup>
+                    <CosmoDropdown width={CosmoInputWidth::Medium} label="Rolle" value={user.role_id.map(|role_id| AttrValue::from(role_id.to_string()))} items={role_items} on_select={select_role} />
+                </CosmoInputGroup>
+            }
             {match (*confirm_state).clone() {
                 UserConfirmActions::MakeMod => html!(
                     <CosmoConfirm message={format!("Soll der Panda {} zum Mod gemacht werden?", user.display_name.clone())} title="Zum Mod machen" on_decline={on_decline} on_confirm={on_confirm} decline_label="Abbrechen" confirm_label="Zum Mod machen" />
@@ -569,7 +543,7 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
                     <CosmoConfirm message={format!("Sollen dem Panda {} wirklich die Modrechte entzogen werden?", user.display_name.clone())} title="Modrechte entziehen" on_decline={on_decline} on_confirm={on_confirm} confirm_label="Modrechte entziehen" decline_label="Abbrechen" />
                 ),
                 UserConfirmActions::Delete => html!(
-                    <CosmoConfirm confirm_type={CosmoModalType::Warning} message={format!("Soll der Panda {} wirklich aus dem Hain geworfen werden?", user.display_name.clone())} title="Panda rauswerfen" on_decline={on_decline} on_confirm={on_confirm} confirm_label="Panda rauswerfen" decline_label="Panda behalten" />
+                    <CosmoConfirm confirm_type={CosmoModalType::Warning} message={format!("Soll der Panda {} wirklich aus dem Hain geworfen werden? Du kannst ihn im Papierkorb wiederherstellen.", user.display_name.clone())} title="Panda rauswerfen" on_decline={on_decline} on_confirm={on_confirm} confirm_label="Panda rauswerfen" decline_label="Panda behalten" />
                 ),
                 UserConfirmActions::DisableTotp => html!(
                     <CosmoConfirm confirm_type={CosmoModalType::Warning} message={format!("Soll die Zwei Faktor Authentifizierung von {} wirklich deaktiviert werden?", user.display_name.clone())} title="Zwei Faktor Authentifizierung deaktivieren" on_decline={on_decline} on_confirm={on_confirm} confirm_label="Deaktivieren" decline_label="Nicht deaktivieren" />
@@ -608,19 +582,61 @@ pub fn user_management_page() -> Html {
 
     let bamboo_error_state = use_state_eq(ApiError::default);
 
+    let event_source_state = use_mut_ref(|| TopicEventSource::new(&[SseTopic::User]));
+
+    let query_state = use_state_eq(ListQuery::default);
+    // The query is read when the request runs, a state would still hold the value of the last render
+    let query_ref = use_mut_ref(ListQuery::default);
+
+    let roles_state = use_async(async move { api::get_roles().await });
     let users_state = {
         let bamboo_error_state = bamboo_error_state.clone();
 
         let unreported_error_toggle = unreported_error_toggle.clone();
 
+        let query_ref = query_ref.clone();
+
         use_async(async move {
             unreported_error_toggle.set(false);
 
-            api::get_users().await.map_err(|err| {
+            let query = query_ref.borrow().clone();
+            api::get_users(query).await.inspect_err(|err| {
                 bamboo_error_state.set(err.clone());
                 unreported_error_toggle.set(true);
+            })
+        })
+    };
 
-                err
+    let load = {
+        let query_state = query_state.clone();
+        let users_state = users_state.clone();
+
+        move |query: ListQuery| {
+            *query_ref.borrow_mut() = query.clone();
+            query_state.set(query);
+            users_state.run();
+        }
+    };
+    let update_filter = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+
+        Callback::from(move |filter: AttrValue| {
+            load(ListQuery {
+                page: 1,
+                filter: filter.to_string(),
+                ..query.clone()
+            })
+        })
+    };
+    let change_page = {
+        let load = load.clone();
+        let query = (*query_state).clone();
+
+        Callback::from(move |page: u64| {
+            load(ListQuery {
+                page,
+                ..query.clone()
             })
         })
     };
@@ -656,9 +672,9 @@ pub fn user_management_page() -> Html {
             users_state.clone(),
             open_create_user_modal_toggle.clone(),
         ),
-        |user: WebUser, (selected_state, users_state, open_create_user_modal_toggle)| {
+        |_: Invitation, (selected_state, users_state, open_create_user_modal_toggle)| {
             users_state.run();
-            selected_state.set(user.id);
+            selected_state.set(0);
             open_create_user_modal_toggle.set(false);
         },
     );
@@ -668,13 +684,32 @@ pub fn user_management_page() -> Html {
 
     {
         let users_state = users_state.clone();
+        let roles_state = roles_state.clone();
+        let event_source_state = event_source_state.clone();
 
         use_mount(move || {
+            roles_state.run();
+            let mut source = event_source_state.borrow_mut();
+            for action in ["created", "updated", "deleted"] {
+                let users_state = users_state.clone();
+                source.register_handler(
+                    SseTopic::User,
+                    action,
+                    Callback::from(move |_: WebUser| {
+                        log::debug!("The users of the grove changed, reload them");
+                        users_state.run();
+                    }),
+                );
+            }
             users_state.run();
         });
     }
+    {
+        let event_source_state = event_source_state.clone();
+        use_unmount(move || event_source_state.borrow().close());
+    }
 
-    if users_state.loading {
+    let content = if users_state.loading {
         html!(
             <CosmoProgressRing />
         )
@@ -688,7 +723,8 @@ pub fn user_management_page() -> Html {
                 <CosmoMessage header="Fehler beim Laden" message="Die Pandas konnten nicht geladen werden" message_type={CosmoMessageType::Negative} />
             )
         }
-    } else if let Some(data) = &users_state.data {
+    } else if let Some(data) = users_state.data.as_ref().map(|page| &page.entries) {
+        let roles = roles_state.data.clone().unwrap_or_default();
         let select_user = {
             let data = data.clone();
             let selected_user_state = selected_user_state.clone();
@@ -700,19 +736,39 @@ pub fn user_management_page() -> Html {
 
         html!(
             <>
-                <CosmoSideList on_select_item={select_user} selected_index={data.iter().position(|u| u.id == *selected_user_state).unwrap_or(0)} has_add_button={current_user.profile.is_mod} add_button_on_click={open_create_user_modal_click} add_button_label="Panda hinzufügen">
+                <CosmoSideList on_select_item={select_user} selected_index={data.iter().position(|u| u.id == *selected_user_state).unwrap_or(0)} has_add_button={current_user.profile.has_permission(Permission::ManageUsers)} add_button_on_click={open_create_user_modal_click} add_button_label="Panda einladen">
                     {for data.iter().map(|user| {
                         CosmoSideListItem::from_label_and_children(user.display_name.clone().into(), html!(
-                            <UserDetails on_delete={on_delete.clone()} on_update={on_update.clone()} user={user.clone()} />
+                            <UserDetails on_delete={on_delete.clone()} on_update={on_update.clone()} user={user.clone()} roles={roles.clone()} />
                         ))
                     })}
                 </CosmoSideList>
                 if *open_create_user_modal_toggle {
-                    <CreateUserModal on_saved={on_create_saved} on_close={on_create_close} />
+                    <CreateInvitationModal on_saved={on_create_saved} on_close={on_create_close} />
                 }
             </>
         )
     } else {
         html!()
-    }
+    };
+
+    let total = users_state
+        .data
+        .as_ref()
+        .map(|page| page.total)
+        .unwrap_or_default();
+
+    html!(
+        <>
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoTextBox width={CosmoInputWidth::Medium} label="Suchen" value={AttrValue::from(query_state.filter.clone())} on_input={update_filter} />
+                </CosmoToolbarGroup>
+                <CosmoToolbarGroup>
+                    <BambooPaging page={query_state.page} limit={query_state.limit} total={total} on_change={change_page} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
+            {content}
+        </>
+    )
 }